@@ -0,0 +1,120 @@
+use super::components::Actor;
+use super::definitions::ActorDefinition;
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use std::ops::Range;
+
+/// Sprite sheet animation state for a billboard
+#[derive(Component, Debug, Clone)]
+pub struct AnimatedBillboard {
+    /// Sprite sheet layout
+    pub columns: u32,
+    pub rows: u32,
+    /// Playback rate in frames per second
+    pub fps: f32,
+    /// Frames played while standing still and while moving
+    pub idle_frames: Range<u32>,
+    pub walk_frames: Range<u32>,
+    /// Time accumulated towards the next frame
+    pub timer: f32,
+    /// Current frame index into the sheet
+    pub frame: u32,
+}
+
+impl AnimatedBillboard {
+    /// Build the animation for an actor, or `None` if its sprite is static
+    pub fn from_definition(def: &ActorDefinition) -> Option<Self> {
+        if def.frames <= 1 {
+            return None;
+        }
+
+        let columns = if def.sheet_columns == 0 {
+            def.frames
+        } else {
+            def.sheet_columns.min(def.frames)
+        };
+        let rows = def.frames.div_ceil(columns);
+
+        let (idle_frames, walk_frames) = match def.walk_frame_start {
+            Some(start) if start > 0 && start < def.frames => (0..start, start..def.frames),
+            _ => (0..def.frames, 0..def.frames),
+        };
+
+        Some(Self {
+            columns,
+            rows,
+            fps: def.fps,
+            idle_frames,
+            walk_frames,
+            timer: 0.0,
+            frame: 0,
+        })
+    }
+
+    /// UV transform that maps the billboard quad onto the current frame
+    pub fn uv_transform(&self) -> Affine2 {
+        let cell = Vec2::new(1.0 / self.columns as f32, 1.0 / self.rows as f32);
+        let offset = Vec2::new(
+            (self.frame % self.columns) as f32,
+            (self.frame / self.columns) as f32,
+        );
+        Affine2::from_scale_angle_translation(cell, 0.0, offset * cell)
+    }
+}
+
+/// Advance a looping animation within `frames` by `dt` seconds.
+///
+/// Returns the new frame and leftover timer. A frame outside the range (e.g.
+/// after switching between idle and walk) restarts at the range's first frame.
+pub fn advance_frame(frame: u32, timer: f32, dt: f32, fps: f32, frames: &Range<u32>) -> (u32, f32) {
+    if frames.is_empty() {
+        return (frame, timer);
+    }
+    if !frames.contains(&frame) {
+        return (frames.start, 0.0);
+    }
+    if fps <= 0.0 {
+        return (frame, timer);
+    }
+
+    let frame_time = 1.0 / fps;
+    let timer = timer + dt;
+    let steps = (timer / frame_time).floor();
+    let len = frames.end - frames.start;
+    let next = frames.start + (frame - frames.start + steps as u32 % len) % len;
+    (next, timer - steps * frame_time)
+}
+
+/// Step every animated billboard and point its material at the current frame
+pub fn update_animated_billboards(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut AnimatedBillboard,
+        &MeshMaterial3d<StandardMaterial>,
+        Option<&Actor>,
+    )>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut animation, material, actor) in query.iter_mut() {
+        let moving = actor.is_some_and(|actor| actor.is_moving);
+        let frames = if moving {
+            animation.walk_frames.clone()
+        } else {
+            animation.idle_frames.clone()
+        };
+
+        let previous = animation.frame;
+        let (frame, timer) =
+            advance_frame(animation.frame, animation.timer, dt, animation.fps, &frames);
+        animation.frame = frame;
+        animation.timer = timer;
+
+        if frame != previous
+            && let Some(material) = materials.get_mut(material.id())
+        {
+            material.uv_transform = animation.uv_transform();
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::animation::advance_frame;
+
+#[test]
+fn test_holds_frame_until_frame_time_elapses() {
+    let (frame, timer) = advance_frame(0, 0.0, 0.05, 10.0, &(0..4));
+    assert_eq!(frame, 0);
+    assert!((timer - 0.05).abs() < 1e-6);
+
+    let (frame, _) = advance_frame(frame, timer, 0.05, 10.0, &(0..4));
+    assert_eq!(frame, 1);
+}
+
+#[test]
+fn test_long_frame_skips_and_wraps() {
+    // 0.35s at 10 fps is three and a half frames
+    let (frame, timer) = advance_frame(2, 0.0, 0.35, 10.0, &(0..4));
+    assert_eq!(frame, 1);
+    assert!((timer - 0.05).abs() < 1e-5);
+}
+
+#[test]
+fn test_stays_within_offset_range() {
+    let frames = 4..7;
+    let mut frame = 4;
+    let mut timer = 0.0;
+    let mut seen = Vec::new();
+    for _ in 0..6 {
+        (frame, timer) = advance_frame(frame, timer, 0.125, 8.0, &frames);
+        seen.push(frame);
+    }
+    assert_eq!(seen, vec![5, 6, 4, 5, 6, 4]);
+}
+
+#[test]
+fn test_switching_range_restarts_cycle() {
+    // Actor starts walking while on an idle frame
+    assert_eq!(advance_frame(1, 0.07, 0.01, 10.0, &(4..8)), (4, 0.0));
+}
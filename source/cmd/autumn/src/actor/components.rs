@@ -1,6 +1,7 @@
+use crate::ai::ActorBehavior;
+use crate::combat::{Resistances, StatusEffectType};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use crate::ai::ActorBehavior;
 
 /// Animation state for actor attacks
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -20,8 +21,8 @@ pub struct Actor {
     pub scale: f32,
     /// Flat damage reduction
     pub armor: i32,
-    /// Resistance to physical damage (0.0 = no resistance, 1.0 = immune)
-    pub physical_resistance: f32,
+    /// Resistance to each damage type
+    pub resistances: Resistances,
     /// Collision radius for movement (3/4 of player radius)
     pub actor_radius: f32,
     /// Movement speed multiplier
@@ -30,10 +31,16 @@ pub struct Actor {
     pub behavior: Option<Box<dyn ActorBehavior>>,
     /// Whether the actor is currently moving (for wiggle animation)
     pub is_moving: bool,
+    /// Movement over the last frame in units per second (for flocking)
+    pub velocity: Vec2,
+    /// Floor of the map the actor is on, for collision and pathfinding
+    pub layer: usize,
     /// Base Z position (for wiggle animation)
     pub base_z: f32,
     /// Attack damage dealt to player
     pub attack_damage: i32,
+    /// Status effect each hit leaves on the player
+    pub attack_effect: Option<StatusEffectType>,
     /// Attack range in units
     pub attack_range: f32,
     /// Cooldown duration between attacks
@@ -47,9 +54,16 @@ pub struct Actor {
 }
 
 /// Position data for actors in the map file
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct ActorPosition {
     pub x: f32,
     pub y: f32,
     pub actor_type: String,
+    /// Floor of the map, left out for the ground floor
+    #[serde(default, skip_serializing_if = "is_ground_floor")]
+    pub layer: usize,
+}
+
+fn is_ground_floor(layer: &usize) -> bool {
+    *layer == 0
 }
@@ -1,6 +1,62 @@
+use crate::combat::{Resistances, StatusEffectType};
+use crate::rendering::BillboardMode;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Behaviors `Map::spawn_actor` knows how to create, named in YAML by
+/// `ActorDefinition::behavior`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorKind {
+    Wander,
+    Stand,
+    Aggressive,
+    Flee,
+    Flock,
+    Ranged,
+}
+
+impl BehaviorKind {
+    pub const ALL: [BehaviorKind; 6] = [
+        BehaviorKind::Wander,
+        BehaviorKind::Stand,
+        BehaviorKind::Aggressive,
+        BehaviorKind::Flee,
+        BehaviorKind::Flock,
+        BehaviorKind::Ranged,
+    ];
+
+    /// Name used for the behavior in actor definitions
+    pub fn name(self) -> &'static str {
+        match self {
+            BehaviorKind::Wander => "wander",
+            BehaviorKind::Stand => "stand",
+            BehaviorKind::Aggressive => "aggressive",
+            BehaviorKind::Flee => "flee",
+            BehaviorKind::Flock => "flock",
+            BehaviorKind::Ranged => "ranged",
+        }
+    }
+}
+
+impl FromStr for BehaviorKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|kind| kind.name()).collect();
+                format!(
+                    "unknown behavior '{}' (expected one of: {})",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+}
 
 /// Definition of an actor type loaded from YAML
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -20,6 +76,100 @@ pub struct ActorDefinition {
     pub attack_range: f32,
     #[serde(default = "default_attack_cooldown")]
     pub attack_cooldown: f32,
+
+    /// Distance a "ranged" actor tries to keep from the player
+    #[serde(default = "default_preferred_distance")]
+    pub preferred_distance: f32,
+    /// How far from preferred_distance a ranged actor may drift before moving
+    #[serde(default = "default_distance_tolerance")]
+    pub distance_tolerance: f32,
+    /// Seconds between ranged attacks
+    #[serde(default = "default_ranged_cooldown")]
+    pub ranged_cooldown: f32,
+    /// Projectile speed in units per second
+    #[serde(default = "default_projectile_speed")]
+    pub projectile_speed: f32,
+
+    /// Number of frames in the sprite sheet (1 = static sprite).
+    /// Frames run left to right, then top to bottom.
+    #[serde(default = "default_frames")]
+    pub frames: u32,
+    /// Animation playback rate in frames per second
+    #[serde(default = "default_fps")]
+    pub fps: f32,
+    /// Columns in the sprite sheet (0 = all frames on a single row)
+    #[serde(default)]
+    pub sheet_columns: u32,
+    /// First frame of the walk cycle; frames before it are the idle cycle.
+    /// When unset, all frames play whether the actor is moving or not.
+    #[serde(default)]
+    pub walk_frame_start: Option<u32>,
+    /// How the sprite turns towards the camera: "upright" (the default)
+    /// or "face_camera", which also tilts it with the camera's height
+    #[serde(default)]
+    pub billboard: BillboardMode,
+
+    /// Keep the actor drawn and acting however far away the player is,
+    /// ignoring `r_cull_distance`
+    #[serde(default)]
+    pub cull_exempt: bool,
+
+    /// Resistance to each damage type (0.0 = none, 1.0 = immune); types
+    /// that aren't listed have none
+    #[serde(default)]
+    pub resistances: Resistances,
+
+    /// Status effect the actor's attacks leave on the player
+    #[serde(default)]
+    pub attack_effect: Option<StatusEffectType>,
+}
+
+impl ActorDefinition {
+    /// Problems with the definition that would otherwise only show up as
+    /// warnings or odd behavior once the actor is spawned
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.sprite.trim().is_empty() {
+            problems.push("sprite is missing".to_string());
+        }
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            problems.push(format!("scale must be greater than 0 (got {})", self.scale));
+        }
+        if !(self.max_health.is_finite() && self.max_health > 0.0) {
+            problems.push(format!(
+                "max_health must be greater than 0 (got {})",
+                self.max_health
+            ));
+        }
+        if let Err(problem) = self.behavior.parse::<BehaviorKind>() {
+            problems.push(problem);
+        }
+        if self.frames == 0 {
+            problems.push("frames must be at least 1".to_string());
+        }
+        if self.frames > 1 && !(self.fps.is_finite() && self.fps > 0.0) {
+            problems.push(format!("fps must be greater than 0 (got {})", self.fps));
+        }
+        if let Some(start) = self.walk_frame_start
+            && start >= self.frames
+        {
+            problems.push(format!(
+                "walk_frame_start {} is past the last of {} frames",
+                start, self.frames
+            ));
+        }
+        let mut resistances: Vec<_> = self.resistances.0.iter().collect();
+        resistances.sort_by_key(|(damage_type, _)| format!("{:?}", damage_type));
+        for (damage_type, resistance) in resistances {
+            if !(0.0..=1.0).contains(resistance) {
+                problems.push(format!(
+                    "{:?} resistance must be between 0 and 1 (got {})",
+                    damage_type, resistance
+                ));
+            }
+        }
+        problems
+    }
 }
 
 fn default_behavior() -> String {
@@ -30,6 +180,14 @@ fn default_speed() -> f32 {
     1.0
 }
 
+fn default_frames() -> u32 {
+    1
+}
+
+fn default_fps() -> f32 {
+    8.0
+}
+
 fn default_attack_range() -> f32 {
     4.0
 }
@@ -38,6 +196,22 @@ fn default_attack_cooldown() -> f32 {
     1.2
 }
 
+fn default_preferred_distance() -> f32 {
+    16.0
+}
+
+fn default_distance_tolerance() -> f32 {
+    3.0
+}
+
+fn default_ranged_cooldown() -> f32 {
+    2.5
+}
+
+fn default_projectile_speed() -> f32 {
+    20.0
+}
+
 /// File structure for loading actor definitions from YAML
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ActorDefinitionsFile {
@@ -49,3 +223,28 @@ pub struct ActorDefinitionsFile {
 pub struct ActorDefinitions {
     pub actors: HashMap<String, ActorDefinition>,
 }
+
+impl ActorDefinitions {
+    /// Parse and validate actor definitions. Every problem found is reported,
+    /// one line per actor, so a file can be fixed in one go.
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        let file: ActorDefinitionsFile = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+
+        let mut names: Vec<&String> = file.actors.keys().collect();
+        names.sort();
+        let errors: Vec<String> = names
+            .into_iter()
+            .filter_map(|name| {
+                let problems = file.actors[name].problems();
+                (!problems.is_empty()).then(|| format!("{}: {}", name, problems.join(", ")))
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        Ok(Self {
+            actors: file.actors,
+        })
+    }
+}
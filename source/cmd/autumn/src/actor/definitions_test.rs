@@ -0,0 +1,115 @@
+use super::definitions::{ActorDefinitions, BehaviorKind};
+use crate::combat::damage::DamageType;
+use crate::rendering::BillboardMode;
+
+const SKELETON: &str = r#"
+actors:
+  skeleton:
+    sprite: "base/sprites/monster-skeleton-01.png"
+    scale: 3.8
+    max_health: 30.0
+    on_hit: "do_damage 10"
+    on_death: ""
+    behavior: "aggressive"
+"#;
+
+#[test]
+fn test_shipped_actor_definitions_are_valid() {
+    let yaml = std::fs::read_to_string("data/actor_definitions.yaml").unwrap();
+    if let Err(e) = ActorDefinitions::from_yaml(&yaml) {
+        panic!("{}", e);
+    }
+}
+
+#[test]
+fn test_valid_definition_loads() {
+    let definitions = ActorDefinitions::from_yaml(SKELETON).unwrap();
+    assert_eq!(definitions.actors["skeleton"].behavior, "aggressive");
+}
+
+#[test]
+fn test_unknown_behavior_is_reported() {
+    let yaml = SKELETON.replace("\"aggressive\"", "\"berserk\"");
+    let error = ActorDefinitions::from_yaml(&yaml).unwrap_err();
+    assert!(error.contains("skeleton"), "{}", error);
+    assert!(error.contains("unknown behavior 'berserk'"), "{}", error);
+}
+
+#[test]
+fn test_behavior_names_round_trip() {
+    for kind in BehaviorKind::ALL {
+        assert_eq!(kind.name().parse::<BehaviorKind>(), Ok(kind));
+    }
+    assert!("Wander".parse::<BehaviorKind>().is_err());
+}
+
+#[test]
+fn test_all_problems_are_reported() {
+    let yaml = format!(
+        "{}{}",
+        SKELETON.replace("scale: 3.8", "scale: 0.0"),
+        r#"
+  ghost:
+    sprite: ""
+    scale: 2.0
+    max_health: 10.0
+    on_hit: ""
+    on_death: ""
+    frames: 4
+    walk_frame_start: 4
+"#
+    );
+    let error = ActorDefinitions::from_yaml(&yaml).unwrap_err();
+    let lines: Vec<&str> = error.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", error);
+    assert!(
+        lines[0].starts_with("ghost: sprite is missing"),
+        "{}",
+        error
+    );
+    assert!(lines[0].contains("walk_frame_start"), "{}", error);
+    assert!(lines[1].starts_with("skeleton: scale must be greater than 0"));
+}
+
+#[test]
+fn test_missing_field_is_a_parse_error() {
+    let yaml = SKELETON.replace("    max_health: 30.0\n", "");
+    let error = ActorDefinitions::from_yaml(&yaml).unwrap_err();
+    assert!(error.contains("max_health"), "{}", error);
+}
+
+#[test]
+fn test_resistances_load_by_damage_type() {
+    let yaml = format!("{}    resistances:\n      Fire: 0.25\n", SKELETON);
+    let definitions = ActorDefinitions::from_yaml(&yaml).unwrap();
+    let resistances = &definitions.actors["skeleton"].resistances;
+    assert_eq!(resistances.get(DamageType::Fire), 0.25);
+    assert_eq!(resistances.get(DamageType::Ice), 0.0);
+}
+
+#[test]
+fn test_out_of_range_resistance_is_reported() {
+    let yaml = format!("{}    resistances:\n      Ice: 1.5\n", SKELETON);
+    let error = ActorDefinitions::from_yaml(&yaml).unwrap_err();
+    assert!(
+        error.contains("Ice resistance must be between 0 and 1"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_billboard_mode_defaults_to_upright() {
+    let definitions = ActorDefinitions::from_yaml(SKELETON).unwrap();
+    assert_eq!(
+        definitions.actors["skeleton"].billboard,
+        BillboardMode::Upright
+    );
+
+    let yaml = format!("{}    billboard: face_camera\n", SKELETON);
+    let definitions = ActorDefinitions::from_yaml(&yaml).unwrap();
+    assert_eq!(
+        definitions.actors["skeleton"].billboard,
+        BillboardMode::FaceCamera
+    );
+}
@@ -1,8 +1,18 @@
+pub mod animation;
+#[cfg(test)]
+mod animation_test;
 pub mod components;
 pub mod definitions;
+#[cfg(test)]
+mod definitions_test;
 pub mod plugin;
+pub mod spatial_hash;
+#[cfg(test)]
+mod spatial_hash_test;
 pub mod systems;
 
+pub use animation::AnimatedBillboard;
 pub use components::{Actor, ActorAttackState, ActorPosition};
-pub use definitions::{ActorDefinition, ActorDefinitions, ActorDefinitionsFile};
+pub use definitions::{ActorDefinition, ActorDefinitions, BehaviorKind};
 pub use plugin::ActorPlugin;
+pub use spatial_hash::{ActorSpatialHash, SpatialHash};
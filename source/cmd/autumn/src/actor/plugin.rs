@@ -1,18 +1,29 @@
-use bevy::prelude::*;
-use crate::game_state::GameState;
+use super::animation::update_animated_billboards;
+use super::spatial_hash::{ActorSpatialHash, update_actor_spatial_hash};
 use super::systems::{update_actor_death, update_actor_health_indicators};
+use crate::game_state::GameState;
+use crate::rendering::{spawn_actor_labels, update_actor_labels};
+use bevy::prelude::*;
 
 pub struct ActorPlugin;
 
 impl Plugin for ActorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                update_actor_death,
-                update_actor_health_indicators,
+        app.init_resource::<ActorSpatialHash>()
+            // Rebuilt before Update so hit detection and AI see this frame's actors
+            .add_systems(
+                PreUpdate,
+                update_actor_spatial_hash.run_if(in_state(GameState::Playing)),
             )
-                .run_if(in_state(GameState::Playing)),
-        );
+            .add_systems(
+                Update,
+                (
+                    update_actor_death,
+                    update_actor_health_indicators,
+                    update_animated_billboards,
+                    (spawn_actor_labels, update_actor_labels).chain(),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
     }
 }
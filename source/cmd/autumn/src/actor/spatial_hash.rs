@@ -0,0 +1,133 @@
+use super::components::Actor;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Bucket size for the actor hash, a little over two wall tiles
+pub const ACTOR_HASH_CELL_SIZE: f32 = 16.0;
+
+/// Up to this many items, queries scan everything instead of the buckets.
+/// For a handful of actors that is cheaper than visiting the cells.
+pub const BRUTE_FORCE_LIMIT: usize = 32;
+
+/// Positions in the XY plane bucketed into square cells, so that finding
+/// what is near a point only looks at the cells around it
+#[derive(Debug, Clone)]
+pub struct SpatialHash<T> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<(T, Vec2)>>,
+    len: usize,
+}
+
+impl<T: Copy> SpatialHash<T> {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove everything, keeping the allocated buckets for reuse
+    pub fn clear(&mut self) {
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.len = 0;
+    }
+
+    fn cell(&self, position: Vec2) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    pub fn insert(&mut self, item: T, position: Vec2) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push((item, position));
+        self.len += 1;
+    }
+
+    /// Everything within `radius` of `center`
+    pub fn query(&self, center: Vec2, radius: f32) -> Vec<T> {
+        let radius_squared = radius * radius;
+        let within =
+            |(_, position): &&(T, Vec2)| position.distance_squared(center) <= radius_squared;
+
+        if self.len() <= BRUTE_FORCE_LIMIT {
+            return self
+                .cells
+                .values()
+                .flatten()
+                .filter(within)
+                .map(|(item, _)| *item)
+                .collect();
+        }
+
+        let (min_x, min_y) = self.cell(center - Vec2::splat(radius));
+        let (max_x, max_y) = self.cell(center + Vec2::splat(radius));
+        let mut found = Vec::new();
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                if let Some(bucket) = self.cells.get(&(x, y)) {
+                    found.extend(bucket.iter().filter(within).map(|(item, _)| *item));
+                }
+            }
+        }
+        found
+    }
+}
+
+/// Where every actor stood at the start of the frame, for hit detection
+#[derive(Resource, Debug)]
+pub struct ActorSpatialHash {
+    pub hash: SpatialHash<Entity>,
+    /// Largest actor scale in the hash. Actors are hit anywhere within their
+    /// scale of a point, so queries are widened by this much.
+    pub max_scale: f32,
+}
+
+impl Default for ActorSpatialHash {
+    fn default() -> Self {
+        Self {
+            hash: SpatialHash::new(ACTOR_HASH_CELL_SIZE),
+            max_scale: 0.0,
+        }
+    }
+}
+
+impl ActorSpatialHash {
+    /// Actors that may be within `radius` of `center`, allowing for their
+    /// size and for a little movement since the hash was built
+    pub fn nearby(&self, center: Vec2, radius: f32) -> Vec<Entity> {
+        if self.hash.is_empty() {
+            return Vec::new();
+        }
+        self.hash
+            .query(center, radius + self.max_scale + ACTOR_HASH_CELL_SIZE / 4.0)
+    }
+}
+
+/// System to rebuild the actor hash before anything queries it this frame
+pub fn update_actor_spatial_hash(
+    actors: Query<(Entity, &Transform, &Actor)>,
+    mut spatial_hash: ResMut<ActorSpatialHash>,
+) {
+    let spatial_hash = &mut *spatial_hash;
+    spatial_hash.hash.clear();
+    spatial_hash.max_scale = 0.0;
+    for (entity, transform, actor) in actors.iter() {
+        spatial_hash
+            .hash
+            .insert(entity, transform.translation.truncate());
+        spatial_hash.max_scale = spatial_hash.max_scale.max(actor.scale);
+    }
+}
@@ -0,0 +1,86 @@
+use super::spatial_hash::{BRUTE_FORCE_LIMIT, SpatialHash};
+use bevy::prelude::*;
+
+fn sorted(mut items: Vec<usize>) -> Vec<usize> {
+    items.sort();
+    items
+}
+
+/// A grid of points one unit apart, indexed row by row
+fn grid(width: usize, height: usize) -> (SpatialHash<usize>, Vec<Vec2>) {
+    let mut hash = SpatialHash::new(4.0);
+    let mut positions = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let position = Vec2::new(x as f32 - 10.0, y as f32 - 10.0);
+            hash.insert(positions.len(), position);
+            positions.push(position);
+        }
+    }
+    (hash, positions)
+}
+
+fn brute_force(positions: &[Vec2], center: Vec2, radius: f32) -> Vec<usize> {
+    (0..positions.len())
+        .filter(|&i| positions[i].distance(center) <= radius)
+        .collect()
+}
+
+#[test]
+fn test_query_returns_items_within_radius() {
+    let mut hash = SpatialHash::new(8.0);
+    hash.insert(1, Vec2::new(0.0, 0.0));
+    hash.insert(2, Vec2::new(5.0, 0.0));
+    hash.insert(3, Vec2::new(-7.5, -7.5));
+    hash.insert(4, Vec2::new(100.0, 100.0));
+    assert_eq!(hash.len(), 4);
+
+    assert_eq!(sorted(hash.query(Vec2::ZERO, 5.0)), vec![1, 2]);
+    assert_eq!(sorted(hash.query(Vec2::ZERO, 11.0)), vec![1, 2, 3]);
+    assert_eq!(hash.query(Vec2::new(100.0, 99.0), 1.0), vec![4]);
+    assert!(hash.query(Vec2::new(50.0, 50.0), 10.0).is_empty());
+}
+
+#[test]
+fn test_bucketed_query_matches_brute_force() {
+    let (hash, positions) = grid(20, 20);
+    assert!(hash.len() > BRUTE_FORCE_LIMIT);
+
+    for (center, radius) in [
+        (Vec2::ZERO, 3.0),
+        (Vec2::new(-10.0, -10.0), 5.5),
+        (Vec2::new(3.7, -2.2), 8.0),
+        (Vec2::new(-4.0, 4.0), 0.0),
+        (Vec2::new(40.0, 40.0), 6.0),
+    ] {
+        assert_eq!(
+            sorted(hash.query(center, radius)),
+            brute_force(&positions, center, radius),
+            "{:?} radius {}",
+            center,
+            radius
+        );
+    }
+}
+
+#[test]
+fn test_small_hash_scans_everything() {
+    let (hash, positions) = grid(4, 4);
+    assert!(hash.len() <= BRUTE_FORCE_LIMIT);
+    let center = Vec2::new(-8.5, -8.5);
+    assert_eq!(
+        sorted(hash.query(center, 2.0)),
+        brute_force(&positions, center, 2.0)
+    );
+}
+
+#[test]
+fn test_clear_empties_hash() {
+    let (mut hash, _) = grid(10, 10);
+    hash.clear();
+    assert!(hash.is_empty());
+    assert!(hash.query(Vec2::ZERO, 100.0).is_empty());
+
+    hash.insert(7, Vec2::new(1.0, 1.0));
+    assert_eq!(hash.query(Vec2::ZERO, 2.0), vec![7]);
+}
@@ -1,7 +1,7 @@
 use super::components::Actor;
 use super::definitions::ActorDefinitions;
 use crate::hud::PlayerStats;
-use crate::scripting::{self, CVarRegistry};
+use crate::scripting::{self, AliasRegistry, BindRegistry, CVarRegistry};
 use crate::world::Map;
 use bevy::prelude::*;
 
@@ -11,6 +11,8 @@ pub fn update_actor_death(
     actor_query: Query<(Entity, &Actor)>,
     mut stats: ResMut<PlayerStats>,
     mut cvars: ResMut<CVarRegistry>,
+    mut aliases: ResMut<AliasRegistry>,
+    mut binds: ResMut<BindRegistry>,
     mut map: ResMut<Map>,
     actor_definitions: Res<ActorDefinitions>,
 ) {
@@ -19,8 +21,13 @@ pub fn update_actor_death(
             // Get the actor definition to run the on_death script
             if let Some(actor_def) = actor_definitions.actors.get(&actor.actor_type) {
                 if !actor_def.on_death.is_empty() {
-                    let output =
-                        scripting::process_script(&actor_def.on_death, &mut stats, &mut cvars);
+                    let output = scripting::process_script(
+                        &actor_def.on_death,
+                        &mut stats,
+                        &mut cvars,
+                        &mut aliases,
+                        &mut binds,
+                    );
                     for line in &output {
                         println!("{}", line);
                     }
@@ -1,6 +1,7 @@
 use super::ActorBehavior;
+use super::path_budget::PathBudget;
 use super::pathfinding;
-use crate::world::Map;
+use crate::world::{GRID_SIZE, Map};
 use bevy::prelude::*;
 use rand::Rng;
 
@@ -43,6 +44,7 @@ enum AggressiveState {
         path: Vec<(f32, f32)>,
         current_index: usize,
         replan_timer: f32,
+        target_cell: (i32, i32), // Grid cell of the player when the path was planned
     },
     /// Currently attacking
     Attacking { timer: f32, has_dealt_damage: bool },
@@ -65,12 +67,12 @@ impl AggressiveBehavior {
     }
 
     /// Check if player is within detection range
-    fn can_detect_player(actor_pos: Vec2, player_pos: Vec2) -> bool {
+    pub(crate) fn can_detect_player(actor_pos: Vec2, player_pos: Vec2) -> bool {
         actor_pos.distance(player_pos) <= DETECTION_RANGE
     }
 
     /// Check if player is within chase range
-    fn in_chase_range(actor_pos: Vec2, player_pos: Vec2) -> bool {
+    pub(crate) fn in_chase_range(actor_pos: Vec2, player_pos: Vec2) -> bool {
         actor_pos.distance(player_pos) <= CHASE_RANGE
     }
 
@@ -84,8 +86,10 @@ impl AggressiveBehavior {
         wander_state: &mut WanderSubState,
         transform: &mut Transform,
         map: &Map,
+        layer: usize,
         delta_time: f32,
         speed_multiplier: f32,
+        path_budget: &mut PathBudget,
     ) -> bool {
         let mut is_moving = false;
 
@@ -98,16 +102,21 @@ impl AggressiveBehavior {
             }
 
             WanderSubState::Planning => {
+                // Stay in planning until a path search is available
+                if !path_budget.try_consume() {
+                    return false;
+                }
+
                 let mut rng = rand::rng();
                 let num_destinations = rng.random_range(2..=3);
                 let mut destinations = Vec::new();
 
                 for _ in 0..num_destinations {
                     for _ in 0..20 {
-                        let dest_x = rng.random_range(0.0..map.width as f32 * 8.0);
-                        let dest_y = rng.random_range(0.0..map.height as f32 * 8.0);
+                        let dest_x = rng.random_range(0.0..map.width as f32 * GRID_SIZE);
+                        let dest_y = rng.random_range(0.0..map.height as f32 * GRID_SIZE);
 
-                        if map.can_move_to(dest_x, dest_y, ACTOR_RADIUS) {
+                        if map.can_move_to_on(layer, dest_x, dest_y, ACTOR_RADIUS) {
                             destinations.push((dest_x, dest_y));
                             break;
                         }
@@ -123,12 +132,14 @@ impl AggressiveBehavior {
                     let current_x = transform.translation.x;
                     let current_y = transform.translation.y;
 
-                    if let Some(path) = pathfinding::find_path(
+                    if let Some(path) = pathfinding::find_path_smoothed(
                         map,
+                        layer,
                         current_x,
                         current_y,
                         destinations[0].0,
                         destinations[0].1,
+                        ACTOR_RADIUS,
                     ) {
                         *wander_state = WanderSubState::Moving {
                             path,
@@ -153,16 +164,23 @@ impl AggressiveBehavior {
 
                 if *current_index >= path.len() {
                     if destinations.len() > 1 {
+                        // Wait at the waypoint until a path search is available
+                        if !path_budget.try_consume() {
+                            return false;
+                        }
+
                         destinations.remove(0);
                         let current_x = transform.translation.x;
                         let current_y = transform.translation.y;
 
-                        if let Some(new_path) = pathfinding::find_path(
+                        if let Some(new_path) = pathfinding::find_path_smoothed(
                             map,
+                            layer,
                             current_x,
                             current_y,
                             destinations[0].0,
                             destinations[0].1,
+                            ACTOR_RADIUS,
                         ) {
                             *path = new_path;
                             *current_index = 0;
@@ -198,7 +216,7 @@ impl AggressiveBehavior {
                         let new_x = current_x + (dx / distance) * move_distance;
                         let new_y = current_y + (dy / distance) * move_distance;
 
-                        if map.can_move_to(new_x, new_y, ACTOR_RADIUS) {
+                        if map.can_move_to_on(layer, new_x, new_y, ACTOR_RADIUS) {
                             transform.translation.x = new_x;
                             transform.translation.y = new_y;
                         } else {
@@ -222,8 +240,10 @@ impl ActorBehavior for AggressiveBehavior {
         speed_multiplier: f32,
         player_position: Option<Vec2>,
         actor: &crate::ai::ActorData,
+        path_budget: &mut PathBudget,
     ) -> bool {
         let actor_pos = Vec2::new(transform.translation.x, transform.translation.y);
+        let layer = actor.layer;
         let mut is_moving = false;
 
         // If no player position available, just wander
@@ -236,8 +256,10 @@ impl ActorBehavior for AggressiveBehavior {
                     wander_state,
                     transform,
                     map,
+                    layer,
                     delta_time,
                     speed_multiplier,
+                    path_budget,
                 );
             }
             self.state = AggressiveState::Wandering {
@@ -250,18 +272,23 @@ impl ActorBehavior for AggressiveBehavior {
             AggressiveState::Wandering { wander_state } => {
                 // Check if player entered detection range
                 if Self::can_detect_player(actor_pos, player_pos) {
-                    // Transition to chasing
-                    if let Some(path) = pathfinding::find_path(
-                        map,
-                        actor_pos.x,
-                        actor_pos.y,
-                        player_pos.x,
-                        player_pos.y,
-                    ) {
+                    // Transition to chasing once a path search is available
+                    if path_budget.try_consume()
+                        && let Some(path) = pathfinding::find_path_smoothed(
+                            map,
+                            layer,
+                            actor_pos.x,
+                            actor_pos.y,
+                            player_pos.x,
+                            player_pos.y,
+                            ACTOR_RADIUS,
+                        )
+                    {
                         self.state = AggressiveState::Chasing {
                             path,
                             current_index: 0,
                             replan_timer: 0.0,
+                            target_cell: pathfinding::world_to_grid(player_pos.x, player_pos.y),
                         };
                     }
                 } else {
@@ -270,8 +297,10 @@ impl ActorBehavior for AggressiveBehavior {
                         wander_state,
                         transform,
                         map,
+                        layer,
                         delta_time,
                         speed_multiplier,
+                        path_budget,
                     );
                 }
             }
@@ -280,11 +309,12 @@ impl ActorBehavior for AggressiveBehavior {
                 path,
                 current_index,
                 replan_timer,
+                target_cell,
             } => {
                 is_moving = true;
 
-                // Check if player escaped
-                if !Self::in_chase_range(actor_pos, player_pos) {
+                // Check if player escaped (a nearly dead player is never let go)
+                if !Self::in_chase_range(actor_pos, player_pos) && !actor.player_near_death() {
                     self.state = AggressiveState::Wandering {
                         wander_state: WanderSubState::Planning,
                     };
@@ -316,60 +346,84 @@ impl ActorBehavior for AggressiveBehavior {
                         let new_x = actor_pos.x + (dx / distance) * move_distance;
                         let new_y = actor_pos.y + (dy / distance) * move_distance;
 
-                        if map.can_move_to(new_x, new_y, ACTOR_RADIUS) {
+                        if map.can_move_to_on(layer, new_x, new_y, ACTOR_RADIUS) {
                             transform.translation.x = new_x;
                             transform.translation.y = new_y;
                         } else {
                             // Hit a wall during direct movement, switch back to pathfinding
-                            if let Some(new_path) = pathfinding::find_path(
-                                map,
-                                actor_pos.x,
-                                actor_pos.y,
-                                player_pos.x,
-                                player_pos.y,
-                            ) {
+                            if path_budget.try_consume()
+                                && let Some(new_path) = pathfinding::find_path_smoothed(
+                                    map,
+                                    layer,
+                                    actor_pos.x,
+                                    actor_pos.y,
+                                    player_pos.x,
+                                    player_pos.y,
+                                    ACTOR_RADIUS,
+                                )
+                            {
                                 *path = new_path;
                                 *current_index = 0;
                                 *replan_timer = 0.0;
+                                *target_cell =
+                                    pathfinding::world_to_grid(player_pos.x, player_pos.y);
                             }
                         }
                     }
                 } else {
                     // Too far - use pathfinding
 
-                    // Replan path periodically to track moving player
+                    // Replan path periodically to track moving player. If the player
+                    // is still in the same cell the current path is reused; if the
+                    // frame's path budget is used up, the replan is retried next frame.
                     *replan_timer += delta_time;
                     if *replan_timer >= PATH_REPLAN_INTERVAL {
-                        *replan_timer = 0.0;
-                        if let Some(new_path) = pathfinding::find_path(
-                            map,
-                            actor_pos.x,
-                            actor_pos.y,
+                        if !pathfinding::target_cell_changed(
+                            *target_cell,
                             player_pos.x,
                             player_pos.y,
                         ) {
-                            *path = new_path;
-                            *current_index = 0;
+                            *replan_timer = 0.0;
+                        } else if path_budget.try_consume() {
+                            *replan_timer = 0.0;
+                            *target_cell = pathfinding::world_to_grid(player_pos.x, player_pos.y);
+                            if let Some(new_path) = pathfinding::find_path_smoothed(
+                                map,
+                                layer,
+                                actor_pos.x,
+                                actor_pos.y,
+                                player_pos.x,
+                                player_pos.y,
+                                ACTOR_RADIUS,
+                            ) {
+                                *path = new_path;
+                                *current_index = 0;
+                            }
                         }
                     }
 
                     // Move along path
                     if *current_index >= path.len() {
-                        // Path exhausted, replan immediately
-                        if let Some(new_path) = pathfinding::find_path(
-                            map,
-                            actor_pos.x,
-                            actor_pos.y,
-                            player_pos.x,
-                            player_pos.y,
-                        ) {
-                            *path = new_path;
-                            *current_index = 0;
-                        } else {
-                            // Can't find path, go back to wandering
-                            self.state = AggressiveState::Wandering {
-                                wander_state: WanderSubState::Planning,
-                            };
+                        // Path exhausted, replan as soon as a path search is available
+                        if path_budget.try_consume() {
+                            *target_cell = pathfinding::world_to_grid(player_pos.x, player_pos.y);
+                            if let Some(new_path) = pathfinding::find_path_smoothed(
+                                map,
+                                layer,
+                                actor_pos.x,
+                                actor_pos.y,
+                                player_pos.x,
+                                player_pos.y,
+                                ACTOR_RADIUS,
+                            ) {
+                                *path = new_path;
+                                *current_index = 0;
+                            } else {
+                                // Can't find path, go back to wandering
+                                self.state = AggressiveState::Wandering {
+                                    wander_state: WanderSubState::Planning,
+                                };
+                            }
                         }
                     } else {
                         let target = path[*current_index];
@@ -386,7 +440,7 @@ impl ActorBehavior for AggressiveBehavior {
                             let new_x = actor_pos.x + (dx / distance) * move_distance;
                             let new_y = actor_pos.y + (dy / distance) * move_distance;
 
-                            if map.can_move_to(new_x, new_y, ACTOR_RADIUS) {
+                            if map.can_move_to_on(layer, new_x, new_y, ACTOR_RADIUS) {
                                 transform.translation.x = new_x;
                                 transform.translation.y = new_y;
                             }
@@ -412,18 +466,26 @@ impl ActorBehavior for AggressiveBehavior {
                         // Player is beyond the buffered range
                         if Self::in_chase_range(actor_pos, player_pos) {
                             // Player moved out of attack range but still in chase range
-                            // Resume chasing
-                            if let Some(path) = pathfinding::find_path(
-                                map,
-                                actor_pos.x,
-                                actor_pos.y,
-                                player_pos.x,
-                                player_pos.y,
-                            ) {
+                            // Resume chasing once a path search is available
+                            if path_budget.try_consume()
+                                && let Some(path) = pathfinding::find_path_smoothed(
+                                    map,
+                                    layer,
+                                    actor_pos.x,
+                                    actor_pos.y,
+                                    player_pos.x,
+                                    player_pos.y,
+                                    ACTOR_RADIUS,
+                                )
+                            {
                                 self.state = AggressiveState::Chasing {
                                     path,
                                     current_index: 0,
                                     replan_timer: 0.0,
+                                    target_cell: pathfinding::world_to_grid(
+                                        player_pos.x,
+                                        player_pos.y,
+                                    ),
                                 };
                             }
                         } else {
@@ -0,0 +1,74 @@
+use super::aggressive_behavior::AggressiveBehavior;
+use super::path_budget::PathBudget;
+use super::{ActorBehavior, ActorData, try_move_with_slide};
+use crate::world::Map;
+use bevy::prelude::*;
+
+const MOVEMENT_SPEED: f32 = 10.0;
+const ACTOR_RADIUS: f32 = 1.2;
+
+/// Own health fraction at or below which the actor runs away
+pub const FLEE_HEALTH: f32 = 0.3;
+
+/// Whether a wounded actor should run rather than fight. It stays to fight
+/// if the player is close to death too.
+pub fn should_flee(actor: &ActorData) -> bool {
+    actor.health_fraction <= FLEE_HEALTH && !actor.player_near_death()
+}
+
+/// Flee behavior - fights like an aggressive actor until badly hurt, then
+/// runs directly away from the player
+pub struct FleeBehavior {
+    aggressive: AggressiveBehavior,
+    fleeing: bool,
+}
+
+impl FleeBehavior {
+    pub fn new() -> Self {
+        Self {
+            aggressive: AggressiveBehavior::new(),
+            fleeing: false,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn is_fleeing(&self) -> bool {
+        self.fleeing
+    }
+}
+
+impl ActorBehavior for FleeBehavior {
+    fn update(
+        &mut self,
+        transform: &mut Transform,
+        map: &Map,
+        delta_time: f32,
+        speed_multiplier: f32,
+        player_position: Option<Vec2>,
+        actor: &ActorData,
+        path_budget: &mut PathBudget,
+    ) -> bool {
+        self.fleeing = should_flee(actor);
+
+        let Some(player_pos) = player_position.filter(|_| self.fleeing) else {
+            return self.aggressive.update(
+                transform,
+                map,
+                delta_time,
+                speed_multiplier,
+                player_position,
+                actor,
+                path_budget,
+            );
+        };
+
+        let actor_pos = Vec2::new(transform.translation.x, transform.translation.y);
+        let away = (actor_pos - player_pos).normalize_or_zero();
+        let step = away * MOVEMENT_SPEED * speed_multiplier * delta_time;
+        try_move_with_slide(transform, map, actor.layer, step, ACTOR_RADIUS)
+    }
+
+    fn get_label(&self) -> &str {
+        if self.fleeing { "flee" } else { "aggressive" }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::actor::ActorAttackState;
+use crate::ai::flee_behavior::{FleeBehavior, should_flee};
+use crate::ai::path_budget::PathBudget;
+use crate::ai::{ActorBehavior, ActorData, try_move_with_slide};
+use crate::world::{Map, MapFile};
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+fn actor_data(health_fraction: f32, player_health_fraction: Option<f32>) -> ActorData {
+    ActorData {
+        layer: 0,
+        attack_state: ActorAttackState::Idle,
+        attack_range: 4.0,
+        neighbors: Vec::new(),
+        health_fraction,
+        player_health_fraction,
+    }
+}
+
+#[test]
+fn test_healthy_actor_fights() {
+    assert!(!should_flee(&actor_data(1.0, Some(1.0))));
+}
+
+#[test]
+fn test_wounded_actor_flees() {
+    assert!(should_flee(&actor_data(0.2, Some(1.0))));
+    assert!(should_flee(&actor_data(0.2, None)));
+}
+
+#[test]
+fn test_wounded_actor_finishes_dying_player() {
+    assert!(!should_flee(&actor_data(0.2, Some(0.1))));
+}
+
+#[test]
+fn test_player_near_death() {
+    assert!(actor_data(1.0, Some(0.25)).player_near_death());
+    assert!(!actor_data(1.0, Some(0.5)).player_near_death());
+    assert!(!actor_data(1.0, None).player_near_death());
+}
+
+#[test]
+fn test_fleeing_actor_moves_away_from_player() {
+    let map = Map::new(20, 20);
+    let mut behavior = FleeBehavior::new();
+    let mut transform = Transform::from_xyz(80.0, 80.0, 0.0);
+    let mut budget = PathBudget::new(1);
+
+    let moving = behavior.update(
+        &mut transform,
+        &map,
+        0.1,
+        1.0,
+        Some(Vec2::new(90.0, 80.0)),
+        &actor_data(0.1, Some(1.0)),
+        &mut budget,
+    );
+
+    assert!(moving);
+    assert!(behavior.is_fleeing());
+    assert_eq!(behavior.get_label(), "flee");
+    assert!(transform.translation.x < 80.0);
+    assert_eq!(transform.translation.y, 80.0);
+}
+
+#[test]
+fn test_stops_fleeing_when_player_near_death() {
+    let map = Map::new(20, 20);
+    let mut behavior = FleeBehavior::new();
+    let mut transform = Transform::from_xyz(80.0, 80.0, 0.0);
+    let mut budget = PathBudget::new(1);
+    let player = Some(Vec2::new(90.0, 80.0));
+
+    behavior.update(
+        &mut transform,
+        &map,
+        0.1,
+        1.0,
+        player,
+        &actor_data(0.1, Some(1.0)),
+        &mut budget,
+    );
+    assert!(behavior.is_fleeing());
+
+    behavior.update(
+        &mut transform,
+        &map,
+        0.1,
+        1.0,
+        player,
+        &actor_data(0.1, Some(0.1)),
+        &mut budget,
+    );
+    assert!(!behavior.is_fleeing());
+    assert_eq!(behavior.get_label(), "aggressive");
+}
+
+#[test]
+fn test_blocked_move_slides_along_wall() {
+    // The wall at (2, 1) blocks moving right from (1, 1), but not down
+    let map = Map::from_map_file(&MapFile {
+        grid: ["XXXXX", "X.X.X", "X...X", "X...X", "XXXXX"]
+            .iter()
+            .map(|row| row.to_string())
+            .collect(),
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    });
+    let mut transform = Transform::from_xyz(12.0, 12.0, 0.0);
+
+    assert!(try_move_with_slide(
+        &mut transform,
+        &map,
+        0,
+        Vec2::new(4.0, 4.0),
+        1.2
+    ));
+    assert_eq!(transform.translation.truncate(), Vec2::new(12.0, 16.0));
+
+    // Nothing to slide along when both axes are blocked
+    let mut cornered = Transform::from_xyz(12.0, 12.0, 0.0);
+    assert!(!try_move_with_slide(
+        &mut cornered,
+        &map,
+        0,
+        Vec2::new(4.0, -4.0),
+        1.2
+    ));
+    assert_eq!(cornered.translation.truncate(), Vec2::new(12.0, 12.0));
+}
@@ -0,0 +1,143 @@
+use super::aggressive_behavior::AggressiveBehavior;
+use super::path_budget::PathBudget;
+use super::wander_behavior::WanderBehavior;
+use super::{ActorBehavior, ActorData, FlockNeighbor, try_move_with_slide};
+use crate::world::Map;
+use bevy::prelude::*;
+
+const MOVEMENT_SPEED: f32 = 10.0;
+const ACTOR_RADIUS: f32 = 1.2;
+
+/// Neighbors closer than this push each other apart
+const SEPARATION_RADIUS: f32 = 4.0;
+
+/// Relative strength of each steering force
+#[derive(Debug, Clone, Copy)]
+pub struct FlockWeights {
+    /// Toward the player
+    pub seek: f32,
+    /// Toward the center of the pack
+    pub cohesion: f32,
+    /// Away from neighbors that are too close
+    pub separation: f32,
+    /// Along the pack's average heading
+    pub alignment: f32,
+}
+
+impl Default for FlockWeights {
+    fn default() -> Self {
+        Self {
+            seek: 1.0,
+            cohesion: 0.4,
+            separation: 1.5,
+            alignment: 0.3,
+        }
+    }
+}
+
+/// Combine seek, cohesion, separation and alignment into a unit steering
+/// direction (zero if the forces cancel out)
+pub fn flock_steering(
+    position: Vec2,
+    target: Vec2,
+    neighbors: &[FlockNeighbor],
+    weights: &FlockWeights,
+) -> Vec2 {
+    let seek = (target - position).normalize_or_zero();
+    if neighbors.is_empty() {
+        return seek;
+    }
+
+    let count = neighbors.len() as f32;
+    let center = neighbors.iter().map(|n| n.position).sum::<Vec2>() / count;
+    let cohesion = (center - position).normalize_or_zero();
+
+    // Closer neighbors push harder
+    let separation = neighbors
+        .iter()
+        .filter_map(|n| {
+            let away = position - n.position;
+            let distance = away.length();
+            (distance > 0.0 && distance < SEPARATION_RADIUS)
+                .then(|| away / distance * (1.0 - distance / SEPARATION_RADIUS))
+        })
+        .sum::<Vec2>();
+
+    let alignment = neighbors
+        .iter()
+        .map(|n| n.velocity)
+        .sum::<Vec2>()
+        .normalize_or_zero();
+
+    (seek * weights.seek
+        + cohesion * weights.cohesion
+        + separation * weights.separation
+        + alignment * weights.alignment)
+        .normalize_or_zero()
+}
+
+/// Flock behavior - wanders until the player is detected, then chases them
+/// as a pack with nearby actors of the same type
+pub struct FlockBehavior {
+    weights: FlockWeights,
+    chasing: bool,
+    wander: WanderBehavior,
+}
+
+impl FlockBehavior {
+    pub fn new() -> Self {
+        Self {
+            weights: FlockWeights::default(),
+            chasing: false,
+            wander: WanderBehavior::new(),
+        }
+    }
+}
+
+impl ActorBehavior for FlockBehavior {
+    fn update(
+        &mut self,
+        transform: &mut Transform,
+        map: &Map,
+        delta_time: f32,
+        speed_multiplier: f32,
+        player_position: Option<Vec2>,
+        actor: &ActorData,
+        path_budget: &mut PathBudget,
+    ) -> bool {
+        let actor_pos = Vec2::new(transform.translation.x, transform.translation.y);
+
+        // Same detection and chase ranges as aggressive actors
+        self.chasing = player_position.is_some_and(|player_pos| {
+            if self.chasing {
+                AggressiveBehavior::in_chase_range(actor_pos, player_pos)
+            } else {
+                AggressiveBehavior::can_detect_player(actor_pos, player_pos)
+            }
+        });
+        let Some(player_pos) = player_position.filter(|_| self.chasing) else {
+            return self.wander.update(
+                transform,
+                map,
+                delta_time,
+                speed_multiplier,
+                player_position,
+                actor,
+                path_budget,
+            );
+        };
+
+        // Let the attack system take over once in reach
+        if actor_pos.distance(player_pos) <= actor.attack_range {
+            return false;
+        }
+
+        let direction = flock_steering(actor_pos, player_pos, &actor.neighbors, &self.weights);
+        let step = direction * MOVEMENT_SPEED * speed_multiplier * delta_time;
+        try_move_with_slide(transform, map, actor.layer, step, ACTOR_RADIUS)
+    }
+
+    fn get_label(&self) -> &str {
+        "flock"
+    }
+}
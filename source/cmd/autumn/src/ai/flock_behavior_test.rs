@@ -0,0 +1,90 @@
+use crate::ai::FlockNeighbor;
+use crate::ai::flock_behavior::{FlockWeights, flock_steering};
+use bevy::prelude::*;
+
+fn neighbor(x: f32, y: f32) -> FlockNeighbor {
+    FlockNeighbor {
+        position: Vec2::new(x, y),
+        velocity: Vec2::ZERO,
+    }
+}
+
+/// All forces off, so a test can enable just the one it checks
+const NONE: FlockWeights = FlockWeights {
+    seek: 0.0,
+    cohesion: 0.0,
+    separation: 0.0,
+    alignment: 0.0,
+};
+
+#[test]
+fn test_alone_seeks_target() {
+    let steering = flock_steering(
+        Vec2::ZERO,
+        Vec2::new(10.0, 0.0),
+        &[],
+        &FlockWeights::default(),
+    );
+    assert_eq!(steering, Vec2::X);
+}
+
+#[test]
+fn test_cohesion_pulls_toward_pack_center() {
+    let neighbors = [neighbor(10.0, 10.0), neighbor(10.0, -10.0)];
+    let weights = FlockWeights {
+        cohesion: 1.0,
+        ..NONE
+    };
+    let steering = flock_steering(Vec2::ZERO, Vec2::ZERO, &neighbors, &weights);
+    assert!((steering - Vec2::X).length() < 1e-5);
+}
+
+#[test]
+fn test_separation_pushes_away_from_close_neighbors() {
+    let neighbors = [neighbor(1.0, 0.0), neighbor(30.0, 0.0)];
+    let weights = FlockWeights {
+        separation: 1.0,
+        ..NONE
+    };
+    let steering = flock_steering(Vec2::ZERO, Vec2::ZERO, &neighbors, &weights);
+    // Only the close neighbor counts
+    assert!((steering - Vec2::NEG_X).length() < 1e-5);
+}
+
+#[test]
+fn test_alignment_follows_average_heading() {
+    let neighbors = [
+        FlockNeighbor {
+            position: Vec2::new(5.0, 0.0),
+            velocity: Vec2::new(0.0, 2.0),
+        },
+        FlockNeighbor {
+            position: Vec2::new(-5.0, 0.0),
+            velocity: Vec2::new(0.0, 4.0),
+        },
+    ];
+    let weights = FlockWeights {
+        alignment: 1.0,
+        ..NONE
+    };
+    let steering = flock_steering(Vec2::ZERO, Vec2::ZERO, &neighbors, &weights);
+    assert!((steering - Vec2::Y).length() < 1e-5);
+}
+
+#[test]
+fn test_forces_combine_into_unit_vector() {
+    // Seek east while a crowding neighbor to the east pushes back west and
+    // the pack drifts north: the result leans north and stays normalized
+    let neighbors = [FlockNeighbor {
+        position: Vec2::new(1.0, 0.0),
+        velocity: Vec2::new(0.0, 1.0),
+    }];
+    let steering = flock_steering(
+        Vec2::ZERO,
+        Vec2::new(20.0, 0.0),
+        &neighbors,
+        &FlockWeights::default(),
+    );
+    assert!((steering.length() - 1.0).abs() < 1e-5);
+    assert!(steering.y > 0.0);
+}
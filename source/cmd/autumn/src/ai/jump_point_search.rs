@@ -0,0 +1,169 @@
+/// Jump Point Search for open, uniform-cost maps
+///
+/// Plain A* over an open room pushes nearly every cell onto the open list.
+/// JPS prunes symmetric paths by "jumping" along straight and diagonal lines
+/// until something interesting (the goal or a forced neighbor next to a wall)
+/// appears, so only those jump points are expanded. Paths are 8-connected and
+/// never cut wall corners; the optimal path length is the same as 8-connected
+/// A* on the same grid.
+use crate::world::Map;
+use pathfinding::prelude::astar;
+
+/// Cost of a straight step
+pub const STRAIGHT_COST: u32 = 100;
+
+/// Cost of a diagonal step (√2, in the same units)
+pub const DIAGONAL_COST: u32 = 141;
+
+/// Result of a grid search, for comparing search strategies
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridSearch {
+    /// Every cell from start to goal, inclusive
+    pub path: Vec<(i32, i32)>,
+    /// Total path cost in straight-step hundredths
+    pub cost: u32,
+    /// Number of nodes the search expanded
+    pub expanded: usize,
+}
+
+/// Exact 8-connected cost between two cells on an open grid
+pub fn octile_distance(from: (i32, i32), to: (i32, i32)) -> u32 {
+    let dx = (from.0 - to.0).unsigned_abs();
+    let dy = (from.1 - to.1).unsigned_abs();
+    DIAGONAL_COST * dx.min(dy) + STRAIGHT_COST * dx.abs_diff(dy)
+}
+
+/// Open cell inside the map bounds. Unlike `Map::is_solid_on`, cells outside
+/// the grid are blocked, since a jump would otherwise run forever.
+fn is_walkable(map: &Map, layer: usize, x: i32, y: i32) -> bool {
+    x >= 0 && y >= 0 && x < map.width && y < map.height && !map.is_solid_on(layer, x, y)
+}
+
+/// A step is allowed if the target is open and, for diagonals, both cells it
+/// squeezes between are open too
+fn can_step(map: &Map, layer: usize, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+    is_walkable(map, layer, x + dx, y + dy)
+        && (dx == 0
+            || dy == 0
+            || (is_walkable(map, layer, x + dx, y) && is_walkable(map, layer, x, y + dy)))
+}
+
+/// Every allowed step out of a cell. A* uses the same steps, so both
+/// searches find paths of the same length.
+pub fn all_directions(map: &Map, layer: usize, x: i32, y: i32) -> Vec<(i32, i32)> {
+    let mut directions = Vec::new();
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            if (dx, dy) != (0, 0) && can_step(map, layer, x, y, dx, dy) {
+                directions.push((dx, dy));
+            }
+        }
+    }
+    directions
+}
+
+/// Directions still worth searching after arriving at a cell moving in
+/// `direction`. Anything else is reached at least as cheaply another way.
+fn pruned_directions(direction: (i32, i32)) -> Vec<(i32, i32)> {
+    match direction {
+        (dx, 0) => vec![(dx, 0), (dx, 1), (dx, -1), (0, 1), (0, -1)],
+        (0, dy) => vec![(0, dy), (1, dy), (-1, dy), (1, 0), (-1, 0)],
+        (dx, dy) => vec![(0, dy), (dx, 0), (dx, dy)],
+    }
+}
+
+/// Walk from a cell in one direction until reaching the goal or a jump
+/// point, returning that cell, or None if the walk hits a wall first
+fn jump(
+    map: &Map,
+    layer: usize,
+    (mut x, mut y): (i32, i32),
+    (dx, dy): (i32, i32),
+    goal: (i32, i32),
+) -> Option<(i32, i32)> {
+    loop {
+        if !can_step(map, layer, x, y, dx, dy) {
+            return None;
+        }
+        x += dx;
+        y += dy;
+
+        if (x, y) == goal {
+            return Some((x, y));
+        }
+
+        let is_jump_point = if dx != 0 && dy != 0 {
+            // A diagonal stops wherever one of its straight components would
+            jump(map, layer, (x, y), (dx, 0), goal).is_some()
+                || jump(map, layer, (x, y), (0, dy), goal).is_some()
+        } else if dx != 0 {
+            // Forced neighbor: a wall behind us ended, opening a new corridor
+            (is_walkable(map, layer, x, y - 1) && !is_walkable(map, layer, x - dx, y - 1))
+                || (is_walkable(map, layer, x, y + 1) && !is_walkable(map, layer, x - dx, y + 1))
+        } else {
+            (is_walkable(map, layer, x - 1, y) && !is_walkable(map, layer, x - 1, y - dy))
+                || (is_walkable(map, layer, x + 1, y) && !is_walkable(map, layer, x + 1, y - dy))
+        };
+        if is_jump_point {
+            return Some((x, y));
+        }
+    }
+}
+
+/// Fill in the cells between consecutive jump points, which always lie on a
+/// straight or diagonal line
+fn expand_jump_points(jump_points: &[(i32, i32)]) -> Vec<(i32, i32)> {
+    let mut path: Vec<(i32, i32)> = jump_points.first().copied().into_iter().collect();
+    for pair in jump_points.windows(2) {
+        let (mut x, mut y) = pair[0];
+        let (to_x, to_y) = pair[1];
+        let (dx, dy) = ((to_x - x).signum(), (to_y - y).signum());
+        while (x, y) != (to_x, to_y) {
+            x += dx;
+            y += dy;
+            path.push((x, y));
+        }
+    }
+    path
+}
+
+/// Find a path with Jump Point Search. Search nodes carry the direction they
+/// were reached from, which decides which directions get pruned.
+pub fn jump_point_search(
+    map: &Map,
+    layer: usize,
+    start: (i32, i32),
+    goal: (i32, i32),
+) -> Option<GridSearch> {
+    if !is_walkable(map, layer, goal.0, goal.1) {
+        return None;
+    }
+
+    let mut expanded = 0;
+    let (nodes, cost) = astar(
+        &(start, None),
+        |&(cell, direction): &((i32, i32), Option<(i32, i32)>)| {
+            expanded += 1;
+            let directions = match direction {
+                Some(direction) => pruned_directions(direction),
+                None => all_directions(map, layer, cell.0, cell.1),
+            };
+            directions
+                .into_iter()
+                .filter_map(|direction| {
+                    let next = jump(map, layer, cell, direction, goal)?;
+                    Some(((next, Some(direction)), octile_distance(cell, next)))
+                })
+                .collect::<Vec<_>>()
+        },
+        |&(cell, _)| octile_distance(cell, goal),
+        |&(cell, _)| cell == goal,
+    )?;
+
+    let jump_points: Vec<(i32, i32)> = nodes.into_iter().map(|(cell, _)| cell).collect();
+    Some(GridSearch {
+        path: expand_jump_points(&jump_points),
+        cost,
+        expanded,
+    })
+}
@@ -0,0 +1,170 @@
+use crate::ai::jump_point_search::jump_point_search;
+use crate::ai::pathfinding::{astar_search, find_path, grid_to_world, uses_jump_point_search};
+use crate::world::{Map, TileType};
+
+fn map_from_rows(rows: &[String]) -> Map {
+    let mut map = Map::new(rows[0].len() as i32, rows.len() as i32);
+    for (y, row) in rows.iter().enumerate() {
+        for (x, ch) in row.chars().enumerate() {
+            let tile = match ch {
+                '#' => TileType::Wall { height: 16.0 },
+                _ => TileType::Empty,
+            };
+            map.layers[0].collision.insert((x as i32, y as i32), tile);
+        }
+    }
+    map
+}
+
+fn open_room(width: usize, height: usize) -> Map {
+    map_from_rows(&vec![".".repeat(width); height])
+}
+
+/// Pillars and wall segments with gaps, so paths have to weave around
+fn room_with_obstacles() -> Map {
+    let mut rows = vec![".".repeat(40); 40];
+    for (y, row) in rows.iter_mut().enumerate() {
+        let mut cells: Vec<char> = row.chars().collect();
+        for (x, cell) in cells.iter_mut().enumerate() {
+            let pillar = x % 6 == 3 && y % 6 == 3;
+            let wall = x == 20 && y != 5 && y != 33;
+            let ledge = y == 12 && (5..30).contains(&x);
+            if pillar || wall || ledge {
+                *cell = '#';
+            }
+        }
+        *row = cells.into_iter().collect();
+    }
+    map_from_rows(&rows)
+}
+
+/// Deterministic scatter of walls at roughly the given density
+fn scattered_walls(seed: u32, density: u32) -> Map {
+    let mut state = seed;
+    let rows: Vec<String> = (0..30)
+        .map(|_| {
+            (0..30)
+                .map(|_| {
+                    state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    if (state >> 16) % 100 < density {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    map_from_rows(&rows)
+}
+
+fn assert_same_cost(map: &Map, start: (i32, i32), goal: (i32, i32)) {
+    let jps = jump_point_search(map, 0, start, goal);
+    let reference = astar_search(map, 0, start, goal);
+    match (jps, reference) {
+        (Some(jps), Some(reference)) => {
+            assert_eq!(jps.cost, reference.cost, "{:?} -> {:?}", start, goal);
+            assert_eq!(jps.path.len(), reference.path.len());
+            assert_eq!(jps.path.first(), Some(&start));
+            assert_eq!(jps.path.last(), Some(&goal));
+        }
+        (None, None) => {}
+        (jps, reference) => panic!(
+            "{:?} -> {:?}: JPS found {:?}, A* found {:?}",
+            start, goal, jps, reference
+        ),
+    }
+}
+
+#[test]
+fn test_jps_matches_astar_in_open_room() {
+    let map = open_room(40, 40);
+    assert_same_cost(&map, (0, 0), (39, 39));
+    assert_same_cost(&map, (2, 30), (37, 5));
+    assert_same_cost(&map, (10, 10), (10, 35));
+}
+
+#[test]
+fn test_jps_matches_astar_around_obstacles() {
+    let map = room_with_obstacles();
+    assert_same_cost(&map, (1, 1), (38, 38));
+    assert_same_cost(&map, (2, 20), (30, 2));
+    assert_same_cost(&map, (10, 14), (10, 8));
+}
+
+#[test]
+fn test_jps_matches_astar_on_scattered_walls() {
+    for seed in 1..=8 {
+        let map = scattered_walls(seed, 30);
+        let open: Vec<(i32, i32)> = map.floor_cells_on(0);
+        let (start, goal) = (open[0], open[open.len() - 1]);
+        assert_same_cost(&map, start, goal);
+        assert_same_cost(&map, open[open.len() / 3], open[open.len() / 2]);
+    }
+}
+
+#[test]
+fn test_jps_path_steps_are_connected() {
+    let map = room_with_obstacles();
+    let search = jump_point_search(&map, 0, (1, 1), (38, 38)).unwrap();
+    for pair in search.path.windows(2) {
+        let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+        assert!(dx.abs() <= 1 && dy.abs() <= 1 && (dx, dy) != (0, 0));
+        assert!(!map.is_solid_on(0, pair[1].0, pair[1].1));
+    }
+}
+
+#[test]
+fn test_jps_expands_far_fewer_nodes_around_a_wall() {
+    // In a truly open room A* already heads straight for the goal and
+    // expands little more than the path itself, so there's nothing to save.
+    // A long wall between start and goal sends A* flooding the near half of
+    // the room, while JPS jumps straight across it.
+    let mut map = open_room(64, 64);
+    for x in 0..60 {
+        map.layers[0]
+            .collision
+            .insert((x, 32), TileType::Wall { height: 16.0 });
+    }
+    let jps = jump_point_search(&map, 0, (0, 0), (0, 63)).unwrap();
+    let reference = astar_search(&map, 0, (0, 0), (0, 63)).unwrap();
+
+    assert_eq!(jps.cost, reference.cost);
+    assert!(
+        jps.expanded * 50 < reference.expanded,
+        "JPS expanded {} nodes, A* expanded {}",
+        jps.expanded,
+        reference.expanded
+    );
+}
+
+#[test]
+fn test_jps_unreachable_goal() {
+    let mut map = open_room(40, 40);
+    for y in 0..40 {
+        map.layers[0]
+            .collision
+            .insert((20, y), TileType::Wall { height: 16.0 });
+    }
+    assert!(jump_point_search(&map, 0, (1, 1), (38, 38)).is_none());
+    assert!(jump_point_search(&map, 0, (1, 1), (20, 5)).is_none());
+}
+
+#[test]
+fn test_find_path_picks_search_by_map() {
+    assert!(!uses_jump_point_search(&open_room(5, 5), 0));
+    assert!(uses_jump_point_search(&open_room(40, 40), 0));
+
+    let mut weighted = open_room(40, 40);
+    weighted.layers[0].costs.insert((3, 3), 2.0);
+    assert!(!uses_jump_point_search(&weighted, 0));
+
+    // A* on the small map and JPS on the large one both cut straight along
+    // the diagonal
+    let (start, goal) = (grid_to_world(0, 0), grid_to_world(10, 10));
+    for size in [20, 40] {
+        let map = open_room(size, size);
+        let path = find_path(&map, 0, start.0, start.1, goal.0, goal.1).unwrap();
+        assert_eq!(path.len(), 11);
+    }
+}
@@ -1,10 +1,26 @@
 use crate::world::Map;
 use bevy::prelude::*;
+use path_budget::PathBudget;
 
 pub mod aggressive_behavior;
+pub mod flee_behavior;
+#[cfg(test)]
+mod flee_behavior_test;
+pub mod flock_behavior;
+#[cfg(test)]
+mod flock_behavior_test;
+pub mod jump_point_search;
+#[cfg(test)]
+mod jump_point_search_test;
+pub mod path_budget;
+#[cfg(test)]
+mod path_budget_test;
 pub mod pathfinding;
 #[cfg(test)]
 mod pathfinding_test;
+pub mod ranged_behavior;
+#[cfg(test)]
+mod ranged_behavior_test;
 pub mod stand_behavior;
 pub mod systems;
 pub mod wander_behavior;
@@ -13,13 +29,66 @@ pub use systems::AIPlugin;
 
 /// Minimal actor data needed by behaviors
 pub struct ActorData {
+    /// Floor of the map the actor is on, for collision and pathfinding
+    pub layer: usize,
     pub attack_state: crate::actor::ActorAttackState,
     pub attack_range: f32,
+    /// Nearby actors of the same type, for behaviors that move as a group
+    pub neighbors: Vec<FlockNeighbor>,
+    /// The actor's own health (0.0 = dead, 1.0 = full)
+    pub health_fraction: f32,
+    /// The player's health on the same scale, if there is a player
+    pub player_health_fraction: Option<f32>,
+}
+
+/// Player health fraction at or below which actors press the attack
+pub const PLAYER_NEAR_DEATH: f32 = 0.25;
+
+impl ActorData {
+    /// True when the player is low enough that actors should go for the kill
+    pub fn player_near_death(&self) -> bool {
+        self.player_health_fraction
+            .is_some_and(|health| health <= PLAYER_NEAR_DEATH)
+    }
+}
+
+/// Move an actor by `step`, sliding along walls by trying each axis on its
+/// own when the full step is blocked. Returns true if the actor moved.
+pub fn try_move_with_slide(
+    transform: &mut Transform,
+    map: &Map,
+    layer: usize,
+    step: Vec2,
+    radius: f32,
+) -> bool {
+    let position = transform.translation.truncate();
+    for offset in [step, Vec2::new(step.x, 0.0), Vec2::new(0.0, step.y)] {
+        let new_pos = position + offset;
+        if offset != Vec2::ZERO && map.can_move_to_on(layer, new_pos.x, new_pos.y, radius) {
+            transform.translation.x = new_pos.x;
+            transform.translation.y = new_pos.y;
+            return true;
+        }
+    }
+    false
+}
+
+/// Position and velocity of a nearby actor
+#[derive(Debug, Clone, Copy)]
+pub struct FlockNeighbor {
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+/// A projectile attack requested by a behavior
+pub struct RangedAttack {
+    pub projectile_speed: f32,
 }
 
 /// Trait for defining actor behaviors
 pub trait ActorBehavior: Send + Sync {
-    /// Update the behavior for the current frame
+    /// Update the behavior for the current frame. Path searches must be taken
+    /// from `path_budget`, which is shared by all actors for the frame.
     /// Returns true if the actor is currently moving
     fn update(
         &mut self,
@@ -29,8 +98,15 @@ pub trait ActorBehavior: Send + Sync {
         speed_multiplier: f32,
         player_position: Option<Vec2>,
         actor: &ActorData,
+        path_budget: &mut PathBudget,
     ) -> bool;
 
+    /// Take the ranged attack requested during `update`, if any. Projectiles
+    /// are spawned by the combat systems, which behaviors don't have access to.
+    fn take_ranged_attack(&mut self) -> Option<RangedAttack> {
+        None
+    }
+
     /// Get the behavior label
     fn get_label(&self) -> &str;
 }
@@ -0,0 +1,62 @@
+/// Maximum number of path searches all actors may run in a single frame.
+/// Actors that miss out keep following their current path and try again on
+/// the next frame.
+pub const MAX_PATH_REPLANS_PER_FRAME: u32 = 4;
+
+/// Shared per-frame budget of path searches, so a large number of actors
+/// replanning at once doesn't stall the frame
+pub struct PathBudget {
+    remaining: u32,
+    denied: u32,
+}
+
+impl PathBudget {
+    pub fn new(max_replans: u32) -> Self {
+        Self {
+            remaining: max_replans,
+            denied: 0,
+        }
+    }
+
+    /// Use one path search from the budget. Returns false if the budget for
+    /// this frame has been used up.
+    pub fn try_consume(&mut self) -> bool {
+        if self.remaining == 0 {
+            self.denied += 1;
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+
+    #[cfg(test)]
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// Number of path searches refused this frame
+    pub fn denied(&self) -> u32 {
+        self.denied
+    }
+}
+
+/// The order actors get to use the path budget in. Each frame starts from
+/// the first actor that was refused a search on the frame before, so actors
+/// late in the list aren't starved by those ahead of them.
+#[derive(Default)]
+pub struct ReplanRotation {
+    start: usize,
+}
+
+impl ReplanRotation {
+    /// Indices of `count` actors in the order they use the budget this frame
+    pub fn order(&self, count: usize) -> impl Iterator<Item = usize> {
+        let start = if count == 0 { 0 } else { self.start % count };
+        (start..count).chain(0..start)
+    }
+
+    /// Start the next frame from the actor at `index`
+    pub fn start_from(&mut self, index: usize) {
+        self.start = index;
+    }
+}
@@ -0,0 +1,64 @@
+use crate::ai::path_budget::{PathBudget, ReplanRotation};
+use crate::ai::pathfinding::target_cell_changed;
+
+#[test]
+fn test_path_budget_is_limited() {
+    let mut budget = PathBudget::new(2);
+    assert!(budget.try_consume());
+    assert!(budget.try_consume());
+    assert!(!budget.try_consume());
+    assert_eq!(budget.remaining(), 0);
+    assert_eq!(budget.denied(), 1);
+}
+
+#[test]
+fn test_replan_rotation_order() {
+    let mut rotation = ReplanRotation::default();
+    assert_eq!(rotation.order(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+
+    rotation.start_from(2);
+    assert_eq!(rotation.order(3).collect::<Vec<_>>(), vec![2, 0, 1]);
+
+    // Actors may have despawned since the last frame
+    rotation.start_from(4);
+    assert_eq!(rotation.order(3).collect::<Vec<_>>(), vec![1, 2, 0]);
+    assert_eq!(rotation.order(0).count(), 0);
+}
+
+#[test]
+fn test_replan_budget_does_not_starve_later_actors() {
+    // Ten actors all want a new path every frame, as update_actor_behavior
+    // hands out a budget of four
+    const ACTORS: usize = 10;
+    let mut rotation = ReplanRotation::default();
+    let mut last_replan = [None; ACTORS];
+
+    for frame in 0..3 {
+        let mut budget = PathBudget::new(4);
+        let mut first_denied = None;
+        for index in rotation.order(ACTORS) {
+            if budget.try_consume() {
+                last_replan[index] = Some(frame);
+            } else if first_denied.is_none() {
+                first_denied = Some(index);
+            }
+        }
+        if let Some(index) = first_denied {
+            rotation.start_from(index);
+        }
+    }
+
+    // Every actor got a search within the three frames it takes to go round
+    assert!(last_replan.iter().all(|frame| frame.is_some()));
+}
+
+#[test]
+fn test_target_cell_unchanged_skips_replan() {
+    // Moving within the same 8x8 cell does not require a new path
+    assert!(!target_cell_changed((2, 3), 16.5, 24.5));
+    assert!(!target_cell_changed((2, 3), 23.9, 31.9));
+
+    // Crossing into a neighboring cell does
+    assert!(target_cell_changed((2, 3), 24.0, 24.5));
+    assert!(target_cell_changed((2, 3), 16.5, 23.9));
+}
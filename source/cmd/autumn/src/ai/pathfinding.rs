@@ -1,7 +1,20 @@
-use crate::world::Map;
+use super::jump_point_search::{
+    DIAGONAL_COST, GridSearch, STRAIGHT_COST, all_directions, jump_point_search, octile_distance,
+};
+use crate::world::{GRID_SIZE, Map};
+use bevy::prelude::*;
 use pathfinding::prelude::astar;
 
-const GRID_SIZE: f32 = 8.0;
+/// Maps with at least this many cells use Jump Point Search when every cell
+/// costs the same. Small maps stay on A*, which is fast enough.
+pub const JPS_MIN_CELLS: i32 = 32 * 32;
+
+/// A* needs integer costs, so cell costs are counted in hundredths
+const COST_SCALE: f32 = 100.0;
+
+fn scaled_cost(cost: f32) -> u32 {
+    (cost * COST_SCALE).round().max(1.0) as u32
+}
 
 /// Convert world coordinates to grid coordinates
 pub fn world_to_grid(world_x: f32, world_y: f32) -> (i32, i32) {
@@ -19,10 +32,27 @@ pub fn grid_to_world(grid_x: i32, grid_y: i32) -> (f32, f32) {
     )
 }
 
-/// Find a path from start to goal using A* pathfinding
+/// Check whether a target has moved into a different grid cell than the one
+/// a path was last planned to. If not, the existing path is still good.
+pub fn target_cell_changed(previous_cell: (i32, i32), target_x: f32, target_y: f32) -> bool {
+    world_to_grid(target_x, target_y) != previous_cell
+}
+
+/// Whether `find_path` will use Jump Point Search on a floor of this map.
+/// JPS assumes uniform costs, so weighted floors always fall back to A*.
+pub fn uses_jump_point_search(map: &Map, layer: usize) -> bool {
+    map.has_uniform_costs_on(layer) && map.width * map.height >= JPS_MIN_CELLS
+}
+
+/// Find a path from start to goal on a floor of the map using A* pathfinding,
+/// weighing each step by the cost of the cell it enters (see
+/// `Map::move_cost_on`). Paths are 8-connected and never cut wall corners.
+/// Large uniform maps use Jump Point Search instead, which finds a path of
+/// the same length while expanding far fewer nodes.
 /// Returns a list of world positions to follow
 pub fn find_path(
     map: &Map,
+    layer: usize,
     start_x: f32,
     start_y: f32,
     goal_x: f32,
@@ -32,35 +62,157 @@ pub fn find_path(
     let goal_grid = world_to_grid(goal_x, goal_y);
 
     // Check if goal is walkable
-    if map.is_solid(goal_grid.0, goal_grid.1) {
+    if map.is_solid_on(layer, goal_grid.0, goal_grid.1) {
         return None;
     }
 
-    let result = astar(
-        &start_grid,
-        |&(x, y)| {
-            // Generate neighbors (4-directional movement)
-            let mut neighbors = Vec::new();
-            for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-                let nx = x + dx;
-                let ny = y + dy;
-                if !map.is_solid(nx, ny) {
-                    neighbors.push(((nx, ny), 1));
-                }
-            }
-            neighbors
-        },
-        |&(x, y)| {
-            // Manhattan distance heuristic
-            ((x - goal_grid.0).abs() + (y - goal_grid.1).abs()) as u32
-        },
-        |&pos| pos == goal_grid,
-    );
+    let search = if uses_jump_point_search(map, layer) {
+        jump_point_search(map, layer, start_grid, goal_grid)
+    } else {
+        astar_search(map, layer, start_grid, goal_grid)
+    };
 
-    result.map(|(path, _cost)| {
+    search.map(|search| {
         // Convert grid path to world positions
-        path.into_iter()
+        search
+            .path
+            .into_iter()
             .map(|(gx, gy)| grid_to_world(gx, gy))
             .collect()
     })
 }
+
+/// A* over grid cells with the same moves as `jump_point_search`. A step
+/// costs the cell it enters, times √2 for diagonals.
+pub fn astar_search(
+    map: &Map,
+    layer: usize,
+    start: (i32, i32),
+    goal: (i32, i32),
+) -> Option<GridSearch> {
+    // Scale the heuristic by the cheapest cell so it never overestimates
+    let min_step = scaled_cost(map.min_move_cost_on(layer));
+
+    let mut expanded = 0;
+    let (path, cost) = astar(
+        &start,
+        |&(x, y)| {
+            expanded += 1;
+            all_directions(map, layer, x, y)
+                .into_iter()
+                .map(|(dx, dy)| {
+                    let (nx, ny) = (x + dx, y + dy);
+                    let step = if dx != 0 && dy != 0 {
+                        DIAGONAL_COST
+                    } else {
+                        STRAIGHT_COST
+                    };
+                    let cost = scaled_cost(map.move_cost_on(layer, nx, ny)) * step / STRAIGHT_COST;
+                    ((nx, ny), cost)
+                })
+                .collect::<Vec<_>>()
+        },
+        |&cell| octile_distance(cell, goal) * min_step / STRAIGHT_COST,
+        |&cell| cell == goal,
+    )?;
+
+    Some(GridSearch {
+        path,
+        cost,
+        expanded,
+    })
+}
+
+/// Find a path like `find_path`, then remove waypoints that can be skipped by
+/// walking in a straight line. `clearance` is the radius of the actor following
+/// the path, so shortcuts don't clip wall corners.
+pub fn find_path_smoothed(
+    map: &Map,
+    layer: usize,
+    start_x: f32,
+    start_y: f32,
+    goal_x: f32,
+    goal_y: f32,
+    clearance: f32,
+) -> Option<Vec<(f32, f32)>> {
+    find_path(map, layer, start_x, start_y, goal_x, goal_y)
+        .map(|path| smooth_path(map, layer, &path, clearance))
+}
+
+/// Line-of-sight string pulling: from each kept waypoint, skip ahead to the
+/// furthest waypoint that can be reached in a straight line. Shortcuts never
+/// cut across cells costlier than the waypoints they skip, so a path that
+/// detours along a road stays on it.
+pub fn smooth_path(
+    map: &Map,
+    layer: usize,
+    path: &[(f32, f32)],
+    clearance: f32,
+) -> Vec<(f32, f32)> {
+    if path.len() <= 2 {
+        return path.to_vec();
+    }
+
+    let mut smoothed = vec![path[0]];
+    let mut anchor = 0;
+
+    while anchor < path.len() - 1 {
+        let mut next = anchor + 1;
+        for candidate in (anchor + 2..path.len()).rev() {
+            if has_clear_line(map, layer, path[anchor], path[candidate], clearance)
+                && !crosses_costlier_cells(map, layer, &path[anchor..=candidate])
+            {
+                next = candidate;
+                break;
+            }
+        }
+        smoothed.push(path[next]);
+        anchor = next;
+    }
+
+    smoothed
+}
+
+/// Check line of sight along the center line and along both edges of a
+/// corridor `clearance` wide on each side
+fn has_clear_line(
+    map: &Map,
+    layer: usize,
+    from: (f32, f32),
+    to: (f32, f32),
+    clearance: f32,
+) -> bool {
+    let from = Vec2::new(from.0, from.1);
+    let to = Vec2::new(to.0, to.1);
+    let offset = (to - from).perp().normalize_or_zero() * clearance;
+
+    map.has_line_of_sight_on(layer, from, to)
+        && map.has_line_of_sight_on(layer, from + offset, to + offset)
+        && map.has_line_of_sight_on(layer, from - offset, to - offset)
+}
+
+/// Check whether the straight line across a run of waypoints enters any cell
+/// that costs more than the most expensive waypoint cell
+fn crosses_costlier_cells(map: &Map, layer: usize, waypoints: &[(f32, f32)]) -> bool {
+    if map.has_uniform_costs_on(layer) {
+        return false;
+    }
+
+    let cost_at = |pos: Vec2| {
+        let (grid_x, grid_y) = world_to_grid(pos.x, pos.y);
+        map.move_cost_on(layer, grid_x, grid_y)
+    };
+    let limit = waypoints
+        .iter()
+        .map(|&(x, y)| cost_at(Vec2::new(x, y)))
+        .fold(0.0, f32::max);
+
+    let (Some(&from), Some(&to)) = (waypoints.first(), waypoints.last()) else {
+        return false;
+    };
+    let from = Vec2::new(from.0, from.1);
+    let to = Vec2::new(to.0, to.1);
+    // Sample every quarter cell along the line
+    let steps = (from.distance(to) / (GRID_SIZE / 4.0)).ceil() as usize;
+    (0..=steps).any(|i| cost_at(from.lerp(to, i as f32 / steps.max(1) as f32)) > limit)
+}
@@ -1,6 +1,6 @@
-use crate::ai::pathfinding::{find_path, grid_to_world, world_to_grid};
-use crate::world::{Map, TileType};
-use std::collections::HashMap;
+use crate::ai::pathfinding::{find_path, find_path_smoothed, grid_to_world, world_to_grid};
+use crate::world::{Map, MapFile, TileType};
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn test_world_to_grid_conversion() {
@@ -27,18 +27,11 @@ fn test_pathfinding_simple_path() {
         }
     }
 
-    let map = Map {
-        width: 5,
-        height: 5,
-        collision_grid,
-        walls: HashMap::new(),
-        items: HashMap::new(),
-        item_world_positions: Vec::new(),
-        actors: HashMap::new(),
-    };
+    let mut map = Map::new(5, 5);
+    map.layers[0].collision = collision_grid;
 
     // Find path from (4.0, 4.0) to (20.0, 20.0)
-    let path = find_path(&map, 4.0, 4.0, 20.0, 20.0);
+    let path = find_path(&map, 0, 4.0, 4.0, 20.0, 20.0);
     assert!(path.is_some());
 
     let path = path.unwrap();
@@ -60,17 +53,109 @@ fn test_pathfinding_blocked_destination() {
     // Block the destination
     collision_grid.insert((2, 2), TileType::Wall { height: 1.0 });
 
-    let map = Map {
-        width: 5,
-        height: 5,
-        collision_grid,
-        walls: HashMap::new(),
-        items: HashMap::new(),
-        item_world_positions: Vec::new(),
-        actors: HashMap::new(),
-    };
+    let mut map = Map::new(5, 5);
+    map.layers[0].collision = collision_grid;
 
     // Try to find path to blocked location
-    let path = find_path(&map, 4.0, 4.0, 20.0, 20.0);
+    let path = find_path(&map, 0, 4.0, 4.0, 20.0, 20.0);
     assert!(path.is_none());
 }
+
+#[test]
+fn test_pathfinding_smoothed_open_room() {
+    // Open 5x5 room: the grid path steps diagonally through every cell, but
+    // the smoothed path is a single straight segment
+    let map = Map::new(5, 5);
+
+    let raw_path = find_path(&map, 0, 4.0, 4.0, 36.0, 36.0).unwrap();
+    assert_eq!(raw_path.len(), 5);
+
+    let path = find_path_smoothed(&map, 0, 4.0, 4.0, 36.0, 36.0, 1.2).unwrap();
+    assert_eq!(path, vec![(4.0, 4.0), (36.0, 36.0)]);
+}
+
+#[test]
+fn test_pathfinding_does_not_cut_corners() {
+    // The diagonal from (1, 1) to (2, 2) squeezes past the wall at (2, 1)
+    let map = map_from_rows(&["XXXX", "X.XX", "X..X", "XXXX"]);
+    let (start, goal) = (grid_to_world(1, 1), grid_to_world(2, 2));
+
+    let path = find_path(&map, 0, start.0, start.1, goal.0, goal.1).unwrap();
+    assert_eq!(path, vec![start, grid_to_world(1, 2), goal]);
+}
+
+#[test]
+fn test_pathfinding_smoothed_around_wall() {
+    // 5x5 room with a wall in the middle column, open only at the top
+    let mut map = Map::new(5, 5);
+    for y in 0..4 {
+        map.layers[0]
+            .collision
+            .insert((2, y), TileType::Wall { height: 16.0 });
+    }
+
+    let raw_path = find_path(&map, 0, 4.0, 4.0, 36.0, 4.0).unwrap();
+    let path = find_path_smoothed(&map, 0, 4.0, 4.0, 36.0, 4.0, 1.2).unwrap();
+
+    assert!(path.len() > 2, "Wall should prevent a direct path");
+    assert!(path.len() < raw_path.len());
+    assert_eq!(path[0], (4.0, 4.0));
+    assert_eq!(path[path.len() - 1], (36.0, 4.0));
+
+    // Every remaining segment must be walkable in a straight line
+    for segment in path.windows(2) {
+        let from = bevy::prelude::Vec2::new(segment[0].0, segment[0].1);
+        let to = bevy::prelude::Vec2::new(segment[1].0, segment[1].1);
+        assert!(map.has_line_of_sight(from, to));
+    }
+}
+
+fn map_from_rows(rows: &[&str]) -> Map {
+    Map::from_map_file(&MapFile {
+        grid: rows.iter().map(|row| row.to_string()).collect(),
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    })
+}
+
+#[test]
+fn test_pathfinding_avoids_expensive_shortcut() {
+    // The straight route along row 2 wades through mud; the detour along
+    // row 1 steps diagonally up and back down, longer but cheaper
+    let map = map_from_rows(&["XXXXXXX", "X.....X", "X.~~~.X", "XXXXXXX"]);
+    let (start, goal) = (grid_to_world(1, 2), grid_to_world(5, 2));
+
+    let path = find_path(&map, 0, start.0, start.1, goal.0, goal.1).unwrap();
+    assert_eq!(path.len(), 5);
+    assert!(!path.contains(&grid_to_world(3, 2)));
+    assert!(path.contains(&grid_to_world(3, 1)));
+
+    // Smoothing must not cut back across the mud
+    let smoothed = find_path_smoothed(&map, 0, start.0, start.1, goal.0, goal.1, 1.2).unwrap();
+    assert!(smoothed.iter().any(|&(_, y)| y == grid_to_world(0, 1).1));
+}
+
+#[test]
+fn test_pathfinding_prefers_road() {
+    // Walking straight along row 2 takes four steps at full cost; the road
+    // along row 1 is longer, with a diagonal at each end, but cheaper overall
+    let map = map_from_rows(&["XXXXXXX", "X=====X", "X.....X", "XXXXXXX"]);
+    let (start, goal) = (grid_to_world(1, 2), grid_to_world(5, 2));
+
+    let path = find_path(&map, 0, start.0, start.1, goal.0, goal.1).unwrap();
+    assert!(path.contains(&grid_to_world(3, 1)));
+    assert!(!path.contains(&grid_to_world(3, 2)));
+}
+
+#[test]
+fn test_pathfinding_uniform_costs_take_shortest_route() {
+    let map = map_from_rows(&["XXXXXXX", "X.....X", "X.....X", "XXXXXXX"]);
+    let (start, goal) = (grid_to_world(1, 2), grid_to_world(5, 2));
+
+    let path = find_path(&map, 0, start.0, start.1, goal.0, goal.1).unwrap();
+    assert_eq!(path.len(), 5);
+}
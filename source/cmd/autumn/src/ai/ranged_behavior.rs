@@ -0,0 +1,143 @@
+use super::path_budget::PathBudget;
+use super::wander_behavior::WanderBehavior;
+use super::{ActorBehavior, RangedAttack};
+use crate::world::Map;
+use bevy::prelude::*;
+
+const MOVEMENT_SPEED: f32 = 10.0;
+const ACTOR_RADIUS: f32 = 1.2;
+
+// Beyond this distance the player is ignored and the actor wanders
+const DETECTION_RANGE: f32 = 35.0;
+
+/// How a ranged actor should move to keep its preferred distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RangeMovement {
+    /// Too far away: move toward the player
+    Approach,
+    /// Too close: back away from the player
+    Retreat,
+    /// Within tolerance of the preferred distance: stay put
+    Hold,
+}
+
+/// Pick the movement that brings `distance` back within `tolerance` of
+/// `preferred_distance`
+pub fn decide_range_movement(
+    distance: f32,
+    preferred_distance: f32,
+    tolerance: f32,
+) -> RangeMovement {
+    if distance > preferred_distance + tolerance {
+        RangeMovement::Approach
+    } else if distance < preferred_distance - tolerance {
+        RangeMovement::Retreat
+    } else {
+        RangeMovement::Hold
+    }
+}
+
+/// Ranged behavior - wanders until it sees the player, then keeps its
+/// distance (kiting) and fires projectiles on a cooldown
+pub struct RangedBehavior {
+    preferred_distance: f32,
+    distance_tolerance: f32,
+    cooldown: f32,
+    projectile_speed: f32,
+    cooldown_timer: f32,
+    fire_requested: bool,
+    wander: WanderBehavior,
+}
+
+impl RangedBehavior {
+    pub fn new(
+        preferred_distance: f32,
+        distance_tolerance: f32,
+        cooldown: f32,
+        projectile_speed: f32,
+    ) -> Self {
+        Self {
+            preferred_distance,
+            distance_tolerance,
+            cooldown,
+            projectile_speed,
+            // Give the player a moment before the first shot
+            cooldown_timer: cooldown,
+            fire_requested: false,
+            wander: WanderBehavior::new(),
+        }
+    }
+}
+
+impl ActorBehavior for RangedBehavior {
+    fn update(
+        &mut self,
+        transform: &mut Transform,
+        map: &Map,
+        delta_time: f32,
+        speed_multiplier: f32,
+        player_position: Option<Vec2>,
+        actor: &crate::ai::ActorData,
+        path_budget: &mut PathBudget,
+    ) -> bool {
+        self.cooldown_timer = (self.cooldown_timer - delta_time).max(0.0);
+
+        let actor_pos = Vec2::new(transform.translation.x, transform.translation.y);
+        let layer = actor.layer;
+        let target = player_position.filter(|player_pos| {
+            actor_pos.distance(*player_pos) <= DETECTION_RANGE
+                && map.has_line_of_sight_on(layer, actor_pos, *player_pos)
+        });
+        let Some(player_pos) = target else {
+            return self.wander.update(
+                transform,
+                map,
+                delta_time,
+                speed_multiplier,
+                player_position,
+                actor,
+                path_budget,
+            );
+        };
+
+        // Shoot whenever the cooldown allows and no melee attack is underway
+        if self.cooldown_timer <= 0.0 && actor.attack_state == crate::actor::ActorAttackState::Idle
+        {
+            self.fire_requested = true;
+            self.cooldown_timer = self.cooldown;
+        }
+
+        let to_player = player_pos - actor_pos;
+        let direction = match decide_range_movement(
+            to_player.length(),
+            self.preferred_distance,
+            self.distance_tolerance,
+        ) {
+            RangeMovement::Approach => to_player.normalize_or_zero(),
+            RangeMovement::Retreat => -to_player.normalize_or_zero(),
+            RangeMovement::Hold => return false,
+        };
+
+        let new_pos = actor_pos + direction * MOVEMENT_SPEED * speed_multiplier * delta_time;
+        if !map.can_move_to_on(layer, new_pos.x, new_pos.y, ACTOR_RADIUS) {
+            // Backed against a wall (or blocked going forward): stand and shoot
+            return false;
+        }
+        transform.translation.x = new_pos.x;
+        transform.translation.y = new_pos.y;
+        true
+    }
+
+    fn take_ranged_attack(&mut self) -> Option<RangedAttack> {
+        if !std::mem::take(&mut self.fire_requested) {
+            return None;
+        }
+        Some(RangedAttack {
+            projectile_speed: self.projectile_speed,
+        })
+    }
+
+    fn get_label(&self) -> &str {
+        "ranged"
+    }
+}
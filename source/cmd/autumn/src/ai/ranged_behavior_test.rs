@@ -0,0 +1,43 @@
+use crate::ai::ranged_behavior::{RangeMovement, decide_range_movement};
+
+#[test]
+fn test_too_far_approaches() {
+    assert_eq!(
+        decide_range_movement(30.0, 15.0, 3.0),
+        RangeMovement::Approach
+    );
+}
+
+#[test]
+fn test_too_close_retreats() {
+    assert_eq!(
+        decide_range_movement(5.0, 15.0, 3.0),
+        RangeMovement::Retreat
+    );
+}
+
+#[test]
+fn test_within_tolerance_holds() {
+    assert_eq!(decide_range_movement(15.0, 15.0, 3.0), RangeMovement::Hold);
+    assert_eq!(decide_range_movement(12.5, 15.0, 3.0), RangeMovement::Hold);
+    assert_eq!(decide_range_movement(17.5, 15.0, 3.0), RangeMovement::Hold);
+}
+
+#[test]
+fn test_tolerance_edges_hold() {
+    assert_eq!(decide_range_movement(12.0, 15.0, 3.0), RangeMovement::Hold);
+    assert_eq!(decide_range_movement(18.0, 15.0, 3.0), RangeMovement::Hold);
+}
+
+#[test]
+fn test_zero_tolerance_only_holds_at_exact_distance() {
+    assert_eq!(decide_range_movement(10.0, 10.0, 0.0), RangeMovement::Hold);
+    assert_eq!(
+        decide_range_movement(10.1, 10.0, 0.0),
+        RangeMovement::Approach
+    );
+    assert_eq!(
+        decide_range_movement(9.9, 10.0, 0.0),
+        RangeMovement::Retreat
+    );
+}
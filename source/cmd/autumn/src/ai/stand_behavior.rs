@@ -14,6 +14,7 @@ impl ActorBehavior for StandBehavior {
         _speed_multiplier: f32,
         _player_position: Option<Vec2>,
         _actor: &crate::ai::ActorData,
+        _path_budget: &mut crate::ai::path_budget::PathBudget,
     ) -> bool {
         false // Not moving
     }
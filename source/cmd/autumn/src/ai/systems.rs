@@ -1,12 +1,21 @@
-use crate::actor::Actor;
-use crate::combat::{update_actor_attack_animation, update_actor_attacks, update_actor_stun};
+use super::FlockNeighbor;
+use super::path_budget::{MAX_PATH_REPLANS_PER_FRAME, PathBudget, ReplanRotation};
+use crate::actor::{Actor, SpatialHash};
+use crate::combat::{
+    update_actor_attack_animation, update_actor_attacks, update_actor_stun, update_projectiles,
+    update_ranged_attacks,
+};
 use crate::game_state::GameState;
+use crate::rendering::Culled;
 use crate::world::Map;
 use bevy::prelude::*;
 
 const WIGGLE_AMPLITUDE: f32 = 0.1;
 const WIGGLE_FREQUENCY: f32 = 10.0;
 
+/// Same-type actors within this distance are passed to behaviors as neighbors
+const NEIGHBOR_RADIUS: f32 = 20.0;
+
 pub struct AIPlugin;
 
 impl Plugin for AIPlugin {
@@ -16,9 +25,11 @@ impl Plugin for AIPlugin {
             (
                 update_actor_stun,
                 update_actor_behavior,
+                update_ranged_attacks,
                 add_actor_wiggle,
                 update_actor_attacks,
                 update_actor_attack_animation,
+                update_projectiles,
             )
                 .chain()
                 .run_if(in_state(GameState::Playing)),
@@ -28,37 +39,100 @@ impl Plugin for AIPlugin {
 
 /// Update all actor behaviors
 fn update_actor_behavior(
-    mut actors: Query<(Entity, &mut Actor, &mut Transform), Without<crate::camera::Player>>,
-    player_query: Query<&Transform, With<crate::camera::Player>>,
+    mut actors: Query<
+        (Entity, &mut Actor, &mut Transform, Has<Culled>),
+        Without<crate::camera::Player>,
+    >,
+    player_query: Query<(&Transform, &crate::camera::Player)>,
     map: Res<Map>,
     time: Res<Time>,
+    mut rotation: Local<ReplanRotation>,
 ) {
-    // Get player position if available
-    let player_position = player_query
-        .single()
-        .ok()
-        .map(|t| Vec2::new(t.translation.x, t.translation.y));
+    // Get player position and health if available
+    let player = player_query.single().ok();
+    let player_position = player.map(|(t, _)| Vec2::new(t.translation.x, t.translation.y));
+    let player_health_fraction =
+        player.map(|(_, player)| player.current_health / player.max_health);
+
+    let mut path_budget = PathBudget::new(MAX_PATH_REPLANS_PER_FRAME);
+    let delta_time = time.delta_secs();
+
+    // Snapshot where every actor starts the frame so neighbor lookups don't
+    // depend on update order
+    let snapshot: Vec<(Entity, String, FlockNeighbor)> = actors
+        .iter()
+        .map(|(entity, actor, transform, _)| {
+            let neighbor = FlockNeighbor {
+                position: transform.translation.truncate(),
+                velocity: actor.velocity,
+            };
+            (entity, actor.actor_type.clone(), neighbor)
+        })
+        .collect();
+    let mut neighbor_hash = SpatialHash::new(NEIGHBOR_RADIUS);
+    for (index, (_, _, neighbor)) in snapshot.iter().enumerate() {
+        neighbor_hash.insert(index, neighbor.position);
+    }
+
+    let mut first_denied = None;
+    for index in rotation.order(snapshot.len()) {
+        let entity = snapshot[index].0;
+        let Ok((_, mut actor, mut transform, culled)) = actors.get_mut(entity) else {
+            continue;
+        };
+
+        // Actors out of view distance stand still until the player returns
+        if culled {
+            actor.is_moving = false;
+            actor.velocity = Vec2::ZERO;
+            continue;
+        }
 
-    for (_entity, mut actor, mut transform) in actors.iter_mut() {
         let speed = actor.speed_multiplier;
+        let start_position = transform.translation.truncate();
+        let neighbors = neighbor_hash
+            .query(start_position, NEIGHBOR_RADIUS)
+            .into_iter()
+            .map(|index| &snapshot[index])
+            .filter(|(other, actor_type, _)| *other != entity && *actor_type == actor.actor_type)
+            .map(|(_, _, neighbor)| *neighbor)
+            .collect();
+
         // Extract necessary actor data before borrowing behavior mutably
         let actor_data = crate::ai::ActorData {
+            layer: actor.layer,
             attack_state: actor.attack_state,
             attack_range: actor.attack_range,
+            neighbors,
+            health_fraction: actor.health / actor.max_health,
+            player_health_fraction,
         };
 
         if let Some(ref mut behavior) = actor.behavior {
+            let denied_before = path_budget.denied();
             let is_moving = behavior.update(
                 &mut transform,
                 &map,
-                time.delta_secs(),
+                delta_time,
                 speed,
-                player_position,
+                player_position.filter(|_| actor_data.layer == map.active_layer),
                 &actor_data,
+                &mut path_budget,
             );
             actor.is_moving = is_moving;
+            if first_denied.is_none() && path_budget.denied() > denied_before {
+                first_denied = Some(index);
+            }
+        }
+
+        if delta_time > 0.0 {
+            actor.velocity = (transform.translation.truncate() - start_position) / delta_time;
         }
     }
+
+    if let Some(index) = first_denied {
+        rotation.start_from(index);
+    }
 }
 
 /// Add wiggle animation to moving actors
@@ -1,6 +1,7 @@
 use super::ActorBehavior;
+use super::path_budget::PathBudget;
 use super::pathfinding;
-use crate::world::Map;
+use crate::world::{GRID_SIZE, Map};
 use bevy::prelude::*;
 use rand::Rng;
 
@@ -43,8 +44,10 @@ impl ActorBehavior for WanderBehavior {
         delta_time: f32,
         speed_multiplier: f32,
         _player_position: Option<Vec2>,
-        _actor: &crate::ai::ActorData,
+        actor: &crate::ai::ActorData,
+        path_budget: &mut PathBudget,
     ) -> bool {
+        let layer = actor.layer;
         let mut is_moving = false;
 
         match &mut self.state {
@@ -57,6 +60,11 @@ impl ActorBehavior for WanderBehavior {
             }
 
             WanderState::Planning => {
+                // Stay in planning until a path search is available
+                if !path_budget.try_consume() {
+                    return false;
+                }
+
                 // Generate 2-3 random destinations
                 let mut rng = rand::rng();
                 let num_destinations = rng.random_range(2..=3);
@@ -66,10 +74,10 @@ impl ActorBehavior for WanderBehavior {
                 for _ in 0..num_destinations {
                     for _ in 0..20 {
                         // Max 20 attempts per destination
-                        let dest_x = rng.random_range(0.0..map.width as f32 * 8.0);
-                        let dest_y = rng.random_range(0.0..map.height as f32 * 8.0);
+                        let dest_x = rng.random_range(0.0..map.width as f32 * GRID_SIZE);
+                        let dest_y = rng.random_range(0.0..map.height as f32 * GRID_SIZE);
 
-                        if map.can_move_to(dest_x, dest_y, ACTOR_RADIUS) {
+                        if map.can_move_to_on(layer, dest_x, dest_y, ACTOR_RADIUS) {
                             destinations.push((dest_x, dest_y));
                             break;
                         }
@@ -87,12 +95,14 @@ impl ActorBehavior for WanderBehavior {
                     let current_x = transform.translation.x;
                     let current_y = transform.translation.y;
 
-                    if let Some(path) = pathfinding::find_path(
+                    if let Some(path) = pathfinding::find_path_smoothed(
                         map,
+                        layer,
                         current_x,
                         current_y,
                         destinations[0].0,
                         destinations[0].1,
+                        ACTOR_RADIUS,
                     ) {
                         self.state = WanderState::Moving {
                             path,
@@ -119,17 +129,24 @@ impl ActorBehavior for WanderBehavior {
                 if *current_index >= path.len() {
                     // Reached end of current path
                     if destinations.len() > 1 {
+                        // Wait at the waypoint until a path search is available
+                        if !path_budget.try_consume() {
+                            return false;
+                        }
+
                         // Remove completed destination and path to next one
                         destinations.remove(0);
                         let current_x = transform.translation.x;
                         let current_y = transform.translation.y;
 
-                        if let Some(new_path) = pathfinding::find_path(
+                        if let Some(new_path) = pathfinding::find_path_smoothed(
                             map,
+                            layer,
                             current_x,
                             current_y,
                             destinations[0].0,
                             destinations[0].1,
+                            ACTOR_RADIUS,
                         ) {
                             *path = new_path;
                             *current_index = 0;
@@ -181,7 +198,7 @@ impl ActorBehavior for WanderBehavior {
                         let new_y = current_y + (dy / distance) * move_distance;
 
                         // Check if new position is valid
-                        if map.can_move_to(new_x, new_y, ACTOR_RADIUS) {
+                        if map.can_move_to_on(layer, new_x, new_y, ACTOR_RADIUS) {
                             transform.translation.x = new_x;
                             transform.translation.y = new_y;
                         } else {
@@ -1,7 +1,109 @@
 /// Camera shake effect system
 ///
 /// Provides camera shake visual feedback for combat actions and other events.
+use crate::scripting::{CVarRegistry, CVarValue};
 use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::TAU;
+
+/// Default shake parameters, used to initialize the `combat.shake.*` cvars
+pub const DEFAULT_HIT_SHAKE_AMPLITUDE: f32 = 0.1;
+pub const DEFAULT_HIT_SHAKE_DURATION: f32 = 0.15;
+pub const DEFAULT_CRIT_SHAKE_AMPLITUDE: f32 = 0.2;
+pub const DEFAULT_CRIT_SHAKE_DURATION: f32 = 0.25;
+pub const DEFAULT_SHAKE_DECAY: ShakeDecay = ShakeDecay::Constant;
+/// Maximum roll in degrees. Off by default so shakes stay positional.
+pub const DEFAULT_HIT_SHAKE_ROLL: f32 = 0.0;
+pub const DEFAULT_CRIT_SHAKE_ROLL: f32 = 0.0;
+
+/// How quickly a shake fades over its duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShakeDecay {
+    /// Full strength for the whole duration, then stops
+    Constant,
+    /// Fades at a steady rate
+    Linear,
+    /// Most of the strength is in the first moments of the shake
+    Exponential,
+    /// Holds close to full strength, then eases out towards the end
+    EaseOut,
+}
+
+impl ShakeDecay {
+    /// Steepness of the exponential curve
+    const EXPONENTIAL_RATE: f32 = 5.0;
+
+    /// Parse a curve name as used by the `combat.shake.*_decay` cvars
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "constant" => Some(ShakeDecay::Constant),
+            "linear" => Some(ShakeDecay::Linear),
+            "exponential" => Some(ShakeDecay::Exponential),
+            "ease_out" => Some(ShakeDecay::EaseOut),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShakeDecay::Constant => "constant",
+            ShakeDecay::Linear => "linear",
+            ShakeDecay::Exponential => "exponential",
+            ShakeDecay::EaseOut => "ease_out",
+        }
+    }
+
+    /// Fraction of the shake's strength left after `progress` (0 at the start,
+    /// 1 at the end) of its duration. Every curve starts at 1 and reaches 0.
+    pub fn factor(self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            ShakeDecay::Constant => {
+                if t < 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ShakeDecay::Linear => 1.0 - t,
+            ShakeDecay::Exponential => {
+                // Shifted and rescaled so the tail lands on zero rather than
+                // approaching it
+                let k = Self::EXPONENTIAL_RATE;
+                ((-k * t).exp() - (-k).exp()) / (1.0 - (-k).exp())
+            }
+            ShakeDecay::EaseOut => 1.0 - t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Register the cvars used to tune the shake presets
+pub fn init_camera_shake_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("combat.shake.hit_amplitude", DEFAULT_HIT_SHAKE_AMPLITUDE);
+    cvars.init_f32("combat.shake.hit_duration", DEFAULT_HIT_SHAKE_DURATION);
+    cvars.init_f32("combat.shake.crit_amplitude", DEFAULT_CRIT_SHAKE_AMPLITUDE);
+    cvars.init_f32("combat.shake.crit_duration", DEFAULT_CRIT_SHAKE_DURATION);
+    cvars.init_f32("combat.shake.hit_roll", DEFAULT_HIT_SHAKE_ROLL);
+    cvars.init_f32("combat.shake.crit_roll", DEFAULT_CRIT_SHAKE_ROLL);
+    for name in ["combat.shake.hit_decay", "combat.shake.crit_decay"] {
+        cvars
+            .init_with_help(
+                name,
+                CVarValue::String(DEFAULT_SHAKE_DECAY.name().to_string()),
+                "Shake fade curve: constant, linear, exponential or ease_out",
+            )
+            .unwrap();
+    }
+}
+
+/// Read a decay curve cvar, falling back to the default if it isn't
+/// registered or doesn't name a curve
+fn shake_decay_cvar(cvars: &CVarRegistry, name: &str) -> ShakeDecay {
+    cvars
+        .get(name)
+        .and_then(|v| ShakeDecay::from_name(&v.as_string()))
+        .unwrap_or(DEFAULT_SHAKE_DECAY)
+}
 
 /// Component for camera shake effect
 #[derive(Component, Debug)]
@@ -12,9 +114,26 @@ pub struct CameraShake {
     /// Duration remaining in seconds
     pub duration: f32,
 
+    /// Full duration in seconds, to know how far the shake has decayed
+    pub total_duration: f32,
+
     /// Frequency of shake oscillation
     pub frequency: f32,
 
+    /// How the intensity fades over the duration
+    pub decay: ShakeDecay,
+
+    /// Maximum roll around the view direction, in radians
+    pub roll: f32,
+
+    /// Offset into the roll oscillation so repeated shakes don't all tilt
+    /// the same way
+    pub roll_phase: f32,
+
+    /// Roll currently added to the camera's rotation. It is taken off again
+    /// before mouse look runs (see `remove_camera_shake_roll`).
+    pub applied_roll: f32,
+
     /// Base camera position when shake started
     pub base_position: Vec3,
 }
@@ -25,19 +144,86 @@ impl CameraShake {
         Self {
             intensity,
             duration,
+            total_duration: duration,
             frequency: 20.0, // Default shake frequency
+            decay: DEFAULT_SHAKE_DECAY,
+            roll: 0.0,
+            roll_phase: rand::rng().random_range(0.0..TAU),
+            applied_roll: 0.0,
             base_position,
         }
     }
 
+    pub fn with_decay(mut self, decay: ShakeDecay) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Add a roll of up to `degrees` around the view direction
+    pub fn with_roll(mut self, degrees: f32) -> Self {
+        self.roll = degrees.to_radians();
+        self
+    }
+
+    /// Take over from a shake that is still running on the camera, keeping
+    /// its resting position and roll so they are restored when this one ends
+    pub fn replacing(mut self, previous: Option<&CameraShake>) -> Self {
+        if let Some(previous) = previous {
+            self.base_position = previous.base_position;
+            self.applied_roll = previous.applied_roll;
+        }
+        self
+    }
+
+    /// Fraction of the full strength left at this point of the shake
+    pub fn falloff(&self) -> f32 {
+        if self.total_duration <= 0.0 {
+            return 0.0;
+        }
+        self.decay.factor(1.0 - self.duration / self.total_duration)
+    }
+
+    /// Displacement magnitude at this point of the shake
+    pub fn current_intensity(&self) -> f32 {
+        self.intensity * self.falloff()
+    }
+
     /// Create shake for a hit effect (base_position will be set when inserted)
-    pub fn hit_shake() -> Self {
-        Self::new(0.1, 0.15, Vec3::ZERO)
+    ///
+    /// Amplitude, duration, roll and decay curve come from the
+    /// `combat.shake.hit_*` cvars.
+    pub fn hit_shake(cvars: &CVarRegistry) -> Self {
+        Self::new(
+            cvars.get_f32_or("combat.shake.hit_amplitude", DEFAULT_HIT_SHAKE_AMPLITUDE),
+            cvars.get_f32_or("combat.shake.hit_duration", DEFAULT_HIT_SHAKE_DURATION),
+            Vec3::ZERO,
+        )
+        .with_decay(shake_decay_cvar(cvars, "combat.shake.hit_decay"))
+        .with_roll(cvars.get_f32_or("combat.shake.hit_roll", DEFAULT_HIT_SHAKE_ROLL))
     }
 
     /// Create shake for a critical hit (base_position will be set when inserted)
-    pub fn critical_shake() -> Self {
-        Self::new(0.2, 0.25, Vec3::ZERO)
+    ///
+    /// Amplitude, duration, roll and decay curve come from the
+    /// `combat.shake.crit_*` cvars.
+    pub fn critical_shake(cvars: &CVarRegistry) -> Self {
+        Self::new(
+            cvars.get_f32_or("combat.shake.crit_amplitude", DEFAULT_CRIT_SHAKE_AMPLITUDE),
+            cvars.get_f32_or("combat.shake.crit_duration", DEFAULT_CRIT_SHAKE_DURATION),
+            Vec3::ZERO,
+        )
+        .with_decay(shake_decay_cvar(cvars, "combat.shake.crit_decay"))
+        .with_roll(cvars.get_f32_or("combat.shake.crit_roll", DEFAULT_CRIT_SHAKE_ROLL))
+    }
+}
+
+/// System to take the shake's roll back off the camera before mouse look
+/// rotates it. Pitching around a rolled right vector would otherwise leave
+/// some of the roll behind once the shake ends.
+pub fn remove_camera_shake_roll(mut query: Query<(&mut Transform, &mut CameraShake)>) {
+    for (mut transform, mut shake) in query.iter_mut() {
+        transform.rotate_local_z(-shake.applied_roll);
+        shake.applied_roll = 0.0;
     }
 }
 
@@ -56,17 +242,27 @@ pub fn update_camera_shake(
         }
 
         if shake.duration <= 0.0 {
-            // Reset to base position before removing
+            // Reset to base position and undo the roll before removing
             transform.translation = shake.base_position;
+            transform.rotate_local_z(-shake.applied_roll);
             to_remove.push(entity);
             continue;
         }
 
         // Calculate shake offset using sine wave
         let elapsed = time.elapsed_secs();
-        let shake_x = (elapsed * shake.frequency).sin() * shake.intensity;
-        let shake_y = (elapsed * shake.frequency * 1.3).cos() * shake.intensity * 0.7;
-        let shake_z = (elapsed * shake.frequency * 0.8).sin() * shake.intensity * 0.5;
+        let intensity = shake.current_intensity();
+        let shake_x = (elapsed * shake.frequency).sin() * intensity;
+        let shake_y = (elapsed * shake.frequency * 1.3).cos() * intensity * 0.7;
+        let shake_z = (elapsed * shake.frequency * 0.8).sin() * intensity * 0.5;
+
+        // Roll fades with the same curve as the offset. It is applied on top
+        // of mouse look, so only what is still applied gets replaced.
+        let roll = (elapsed * shake.frequency * 0.6 + shake.roll_phase).sin()
+            * shake.roll
+            * shake.falloff();
+        transform.rotate_local_z(roll - shake.applied_roll);
+        shake.applied_roll = roll;
 
         // Update
         transform.translation = shake.base_position + Vec3::new(shake_x, shake_y, shake_z);
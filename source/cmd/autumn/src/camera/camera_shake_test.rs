@@ -0,0 +1,195 @@
+use super::camera_shake::*;
+use crate::scripting::{CVarRegistry, CVarValue};
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+#[test]
+fn test_presets_use_defaults() {
+    let mut cvars = CVarRegistry::new();
+    init_camera_shake_cvars(&mut cvars);
+
+    let hit = CameraShake::hit_shake(&cvars);
+    assert_eq!(hit.intensity, DEFAULT_HIT_SHAKE_AMPLITUDE);
+    assert_eq!(hit.duration, DEFAULT_HIT_SHAKE_DURATION);
+
+    let crit = CameraShake::critical_shake(&cvars);
+    assert_eq!(crit.intensity, DEFAULT_CRIT_SHAKE_AMPLITUDE);
+    assert_eq!(crit.duration, DEFAULT_CRIT_SHAKE_DURATION);
+}
+
+#[test]
+fn test_presets_use_overridden_cvars() {
+    let mut cvars = CVarRegistry::new();
+    init_camera_shake_cvars(&mut cvars);
+    cvars
+        .set("combat.shake.hit_amplitude", CVarValue::F32(0.5))
+        .unwrap();
+    cvars
+        .set("combat.shake.hit_duration", CVarValue::F32(0.4))
+        .unwrap();
+    cvars
+        .set("combat.shake.crit_amplitude", CVarValue::F32(1.25))
+        .unwrap();
+    cvars
+        .set("combat.shake.crit_duration", CVarValue::F32(0.75))
+        .unwrap();
+
+    let hit = CameraShake::hit_shake(&cvars);
+    assert_eq!(hit.intensity, 0.5);
+    assert_eq!(hit.duration, 0.4);
+
+    let crit = CameraShake::critical_shake(&cvars);
+    assert_eq!(crit.intensity, 1.25);
+    assert_eq!(crit.duration, 0.75);
+}
+
+#[test]
+fn test_presets_fall_back_without_cvars() {
+    let cvars = CVarRegistry::new();
+    let hit = CameraShake::hit_shake(&cvars);
+    assert_eq!(hit.intensity, DEFAULT_HIT_SHAKE_AMPLITUDE);
+    assert_eq!(hit.duration, DEFAULT_HIT_SHAKE_DURATION);
+}
+
+const CURVES: [ShakeDecay; 3] = [
+    ShakeDecay::Linear,
+    ShakeDecay::Exponential,
+    ShakeDecay::EaseOut,
+];
+
+#[test]
+fn test_default_decay_keeps_full_strength() {
+    assert_eq!(DEFAULT_SHAKE_DECAY, ShakeDecay::Constant);
+    let mut shake = CameraShake::new(0.2, 0.5, Vec3::ZERO);
+    shake.duration = 0.01;
+    assert_eq!(shake.current_intensity(), 0.2);
+    shake.duration = 0.0;
+    assert_eq!(shake.current_intensity(), 0.0);
+}
+
+#[test]
+fn test_decay_curves_start_full_and_reach_zero() {
+    for decay in CURVES.into_iter().chain([ShakeDecay::Constant]) {
+        assert!((decay.factor(0.0) - 1.0).abs() < 1e-6, "{:?}", decay);
+        assert!(decay.factor(1.0).abs() < 1e-6, "{:?}", decay);
+        // Past the end stays at zero
+        assert!(decay.factor(1.5).abs() < 1e-6, "{:?}", decay);
+    }
+}
+
+#[test]
+fn test_decay_curves_never_grow() {
+    for decay in CURVES {
+        let samples: Vec<f32> = (0..=20).map(|i| decay.factor(i as f32 / 20.0)).collect();
+        for pair in samples.windows(2) {
+            assert!(pair[1] <= pair[0], "{:?}", decay);
+        }
+    }
+}
+
+#[test]
+fn test_exponential_front_loads_amplitude() {
+    let exponential = ShakeDecay::Exponential.factor(0.25);
+    assert!(exponential < ShakeDecay::Linear.factor(0.25));
+    assert!(exponential < ShakeDecay::EaseOut.factor(0.25));
+    // Most of the strength is gone by the halfway point
+    assert!(ShakeDecay::Exponential.factor(0.5) < 0.25);
+}
+
+#[test]
+fn test_shake_intensity_reaches_zero_at_end_of_duration() {
+    for decay in CURVES {
+        let mut shake = CameraShake::new(0.2, 0.5, Vec3::ZERO).with_decay(decay);
+        assert_eq!(shake.current_intensity(), 0.2);
+        shake.duration = 0.25;
+        assert!(shake.current_intensity() < 0.2);
+        shake.duration = 0.0;
+        assert!(shake.current_intensity().abs() < 1e-6, "{:?}", decay);
+    }
+}
+
+#[test]
+fn test_presets_read_decay_and_roll_cvars() {
+    let mut cvars = CVarRegistry::new();
+    init_camera_shake_cvars(&mut cvars);
+
+    let hit = CameraShake::hit_shake(&cvars);
+    assert_eq!(hit.decay, DEFAULT_SHAKE_DECAY);
+    assert_eq!(hit.roll, DEFAULT_HIT_SHAKE_ROLL.to_radians());
+
+    cvars
+        .set(
+            "combat.shake.crit_decay",
+            CVarValue::String("exponential".to_string()),
+        )
+        .unwrap();
+    cvars
+        .set("combat.shake.crit_roll", CVarValue::F32(2.0))
+        .unwrap();
+    let crit = CameraShake::critical_shake(&cvars);
+    assert_eq!(crit.decay, ShakeDecay::Exponential);
+    assert_eq!(crit.roll, 2.0_f32.to_radians());
+
+    // Unknown curve names fall back to the default
+    cvars
+        .set(
+            "combat.shake.hit_decay",
+            CVarValue::String("wobbly".to_string()),
+        )
+        .unwrap();
+    assert_eq!(CameraShake::hit_shake(&cvars).decay, DEFAULT_SHAKE_DECAY);
+}
+
+#[test]
+fn test_replacing_keeps_resting_position_and_roll() {
+    let mut previous = CameraShake::new(0.1, 0.1, Vec3::new(1.0, 2.0, 3.0));
+    previous.applied_roll = 0.02;
+    let shake = CameraShake::new(0.2, 0.3, Vec3::ZERO).replacing(Some(&previous));
+    assert_eq!(shake.base_position, Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(shake.applied_roll, 0.02);
+    assert_eq!(shake.intensity, 0.2);
+}
+
+/// Stand-in for mouse look: pitch up around the camera's right vector
+fn pitch_up(mut query: Query<&mut Transform, With<CameraShake>>) {
+    for mut transform in query.iter_mut() {
+        let right = transform.right().as_vec3();
+        transform.rotation = Quat::from_axis_angle(right, 0.1) * transform.rotation;
+    }
+}
+
+#[test]
+fn test_roll_leaves_no_tilt_with_mouse_look() {
+    let mut app = App::new();
+    app.init_resource::<Time>().add_systems(
+        Update,
+        (remove_camera_shake_roll, pitch_up, update_camera_shake).chain(),
+    );
+
+    let mut shake = CameraShake::new(0.0, 1.0, Vec3::ONE).with_roll(10.0);
+    shake.roll_phase = FRAC_PI_2;
+    let camera = app
+        .world_mut()
+        .spawn((
+            Transform::from_translation(Vec3::ONE).looking_to(Vec3::Y, Vec3::Z),
+            shake,
+        ))
+        .id();
+
+    for _ in 0..5 {
+        app.update();
+    }
+    let tilt = app.world().get::<Transform>(camera).unwrap().right().z;
+    assert!(tilt.abs() > 0.01, "shake should roll the camera");
+
+    // End the shake: the camera is left pitched but level
+    app.world_mut()
+        .get_mut::<CameraShake>(camera)
+        .unwrap()
+        .duration = 0.0;
+    app.update();
+    let transform = app.world().get::<Transform>(camera).unwrap();
+    assert!(transform.right().z.abs() < 1e-4);
+    assert!(transform.forward().z > 0.3);
+    assert!(app.world().get::<CameraShake>(camera).is_none());
+}
@@ -0,0 +1,55 @@
+use crate::scripting::CVarRegistry;
+
+const DEFAULT_DODGE_COST: f32 = 30.0;
+const DEFAULT_DODGE_COOLDOWN: f32 = 1.0;
+const DEFAULT_DODGE_DURATION: f32 = 0.3;
+const DEFAULT_DODGE_MULTIPLIER: f32 = 3.0;
+
+/// Dodge tuning, read from the `player.dodge_*` cvars
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DodgeSettings {
+    /// Stamina spent on each dodge
+    pub cost: f32,
+    /// Seconds from the start of one dodge until the next is allowed
+    pub cooldown: f32,
+    /// Seconds the movement burst and invulnerability last
+    pub duration: f32,
+    /// Movement speed multiplier during the burst
+    pub multiplier: f32,
+}
+
+impl DodgeSettings {
+    pub fn from_cvars(cvars: &CVarRegistry) -> Self {
+        Self {
+            cost: cvars.get_f32_or("player.dodge_cost", DEFAULT_DODGE_COST),
+            cooldown: cvars.get_f32_or("player.dodge_cooldown", DEFAULT_DODGE_COOLDOWN),
+            duration: cvars.get_f32_or("player.dodge_duration", DEFAULT_DODGE_DURATION),
+            multiplier: cvars.get_f32_or("player.dodge_multiplier", DEFAULT_DODGE_MULTIPLIER),
+        }
+    }
+}
+
+impl Default for DodgeSettings {
+    fn default() -> Self {
+        Self {
+            cost: DEFAULT_DODGE_COST,
+            cooldown: DEFAULT_DODGE_COOLDOWN,
+            duration: DEFAULT_DODGE_DURATION,
+            multiplier: DEFAULT_DODGE_MULTIPLIER,
+        }
+    }
+}
+
+/// Register the dodge cvars with their default values
+pub fn init_dodge_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("player.dodge_cost", DEFAULT_DODGE_COST);
+    cvars.init_f32("player.dodge_cooldown", DEFAULT_DODGE_COOLDOWN);
+    cvars.init_f32("player.dodge_duration", DEFAULT_DODGE_DURATION);
+    cvars.init_f32("player.dodge_multiplier", DEFAULT_DODGE_MULTIPLIER);
+}
+
+/// Whether the player may start a dodge: the cooldown from the last one has
+/// run out and there is enough stamina to pay for it.
+pub fn can_dodge(stamina: f32, cooldown_remaining: f32, settings: &DodgeSettings) -> bool {
+    cooldown_remaining <= 0.0 && stamina >= settings.cost
+}
@@ -0,0 +1,32 @@
+use super::dodge::{DodgeSettings, can_dodge};
+
+fn settings() -> DodgeSettings {
+    DodgeSettings {
+        cost: 30.0,
+        cooldown: 1.0,
+        duration: 0.3,
+        multiplier: 3.0,
+    }
+}
+
+#[test]
+fn test_dodge_with_stamina_and_no_cooldown() {
+    assert!(can_dodge(80.0, 0.0, &settings()));
+}
+
+#[test]
+fn test_dodge_with_exactly_enough_stamina() {
+    assert!(can_dodge(30.0, 0.0, &settings()));
+}
+
+#[test]
+fn test_no_dodge_without_enough_stamina() {
+    assert!(!can_dodge(29.0, 0.0, &settings()));
+    assert!(!can_dodge(0.0, 0.0, &settings()));
+}
+
+#[test]
+fn test_no_dodge_during_cooldown() {
+    assert!(!can_dodge(80.0, 0.5, &settings()));
+    assert!(can_dodge(80.0, -0.01, &settings()));
+}
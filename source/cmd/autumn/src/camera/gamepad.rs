@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+/// Apply a radial deadzone to an analog stick value. Input inside the deadzone
+/// maps to zero and the remaining range is rescaled to start from zero, so
+/// small stick movements still give fine control. The result has a length of
+/// at most 1.
+pub fn apply_stick_deadzone(stick: Vec2, deadzone: f32) -> Vec2 {
+    let deadzone = deadzone.clamp(0.0, 0.99);
+    let magnitude = stick.length();
+    if magnitude <= deadzone {
+        return Vec2::ZERO;
+    }
+
+    let scaled_magnitude = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+    stick / magnitude * scaled_magnitude
+}
+
+//=============================================================================
+// Tests
+//=============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadzone_ignores_small_input() {
+        assert_eq!(apply_stick_deadzone(Vec2::ZERO, 0.2), Vec2::ZERO);
+        assert_eq!(apply_stick_deadzone(Vec2::new(0.1, 0.1), 0.2), Vec2::ZERO);
+        assert_eq!(apply_stick_deadzone(Vec2::new(0.0, -0.2), 0.2), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_deadzone_rescales_remaining_range() {
+        // Halfway between the deadzone edge and full deflection
+        let result = apply_stick_deadzone(Vec2::new(0.6, 0.0), 0.2);
+        assert!((result - Vec2::new(0.5, 0.0)).length() < 1e-5);
+
+        // Full deflection stays at full speed
+        let result = apply_stick_deadzone(Vec2::new(0.0, -1.0), 0.2);
+        assert!((result - Vec2::new(0.0, -1.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_deadzone_preserves_direction_and_clamps_length() {
+        // Sticks can report slightly more than 1.0 on the diagonals
+        let result = apply_stick_deadzone(Vec2::new(0.9, 0.9), 0.1);
+        assert!((result.length() - 1.0).abs() < 1e-5);
+        assert!((result.x - result.y).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_zero_deadzone_passes_input_through() {
+        let result = apply_stick_deadzone(Vec2::new(0.3, -0.4), 0.0);
+        assert!((result - Vec2::new(0.3, -0.4)).length() < 1e-5);
+    }
+}
@@ -1,14 +1,29 @@
 mod camera_plugin;
 mod camera_shake;
+#[cfg(test)]
+mod camera_shake_test;
 mod cursor_toggle;
+mod dodge;
+#[cfg(test)]
+mod dodge_test;
+mod gamepad;
 mod mouse_look_settings;
+#[cfg(test)]
+mod movement_test;
 mod player;
 mod player_light;
+mod sprint;
+#[cfg(test)]
+mod sprint_test;
 mod systems;
 
 pub use camera_plugin::CameraPlugin;
-pub use camera_shake::{CameraShake, update_camera_shake};
+pub use camera_shake::{
+    CameraShake, init_camera_shake_cvars, remove_camera_shake_roll, update_camera_shake,
+};
+pub use dodge::init_dodge_cvars;
 pub use mouse_look_settings::MouseLookSettings;
 pub use player::Player;
 pub use player_light::{PlayerLightPlugin, spawn_player_lights};
+pub use sprint::init_sprint_cvars;
 pub use systems::{spawn_camera, update_camera_control_system};
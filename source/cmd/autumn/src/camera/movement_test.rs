@@ -0,0 +1,26 @@
+use super::systems::can_player_move_to;
+use crate::world::{Map, TileType};
+
+fn map_with_wall() -> Map {
+    let mut map = Map::new(4, 4);
+    map.layers[0]
+        .collision
+        .insert((1, 1), TileType::Wall { height: 16.0 });
+    map
+}
+
+#[test]
+fn test_wall_blocks_movement() {
+    let map = map_with_wall();
+    assert!(can_player_move_to(&map, 4.0, 4.0, false));
+    assert!(!can_player_move_to(&map, 12.0, 12.0, false));
+}
+
+#[test]
+fn test_noclip_skips_collision() {
+    let map = map_with_wall();
+    assert!(can_player_move_to(&map, 12.0, 12.0, true));
+
+    // Out of bounds is allowed too
+    assert!(can_player_move_to(&map, -50.0, 100.0, true));
+}
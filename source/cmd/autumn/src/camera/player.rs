@@ -5,6 +5,18 @@ use bevy::prelude::*;
 pub struct Player {
     pub speed: f32,
 
+    /// Set while the sprint key is held, the player is moving and stamina remains
+    pub sprinting: bool,
+    /// Set when stamina runs out; sprinting stays blocked until it recovers
+    pub sprint_exhausted: bool,
+
+    /// Seconds left in the current dodge; the player can't be hit meanwhile
+    pub dodge_timer: f32,
+    /// Seconds until another dodge is allowed
+    pub dodge_cooldown: f32,
+    /// Movement input the current dodge is heading along (x = right, y = forward)
+    pub dodge_direction: Vec2,
+
     /// Accumulators for smooth mouse movement
     pub yaw_velocity: f32,
     pub pitch_velocity: f32,
@@ -19,6 +31,11 @@ impl Player {
     pub fn new(speed: f32, max_health: f32) -> Self {
         Self {
             speed,
+            sprinting: false,
+            sprint_exhausted: false,
+            dodge_timer: 0.0,
+            dodge_cooldown: 0.0,
+            dodge_direction: Vec2::ZERO,
             yaw_velocity: 0.0,
             pitch_velocity: 0.0,
             current_health: max_health,
@@ -30,6 +47,11 @@ impl Player {
         self.current_health > 0.0
     }
 
+    /// Whether a dodge is in progress, during which attacks miss the player
+    pub fn is_dodging(&self) -> bool {
+        self.dodge_timer > 0.0
+    }
+
     pub fn take_damage(&mut self, amount: f32) {
         self.current_health = (self.current_health - amount).max(0.0);
     }
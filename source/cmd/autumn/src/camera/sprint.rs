@@ -0,0 +1,62 @@
+use crate::scripting::CVarRegistry;
+
+const DEFAULT_SPRINT_MULTIPLIER: f32 = 1.6;
+const DEFAULT_SPRINT_RECOVERY: f32 = 25.0;
+
+/// Sprint tuning, read from the `player.sprint_*` cvars
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SprintSettings {
+    /// Movement speed multiplier while sprinting
+    pub multiplier: f32,
+    /// Stamina needed to sprint again after running out
+    pub recovery_threshold: f32,
+}
+
+impl SprintSettings {
+    pub fn from_cvars(cvars: &CVarRegistry) -> Self {
+        Self {
+            multiplier: cvars.get_f32_or("player.sprint_multiplier", DEFAULT_SPRINT_MULTIPLIER),
+            recovery_threshold: cvars.get_f32_or("player.sprint_recovery", DEFAULT_SPRINT_RECOVERY),
+        }
+    }
+}
+
+impl Default for SprintSettings {
+    fn default() -> Self {
+        Self {
+            multiplier: DEFAULT_SPRINT_MULTIPLIER,
+            recovery_threshold: DEFAULT_SPRINT_RECOVERY,
+        }
+    }
+}
+
+/// Register the sprint cvars with their default values
+pub fn init_sprint_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("player.sprint_multiplier", DEFAULT_SPRINT_MULTIPLIER);
+    cvars.init_f32("player.sprint_recovery", DEFAULT_SPRINT_RECOVERY);
+}
+
+/// Pick the movement speed for this frame and whether the player is sprinting.
+///
+/// Running out of stamina sets `exhausted`, which blocks sprinting until
+/// stamina climbs back to the recovery threshold. This keeps the player from
+/// stuttering between sprint and walk while stamina hovers near zero.
+pub fn select_move_speed(
+    base_speed: f32,
+    sprint_requested: bool,
+    stamina: f32,
+    exhausted: &mut bool,
+    settings: &SprintSettings,
+) -> (f32, bool) {
+    if stamina <= 0.0 {
+        *exhausted = true;
+    } else if *exhausted && stamina >= settings.recovery_threshold {
+        *exhausted = false;
+    }
+
+    if sprint_requested && !*exhausted {
+        (base_speed * settings.multiplier, true)
+    } else {
+        (base_speed, false)
+    }
+}
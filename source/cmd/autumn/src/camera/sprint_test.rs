@@ -0,0 +1,43 @@
+use super::sprint::{SprintSettings, select_move_speed};
+
+fn settings() -> SprintSettings {
+    SprintSettings {
+        multiplier: 2.0,
+        recovery_threshold: 30.0,
+    }
+}
+
+#[test]
+fn test_walk_without_sprint_key() {
+    let mut exhausted = false;
+    let speed = select_move_speed(10.0, false, 80.0, &mut exhausted, &settings());
+    assert_eq!(speed, (10.0, false));
+}
+
+#[test]
+fn test_sprint_with_stamina() {
+    let mut exhausted = false;
+    let speed = select_move_speed(10.0, true, 80.0, &mut exhausted, &settings());
+    assert_eq!(speed, (20.0, true));
+}
+
+#[test]
+fn test_no_sprint_at_zero_stamina() {
+    let mut exhausted = false;
+    let speed = select_move_speed(10.0, true, 0.0, &mut exhausted, &settings());
+    assert_eq!(speed, (10.0, false));
+    assert!(exhausted);
+}
+
+#[test]
+fn test_exhaustion_holds_until_recovery_threshold() {
+    let mut exhausted = true;
+
+    let speed = select_move_speed(10.0, true, 29.0, &mut exhausted, &settings());
+    assert_eq!(speed, (10.0, false));
+    assert!(exhausted);
+
+    let speed = select_move_speed(10.0, true, 30.0, &mut exhausted, &settings());
+    assert_eq!(speed, (20.0, true));
+    assert!(!exhausted);
+}
@@ -1,7 +1,11 @@
+use super::dodge::{DodgeSettings, can_dodge};
+use super::gamepad::apply_stick_deadzone;
 use super::mouse_look_settings::MouseLookSettings;
 use super::player::Player;
+use super::sprint::{SprintSettings, select_move_speed};
 use crate::console::ConsoleState;
 use crate::game_state::GamePlayEntity;
+use crate::hud::PlayerStats;
 use crate::scripting::CVarRegistry;
 use crate::world::Map;
 use crate::world::PLAYER_RADIUS;
@@ -18,9 +22,15 @@ pub fn update_camera_control_system(
     cvars: Res<CVarRegistry>,
     mut query: Query<(&mut Transform, &mut Player)>,
     ui_interaction_query: Query<&Interaction>,
+    gamepads: Query<&Gamepad>,
+    mut stats: ResMut<PlayerStats>,
 ) {
     // Don't process camera controls if console is open
     if console_state.visible {
+        for (_, mut player) in query.iter_mut() {
+            player.sprinting = false;
+            player.dodge_timer = 0.0;
+        }
         return;
     }
 
@@ -33,6 +43,19 @@ pub fn update_camera_control_system(
     } else {
         -1.0
     };
+    let gamepad_sensitivity = cvars.get_f32("gamepad.sensitivity");
+    let gamepad_deadzone = cvars.get_f32("gamepad.deadzone");
+    let sprint_settings = SprintSettings::from_cvars(&cvars);
+    let dodge_settings = DodgeSettings::from_cvars(&cvars);
+    let noclip = cvars.get_bool_or("noclip", false);
+
+    // Combine the sticks of all connected gamepads
+    let mut left_stick = Vec2::ZERO;
+    let mut right_stick = Vec2::ZERO;
+    for gamepad in gamepads.iter() {
+        left_stick += apply_stick_deadzone(gamepad.left_stick(), gamepad_deadzone);
+        right_stick += apply_stick_deadzone(gamepad.right_stick(), gamepad_deadzone);
+    }
 
     for (mut transform, mut player) in query.iter_mut() {
         let dt = time.delta_secs();
@@ -63,6 +86,7 @@ pub fn update_camera_control_system(
         // Check if modifier keys are pressed
         let ctrl_pressed =
             input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
+        let shift_pressed = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
 
         // Movement input (WASD + RF)
         // WASD moves in the XY plane, RF moves along Z axis
@@ -90,6 +114,39 @@ pub fn update_camera_control_system(
             }
         }
 
+        // Left stick moves in the XY plane, like WASD
+        movement_xy += left_stick;
+
+        // Sprinting needs stamina and only applies while actually moving
+        let mut exhausted = player.sprint_exhausted;
+        let (mut move_speed, sprinting) = select_move_speed(
+            player.speed,
+            shift_pressed && movement_xy != Vec2::ZERO,
+            stats.stamina,
+            &mut exhausted,
+            &sprint_settings,
+        );
+        player.sprint_exhausted = exhausted;
+        player.sprinting = sprinting;
+
+        // Alt dodges along the movement input, or backwards when standing
+        // still. The stamina is paid up front and the burst replaces normal
+        // movement until it ends.
+        player.dodge_timer = (player.dodge_timer - dt).max(0.0);
+        player.dodge_cooldown = (player.dodge_cooldown - dt).max(0.0);
+        let dodge_pressed =
+            input.just_pressed(KeyCode::AltLeft) || input.just_pressed(KeyCode::AltRight);
+        if dodge_pressed && can_dodge(stats.stamina, player.dodge_cooldown, &dodge_settings) {
+            stats.stamina -= dodge_settings.cost;
+            player.dodge_timer = dodge_settings.duration;
+            player.dodge_cooldown = dodge_settings.cooldown;
+            player.dodge_direction = movement_xy.normalize_or(Vec2::NEG_Y);
+        }
+        if player.is_dodging() {
+            movement_xy = player.dodge_direction;
+            move_speed = player.speed * dodge_settings.multiplier;
+        }
+
         // Rotation input (Arrow keys)
         // Arrow left/right rotates around Z axis (yaw)
         // Arrow up/down changes pitch (looking up/down)
@@ -111,6 +168,10 @@ pub fn update_camera_control_system(
             pitch_delta -= arrow_sensitivity * dt;
         }
 
+        // Right stick rotates like the arrow keys, scaled by how far it is pushed
+        yaw_delta -= right_stick.x * gamepad_sensitivity * dt;
+        pitch_delta += right_stick.y * gamepad_sensitivity * dt;
+
         // Apply smooth mouse rotation (velocity-based)
         if smooth_enabled {
             let dt_factor = dt * 60.0; // Frame-rate independence (60 FPS baseline)
@@ -163,7 +224,9 @@ pub fn update_camera_control_system(
 
         // Apply XY plane movement in camera's local orientation (projected to XY plane)
         if movement_xy != Vec2::ZERO {
-            movement_xy = movement_xy.normalize();
+            // Keep partial stick deflection as slower movement, but never
+            // faster than full speed (e.g. diagonal keys)
+            movement_xy = movement_xy.clamp_length_max(1.0);
 
             // Get forward and right directions, but project them onto the XY plane
             let forward_3d = transform.forward();
@@ -176,11 +239,11 @@ pub fn update_camera_control_system(
             let move_vec_xy = forward_xy * movement_xy.y + right_xy * movement_xy.x;
 
             // Calculate new position
-            let new_x = transform.translation.x + move_vec_xy.x * player.speed * dt;
-            let new_y = transform.translation.y + move_vec_xy.y * player.speed * dt;
+            let new_x = transform.translation.x + move_vec_xy.x * move_speed * dt;
+            let new_y = transform.translation.y + move_vec_xy.y * move_speed * dt;
 
             // Check collision before moving
-            if map.can_move_to(new_x, new_y, PLAYER_RADIUS) {
+            if can_player_move_to(&map, new_x, new_y, noclip) {
                 transform.translation.x = new_x;
                 transform.translation.y = new_y;
             }
@@ -193,6 +256,12 @@ pub fn update_camera_control_system(
     }
 }
 
+/// Whether the player may move to the given world position. With `noclip`
+/// on, walls and map bounds are ignored entirely.
+pub(crate) fn can_player_move_to(map: &Map, world_x: f32, world_y: f32, noclip: bool) -> bool {
+    noclip || map.can_move_to(world_x, world_y, PLAYER_RADIUS)
+}
+
 /// Spawn camera at given position and return its entity ID
 pub fn spawn_camera(commands: &mut Commands, position: Vec3) -> Entity {
     commands
@@ -1,5 +1,8 @@
 use crate::actor::{Actor, ActorAttackState};
 use crate::camera::Player;
+use crate::combat::{StatusEffect, add_status_effect};
+use crate::rendering::Culled;
+use crate::scripting::CVarRegistry;
 use bevy::prelude::*;
 
 // Attack animation timing
@@ -11,20 +14,26 @@ const ATTACK_TOTAL_DURATION: f32 = WINDUP_DURATION + STRIKE_DURATION + RECOVERY_
 // Damage is dealt at 50% through the attack (during strike phase)
 const DAMAGE_TIMING: f32 = WINDUP_DURATION + STRIKE_DURATION * 0.5;
 
-/// System to handle actor attacks on player
+/// Seconds a status effect from an actor's attack lasts on the player
+const ATTACK_EFFECT_DURATION: f32 = 5.0;
+
+/// System to handle actor attacks on player. Culled actors don't attack.
 pub fn update_actor_attacks(
-    mut actors: Query<(&mut Actor, &Transform)>,
-    mut player_query: Query<(&mut Player, &Transform)>,
+    mut actors: Query<(&mut Actor, &Transform), Without<Culled>>,
+    mut player_query: Query<(Entity, &mut Player, &Transform)>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     combat_audio: Res<crate::combat::CombatAudio>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut blood_particle_pool: ResMut<crate::combat::BloodParticlePool>,
+    mut damage_number_pool: ResMut<crate::combat::DamageNumberPool>,
+    mut damage_indicators: ResMut<crate::hud::DamageIndicators>,
     time: Res<Time>,
+    cvars: Res<CVarRegistry>,
 ) {
-    let Ok((mut player, player_transform)) = player_query.single_mut() else {
+    let Ok((player_entity, mut player, player_transform)) = player_query.single_mut() else {
         return;
     };
+    let god = cvars.get_bool_or("god", false);
 
     let player_pos = Vec2::new(
         player_transform.translation.x,
@@ -67,14 +76,25 @@ pub fn update_actor_attacks(
                 if actor.attack_timer >= DAMAGE_TIMING
                     && actor.attack_timer < DAMAGE_TIMING + time.delta_secs()
                 {
-                    // Check if still in range
-                    if distance <= actor.attack_range {
+                    // Check if still in range (god mode and dodging ignore
+                    // the hit entirely)
+                    if distance <= actor.attack_range && !god && !player.is_dodging() {
                         player.take_damage(actor.attack_damage as f32);
+                        damage_indicators.record(actor_pos);
+
+                        if let Some(effect_type) = actor.attack_effect {
+                            add_status_effect(
+                                &mut commands,
+                                player_entity,
+                                StatusEffect::new(effect_type, ATTACK_EFFECT_DURATION),
+                            );
+                        }
 
                         // Spawn visual/audio feedback
                         crate::combat::spawn_damage_number(
                             &mut commands,
                             &asset_server,
+                            &mut damage_number_pool,
                             player_transform.translation,
                             actor.attack_damage,
                             false, // Not a crit
@@ -82,8 +102,7 @@ pub fn update_actor_attacks(
 
                         crate::combat::spawn_blood_particles(
                             &mut commands,
-                            &mut meshes,
-                            &mut materials,
+                            &mut blood_particle_pool,
                             player_transform.translation,
                             5,
                         );
@@ -0,0 +1,81 @@
+/// Combo (chained attack) tracking
+///
+/// Attacking again shortly after an attack recovers chains into the next
+/// swing of the combo, which uses the weapon's next keyframe set and deals
+/// extra damage. Waiting too long resets the chain.
+use crate::scripting::CVarRegistry;
+
+/// Seconds after recovery in which a new attack continues the combo
+pub const DEFAULT_COMBO_WINDOW: f32 = 0.4;
+
+/// Extra damage per combo step (0.25 = +25% per chained attack)
+pub const DEFAULT_COMBO_DAMAGE_STEP: f32 = 0.25;
+
+/// Combo depth beyond which the damage bonus stops growing
+pub const MAX_COMBO_DAMAGE_DEPTH: u32 = 3;
+
+/// Register combo CVars with their default values
+pub fn init_combo_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("combat.combo_window", DEFAULT_COMBO_WINDOW);
+    cvars.init_f32("combat.combo_damage_step", DEFAULT_COMBO_DAMAGE_STEP);
+}
+
+/// Combo tuning values read from CVars
+#[derive(Clone, Copy, Debug)]
+pub struct ComboSettings {
+    pub window: f32,
+    pub damage_step: f32,
+}
+
+impl ComboSettings {
+    pub fn from_cvars(cvars: &CVarRegistry) -> Self {
+        Self {
+            window: cvars.get_f32_or("combat.combo_window", DEFAULT_COMBO_WINDOW),
+            damage_step: cvars.get_f32_or("combat.combo_damage_step", DEFAULT_COMBO_DAMAGE_STEP),
+        }
+    }
+}
+
+/// Per-weapon combo progress
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ComboState {
+    /// Number of attacks chained onto the first one (0 = not in a combo)
+    pub depth: u32,
+
+    /// Time left to chain the next attack (0 = window closed)
+    pub window_remaining: f32,
+}
+
+impl ComboState {
+    /// Open the follow-up window once an attack has fully recovered
+    pub fn open_window(&mut self, window: f32) {
+        self.window_remaining = window.max(0.0);
+    }
+
+    /// Count down the follow-up window, resetting the combo when it lapses
+    pub fn tick(&mut self, dt: f32) {
+        if self.window_remaining <= 0.0 {
+            return;
+        }
+        self.window_remaining -= dt;
+        if self.window_remaining <= 0.0 {
+            self.window_remaining = 0.0;
+            self.depth = 0;
+        }
+    }
+
+    /// Record the start of a new attack, chaining it if the window is open
+    pub fn start_attack(&mut self) {
+        self.depth = if self.window_remaining > 0.0 {
+            self.depth + 1
+        } else {
+            0
+        };
+        self.window_remaining = 0.0;
+    }
+
+    /// Damage multiplier for the current attack
+    pub fn damage_multiplier(&self, damage_step: f32) -> f32 {
+        1.0 + damage_step * self.depth.min(MAX_COMBO_DAMAGE_DEPTH) as f32
+    }
+}
@@ -0,0 +1,85 @@
+use super::combo::*;
+use super::weapon::combo_keyframe_index;
+
+/// Run one full attack: start it, then let it recover and open the window
+fn attack(combo: &mut ComboState, window: f32) {
+    combo.start_attack();
+    combo.open_window(window);
+}
+
+#[test]
+fn test_first_attack_has_no_combo() {
+    let mut combo = ComboState::default();
+    combo.start_attack();
+    assert_eq!(combo.depth, 0);
+    assert_eq!(combo.damage_multiplier(0.25), 1.0);
+}
+
+#[test]
+fn test_attack_within_window_advances_combo() {
+    let mut combo = ComboState::default();
+    attack(&mut combo, 0.5);
+    combo.tick(0.3);
+    combo.start_attack();
+    assert_eq!(combo.depth, 1);
+    assert_eq!(combo.damage_multiplier(0.25), 1.25);
+}
+
+#[test]
+fn test_window_lapse_resets_combo() {
+    let mut combo = ComboState::default();
+    attack(&mut combo, 0.5);
+    combo.tick(0.1);
+    attack(&mut combo, 0.5);
+    assert_eq!(combo.depth, 1);
+
+    combo.tick(0.6);
+    assert_eq!(combo.depth, 0);
+    assert_eq!(combo.window_remaining, 0.0);
+
+    combo.start_attack();
+    assert_eq!(combo.depth, 0);
+}
+
+#[test]
+fn test_window_closes_while_attacking() {
+    // The window only runs after recovery, so a slow attack can't chain off
+    // a window that was still open when it started
+    let mut combo = ComboState::default();
+    attack(&mut combo, 0.5);
+    combo.start_attack();
+    assert_eq!(combo.window_remaining, 0.0);
+}
+
+#[test]
+fn test_zero_window_disables_combos() {
+    let mut combo = ComboState::default();
+    attack(&mut combo, 0.0);
+    combo.start_attack();
+    assert_eq!(combo.depth, 0);
+}
+
+#[test]
+fn test_damage_bonus_is_capped() {
+    let mut combo = ComboState::default();
+    for _ in 0..10 {
+        attack(&mut combo, 0.5);
+    }
+    assert_eq!(combo.depth, 9);
+    assert_eq!(
+        combo.damage_multiplier(0.5),
+        1.0 + 0.5 * MAX_COMBO_DAMAGE_DEPTH as f32
+    );
+}
+
+#[test]
+fn test_keyframe_sets_alternate() {
+    // One extra set: base, combo, base, combo...
+    assert_eq!(combo_keyframe_index(0, 1), None);
+    assert_eq!(combo_keyframe_index(1, 1), Some(0));
+    assert_eq!(combo_keyframe_index(2, 1), None);
+    assert_eq!(combo_keyframe_index(3, 1), Some(0));
+
+    // Weapons without combo keyframes always use the base set
+    assert_eq!(combo_keyframe_index(5, 0), None);
+}
@@ -4,11 +4,28 @@
 /// critical hits, and target resistances.
 use super::weapon::WeaponDefinition;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Types of damage that can be dealt
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DamageType {
     Physical,
+    Fire,
+    Ice,
+}
+
+/// Resistance to each damage type (0.0 = no resistance, 1.0 = immune)
+///
+/// Damage types that aren't listed have no resistance.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Resistances(pub HashMap<DamageType, f32>);
+
+impl Resistances {
+    /// Resistance to the given damage type, 0.0 if none is set
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        self.0.get(&damage_type).copied().unwrap_or(0.0)
+    }
 }
 
 /// Result of a damage calculation
@@ -31,12 +48,12 @@ pub struct DamageResult {
 /// - Charge ratio (0.0 to 1.0)
 /// - Critical hit chance (5%)
 /// - Target armor
-/// - Target resistance to damage type
+/// - Target resistance to the weapon's damage type
 pub fn calculate_damage(
     weapon: &WeaponDefinition,
     charge_ratio: f32,
     target_armor: i32,
-    target_resistance: f32,
+    target_resistances: &Resistances,
 ) -> DamageResult {
     // Start with base weapon damage
     let mut damage = weapon.attack_power as f32;
@@ -54,7 +71,8 @@ pub fn calculate_damage(
     damage -= target_armor as f32;
 
     // Apply resistance multiplier (0.0 = no damage, 1.0 = full damage)
-    damage *= 1.0 - target_resistance.clamp(0.0, 1.0);
+    let resistance = target_resistances.get(weapon.damage_type);
+    damage *= 1.0 - resistance.clamp(0.0, 1.0);
 
     // Ensure damage is at least 0
     let final_damage = damage.max(0.0).round() as i32;
@@ -0,0 +1,48 @@
+use super::damage::{DamageType, Resistances, calculate_damage};
+use super::weapon::{WeaponDefinition, WeaponDefinitions};
+use std::collections::HashMap;
+
+fn sword(damage_type: DamageType) -> WeaponDefinition {
+    let definitions =
+        WeaponDefinitions::load_from_file("data/weapons.yaml").expect("weapons.yaml should load");
+    let mut sword = definitions.weapons["sword"].clone();
+    sword.attack_power = 20;
+    sword.damage_type = damage_type;
+    sword
+}
+
+/// Damage the sword should deal, allowing for a random critical hit
+fn expected(critical: bool, resistance: f32) -> i32 {
+    let base = if critical { 40.0 } else { 20.0 };
+    (base * (1.0 - resistance)).round() as i32
+}
+
+fn resistances() -> Resistances {
+    Resistances(HashMap::from([
+        (DamageType::Physical, 0.25),
+        (DamageType::Fire, 0.5),
+    ]))
+}
+
+#[test]
+fn test_resistance_matches_damage_type() {
+    let resistances = resistances();
+    for (damage_type, resistance) in [(DamageType::Physical, 0.25), (DamageType::Fire, 0.5)] {
+        let result = calculate_damage(&sword(damage_type), 0.0, 0, &resistances);
+        assert_eq!(result.damage_type, damage_type);
+        assert_eq!(result.amount, expected(result.critical, resistance));
+    }
+}
+
+#[test]
+fn test_unlisted_damage_type_has_no_resistance() {
+    let result = calculate_damage(&sword(DamageType::Ice), 0.0, 0, &resistances());
+    assert_eq!(result.amount, expected(result.critical, 0.0));
+}
+
+#[test]
+fn test_full_resistance_blocks_damage() {
+    let resistances = Resistances(HashMap::from([(DamageType::Ice, 1.0)]));
+    let result = calculate_damage(&sword(DamageType::Ice), 1.0, 0, &resistances);
+    assert_eq!(result.amount, 0);
+}
@@ -5,19 +5,39 @@ pub mod actor_attack;
 /// Organized into submodules for clarity and maintainability.
 pub mod attack_state;
 pub mod combat_audio;
+pub mod combo;
+#[cfg(test)]
+mod combo_test;
 pub mod damage;
+#[cfg(test)]
+mod damage_test;
+pub mod projectile;
+pub mod recycle_pool;
+#[cfg(test)]
+mod recycle_pool_test;
 pub mod status_effects;
+#[cfg(test)]
+mod status_effects_test;
 pub mod visual_feedback;
 pub mod weapon;
+#[cfg(test)]
+mod weapon_test;
 
 pub use actor_attack::{
     handle_actor_hit, update_actor_attack_animation, update_actor_attacks, update_actor_stun,
 };
 pub use attack_state::{AttackState, CombatInput, StateTransition};
 pub use combat_audio::CombatAudio;
-pub use damage::calculate_damage;
-pub use status_effects::{apply_status_effect, update_status_effects};
+pub use combo::{ComboSettings, ComboState, init_combo_cvars};
+pub use damage::{Resistances, calculate_damage};
+pub use projectile::{update_projectiles, update_ranged_attacks};
+pub use status_effects::{
+    StatusEffect, StatusEffectType, StatusEffects, add_status_effect, apply_status_effect,
+    update_player_status_effects, update_status_effects,
+};
 pub use visual_feedback::{
-    spawn_blood_particles, spawn_damage_number, update_blood_particles, update_damage_numbers,
+    BloodParticlePool, DamageNumberPool, clear_effect_pools, init_blood_particle_pool,
+    init_visual_feedback_cvars, spawn_blood_particles, spawn_damage_number, update_blood_particles,
+    update_damage_numbers,
 };
-pub use weapon::WeaponDefinitions;
+pub use weapon::{WeaponDefinitions, process_weapon_dump_command, process_weapon_reset_command};
@@ -0,0 +1,139 @@
+/// Projectiles fired by ranged actors
+///
+/// Behaviors request ranged attacks; these systems spawn the projectiles,
+/// fly them in a straight line and damage the player on contact.
+use crate::actor::Actor;
+use crate::camera::Player;
+use crate::game_state::GamePlayEntity;
+use crate::hud::DamageIndicators;
+use crate::rendering::Culled;
+use crate::scripting::CVarRegistry;
+use crate::world::Map;
+use bevy::prelude::*;
+
+/// Seconds before a projectile that hits nothing is removed
+const PROJECTILE_LIFETIME: f32 = 4.0;
+
+/// Visual and wall-collision radius
+const PROJECTILE_RADIUS: f32 = 0.3;
+
+/// How close a projectile must pass to the player to hit
+const PLAYER_HIT_RADIUS: f32 = 1.6;
+
+/// Component for an in-flight projectile
+#[derive(Component)]
+pub struct Projectile {
+    pub velocity: Vec3,
+    pub damage: i32,
+    /// Where the projectile was fired from, for the damage indicator
+    pub source: Vec2,
+    pub lifetime: f32,
+    /// Floor of the map the projectile was fired on
+    pub layer: usize,
+}
+
+/// Actors that can fire: not the player, and not culled
+type ShooterQuery<'w, 's> =
+    Query<'w, 's, (&'static mut Actor, &'static Transform), (Without<Player>, Without<Culled>)>;
+
+/// System to spawn projectiles for ranged attacks requested by behaviors.
+/// Culled actors don't fire.
+pub fn update_ranged_attacks(
+    mut commands: Commands,
+    mut actors: ShooterQuery,
+    player_query: Query<&Transform, With<Player>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    for (mut actor, actor_transform) in actors.iter_mut() {
+        let damage = actor.attack_damage;
+        let Some(attack) = actor
+            .behavior
+            .as_mut()
+            .and_then(|behavior| behavior.take_ranged_attack())
+        else {
+            continue;
+        };
+
+        // Stunned actors lose their shot
+        if actor.stun_timer > 0.0 {
+            continue;
+        }
+
+        let origin = actor_transform.translation;
+        let direction = (player_transform.translation - origin).normalize_or_zero();
+        commands.spawn((
+            GamePlayEntity,
+            Projectile {
+                velocity: direction * attack.projectile_speed,
+                damage,
+                source: origin.truncate(),
+                lifetime: PROJECTILE_LIFETIME,
+                layer: actor.layer,
+            },
+            Mesh3d(meshes.add(Sphere::new(PROJECTILE_RADIUS))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgb(1.0, 0.6, 0.2),
+                unlit: true,
+                ..default()
+            })),
+            Transform::from_translation(origin),
+        ));
+    }
+}
+
+/// System to move projectiles and resolve hits against walls and the player
+pub fn update_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    map: Res<Map>,
+    cvars: Res<CVarRegistry>,
+    combat_audio: Res<super::CombatAudio>,
+    asset_server: Res<AssetServer>,
+    mut damage_number_pool: ResMut<super::DamageNumberPool>,
+    mut damage_indicators: ResMut<DamageIndicators>,
+    mut projectiles: Query<(Entity, &mut Transform, &mut Projectile), Without<Player>>,
+    mut player_query: Query<(&mut Player, &Transform)>,
+) {
+    let dt = time.delta_secs();
+    let god = cvars.get_bool_or("god", false);
+    let mut player = player_query.single_mut().ok();
+
+    for (entity, mut transform, mut projectile) in projectiles.iter_mut() {
+        projectile.lifetime -= dt;
+        transform.translation += projectile.velocity * dt;
+        let pos = transform.translation;
+
+        // Projectiles pass straight through a dodging player
+        if let Some((player, player_transform)) = player.as_mut()
+            && !player.is_dodging()
+            && pos.distance(player_transform.translation) <= PLAYER_HIT_RADIUS
+        {
+            if !god {
+                player.take_damage(projectile.damage as f32);
+                damage_indicators.record(projectile.source);
+                super::spawn_damage_number(
+                    &mut commands,
+                    &asset_server,
+                    &mut damage_number_pool,
+                    player_transform.translation,
+                    projectile.damage,
+                    false,
+                );
+                combat_audio.play_hit_sound(&mut commands, false);
+            }
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if projectile.lifetime <= 0.0
+            || !map.can_move_to_on(projectile.layer, pos.x, pos.y, PROJECTILE_RADIUS)
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Bookkeeping for recycling short-lived effect entities
+///
+/// Tracks which items are free for reuse and how many are currently in use.
+/// The pool doesn't create items itself: when `acquire` has nothing to hand
+/// out the caller spawns a new one, which then counts as active until it is
+/// released back to the pool.
+#[derive(Debug)]
+pub struct RecyclePool<T> {
+    free: Vec<T>,
+    active: usize,
+}
+
+impl<T> Default for RecyclePool<T> {
+    fn default() -> Self {
+        Self {
+            free: Vec::new(),
+            active: 0,
+        }
+    }
+}
+
+impl<T> RecyclePool<T> {
+    /// Take a free item for reuse, or `None` if the caller needs to create a
+    /// new one. Either way the item is counted as active.
+    pub fn acquire(&mut self) -> Option<T> {
+        self.active += 1;
+        self.free.pop()
+    }
+
+    /// Return an item to the pool once it is no longer in use
+    pub fn release(&mut self, item: T) {
+        self.active = self.active.saturating_sub(1);
+        self.free.push(item);
+    }
+
+    /// Number of items currently handed out
+    #[cfg(test)]
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Number of items waiting to be reused
+    #[cfg(test)]
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Forget every item, e.g. once the entities have been despawned
+    pub fn clear(&mut self) {
+        self.free.clear();
+        self.active = 0;
+    }
+}
+
+/// Fixed-size pool that never grows: once every item is in use, acquiring
+/// recycles the item that has been active the longest.
+///
+/// Used for effects where dropping the oldest instance is preferable to
+/// allocating more (e.g. particles during sustained combat).
+#[derive(Debug)]
+pub struct BoundedPool<T> {
+    free: VecDeque<T>,
+    /// In-use items, with the ticket of the acquire that handed each one out
+    active: HashMap<T, u64>,
+    /// Acquires, oldest first. Releasing an item leaves its entry behind;
+    /// entries whose ticket no longer matches `active` are skipped.
+    order: VecDeque<(T, u64)>,
+    next_ticket: u64,
+}
+
+impl<T> Default for BoundedPool<T> {
+    fn default() -> Self {
+        Self {
+            free: VecDeque::new(),
+            active: HashMap::new(),
+            order: VecDeque::new(),
+            next_ticket: 0,
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> BoundedPool<T> {
+    /// Create a pool from pre-allocated items, all initially free
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            free: items.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Take a free item, or recycle the oldest active one if the pool is
+    /// exhausted. Returns `None` only for an empty pool.
+    pub fn acquire(&mut self) -> Option<T> {
+        let item = match self.free.pop_front() {
+            Some(item) => item,
+            None => self.pop_oldest_active()?,
+        };
+
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        self.active.insert(item, ticket);
+        self.order.push_back((item, ticket));
+
+        // Drop entries left behind by releases so the queue stays in
+        // proportion to the pool
+        if self.order.len() > 2 * self.capacity() {
+            let active = &self.active;
+            self.order
+                .retain(|(item, ticket)| active.get(item) == Some(ticket));
+        }
+        Some(item)
+    }
+
+    fn pop_oldest_active(&mut self) -> Option<T> {
+        while let Some((item, ticket)) = self.order.pop_front() {
+            if self.active.get(&item) == Some(&ticket) {
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    /// Return an active item to the pool. Returns false if the item wasn't
+    /// active (e.g. it was already released).
+    pub fn release(&mut self, item: T) -> bool {
+        if self.active.remove(&item).is_none() {
+            return false;
+        }
+        self.free.push_back(item);
+        true
+    }
+
+    /// Remove and return every item in the pool, free and active
+    pub fn drain(&mut self) -> Vec<T> {
+        self.order.clear();
+        self.free
+            .drain(..)
+            .chain(self.active.drain().map(|(item, _)| item))
+            .collect()
+    }
+
+    /// Forget every item, e.g. once the entities have been despawned
+    pub fn clear(&mut self) {
+        self.free.clear();
+        self.active.clear();
+        self.order.clear();
+    }
+
+    /// Total number of items owned by the pool
+    pub fn capacity(&self) -> usize {
+        self.free.len() + self.active.len()
+    }
+
+    /// Number of items currently handed out
+    #[cfg(test)]
+    pub fn active(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Number of items waiting to be reused
+    #[cfg(test)]
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+}
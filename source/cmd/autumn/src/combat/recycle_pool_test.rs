@@ -0,0 +1,145 @@
+use super::recycle_pool::*;
+
+#[test]
+fn test_empty_pool_has_nothing_to_reuse() {
+    let mut pool: RecyclePool<u32> = RecyclePool::default();
+    assert_eq!(pool.acquire(), None);
+    assert_eq!(pool.active(), 1);
+    assert_eq!(pool.available(), 0);
+}
+
+#[test]
+fn test_released_items_are_reused() {
+    let mut pool = RecyclePool::default();
+
+    // Two items created by the caller after the pool came up empty
+    assert_eq!(pool.acquire(), None);
+    assert_eq!(pool.acquire(), None);
+    assert_eq!(pool.active(), 2);
+
+    pool.release(10);
+    pool.release(11);
+    assert_eq!(pool.active(), 0);
+    assert_eq!(pool.available(), 2);
+
+    let first = pool.acquire().unwrap();
+    let second = pool.acquire().unwrap();
+    assert_ne!(first, second);
+    assert!([10, 11].contains(&first) && [10, 11].contains(&second));
+    assert_eq!(pool.active(), 2);
+    assert_eq!(pool.available(), 0);
+
+    // Pool is drained again, so the next acquire needs a new item
+    assert_eq!(pool.acquire(), None);
+    assert_eq!(pool.active(), 3);
+}
+
+#[test]
+fn test_release_without_acquire_does_not_underflow() {
+    let mut pool = RecyclePool::default();
+    pool.release(1);
+    assert_eq!(pool.active(), 0);
+    assert_eq!(pool.available(), 1);
+}
+
+#[test]
+fn test_bounded_pool_capacity() {
+    let mut pool = BoundedPool::new(vec![1, 2, 3]);
+    assert_eq!(pool.capacity(), 3);
+    assert_eq!(pool.available(), 3);
+
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+    assert_eq!(pool.active(), 2);
+    assert_eq!(pool.available(), 1);
+    assert_eq!(pool.capacity(), 3);
+}
+
+#[test]
+fn test_bounded_pool_overflow_recycles_oldest() {
+    let mut pool = BoundedPool::new(vec![1, 2, 3]);
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+    assert_eq!(pool.acquire(), Some(3));
+
+    // Exhausted: the oldest active items are recycled in order
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+    assert_eq!(pool.active(), 3);
+    assert_eq!(pool.capacity(), 3);
+
+    // 3 is now the oldest
+    assert_eq!(pool.acquire(), Some(3));
+}
+
+#[test]
+fn test_bounded_pool_release() {
+    let mut pool = BoundedPool::new(vec![1, 2]);
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+
+    assert!(pool.release(1));
+    assert!(!pool.release(1), "Double release should be rejected");
+    assert_eq!(pool.active(), 1);
+    assert_eq!(pool.available(), 1);
+
+    // Free items are handed out before active ones are recycled
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+}
+
+#[test]
+fn test_empty_bounded_pool() {
+    let mut pool: BoundedPool<u32> = BoundedPool::new(Vec::new());
+    assert_eq!(pool.acquire(), None);
+    assert_eq!(pool.capacity(), 0);
+}
+
+#[test]
+fn test_bounded_pool_drain() {
+    let mut pool = BoundedPool::new(vec![1, 2, 3]);
+    pool.acquire();
+    let mut items = pool.drain();
+    items.sort();
+    assert_eq!(items, vec![1, 2, 3]);
+    assert_eq!(pool.capacity(), 0);
+}
+
+#[test]
+fn test_bounded_pool_recycling_skips_released_items() {
+    let mut pool = BoundedPool::new(vec![1, 2, 3]);
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+    assert_eq!(pool.acquire(), Some(3));
+
+    // 1 comes back and goes out again, so it is now the newest
+    assert!(pool.release(1));
+    assert_eq!(pool.acquire(), Some(1));
+    assert_eq!(pool.acquire(), Some(2));
+    assert_eq!(pool.acquire(), Some(3));
+    assert_eq!(pool.acquire(), Some(1));
+
+    // Many short-lived acquires don't change which item is oldest
+    for _ in 0..100 {
+        assert!(pool.release(3));
+        assert_eq!(pool.acquire(), Some(3));
+    }
+    assert_eq!(pool.acquire(), Some(2));
+    assert_eq!(pool.capacity(), 3);
+}
+
+#[test]
+fn test_pools_clear() {
+    let mut pool = RecyclePool::default();
+    assert_eq!(pool.acquire(), None);
+    pool.release(1);
+    pool.clear();
+    assert_eq!(pool.acquire(), None);
+    assert_eq!(pool.active(), 1);
+
+    let mut pool = BoundedPool::new(vec![1, 2]);
+    pool.acquire();
+    pool.clear();
+    assert_eq!(pool.capacity(), 0);
+    assert_eq!(pool.acquire(), None);
+}
@@ -1,48 +1,90 @@
 /// Status effects system for combat
-/// 
-/// Handles temporary status effects on actors.
+///
+/// Handles temporary status effects on actors and the player.
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use super::damage::DamageType;
+use crate::camera::Player;
 
-/// Status effect that can be applied to actors
-#[derive(Component, Debug, Clone)]
+/// A single timed status effect
+#[derive(Debug, Clone, PartialEq)]
 pub struct StatusEffect {
     /// Type of effect (determines behavior)
     pub effect_type: StatusEffectType,
-    
+
     /// Time remaining before effect expires
     pub duration: f32,
-    
+
     /// Time between damage ticks (for DoT effects)
     pub tick_interval: f32,
-    
+
     /// Time since last damage tick
     pub time_since_tick: f32,
-    
+
     /// Damage per tick (for DoT effects)
     pub damage_per_tick: i32,
 }
 
 /// Types of status effects
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum StatusEffectType {
+    /// Poisoned: slow damage over time
+    Poison,
+    /// Burning: fast damage over time
+    Burn,
+    /// Stunned: can't act (not implemented yet)
+    Stun,
     /// Frozen: slows movement (not implemented yet)
     Frozen,
 }
 
 impl StatusEffect {
+    /// Create an effect of the given type with its usual damage over time
+    pub fn new(effect_type: StatusEffectType, duration: f32) -> Self {
+        match effect_type {
+            StatusEffectType::Poison => Self::poison(duration),
+            StatusEffectType::Burn => Self::burn(duration),
+            StatusEffectType::Stun => Self::stun(duration),
+            StatusEffectType::Frozen => Self::frozen(duration),
+        }
+    }
+
+    /// Create a new poison effect: 2 damage every second
+    pub fn poison(duration: f32) -> Self {
+        Self::damage_over_time(StatusEffectType::Poison, duration, 1.0, 2)
+    }
+
+    /// Create a new burn effect: 3 damage every half second
+    pub fn burn(duration: f32) -> Self {
+        Self::damage_over_time(StatusEffectType::Burn, duration, 0.5, 3)
+    }
+
+    /// Create a new stun effect
+    pub fn stun(duration: f32) -> Self {
+        Self::damage_over_time(StatusEffectType::Stun, duration, 0.0, 0)
+    }
+
     /// Create a new frozen effect
     pub fn frozen(duration: f32) -> Self {
+        Self::damage_over_time(StatusEffectType::Frozen, duration, 0.0, 0)
+    }
+
+    fn damage_over_time(
+        effect_type: StatusEffectType,
+        duration: f32,
+        tick_interval: f32,
+        damage_per_tick: i32,
+    ) -> Self {
         Self {
-            effect_type: StatusEffectType::Frozen,
+            effect_type,
             duration,
-            tick_interval: 0.0,
+            tick_interval,
             time_since_tick: 0.0,
-            damage_per_tick: 0,
+            damage_per_tick,
         }
     }
-    
+
     /// Check if this effect should deal damage this frame
     pub fn should_tick(&mut self, dt: f32) -> bool {
         self.time_since_tick += dt;
@@ -55,55 +97,138 @@ impl StatusEffect {
     }
 }
 
+/// Every status effect active on an actor or the player
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+/// What happened to a set of effects over one frame
+#[derive(Debug, Default, PartialEq)]
+pub struct StatusTick {
+    /// Damage over time dealt this frame
+    pub damage: i32,
+    /// Effects that ran out this frame
+    pub expired: Vec<StatusEffectType>,
+}
+
+impl StatusEffects {
+    /// Start an effect. An effect of the same type that is already running is
+    /// replaced if the new one lasts longer, so repeated hits refresh rather
+    /// than stack.
+    pub fn add(&mut self, effect: StatusEffect) {
+        match self
+            .0
+            .iter_mut()
+            .find(|active| active.effect_type == effect.effect_type)
+        {
+            Some(active) if active.duration < effect.duration => *active = effect,
+            Some(_) => {}
+            None => self.0.push(effect),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.0.iter()
+    }
+
+    /// Advance every effect by `dt`, dropping the ones that run out
+    pub fn tick(&mut self, dt: f32) -> StatusTick {
+        let mut result = StatusTick::default();
+        for effect in self.0.iter_mut() {
+            effect.duration -= dt;
+            if effect.should_tick(dt) {
+                result.damage += effect.damage_per_tick;
+            }
+            if effect.duration <= 0.0 {
+                result.expired.push(effect.effect_type);
+            }
+        }
+        self.0.retain(|effect| effect.duration > 0.0);
+        result
+    }
+}
+
+/// Start a status effect on an entity, adding `StatusEffects` if it has none
+pub fn add_status_effect(commands: &mut Commands, entity: Entity, effect: StatusEffect) {
+    commands
+        .entity(entity)
+        .entry::<StatusEffects>()
+        .or_default()
+        .and_modify(move |mut effects| effects.add(effect));
+}
+
 /// System to update status effects on actors
 pub fn update_status_effects(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut StatusEffect, &mut crate::actor::Actor)>,
+    mut query: Query<(Entity, &mut StatusEffects, &mut crate::actor::Actor)>,
     mut commands: Commands,
 ) {
     let dt = time.delta_secs();
-    
-    for (entity, mut effect, mut actor) in query.iter_mut() {
-        // Update duration
-        effect.duration -= dt;
-        
-        // Apply damage if it's time to tick
-        if effect.should_tick(dt) {
-            actor.health -= effect.damage_per_tick as f32;
-            
-            // Print feedback (currently only Frozen, which doesn't deal damage)
-            match effect.effect_type {
-                StatusEffectType::Frozen => {
-                    // Frozen doesn't deal damage
-                }
-            }
+
+    for (entity, mut effects, mut actor) in query.iter_mut() {
+        let tick = effects.tick(dt);
+        actor.health -= tick.damage as f32;
+
+        if tick.expired.contains(&StatusEffectType::Frozen) {
+            println!("{} thawed out", actor.actor_type);
         }
-        
-        // Remove effect when expired
-        if effect.duration <= 0.0 {
-            commands.entity(entity).remove::<StatusEffect>();
-            
-            match effect.effect_type {
-                StatusEffectType::Frozen => {
-                    println!("{} thawed out", actor.actor_type);
-                }
-            }
+
+        // Remove the component once every effect has expired
+        if effects.is_empty() {
+            commands.entity(entity).remove::<StatusEffects>();
         }
     }
 }
 
+/// System to update status effects on the player. Damage over time comes out
+/// of the player's health like any other hit.
+pub fn update_player_status_effects(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut StatusEffects, &mut Player)>,
+    mut commands: Commands,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut effects, mut player) in query.iter_mut() {
+        let tick = effects.tick(dt);
+        player.take_damage(tick.damage as f32);
+
+        if effects.is_empty() {
+            commands.entity(entity).remove::<StatusEffects>();
+        }
+    }
+}
+
+/// How long a fire hit keeps the target burning, in seconds
+pub const BURN_DURATION: f32 = 3.0;
+
+/// How long an ice hit keeps the target frozen, in seconds
+pub const FROZEN_DURATION: f32 = 2.0;
+
+/// Status effect a hit of the given damage type leaves behind, if any
+pub fn status_effect_for(damage_type: DamageType) -> Option<StatusEffect> {
+    match damage_type {
+        DamageType::Physical => None,
+        DamageType::Fire => Some(StatusEffect::burn(BURN_DURATION)),
+        DamageType::Ice => Some(StatusEffect::frozen(FROZEN_DURATION)),
+    }
+}
+
 /// Apply a status effect to an actor based on damage type
 /// Returns true if an effect was applied
 pub fn apply_status_effect(
-    _commands: &mut Commands,
-    _entity: Entity,
+    commands: &mut Commands,
+    entity: Entity,
     damage_type: DamageType,
-    _actor_type: &str,
 ) -> bool {
-    match damage_type {
-        DamageType::Physical => {
-            // Physical damage doesn't apply status effects
-            false
+    match status_effect_for(damage_type) {
+        Some(effect) => {
+            add_status_effect(commands, entity, effect);
+            true
         }
+        None => false,
     }
 }
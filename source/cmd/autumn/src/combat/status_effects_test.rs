@@ -0,0 +1,86 @@
+use super::damage::DamageType;
+use super::status_effects::{
+    BURN_DURATION, FROZEN_DURATION, StatusEffect, StatusEffectType, StatusEffects, StatusTick,
+    status_effect_for,
+};
+use super::weapon::WeaponDefinitions;
+
+fn has(effects: &StatusEffects, effect_type: StatusEffectType) -> bool {
+    effects.iter().any(|effect| effect.effect_type == effect_type)
+}
+
+#[test]
+fn test_constructors_set_damage_over_time() {
+    let poison = StatusEffect::poison(5.0);
+    assert_eq!(poison.effect_type, StatusEffectType::Poison);
+    assert!(poison.damage_per_tick > 0 && poison.tick_interval > 0.0);
+
+    let burn = StatusEffect::burn(3.0);
+    assert_eq!(burn.effect_type, StatusEffectType::Burn);
+    assert!(burn.tick_interval < poison.tick_interval);
+
+    let stun = StatusEffect::stun(1.0);
+    assert_eq!(stun.damage_per_tick, 0);
+    assert_eq!(StatusEffect::new(StatusEffectType::Stun, 1.0), stun);
+}
+
+#[test]
+fn test_same_effect_refreshes_instead_of_stacking() {
+    let mut effects = StatusEffects::default();
+    effects.add(StatusEffect::poison(2.0));
+    effects.add(StatusEffect::poison(5.0));
+    effects.add(StatusEffect::poison(1.0));
+    effects.add(StatusEffect::burn(1.0));
+
+    assert_eq!(effects.0.len(), 2);
+    assert_eq!(effects.0[0].duration, 5.0);
+    assert!(has(&effects, StatusEffectType::Burn));
+    assert!(!has(&effects, StatusEffectType::Stun));
+}
+
+#[test]
+fn test_tick_deals_damage_and_drops_expired_effects() {
+    let mut effects = StatusEffects::default();
+    effects.add(StatusEffect::poison(1.5));
+    effects.add(StatusEffect::stun(0.5));
+
+    let tick = effects.tick(1.0);
+    assert_eq!(
+        tick,
+        StatusTick {
+            damage: StatusEffect::poison(0.0).damage_per_tick,
+            expired: vec![StatusEffectType::Stun],
+        }
+    );
+    assert!(has(&effects, StatusEffectType::Poison));
+    assert!(!has(&effects, StatusEffectType::Stun));
+
+    let tick = effects.tick(1.0);
+    assert_eq!(tick.expired, vec![StatusEffectType::Poison]);
+    assert!(effects.is_empty());
+}
+
+#[test]
+fn test_damage_types_leave_their_status_effect() {
+    assert_eq!(status_effect_for(DamageType::Physical), None);
+    assert_eq!(
+        status_effect_for(DamageType::Fire),
+        Some(StatusEffect::burn(BURN_DURATION))
+    );
+    assert_eq!(
+        status_effect_for(DamageType::Ice),
+        Some(StatusEffect::frozen(FROZEN_DURATION))
+    );
+}
+
+#[test]
+fn test_shipped_weapons_include_one_with_a_status_effect() {
+    let definitions =
+        WeaponDefinitions::load_from_file("data/weapons.yaml").expect("weapons.yaml should load");
+    assert!(
+        definitions
+            .weapons
+            .values()
+            .any(|weapon| status_effect_for(weapon.damage_type).is_some())
+    );
+}
@@ -1,6 +1,9 @@
 /// Visual feedback for combat actions
 ///
 /// Handles blood particles, damage numbers, and other visual effects.
+use super::recycle_pool::{BoundedPool, RecyclePool};
+use crate::game_state::GamePlayEntity;
+use crate::scripting::{CVarRegistry, CVarValue};
 use bevy::prelude::*;
 
 /// Component for damage number floating text
@@ -22,13 +25,33 @@ impl DamageNumber {
     }
 }
 
+/// Damage number entities that have expired and can be reused
+///
+/// Expired numbers are hidden rather than despawned so heavy combat doesn't
+/// spawn and despawn text entities every hit. The numbers belong to the game
+/// and go away with it, so the pool is cleared when leaving gameplay.
+#[derive(Resource, Default)]
+pub struct DamageNumberPool(pub RecyclePool<Entity>);
+
 /// System to update damage number positions and lifetime
 pub fn update_damage_numbers(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Transform, &mut DamageNumber, &mut TextColor)>,
-    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut DamageNumber,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+    mut pool: ResMut<DamageNumberPool>,
 ) {
-    for (entity, mut transform, mut damage_num, mut text_color) in query.iter_mut() {
+    for (entity, mut transform, mut damage_num, mut text_color, mut visibility) in query.iter_mut()
+    {
+        // Pooled numbers waiting for reuse
+        if damage_num.lifetime <= 0.0 {
+            continue;
+        }
+
         // Move upward
         transform.translation.z += time.delta_secs() * 2.0;
 
@@ -39,9 +62,10 @@ pub fn update_damage_numbers(
         // Update lifetime
         damage_num.lifetime -= time.delta_secs();
 
-        // Despawn when expired
+        // Hide and return to the pool when expired
         if damage_num.lifetime <= 0.0 {
-            commands.entity(entity).despawn();
+            *visibility = Visibility::Hidden;
+            pool.0.release(entity);
         }
     }
 }
@@ -50,6 +74,7 @@ pub fn update_damage_numbers(
 pub fn spawn_damage_number(
     commands: &mut Commands,
     _asset_server: &Res<AssetServer>,
+    pool: &mut DamageNumberPool,
     position: Vec3,
     damage: i32,
     critical: bool,
@@ -62,7 +87,7 @@ pub fn spawn_damage_number(
 
     let font_size = if critical { 48.0 } else { 32.0 };
 
-    commands.spawn((
+    let bundle = (
         Text2d::new(damage.to_string()),
         TextFont {
             font_size,
@@ -71,7 +96,18 @@ pub fn spawn_damage_number(
         TextColor(color),
         Transform::from_translation(position + Vec3::new(0.0, 0.0, 2.0)),
         DamageNumber::new(),
-    ));
+        Visibility::Visible,
+    );
+
+    // Reset a pooled number if one is free, otherwise spawn a new one
+    match pool.0.acquire() {
+        Some(entity) => {
+            commands.entity(entity).try_insert(bundle);
+        }
+        None => {
+            commands.spawn((GamePlayEntity, bundle));
+        }
+    }
 }
 
 /// Component for blood particle effect
@@ -93,15 +129,96 @@ impl BloodParticle {
     }
 }
 
+/// Default number of pre-allocated blood particle entities
+pub const DEFAULT_PARTICLE_POOL_SIZE: i32 = 64;
+
+/// Pre-allocated blood particle entities sharing one mesh and material
+///
+/// Particles are hidden when they expire and reused on the next hit. When
+/// every particle is in flight the oldest one is recycled, so sustained combat
+/// never allocates more than `combat.particle_pool_size` entities.
+#[derive(Resource, Default)]
+pub struct BloodParticlePool(pub BoundedPool<Entity>);
+
+/// Register the visual feedback cvars
+pub fn init_visual_feedback_cvars(cvars: &mut CVarRegistry) {
+    cvars
+        .init(
+            "combat.particle_pool_size",
+            CVarValue::Int32(DEFAULT_PARTICLE_POOL_SIZE),
+        )
+        .unwrap();
+}
+
+/// System to build the blood particle pool when entering gameplay. Changes to
+/// `combat.particle_pool_size` take effect the next time the game is started.
+pub fn init_blood_particle_pool(
+    mut commands: Commands,
+    mut pool: ResMut<BloodParticlePool>,
+    cvars: Res<CVarRegistry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let size = cvars
+        .get_i32_or("combat.particle_pool_size", DEFAULT_PARTICLE_POOL_SIZE)
+        .max(0) as usize;
+
+    // Normally empty, since leaving gameplay clears the pool
+    for entity in pool.0.drain() {
+        commands.entity(entity).try_despawn();
+    }
+
+    let blood_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.6, 0.0, 0.0),
+        unlit: true,
+        ..default()
+    });
+    let particle_mesh = meshes.add(Sphere::new(0.05));
+
+    let entities = (0..size)
+        .map(|_| {
+            commands
+                .spawn((
+                    GamePlayEntity,
+                    Mesh3d(particle_mesh.clone()),
+                    MeshMaterial3d(blood_material.clone()),
+                    Transform::default(),
+                    BloodParticle {
+                        velocity: Vec3::ZERO,
+                        lifetime: 0.0,
+                    },
+                    Visibility::Hidden,
+                ))
+                .id()
+        })
+        .collect();
+    pool.0 = BoundedPool::new(entities);
+}
+
+/// System to forget the pooled damage numbers and blood particles when leaving
+/// gameplay, as the entities are despawned along with the rest of the game
+pub fn clear_effect_pools(
+    mut damage_numbers: ResMut<DamageNumberPool>,
+    mut blood_particles: ResMut<BloodParticlePool>,
+) {
+    damage_numbers.0.clear();
+    blood_particles.0.clear();
+}
+
 /// System to update blood particles
 pub fn update_blood_particles(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Transform, &mut BloodParticle)>,
-    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut BloodParticle, &mut Visibility)>,
+    mut pool: ResMut<BloodParticlePool>,
 ) {
     let dt = time.delta_secs();
 
-    for (entity, mut transform, mut particle) in query.iter_mut() {
+    for (entity, mut transform, mut particle, mut visibility) in query.iter_mut() {
+        // Pooled particles waiting for reuse
+        if particle.lifetime <= 0.0 {
+            continue;
+        }
+
         // Apply velocity
         transform.translation += particle.velocity * dt;
 
@@ -111,9 +228,10 @@ pub fn update_blood_particles(
         // Update lifetime
         particle.lifetime -= dt;
 
-        // Despawn when expired
+        // Hide and return to the pool when expired
         if particle.lifetime <= 0.0 {
-            commands.entity(entity).despawn();
+            *visibility = Visibility::Hidden;
+            pool.0.release(entity);
         }
     }
 }
@@ -121,20 +239,16 @@ pub fn update_blood_particles(
 /// Spawn blood particles at the given position
 pub fn spawn_blood_particles(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    pool: &mut BloodParticlePool,
     position: Vec3,
     count: u32,
 ) {
-    let blood_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.6, 0.0, 0.0),
-        unlit: true,
-        ..default()
-    });
-
-    let particle_mesh = meshes.add(Sphere::new(0.05));
-
     for _ in 0..count {
+        let Some(entity) = pool.0.acquire() else {
+            // Pool size of zero disables blood particles
+            return;
+        };
+
         // Random velocity
         let velocity = Vec3::new(
             (rand::random::<f32>() - 0.5) * 4.0,
@@ -142,11 +256,10 @@ pub fn spawn_blood_particles(
             rand::random::<f32>() * 3.0,
         );
 
-        commands.spawn((
-            Mesh3d(particle_mesh.clone()),
-            MeshMaterial3d(blood_material.clone()),
+        commands.entity(entity).try_insert((
             Transform::from_translation(position),
             BloodParticle::new(velocity),
+            Visibility::Visible,
         ));
     }
 }
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::damage::DamageType;
-use crate::scripting::{CVarRegistry, CVarValue};
+use crate::scripting::{CVarRegistry, CVarValue, ScriptOutput};
 
 /// Animation keyframe positions and rotations
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,6 +20,14 @@ pub struct AnimationKeyframe {
     pub rotation: (f32, f32),
 }
 
+/// Alternate attack keyframes used by chained (combo) attacks
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComboKeyframes {
+    pub windup_keyframe: AnimationKeyframe,
+    pub swing_keyframe: AnimationKeyframe,
+    pub thrust_keyframe: AnimationKeyframe,
+}
+
 /// Complete definition of a weapon type
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WeaponDefinition {
@@ -47,14 +55,128 @@ pub struct WeaponDefinition {
     /// Height of attack hitbox (vertical reach)
     pub hitbox_height: f32,
     
+    /// Most actors a single attack can hit; the nearest are hit first
+    #[serde(default = "default_max_targets")]
+    pub max_targets: i32,
+    
     /// Type of damage this weapon deals
     pub damage_type: DamageType,
     
+    /// Durability when new; weapons without one never break
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_durability: Option<f32>,
+    
     /// Animation keyframes for different attack phases
     pub rest_keyframe: AnimationKeyframe,
     pub windup_keyframe: AnimationKeyframe,
     pub swing_keyframe: AnimationKeyframe,
     pub thrust_keyframe: AnimationKeyframe,
+    
+    /// Extra keyframe sets that combo attacks cycle through after the base set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub combo_keyframes: Vec<ComboKeyframes>,
+}
+
+fn default_max_targets() -> i32 {
+    1
+}
+
+impl WeaponDefinition {
+    /// Replace the attack keyframes with the set used at the given combo depth
+    pub fn apply_combo_keyframes(&mut self, combo_depth: u32) {
+        let Some(index) = combo_keyframe_index(combo_depth, self.combo_keyframes.len()) else {
+            return;
+        };
+        let set = self.combo_keyframes[index].clone();
+        self.windup_keyframe = set.windup_keyframe;
+        self.swing_keyframe = set.swing_keyframe;
+        self.thrust_keyframe = set.thrust_keyframe;
+    }
+    
+    /// Check the stats are usable, describing every bad value found
+    /// 
+    /// The attack animation divides by the swing duration, so a zero or
+    /// negative one breaks it in ways that are hard to trace back to the file.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+        let mut check = |field: &str, value: f32, valid: bool, rule: &str| {
+            if !valid || !value.is_finite() {
+                problems.push(format!("{} must be {} (got {})", field, rule, value));
+            }
+        };
+        
+        check("swing_duration", self.swing_duration, self.swing_duration > 0.0, "greater than 0");
+        check("max_charge_time", self.max_charge_time, self.max_charge_time >= 0.0, "at least 0");
+        check("charge_bonus", self.charge_bonus, self.charge_bonus >= 0.0, "at least 0");
+        check("range", self.range, self.range >= 0.0, "at least 0");
+        check("hitbox_width", self.hitbox_width, self.hitbox_width >= 0.0, "at least 0");
+        check("hitbox_height", self.hitbox_height, self.hitbox_height >= 0.0, "at least 0");
+        if let Some(max_durability) = self.max_durability {
+            check("max_durability", max_durability, max_durability > 0.0, "greater than 0");
+        }
+        if self.max_targets < 1 {
+            problems.push(format!("max_targets must be at least 1 (got {})", self.max_targets));
+        }
+        
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join(", "))
+        }
+    }
+}
+
+/// Index into `combo_keyframes` for a combo depth, or None for the base set
+/// 
+/// Combo attacks cycle through the base set followed by each extra set, so a
+/// single extra set alternates with the base swing.
+pub fn combo_keyframe_index(combo_depth: u32, combo_sets: usize) -> Option<usize> {
+    let index = combo_depth as usize % (combo_sets + 1);
+    index.checked_sub(1)
+}
+
+/// Every runtime-tunable CVar of a weapon, as (field name, value) pairs
+/// 
+/// The full CVar name is `weapon.<type>.<field>`, for example:
+/// - weapon.sword.attack_power
+/// - weapon.sword.swing_duration
+/// - weapon.sword.windup_pos_x
+fn weapon_cvar_values(weapon: &WeaponDefinition) -> Vec<(&'static str, CVarValue)> {
+    // Stats
+    let mut values = vec![
+        ("attack_power", CVarValue::Int32(weapon.attack_power)),
+        ("swing_duration", CVarValue::F32(weapon.swing_duration)),
+        ("max_charge_time", CVarValue::F32(weapon.max_charge_time)),
+        ("charge_bonus", CVarValue::F32(weapon.charge_bonus)),
+        ("range", CVarValue::F32(weapon.range)),
+        ("hitbox_width", CVarValue::F32(weapon.hitbox_width)),
+        ("hitbox_height", CVarValue::F32(weapon.hitbox_height)),
+        ("max_targets", CVarValue::Int32(weapon.max_targets)),
+    ];
+    if let Some(max_durability) = weapon.max_durability {
+        values.push(("max_durability", CVarValue::F32(max_durability)));
+    }
+    
+    // Animation keyframes
+    let keyframes = [
+        (["rest_pos_x", "rest_pos_y", "rest_pos_z", "rest_rotation_z", "rest_rotation_y"], &weapon.rest_keyframe),
+        (["windup_pos_x", "windup_pos_y", "windup_pos_z", "windup_rotation_z", "windup_rotation_y"], &weapon.windup_keyframe),
+        (["swing_pos_x", "swing_pos_y", "swing_pos_z", "swing_rotation_z", "swing_rotation_y"], &weapon.swing_keyframe),
+        (["thrust_pos_x", "thrust_pos_y", "thrust_pos_z", "thrust_rotation_z", "thrust_rotation_y"], &weapon.thrust_keyframe),
+    ];
+    for (fields, keyframe) in keyframes {
+        let components = [
+            keyframe.position.x,
+            keyframe.position.y,
+            keyframe.position.z,
+            keyframe.rotation.0,
+            keyframe.rotation.1,
+        ];
+        for (field, value) in fields.into_iter().zip(components) {
+            values.push((field, CVarValue::F32(value)));
+        }
+    }
+    values
 }
 
 /// Resource holding all loaded weapon definitions
@@ -72,6 +194,14 @@ impl WeaponDefinitions {
         let weapons: HashMap<String, WeaponDefinition> = serde_yaml::from_str(&yaml)
             .map_err(|e| format!("Failed to parse {}: {}", path, e))?;
         
+        let mut weapon_types: Vec<&String> = weapons.keys().collect();
+        weapon_types.sort();
+        for weapon_type in weapon_types {
+            weapons[weapon_type]
+                .validate()
+                .map_err(|e| format!("Invalid weapon '{}' in {}: {}", weapon_type, path, e))?;
+        }
+        
         Ok(Self { weapons })
     }
     
@@ -84,45 +214,33 @@ impl WeaponDefinitions {
     /// etc.
     pub fn register_cvars(&self, cvars: &mut CVarRegistry) {
         for (weapon_type, weapon) in &self.weapons {
-            let prefix = format!("weapon.{}", weapon_type);
-            
-            // Register stat CVars
-            let _ = cvars.init(&format!("{}.attack_power", prefix), CVarValue::Int32(weapon.attack_power));
-            let _ = cvars.init(&format!("{}.swing_duration", prefix), CVarValue::F32(weapon.swing_duration));
-            let _ = cvars.init(&format!("{}.max_charge_time", prefix), CVarValue::F32(weapon.max_charge_time));
-            let _ = cvars.init(&format!("{}.charge_bonus", prefix), CVarValue::F32(weapon.charge_bonus));
-            let _ = cvars.init(&format!("{}.range", prefix), CVarValue::F32(weapon.range));
-            let _ = cvars.init(&format!("{}.hitbox_width", prefix), CVarValue::F32(weapon.hitbox_width));
-            let _ = cvars.init(&format!("{}.hitbox_height", prefix), CVarValue::F32(weapon.hitbox_height));
-            
-            // Register animation keyframe CVars - Rest
-            let _ = cvars.init(&format!("{}.rest_pos_x", prefix), CVarValue::F32(weapon.rest_keyframe.position.x));
-            let _ = cvars.init(&format!("{}.rest_pos_y", prefix), CVarValue::F32(weapon.rest_keyframe.position.y));
-            let _ = cvars.init(&format!("{}.rest_pos_z", prefix), CVarValue::F32(weapon.rest_keyframe.position.z));
-            let _ = cvars.init(&format!("{}.rest_rotation_z", prefix), CVarValue::F32(weapon.rest_keyframe.rotation.0));
-            let _ = cvars.init(&format!("{}.rest_rotation_y", prefix), CVarValue::F32(weapon.rest_keyframe.rotation.1));
-            
-            // Register animation keyframe CVars - Windup
-            let _ = cvars.init(&format!("{}.windup_pos_x", prefix), CVarValue::F32(weapon.windup_keyframe.position.x));
-            let _ = cvars.init(&format!("{}.windup_pos_y", prefix), CVarValue::F32(weapon.windup_keyframe.position.y));
-            let _ = cvars.init(&format!("{}.windup_pos_z", prefix), CVarValue::F32(weapon.windup_keyframe.position.z));
-            let _ = cvars.init(&format!("{}.windup_rotation_z", prefix), CVarValue::F32(weapon.windup_keyframe.rotation.0));
-            let _ = cvars.init(&format!("{}.windup_rotation_y", prefix), CVarValue::F32(weapon.windup_keyframe.rotation.1));
-            
-            // Register animation keyframe CVars - Swing
-            let _ = cvars.init(&format!("{}.swing_pos_x", prefix), CVarValue::F32(weapon.swing_keyframe.position.x));
-            let _ = cvars.init(&format!("{}.swing_pos_y", prefix), CVarValue::F32(weapon.swing_keyframe.position.y));
-            let _ = cvars.init(&format!("{}.swing_pos_z", prefix), CVarValue::F32(weapon.swing_keyframe.position.z));
-            let _ = cvars.init(&format!("{}.swing_rotation_z", prefix), CVarValue::F32(weapon.swing_keyframe.rotation.0));
-            let _ = cvars.init(&format!("{}.swing_rotation_y", prefix), CVarValue::F32(weapon.swing_keyframe.rotation.1));
-            
-            // Register animation keyframe CVars - Thrust
-            let _ = cvars.init(&format!("{}.thrust_pos_x", prefix), CVarValue::F32(weapon.thrust_keyframe.position.x));
-            let _ = cvars.init(&format!("{}.thrust_pos_y", prefix), CVarValue::F32(weapon.thrust_keyframe.position.y));
-            let _ = cvars.init(&format!("{}.thrust_pos_z", prefix), CVarValue::F32(weapon.thrust_keyframe.position.z));
-            let _ = cvars.init(&format!("{}.thrust_rotation_z", prefix), CVarValue::F32(weapon.thrust_keyframe.rotation.0));
-            let _ = cvars.init(&format!("{}.thrust_rotation_y", prefix), CVarValue::F32(weapon.thrust_keyframe.rotation.1));
+            for (field, value) in weapon_cvar_values(weapon) {
+                let _ = cvars.init(&format!("weapon.{}.{}", weapon_type, field), value);
+            }
+        }
+    }
+    
+    /// Set every CVar of a weapon back to the value from its definition
+    /// 
+    /// Returns the number of CVars reset, or an error for an unknown weapon.
+    pub fn reset_cvars(&self, weapon_type: &str, cvars: &mut CVarRegistry) -> Result<usize, String> {
+        let weapon = self
+            .weapons
+            .get(weapon_type)
+            .ok_or_else(|| format!("Unknown weapon type: {}", weapon_type))?;
+        
+        let values = weapon_cvar_values(weapon);
+        let count = values.len();
+        for (field, value) in values {
+            let name = format!("weapon.{}.{}", weapon_type, field);
+            // Register anything that was never initialized rather than failing
+            if cvars.exists(&name) {
+                cvars.set(&name, value)?;
+            } else {
+                cvars.init(&name, value)?;
+            }
         }
+        Ok(count)
     }
     
     /// Get a weapon definition with values updated from CVars
@@ -154,6 +272,12 @@ impl WeaponDefinitions {
         if cvars.exists(&format!("{}.hitbox_height", prefix)) {
             weapon.hitbox_height = cvars.get_f32(&format!("{}.hitbox_height", prefix));
         }
+        if cvars.exists(&format!("{}.max_targets", prefix)) {
+            weapon.max_targets = cvars.get_i32(&format!("{}.max_targets", prefix));
+        }
+        if weapon.max_durability.is_some() && cvars.exists(&format!("{}.max_durability", prefix)) {
+            weapon.max_durability = Some(cvars.get_f32(&format!("{}.max_durability", prefix)));
+        }
         
         // Update rest keyframe from CVars
         weapon.rest_keyframe.position.x = cvars.get_f32(&format!("{}.rest_pos_x", prefix));
@@ -185,4 +309,75 @@ impl WeaponDefinitions {
         
         Some(weapon)
     }
+    
+    /// Serialize a weapon, including any values tuned through its CVars, as a
+    /// YAML snippet in the same layout as weapons.yaml
+    pub fn dump_yaml(&self, weapon_type: &str, cvars: &CVarRegistry) -> Result<String, String> {
+        let weapon = self
+            .get_with_cvars(weapon_type, cvars)
+            .ok_or_else(|| format!("Unknown weapon type: {}", weapon_type))?;
+        
+        let mut mapping = serde_yaml::Mapping::new();
+        let value = serde_yaml::to_value(&weapon)
+            .map_err(|e| format!("Failed to serialize {}: {}", weapon_type, e))?;
+        mapping.insert(serde_yaml::Value::String(weapon_type.to_string()), value);
+        
+        serde_yaml::to_string(&mapping)
+            .map_err(|e| format!("Failed to serialize {}: {}", weapon_type, e))
+    }
+}
+
+/// Handle the console-only `weapon_reset <type>` command, which restores a
+/// weapon's CVars to the values from weapons.yaml. Returns None for any other
+/// command.
+pub fn process_weapon_reset_command(
+    script: &str,
+    cvars: &mut CVarRegistry,
+    weapon_definitions: &WeaponDefinitions,
+) -> Option<Vec<ScriptOutput>> {
+    let mut tokens = script.split_whitespace();
+    if tokens.next() != Some("weapon_reset") {
+        return None;
+    }
+
+    let output = match (tokens.next(), tokens.next()) {
+        (Some(weapon_type), None) => match weapon_definitions.reset_cvars(weapon_type, cvars) {
+            Ok(count) => ScriptOutput::info(format!("Reset {} cvars for {}", count, weapon_type)),
+            Err(e) => ScriptOutput::error(e),
+        },
+        _ => ScriptOutput::error("Usage: weapon_reset <type>"),
+    };
+    Some(vec![output])
+}
+
+/// Handle the console-only `weapon_dump <type> [path]` command, which writes
+/// a weapon's current CVar values as YAML to a file, or to the console if no
+/// path is given. Returns None for any other command.
+pub fn process_weapon_dump_command(
+    script: &str,
+    cvars: &CVarRegistry,
+    weapon_definitions: &WeaponDefinitions,
+) -> Option<Vec<ScriptOutput>> {
+    let mut tokens = script.split_whitespace();
+    if tokens.next() != Some("weapon_dump") {
+        return None;
+    }
+
+    let (Some(weapon_type), path, None) = (tokens.next(), tokens.next(), tokens.next()) else {
+        return Some(vec![ScriptOutput::error("Usage: weapon_dump <type> [path]")]);
+    };
+
+    let yaml = match weapon_definitions.dump_yaml(weapon_type, cvars) {
+        Ok(yaml) => yaml,
+        Err(e) => return Some(vec![ScriptOutput::error(e)]),
+    };
+
+    let output = match path {
+        Some(path) => match std::fs::write(path, &yaml) {
+            Ok(()) => vec![ScriptOutput::info(format!("Wrote {} to {}", weapon_type, path))],
+            Err(e) => vec![ScriptOutput::error(format!("Failed to write {}: {}", path, e))],
+        },
+        None => yaml.lines().map(ScriptOutput::info).collect(),
+    };
+    Some(output)
 }
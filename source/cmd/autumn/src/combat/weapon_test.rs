@@ -0,0 +1,161 @@
+use super::weapon::{
+    WeaponDefinition, WeaponDefinitions, process_weapon_dump_command, process_weapon_reset_command,
+};
+use crate::scripting::{CVarRegistry, CVarValue, Severity, cmd_setvar_worker};
+use std::collections::HashMap;
+
+fn definitions() -> WeaponDefinitions {
+    WeaponDefinitions::load_from_file("data/weapons.yaml").expect("weapons.yaml should load")
+}
+
+fn registered_cvars(definitions: &WeaponDefinitions) -> CVarRegistry {
+    let mut cvars = CVarRegistry::new();
+    definitions.register_cvars(&mut cvars);
+    cvars
+}
+
+#[test]
+fn test_weapon_reset_restores_definition_values() {
+    let definitions = definitions();
+    let sword = &definitions.weapons["sword"];
+    let mut cvars = registered_cvars(&definitions);
+
+    cvars
+        .set("weapon.sword.attack_power", CVarValue::Int32(999))
+        .unwrap();
+    cvars.set_f32("weapon.sword.range", 1.0);
+    cvars.set_f32("weapon.sword.windup_pos_x", 42.0);
+    cvars.set_f32("weapon.sword.thrust_rotation_y", -3.0);
+
+    let output = process_weapon_reset_command("weapon_reset sword", &mut cvars, &definitions)
+        .expect("weapon_reset should be handled");
+    assert_eq!(output[0].severity, Severity::Info);
+
+    assert_eq!(
+        cvars.get_i32("weapon.sword.attack_power"),
+        sword.attack_power
+    );
+    assert_eq!(cvars.get_f32("weapon.sword.range"), sword.range);
+    assert_eq!(
+        cvars.get_f32("weapon.sword.windup_pos_x"),
+        sword.windup_keyframe.position.x
+    );
+    assert_eq!(
+        cvars.get_f32("weapon.sword.thrust_rotation_y"),
+        sword.thrust_keyframe.rotation.1
+    );
+
+    // Tuned values now match what get_with_cvars reads back
+    let tuned = definitions.get_with_cvars("sword", &cvars).unwrap();
+    assert_eq!(tuned.attack_power, sword.attack_power);
+    assert_eq!(tuned.swing_keyframe.position, sword.swing_keyframe.position);
+}
+
+#[test]
+fn test_weapon_reset_leaves_other_weapons_alone() {
+    let definitions = definitions();
+    let mut cvars = registered_cvars(&definitions);
+
+    cvars.set_f32("weapon.axe.range", 1.0);
+    definitions.reset_cvars("sword", &mut cvars).unwrap();
+    assert_eq!(cvars.get_f32("weapon.axe.range"), 1.0);
+}
+
+#[test]
+fn test_weapon_reset_errors() {
+    let definitions = definitions();
+    let mut cvars = registered_cvars(&definitions);
+
+    let output =
+        process_weapon_reset_command("weapon_reset club", &mut cvars, &definitions).unwrap();
+    assert_eq!(output[0].severity, Severity::Error);
+
+    let output = process_weapon_reset_command("weapon_reset", &mut cvars, &definitions).unwrap();
+    assert_eq!(output[0].severity, Severity::Error);
+
+    assert!(process_weapon_reset_command("repair", &mut cvars, &definitions).is_none());
+}
+
+#[test]
+fn test_weapon_dump_reflects_setvar() {
+    let definitions = definitions();
+    let mut cvars = registered_cvars(&definitions);
+
+    cmd_setvar_worker(&["setvar", "weapon.axe.range", "12.5"], &mut cvars);
+    cmd_setvar_worker(&["setvar", "weapon.axe.swing_pos_y", "-0.25"], &mut cvars);
+
+    let yaml = definitions.dump_yaml("axe", &cvars).unwrap();
+    let dumped: HashMap<String, WeaponDefinition> = serde_yaml::from_str(&yaml).unwrap();
+    let axe = &dumped["axe"];
+    assert_eq!(axe.range, 12.5);
+    assert_eq!(axe.swing_keyframe.position.y, -0.25);
+    assert_eq!(axe.attack_power, definitions.weapons["axe"].attack_power);
+}
+
+#[test]
+fn test_weapon_dump_command() {
+    let definitions = definitions();
+    let mut cvars = registered_cvars(&definitions);
+    cmd_setvar_worker(&["setvar", "weapon.spear.attack_power", "42"], &mut cvars);
+
+    let output = process_weapon_dump_command("weapon_dump spear", &cvars, &definitions).unwrap();
+    assert!(output.iter().all(|line| line.severity == Severity::Info));
+    assert!(
+        output
+            .iter()
+            .any(|line| line.text.trim() == "attack_power: 42")
+    );
+
+    let path = std::env::temp_dir().join("weapon_dump_test.yaml");
+    let script = format!("weapon_dump spear {}", path.display());
+    let output = process_weapon_dump_command(&script, &cvars, &definitions).unwrap();
+    assert_eq!(output[0].severity, Severity::Info);
+    let written = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(written.starts_with("spear:"));
+    assert!(written.contains("attack_power: 42"));
+
+    let output = process_weapon_dump_command("weapon_dump club", &cvars, &definitions).unwrap();
+    assert_eq!(output[0].severity, Severity::Error);
+    let output = process_weapon_dump_command("weapon_dump", &cvars, &definitions).unwrap();
+    assert_eq!(output[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_shipped_weapons_are_valid() {
+    for (weapon_type, weapon) in &definitions().weapons {
+        assert_eq!(weapon.validate(), Ok(()), "{}", weapon_type);
+    }
+}
+
+#[test]
+fn test_validate_rejects_zero_swing_duration() {
+    let mut sword = definitions().weapons["sword"].clone();
+    sword.swing_duration = 0.0;
+    let error = sword.validate().unwrap_err();
+    assert!(error.contains("swing_duration"), "{}", error);
+
+    // Every bad value is reported, not just the first
+    sword.range = -1.0;
+    sword.max_targets = 0;
+    let error = sword.validate().unwrap_err();
+    assert!(error.contains("swing_duration"), "{}", error);
+    assert!(error.contains("range"), "{}", error);
+    assert!(error.contains("max_targets"), "{}", error);
+}
+
+#[test]
+fn test_load_rejects_invalid_weapon() {
+    let yaml = std::fs::read_to_string("data/weapons.yaml").unwrap();
+    let yaml = yaml.replacen("swing_duration: 0.4", "swing_duration: 0.0", 1);
+    let path = std::env::temp_dir().join("weapon_validate_test.yaml");
+    std::fs::write(&path, yaml).unwrap();
+    let result = WeaponDefinitions::load_from_file(path.to_str().unwrap());
+    let _ = std::fs::remove_file(&path);
+
+    let error = result
+        .err()
+        .expect("zero swing_duration should be rejected");
+    assert!(error.contains("'sword'"), "{}", error);
+    assert!(error.contains("swing_duration"), "{}", error);
+}
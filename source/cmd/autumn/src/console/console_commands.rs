@@ -0,0 +1,290 @@
+use super::ConsoleState;
+use super::internal::*;
+use crate::camera::Player;
+use crate::combat::{WeaponDefinitions, process_weapon_dump_command, process_weapon_reset_command};
+use crate::hud::{PlayerStats, Toolbar};
+use crate::item::{Inventory, ItemDefinitions, UseItemContext, process_use_item_command};
+use crate::scripting::{
+    AliasRegistry, BindRegistry, CONSOLE_COMMAND_NAMES, CVarRegistry, MapCommandContext,
+    ScriptOutput, WallCommandContext, process_map_command, process_script, process_wall_command,
+};
+use crate::weapon::{WeaponSprite, process_repair_command};
+use crate::world::{CurrentMap, Map};
+
+//=============================================================================
+// Console Commands
+//=============================================================================
+
+/// A command to run, sent when a line is entered in the console or a bound
+/// key is pressed.
+///
+/// Each system below runs the commands it knows about and appends their
+/// output to the console log. Most commands only need resources and go
+/// through `process_script`; the console-only ones (see
+/// `CONSOLE_COMMAND_NAMES`) need entities or game state and get a system of
+/// their own. Senders build it with `ConsoleCommand::expanded` so the systems
+/// see the real command name.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct ConsoleCommand {
+    pub script: String,
+}
+
+impl ConsoleCommand {
+    pub fn new(script: impl Into<String>) -> Self {
+        Self {
+            script: script.into(),
+        }
+    }
+
+    /// A command with the alias it starts with expanded, so an alias of a
+    /// console-only command is routed to the system that runs it. An alias
+    /// loop is passed along as typed for `process_script` to report.
+    pub fn expanded(script: &str, aliases: &AliasRegistry) -> Self {
+        Self::new(aliases.expand(script).unwrap_or_else(|_| script.to_string()))
+    }
+
+    fn is_console_only(&self) -> bool {
+        self.script
+            .split_whitespace()
+            .next()
+            .is_some_and(|name| CONSOLE_COMMAND_NAMES.contains(&name))
+    }
+}
+
+/// Run the commands that only need resources
+pub(super) fn run_script_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+    mut stats: ResMut<PlayerStats>,
+    mut cvars: ResMut<CVarRegistry>,
+    mut aliases: ResMut<AliasRegistry>,
+    mut binds: ResMut<BindRegistry>,
+) {
+    for command in command_reader.read() {
+        if command.is_console_only() {
+            continue;
+        }
+        let output = process_script(
+            &command.script,
+            &mut stats,
+            &mut cvars,
+            &mut aliases,
+            &mut binds,
+        );
+        console_state.push_output(output);
+    }
+}
+
+/// Run `addwall` and `delwall`
+pub(super) fn run_wall_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut map: Option<ResMut<Map>>,
+) {
+    for command in command_reader.read() {
+        let mut ctx = WallCommandContext {
+            commands: &mut commands,
+            asset_server: &asset_server,
+            meshes: &mut meshes,
+            materials: &mut materials,
+            map: map.as_deref_mut(),
+        };
+        if let Some(output) = process_wall_command(&command.script, &mut ctx) {
+            console_state.push_output(output);
+        }
+    }
+}
+
+/// Run `map`
+pub(super) fn run_map_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+    mut current_map: ResMut<CurrentMap>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for command in command_reader.read() {
+        let mut ctx = MapCommandContext {
+            current_map: &mut current_map,
+            next_state: &mut next_state,
+        };
+        if let Some(output) = process_map_command(&command.script, &mut ctx) {
+            console_state.push_output(output);
+        }
+    }
+}
+
+/// Run `repair`
+pub(super) fn run_repair_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+    mut weapons: Query<&mut WeaponSprite>,
+) {
+    for command in command_reader.read() {
+        if let Some(output) = process_repair_command(&command.script, &mut weapons) {
+            console_state.push_output(output);
+        }
+    }
+}
+
+/// Run `use_item`. The item definitions only exist once a game has started.
+pub(super) fn run_item_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+    mut toolbar: ResMut<Toolbar>,
+    mut inventory: ResMut<Inventory>,
+    mut stats: ResMut<PlayerStats>,
+    item_definitions: Option<Res<ItemDefinitions>>,
+    mut players: Query<&mut Player>,
+) {
+    let Some(item_definitions) = item_definitions else {
+        return;
+    };
+    for command in command_reader.read() {
+        let mut player = players.single_mut().ok();
+        let mut ctx = UseItemContext {
+            toolbar: &mut toolbar,
+            inventory: &mut inventory,
+            item_definitions: &item_definitions,
+            stats: &mut stats,
+            player: player.as_deref_mut(),
+        };
+        if let Some(output) = process_use_item_command(&command.script, &mut ctx) {
+            console_state.push_output(output);
+        }
+    }
+}
+
+/// Run `clear`, whether typed, aliased or bound to a key
+pub(super) fn run_clear_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+) {
+    for command in command_reader.read() {
+        if command.script.split_whitespace().next() == Some("clear") {
+            console_state.clear_log();
+        }
+    }
+}
+
+/// Run `weapon_reset` and `weapon_dump`. The definitions only exist once a
+/// game has started.
+pub(super) fn run_weapon_commands(
+    mut command_reader: MessageReader<ConsoleCommand>,
+    mut console_state: ResMut<ConsoleState>,
+    mut cvars: ResMut<CVarRegistry>,
+    weapon_definitions: Option<Res<WeaponDefinitions>>,
+) {
+    for command in command_reader.read() {
+        let name = command.script.split_whitespace().next().unwrap_or("");
+        if name != "weapon_reset" && name != "weapon_dump" {
+            continue;
+        }
+        let output = match weapon_definitions.as_deref() {
+            Some(definitions) => {
+                process_weapon_reset_command(&command.script, &mut cvars, definitions)
+                    .or_else(|| process_weapon_dump_command(&command.script, &cvars, definitions))
+                    .unwrap_or_default()
+            }
+            None => vec![ScriptOutput::error(format!(
+                "{} needs the weapon definitions, which load when a game starts",
+                name
+            ))],
+        };
+        console_state.push_output(output);
+    }
+}
+
+//=============================================================================
+// Tests
+//=============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripting::Severity;
+
+    fn make_app() -> App {
+        let mut app = App::new();
+        app.add_message::<ConsoleCommand>()
+            .init_resource::<ConsoleState>()
+            .init_resource::<PlayerStats>()
+            .init_resource::<CVarRegistry>()
+            .init_resource::<AliasRegistry>()
+            .init_resource::<BindRegistry>()
+            .add_systems(
+                Update,
+                (run_script_commands, run_weapon_commands, run_clear_commands),
+            );
+        app
+    }
+
+    fn log_texts(app: &App) -> Vec<String> {
+        let console_state = app.world().resource::<ConsoleState>();
+        console_state
+            .log
+            .iter()
+            .map(|line| line.text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_command_output_is_logged() {
+        let mut app = make_app();
+        app.world_mut()
+            .write_message(ConsoleCommand::new("echo hi"));
+        app.update();
+
+        assert_eq!(log_texts(&app), vec!["  hi"]);
+        assert!(app.world().resource::<ConsoleState>().log_dirty);
+    }
+
+    #[test]
+    fn test_console_only_command_left_to_its_system() {
+        let mut app = make_app();
+        app.world_mut()
+            .write_message(ConsoleCommand::new("addwall 3 4"));
+        app.update();
+
+        // process_script doesn't answer as well as the wall system
+        assert!(log_texts(&app).is_empty());
+    }
+
+    #[test]
+    fn test_console_only_command_runs_once() {
+        let mut app = make_app();
+        app.world_mut()
+            .write_message(ConsoleCommand::new("weapon_dump sword"));
+        app.update();
+
+        // Only the weapon system answers, not process_script as well
+        let console_state = app.world().resource::<ConsoleState>();
+        assert_eq!(console_state.log.len(), 1);
+        assert_eq!(console_state.log[0].severity, Severity::Error);
+        assert!(console_state.log[0].text.contains("weapon definitions"));
+    }
+
+    #[test]
+    fn test_bound_clear_empties_the_log() {
+        let mut app = make_app();
+        app.world_mut()
+            .resource_mut::<AliasRegistry>()
+            .set("cls", "clear")
+            .unwrap();
+        app.world_mut()
+            .write_message(ConsoleCommand::new("echo hi"));
+        app.update();
+        assert_eq!(log_texts(&app), vec!["  hi"]);
+
+        // A key bound to an alias of clear sends the expanded command
+        let aliases = app.world().resource::<AliasRegistry>();
+        let command = ConsoleCommand::expanded("cls", aliases);
+        app.world_mut().write_message(command);
+        app.update();
+
+        assert!(log_texts(&app).is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use super::console_commands::*;
 use super::console_ui::*;
 use super::internal::*;
 
@@ -10,6 +11,7 @@ pub struct ConsolePlugin;
 impl Plugin for ConsolePlugin {
     fn build(&self, app: &mut App) {
         app //
+            .add_message::<ConsoleCommand>()
             .add_systems(
                 OnEnter(GameState::Playing), //
                 startup_console,
@@ -19,6 +21,18 @@ impl Plugin for ConsolePlugin {
                 (
                     update_console_toggle,
                     update_console_input,
+                    (
+                        run_script_commands,
+                        run_wall_commands,
+                        run_map_commands,
+                        run_repair_commands,
+                        run_item_commands,
+                        run_weapon_commands,
+                        run_clear_commands,
+                    )
+                        .after(update_console_input),
+                    update_console_max_lines,
+                    update_console_history,
                     update_console_scroll,
                 )
                     .run_if(in_state(GameState::Playing)),
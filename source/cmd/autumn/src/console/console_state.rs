@@ -1,4 +1,5 @@
 use super::internal::*;
+use crate::scripting::ScriptOutput;
 
 //=============================================================================
 // Console State
@@ -9,9 +10,13 @@ pub struct ConsoleState {
     pub visible: bool,
     pub input_text: String,
     pub cursor_position: usize, // Cursor position in the input text (in chars, not bytes)
-    pub log: Vec<String>,
+    pub log: Vec<ScriptOutput>,
+    pub log_dirty: bool, // Set when the log changes so the history text is rebuilt
+    pub scroll_offset: usize, // Number of rendered lines scrolled up from the bottom of the log
     pub command_history: Vec<String>, // Stores only commands (not output)
     pub history_index: Option<usize>, // Current position in command history
+    pub fuzzy_completions: Vec<String>, // Ranked fuzzy matches cycled by repeated Tab
+    pub fuzzy_completion_index: usize,
 
     // Manual implementation to handle key repeats in the console.
     // TODO: is there a a standard way to implement this so a manual implementation is
@@ -29,11 +34,215 @@ impl Default for ConsoleState {
             input_text: String::new(),
             cursor_position: 0,
             log: Vec::new(),
+            log_dirty: false,
+            scroll_offset: 0,
             command_history: Vec::new(),
             history_index: None,
+            fuzzy_completions: Vec::new(),
+            fuzzy_completion_index: 0,
             key_repeat_timer: 0.0,
             key_repeat_initial_delay: 0.3, // initial delay (in seconds)
             key_repeat_rate: 0.015,        // time between repeats (in seconds)
         }
     }
 }
+
+impl ConsoleState {
+    /// Forget the fuzzy matches Tab is cycling through, so the next Tab
+    /// completes the input afresh
+    pub fn reset_completion(&mut self) {
+        self.fuzzy_completions.clear();
+        self.fuzzy_completion_index = 0;
+    }
+
+    /// Scroll towards older log lines, stopping at the first line
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_offset = (self.scroll_offset + lines).min(self.max_scroll_offset());
+    }
+
+    /// Scroll towards newer log lines, stopping at the bottom
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    pub fn is_scrolled_to_bottom(&self) -> bool {
+        self.scroll_offset == 0
+    }
+
+    /// Empty the log, as the `clear` command does
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+        self.log_dirty = true;
+        self.scroll_to_bottom();
+    }
+
+    /// Append the output of a command to the log, indented under it
+    pub fn push_output(&mut self, output: Vec<ScriptOutput>) {
+        for line in output {
+            self.push_entry(ScriptOutput::new(line.severity, format!("  {}", line.text)));
+        }
+    }
+
+    /// Append a message from the game itself, such as a weapon breaking
+    pub fn push_message(&mut self, message: ScriptOutput) {
+        self.push_entry(message);
+    }
+
+    /// Append an entry to the log. A view scrolled up stays on the lines it
+    /// shows; one at the bottom follows the new output.
+    fn push_entry(&mut self, entry: ScriptOutput) {
+        if !self.is_scrolled_to_bottom() {
+            self.scroll_offset += rendered_lines(&entry);
+        }
+        self.log.push(entry);
+        self.log_dirty = true;
+    }
+
+    /// Drop the oldest log lines so at most `max_lines` remain
+    pub fn trim_log(&mut self, max_lines: usize) {
+        let len = self.log.len();
+        if len <= max_lines {
+            return;
+        }
+        self.log.drain(0..len - max_lines);
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+        self.log_dirty = true;
+    }
+
+    /// The scroll offset that puts the first rendered line of the log at the
+    /// bottom of the view
+    fn max_scroll_offset(&self) -> usize {
+        self.log
+            .iter()
+            .map(rendered_lines)
+            .sum::<usize>()
+            .saturating_sub(1)
+    }
+}
+
+/// Number of lines a log entry takes up in the console, as multi-line output
+/// such as `listvars` is shown one line per row
+fn rendered_lines(entry: &ScriptOutput) -> usize {
+    entry.text.split('\n').count()
+}
+
+//=============================================================================
+// Tests
+//=============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_lines(count: usize) -> ConsoleState {
+        let mut console_state = ConsoleState::default();
+        for i in 0..count {
+            console_state
+                .log
+                .push(ScriptOutput::info(format!("line {}", i)));
+        }
+        console_state
+    }
+
+    #[test]
+    fn test_push_message_is_not_indented() {
+        let mut console_state = state_with_lines(3);
+        console_state.scroll_up(2);
+
+        console_state.push_message(ScriptOutput::warning("Your axe breaks!"));
+        assert_eq!(
+            console_state.log.last(),
+            Some(&ScriptOutput::warning("Your axe breaks!"))
+        );
+        assert!(console_state.log_dirty);
+    }
+
+    #[test]
+    fn test_new_output_follows_the_bottom() {
+        let mut console_state = state_with_lines(3);
+
+        console_state.push_output(vec![ScriptOutput::info("a\nb")]);
+        assert!(console_state.is_scrolled_to_bottom());
+    }
+
+    #[test]
+    fn test_new_output_keeps_scrolled_up_view() {
+        let mut console_state = state_with_lines(5);
+        console_state.scroll_up(2);
+
+        // The view stays on the same lines as output arrives below it
+        console_state.push_output(vec![ScriptOutput::info("a\nb\nc")]);
+        assert_eq!(console_state.scroll_offset, 5);
+        console_state.push_message(ScriptOutput::warning("Your axe breaks!"));
+        assert_eq!(console_state.scroll_offset, 6);
+    }
+
+    #[test]
+    fn test_scroll_up_counts_rendered_lines() {
+        let mut console_state = state_with_lines(2);
+        console_state
+            .log
+            .push(ScriptOutput::info("3 cvars:\n  a = 1\n  b = 2\n  c = 3"));
+
+        // Each line of multi-line output can be scrolled to
+        console_state.scroll_up(100);
+        assert_eq!(console_state.scroll_offset, 5);
+    }
+
+    #[test]
+    fn test_scroll_up_clamps_to_log_length() {
+        let mut console_state = state_with_lines(5);
+
+        console_state.scroll_up(3);
+        assert_eq!(console_state.scroll_offset, 3);
+
+        console_state.scroll_up(10);
+        assert_eq!(console_state.scroll_offset, 4);
+        assert!(!console_state.is_scrolled_to_bottom());
+    }
+
+    #[test]
+    fn test_trim_log_keeps_newest_lines() {
+        let mut console_state = state_with_lines(10);
+        console_state.scroll_up(8);
+
+        console_state.trim_log(20);
+        assert_eq!(console_state.log.len(), 10);
+        assert!(!console_state.log_dirty);
+
+        // Lowering the limit below the log size drops the oldest lines
+        console_state.trim_log(4);
+        let texts: Vec<&str> = console_state.log.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, vec!["line 6", "line 7", "line 8", "line 9"]);
+        assert_eq!(console_state.scroll_offset, 3);
+        assert!(console_state.log_dirty);
+
+        console_state.trim_log(0);
+        assert!(console_state.log.is_empty());
+        assert!(console_state.is_scrolled_to_bottom());
+    }
+
+    #[test]
+    fn test_scroll_down_clamps_to_bottom() {
+        let mut console_state = state_with_lines(5);
+        console_state.scroll_up(4);
+
+        console_state.scroll_down(1);
+        assert_eq!(console_state.scroll_offset, 3);
+
+        console_state.scroll_down(10);
+        assert_eq!(console_state.scroll_offset, 0);
+        assert!(console_state.is_scrolled_to_bottom());
+    }
+
+    #[test]
+    fn test_scroll_empty_log() {
+        let mut console_state = ConsoleState::default();
+        console_state.scroll_up(10);
+        assert_eq!(console_state.scroll_offset, 0);
+    }
+}
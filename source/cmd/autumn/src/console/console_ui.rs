@@ -1,10 +1,22 @@
 use super::ConsoleState;
-use crate::hud::PlayerStats;
+use super::console_commands::ConsoleCommand;
 use crate::internal::*;
-use crate::scripting::CVarRegistry;
-use crate::scripting::process_script;
+use crate::scripting::{
+    AliasRegistry, COMMAND_NAMES, CVarRegistry, CVarValue, ScriptOutput, Severity,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::{AccumulatedMouseScroll, MouseScrollUnit};
 use fallgray_bevy_ui::EntityCommandsUIExt;
 
+/// Approximate height of one line of history text (font-size-16), in logical pixels
+const CONSOLE_LINE_HEIGHT: f32 = 19.0;
+
+/// Number of lines scrolled by PageUp/PageDown
+const CONSOLE_PAGE_LINES: usize = 10;
+
+/// Number of lines scrolled per mouse wheel notch
+const CONSOLE_WHEEL_LINES: usize = 3;
+
 //=============================================================================
 // Console UI Components
 //=============================================================================
@@ -92,20 +104,27 @@ pub(super) fn update_console_toggle(
     }
 }
 
+/// The names Tab can complete to
+#[derive(SystemParam)]
+pub(super) struct AutocompleteSources<'w> {
+    cvars: Res<'w, CVarRegistry>,
+    aliases: Res<'w, AliasRegistry>,
+}
+
 pub(super) fn update_console_input(
     time: Res<Time>,
     mut char_events: MessageReader<bevy::input::keyboard::KeyboardInput>,
     input: Res<ButtonInput<KeyCode>>,
     mut console_state: ResMut<ConsoleState>,
-    mut stats: ResMut<PlayerStats>,
-    mut cvars: ResMut<CVarRegistry>,
+    sources: AutocompleteSources,
     mut input_text_query: Query<&mut Text, With<ConsoleInputText>>,
-    mut history_text_query: Query<&mut Text, (With<ConsoleHistoryText>, Without<ConsoleInputText>)>,
+    mut command_writer: MessageWriter<ConsoleCommand>,
 ) {
     if !console_state.visible {
         return;
     }
 
+    let text_before = console_state.input_text.clone();
     let dt = time.delta_secs();
     let mut should_handle_backspace = false;
     let mut should_handle_delete = false;
@@ -203,36 +222,19 @@ pub(super) fn update_console_input(
         console_state.cursor_position = console_state.input_text.chars().count();
     }
 
-    // Handle Tab - autocomplete cvar names for setvar/getvar commands
+    // Handle Tab - autocomplete command names and cvar names for setvar/getvar
     if input.just_pressed(KeyCode::Tab) {
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &sources.cvars, &sources.aliases);
     }
 
     // Handle Enter key - submit command
     if input.just_pressed(KeyCode::Enter) && !console_state.input_text.is_empty() {
-        // Echo the command to history
         let command = console_state.input_text.clone();
-        console_state.log.push(format!(": {}", command));
-
-        // Add to command history (for up/down arrow navigation)
-        console_state.command_history.push(command.clone());
-        console_state.history_index = None; // Reset history navigation
-
-        // Process the command and get output
-        let output = process_script(&command, &mut stats, &mut cvars);
-        for line in output {
-            console_state.log.push(format!("  {}", line));
-        }
-
-        let history_len = console_state.log.len();
-        if history_len > MAX_HISTORY_LINES {
-            console_state.log.drain(0..history_len - MAX_HISTORY_LINES);
-        }
-
-        // Update history display
-        if let Ok(mut text) = history_text_query.single_mut() {
-            **text = console_state.log.join("\n");
-        }
+        command_writer.write(submit_command(
+            &mut console_state,
+            &command,
+            &sources.aliases,
+        ));
 
         // Clear input and reset cursor
         console_state.input_text.clear();
@@ -262,40 +264,46 @@ pub(super) fn update_console_input(
         }
     }
 
+    // Handle Ctrl+V / Ctrl+C - paste from and copy to the system clipboard
+    let ctrl_pressed = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
+    if ctrl_pressed && input.just_pressed(KeyCode::KeyV) {
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+            Ok(text) => insert_at_cursor(&mut console_state, &sanitize_pasted_text(&text)),
+            Err(e) => warn!("Failed to read clipboard: {}", e),
+        }
+    }
+    if ctrl_pressed && input.just_pressed(KeyCode::KeyC) {
+        let text = console_state.input_text.clone();
+        if let Err(e) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text))
+        {
+            warn!("Failed to write clipboard: {}", e);
+        }
+    }
+
     // Handle Space key explicitly
     if input.just_pressed(KeyCode::Space) {
-        let char_indices: Vec<_> = console_state.input_text.char_indices().collect();
-        let byte_pos = if console_state.cursor_position < char_indices.len() {
-            char_indices[console_state.cursor_position].0
-        } else {
-            console_state.input_text.len()
-        };
-        console_state.input_text.insert(byte_pos, ' ');
-        console_state.cursor_position += 1;
-        console_state.history_index = None;
+        insert_at_cursor(&mut console_state, " ");
     }
 
     // Handle character input
     for event in char_events.read() {
         if event.state.is_pressed()
+            && !ctrl_pressed
             && let bevy::input::keyboard::Key::Character(ref s) = event.logical_key
         {
             // Ignore backtick to prevent it being added when opening console
             // Also ignore space since we handle it explicitly above
             if s.as_str() != "`" && s.as_str() != "~" && s.as_str() != " " {
-                let char_indices: Vec<_> = console_state.input_text.char_indices().collect();
-                let byte_pos = if console_state.cursor_position < char_indices.len() {
-                    char_indices[console_state.cursor_position].0
-                } else {
-                    console_state.input_text.len()
-                };
-                console_state.input_text.insert_str(byte_pos, s.as_str());
-                console_state.cursor_position += s.chars().count();
-                console_state.history_index = None;
+                insert_at_cursor(&mut console_state, s.as_str());
             }
         }
     }
 
+    // Any edit other than Tab ends the completion cycle
+    if !input.just_pressed(KeyCode::Tab) && console_state.input_text != text_before {
+        console_state.reset_completion();
+    }
+
     // Update input text display with cursor
     if let Ok(mut text) = input_text_query.single_mut() {
         let char_indices: Vec<_> = console_state.input_text.char_indices().collect();
@@ -317,19 +325,84 @@ pub(super) fn update_console_input(
     }
 }
 
-/// Automatically scrolls the console history to the bottom when the console is visible.
-/// This ensures that the most recent log entries are always visible to the user.
+/// Rebuilds the console history text whenever the log has changed. Each log line
+/// is a separate text span so it can be colored by severity.
+pub(super) fn update_console_history(
+    mut commands: Commands,
+    mut console_state: ResMut<ConsoleState>,
+    history_query: Query<Entity, With<ConsoleHistoryText>>,
+) {
+    if !console_state.log_dirty {
+        return;
+    }
+    console_state.log_dirty = false;
+
+    let Ok(history_entity) = history_query.single() else {
+        error!("Console history text not found");
+        return;
+    };
+
+    let mut entity = commands.entity(history_entity);
+    entity.despawn_related::<Children>();
+    entity.with_children(|parent| {
+        for (i, line) in console_state.log.iter().enumerate() {
+            let text = if i == 0 {
+                line.text.clone()
+            } else {
+                format!("\n{}", line.text)
+            };
+            parent.spawn((
+                TextSpan::new(text),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(severity_color(line.severity)),
+            ));
+        }
+    });
+}
+
+/// Scrolls the console history with PageUp/PageDown and the mouse wheel. The
+/// history stays pinned to the bottom unless the user has scrolled up.
 pub(super) fn update_console_scroll(
-    console_state: Res<ConsoleState>,
-    mut scroll_query: Query<&mut ScrollPosition, With<ConsoleHistoryScroll>>,
+    input: Res<ButtonInput<KeyCode>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    mut console_state: ResMut<ConsoleState>,
+    mut scroll_query: Query<(&mut ScrollPosition, &ComputedNode), With<ConsoleHistoryScroll>>,
 ) {
     if !console_state.visible {
         return;
     }
 
-    // Auto-scroll to bottom when console is visible
-    if let Ok(mut scroll_position) = scroll_query.single_mut() {
-        scroll_position.y = f32::MAX; // Scroll to bottom
+    if input.just_pressed(KeyCode::PageUp) {
+        console_state.scroll_up(CONSOLE_PAGE_LINES);
+    }
+    if input.just_pressed(KeyCode::PageDown) {
+        console_state.scroll_down(CONSOLE_PAGE_LINES);
+    }
+
+    let wheel_lines = match mouse_scroll.unit {
+        MouseScrollUnit::Line => mouse_scroll.delta.y * CONSOLE_WHEEL_LINES as f32,
+        MouseScrollUnit::Pixel => mouse_scroll.delta.y / CONSOLE_LINE_HEIGHT,
+    }
+    .round() as i32;
+    if wheel_lines > 0 {
+        console_state.scroll_up(wheel_lines as usize);
+    } else if wheel_lines < 0 {
+        console_state.scroll_down(wheel_lines.unsigned_abs() as usize);
+    }
+
+    if let Ok((mut scroll_position, computed)) = scroll_query.single_mut() {
+        if console_state.is_scrolled_to_bottom() {
+            scroll_position.y = f32::MAX; // Scroll to bottom
+        } else {
+            let max_scroll = ((computed.content_size().y - computed.size().y)
+                * computed.inverse_scale_factor())
+            .max(0.0);
+            scroll_position.y =
+                (max_scroll - console_state.scroll_offset as f32 * CONSOLE_LINE_HEIGHT).max(0.0);
+        }
     }
 }
 
@@ -337,60 +410,181 @@ pub(super) fn update_console_scroll(
 // Helper Functions
 //=============================================================================
 
-/// Handle Tab completion for cvar names in setvar/getvar commands
-fn handle_autocomplete(console_state: &mut ConsoleState, cvars: &CVarRegistry) {
-    let words: Vec<&str> = console_state.input_text.split_whitespace().collect();
+/// Record a submitted command in the history and echo it to the log,
+/// returning the command to run with its alias expanded. Its output is
+/// appended to the log by the system that runs it.
+fn submit_command(
+    console_state: &mut ConsoleState,
+    command: &str,
+    aliases: &AliasRegistry,
+) -> ConsoleCommand {
+    // Add to command history (for up/down arrow navigation)
+    console_state.command_history.push(command.to_string());
+    console_state.history_index = None; // Reset history navigation
+    console_state.log_dirty = true;
+    console_state.scroll_to_bottom();
+
+    // Echo the command to history
+    console_state.log.push(ScriptOutput::new(
+        Severity::Command,
+        format!(": {}", command),
+    ));
+
+    ConsoleCommand::expanded(command, aliases)
+}
 
-    // Check if first word is setvar or getvar
-    if words.is_empty() || (words[0] != "setvar" && words[0] != "getvar") {
-        return;
+/// Register the scrollback length cvar
+pub fn init_console_cvars(cvars: &mut CVarRegistry) {
+    cvars
+        .init_with_help(
+            "con_maxlines",
+            CVarValue::Int32(DEFAULT_MAX_HISTORY_LINES as i32),
+            "Lines of output kept in the console scrollback",
+        )
+        .unwrap();
+}
+
+/// Number of log lines to keep, from `con_maxlines`
+fn max_history_lines(cvars: &CVarRegistry) -> usize {
+    cvars
+        .get_i32_or("con_maxlines", DEFAULT_MAX_HISTORY_LINES as i32)
+        .max(0) as usize
+}
+
+/// Trims the console log to `con_maxlines`, both as output is added and
+/// when the limit is lowered
+pub(super) fn update_console_max_lines(
+    cvars: Res<CVarRegistry>,
+    mut console_state: ResMut<ConsoleState>,
+) {
+    let max_lines = max_history_lines(&cvars);
+    if console_state.log.len() > max_lines {
+        console_state.trim_log(max_lines);
     }
+}
 
-    // Check if there's a second word (partial variable name)
-    if words.len() < 2 || words[1].is_empty() {
-        return;
+/// Insert text into the console input at the cursor and move the cursor past it
+fn insert_at_cursor(console_state: &mut ConsoleState, text: &str) {
+    let char_indices: Vec<_> = console_state.input_text.char_indices().collect();
+    let byte_pos = if console_state.cursor_position < char_indices.len() {
+        char_indices[console_state.cursor_position].0
+    } else {
+        console_state.input_text.len()
+    };
+    console_state.input_text.insert_str(byte_pos, text);
+    console_state.cursor_position += text.chars().count();
+    console_state.history_index = None;
+}
+
+/// The console input is a single line, so line breaks in pasted text are
+/// replaced with spaces
+fn sanitize_pasted_text(text: &str) -> String {
+    text.replace("\r\n", " ").replace(['\r', '\n'], " ")
+}
+
+/// Find all candidates that contain the characters of `query` in order (not
+/// necessarily contiguous), best matches first. Contiguous matches rank ahead
+/// of scattered ones, then tighter and earlier matches, then alphabetical order.
+fn find_fuzzy_completions(candidates: &[String], query: &str) -> Vec<String> {
+    let mut scored: Vec<((bool, usize, usize), &String)> = candidates
+        .iter()
+        .filter_map(|name| {
+            let positions = subsequence_positions(name, query)?;
+            let first = *positions.first()?;
+            let last = *positions.last()?;
+            let is_substring = name.contains(query);
+            Some(((!is_substring, last - first, first), name))
+        })
+        .collect();
+
+    // Stable sort keeps alphabetical order for equally ranked matches
+    scored.sort_by_key(|(score, _)| *score);
+    scored.into_iter().map(|(_, name)| name.clone()).collect()
+}
+
+/// Char positions in `text` of the characters of `query`, matched greedily in
+/// order. Returns None if `query` is not a subsequence of `text`.
+fn subsequence_positions(text: &str, query: &str) -> Option<Vec<usize>> {
+    let mut positions = Vec::new();
+    let mut text_chars = text.chars().enumerate();
+
+    for query_char in query.chars() {
+        let (pos, _) = text_chars.find(|(_, ch)| *ch == query_char)?;
+        positions.push(pos);
     }
 
-    let current_word = words[1];
+    Some(positions)
+}
 
-    // Get all the cvars; they are already in alphabetical order
-    let all_cvars = cvars.list();
+/// Color used to render a console log line of the given severity
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Command => Color::srgba(0.8, 0.8, 0.6, 1.0),
+        Severity::Info => Color::srgba(0.85, 0.85, 0.85, 1.0),
+        Severity::Warning => Color::srgba(1.0, 0.85, 0.2, 1.0),
+        Severity::Error => Color::srgba(1.0, 0.3, 0.3, 1.0),
+    }
+}
 
-    let is_exact_match = all_cvars.iter().any(|(name, _)| name == current_word);
-    let matching_cvar: Option<String> = if is_exact_match {
-        // Current word is exact match - find next cvar in the list
-        let mut found_current = false;
-        let mut next_cvar: Option<String> = None;
+/// Handle Tab completion for command names (including aliases) and for cvar
+/// names in setvar/getvar commands
+fn handle_autocomplete(
+    console_state: &mut ConsoleState,
+    cvars: &CVarRegistry,
+    aliases: &AliasRegistry,
+) {
+    let words: Vec<&str> = console_state.input_text.split_whitespace().collect();
 
-        for (name, _) in &all_cvars {
-            if found_current {
-                next_cvar = Some(name.clone());
-                break;
-            }
-            if name == current_word {
-                found_current = true;
-            }
-        }
+    if words.is_empty() {
+        return;
+    }
 
-        // If we didn't find a next one (we were at the end), wrap to first
-        if next_cvar.is_none() && !all_cvars.is_empty() {
-            next_cvar = Some(all_cvars[0].0.clone());
+    // A single word without a trailing space is a partial command name
+    if words.len() == 1 && !console_state.input_text.ends_with(char::is_whitespace) {
+        let mut all_commands: Vec<String> = COMMAND_NAMES.iter().map(|s| s.to_string()).collect();
+        all_commands.extend(aliases.list().into_iter().map(|(name, _)| name));
+        all_commands.sort();
+        all_commands.dedup();
+
+        if let Some(full_name) = find_completion(&all_commands, words[0]) {
+            console_state.input_text = full_name;
+            console_state.cursor_position = console_state.input_text.chars().count();
+            console_state.history_index = None;
         }
+        return;
+    }
 
-        next_cvar
-    } else {
-        // Not an exact match - find first cvar that starts with this prefix
-        let mut first_match: Option<String> = None;
+    // Check if first word is a command that takes a variable name
+    if !matches!(words[0], "setvar" | "getvar" | "help") {
+        return;
+    }
 
-        for (name, _) in &all_cvars {
-            if name.starts_with(current_word) {
-                first_match = Some(name.clone());
-                break;
-            }
-        }
+    // Check if there's a second word (partial variable name)
+    if words.len() < 2 || words[1].is_empty() {
+        return;
+    }
 
-        first_match
-    };
+    let current_word = words[1];
+
+    // Get all the cvars; they are already in alphabetical order
+    let all_cvars: Vec<String> = cvars.list().into_iter().map(|(name, _)| name).collect();
+
+    // Repeated Tab after a fuzzy completion cycles through the ranked matches
+    let cycle = &console_state.fuzzy_completions;
+    let matching_cvar =
+        if !cycle.is_empty() && cycle[console_state.fuzzy_completion_index] == current_word {
+            console_state.fuzzy_completion_index =
+                (console_state.fuzzy_completion_index + 1) % cycle.len();
+            Some(cycle[console_state.fuzzy_completion_index].clone())
+        } else if let Some(name) = find_completion(&all_cvars, current_word) {
+            console_state.fuzzy_completions.clear();
+            Some(name)
+        } else {
+            // No prefix match - fall back to fuzzy matching
+            console_state.fuzzy_completions = find_fuzzy_completions(&all_cvars, current_word);
+            console_state.fuzzy_completion_index = 0;
+            console_state.fuzzy_completions.first().cloned()
+        };
 
     // If we found a match, replace the partial name with the full name
     if let Some(full_name) = matching_cvar {
@@ -411,6 +605,41 @@ fn handle_autocomplete(console_state: &mut ConsoleState, cvars: &CVarRegistry) {
     }
 }
 
+/// Find the completion for `current_word` in an alphabetically sorted list of
+/// candidates. An exact match cycles to the next candidate (wrapping around),
+/// otherwise the first candidate starting with `current_word` is returned.
+fn find_completion(candidates: &[String], current_word: &str) -> Option<String> {
+    let is_exact_match = candidates.iter().any(|name| name == current_word);
+    if is_exact_match {
+        // Current word is exact match - find next candidate in the list
+        let mut found_current = false;
+        let mut next_candidate: Option<String> = None;
+
+        for name in candidates {
+            if found_current {
+                next_candidate = Some(name.clone());
+                break;
+            }
+            if name == current_word {
+                found_current = true;
+            }
+        }
+
+        // If we didn't find a next one (we were at the end), wrap to first
+        if next_candidate.is_none() && !candidates.is_empty() {
+            next_candidate = Some(candidates[0].clone());
+        }
+
+        next_candidate
+    } else {
+        // Not an exact match - find first candidate that starts with this prefix
+        candidates
+            .iter()
+            .find(|name| name.starts_with(current_word))
+            .cloned()
+    }
+}
+
 //=============================================================================
 // Tests
 //=============================================================================
@@ -434,7 +663,7 @@ mod tests {
         console_state.input_text = "setvar player".to_string();
         console_state.cursor_position = console_state.input_text.len();
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should complete to first matching cvar (alphabetically)
         assert_eq!(console_state.input_text, "setvar player_health");
@@ -457,7 +686,7 @@ mod tests {
         console_state.input_text = "getvar player_health".to_string();
         console_state.cursor_position = console_state.input_text.len();
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should cycle to next cvar starting with "player_"
         assert_eq!(console_state.input_text, "getvar player_speed");
@@ -476,7 +705,7 @@ mod tests {
         console_state.input_text = "setvar ccc".to_string();
         console_state.cursor_position = console_state.input_text.len();
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should wrap to first cvar
         assert_eq!(console_state.input_text, "setvar aaa");
@@ -494,7 +723,7 @@ mod tests {
         console_state.input_text = "setvar player 5.0".to_string();
         console_state.cursor_position = console_state.input_text.len();
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should complete cvar name but preserve the value
         assert_eq!(console_state.input_text, "setvar player_health 5.0");
@@ -512,7 +741,7 @@ mod tests {
         let original_text = console_state.input_text.clone();
         console_state.cursor_position = console_state.input_text.len();
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should not change anything
         assert_eq!(console_state.input_text, original_text);
@@ -530,12 +759,252 @@ mod tests {
         let original_text = console_state.input_text.clone();
         console_state.cursor_position = console_state.input_text.len();
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should not autocomplete for non-setvar/getvar commands
         assert_eq!(console_state.input_text, original_text);
     }
 
+    #[test]
+    fn test_autocomplete_command_name() {
+        let mut console_state = ConsoleState::default();
+        let cvars = CVarRegistry::default();
+
+        console_state.input_text = "set".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+
+        assert_eq!(console_state.input_text, "setvar");
+        assert_eq!(console_state.cursor_position, 6);
+    }
+
+    #[test]
+    fn test_autocomplete_includes_aliases() {
+        let mut console_state = ConsoleState::default();
+        let cvars = CVarRegistry::default();
+        let mut aliases = AliasRegistry::default();
+        aliases.set("heal", "add_stamina 50").unwrap();
+
+        console_state.input_text = "he".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &aliases);
+
+        assert_eq!(console_state.input_text, "heal");
+    }
+
+    #[test]
+    fn test_autocomplete_fuzzy_substring() {
+        let mut console_state = ConsoleState::default();
+        let mut cvars = CVarRegistry::default();
+
+        cvars
+            .init("mouse.sensitivity", CVarValue::F32(1.0))
+            .unwrap();
+        cvars.init("mouse.smooth", CVarValue::Bool(true)).unwrap();
+        cvars.init("player_speed", CVarValue::F32(1.0)).unwrap();
+
+        // No cvar starts with "sens", but one contains it
+        console_state.input_text = "setvar sens".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+
+        assert_eq!(console_state.input_text, "setvar mouse.sensitivity");
+        assert_eq!(
+            console_state.cursor_position,
+            console_state.input_text.len()
+        );
+    }
+
+    #[test]
+    fn test_autocomplete_fuzzy_cycles_ranked_matches() {
+        let mut console_state = ConsoleState::default();
+        let mut cvars = CVarRegistry::default();
+
+        cvars.init("gamepad.speed", CVarValue::F32(1.0)).unwrap();
+        cvars.init("player_speed", CVarValue::F32(1.0)).unwrap();
+        cvars.init("sprite.dead", CVarValue::Bool(false)).unwrap();
+
+        // "sped" is not a substring of any name; it is a tight subsequence of
+        // the "speed" names (earliest match first) and a loose one of "sprite.dead"
+        console_state.input_text = "getvar sped".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar player_speed");
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar gamepad.speed");
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar sprite.dead");
+
+        // Wraps back around to the best match
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar player_speed");
+    }
+
+    #[test]
+    fn test_edit_restarts_fuzzy_cycle() {
+        let mut console_state = ConsoleState::default();
+        let mut cvars = CVarRegistry::default();
+
+        cvars.init("gamepad.speed", CVarValue::F32(1.0)).unwrap();
+        cvars.init("player_speed", CVarValue::F32(1.0)).unwrap();
+
+        console_state.input_text = "getvar sped".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar player_speed");
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar gamepad.speed");
+
+        // Typing over the completion starts a new cycle from the best match
+        console_state.reset_completion();
+        assert!(console_state.fuzzy_completions.is_empty());
+        console_state.input_text = "getvar sped".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+        assert_eq!(console_state.input_text, "getvar player_speed");
+        assert_eq!(console_state.fuzzy_completion_index, 0);
+    }
+
+    #[test]
+    fn test_autocomplete_fuzzy_preserves_value() {
+        let mut console_state = ConsoleState::default();
+        let mut cvars = CVarRegistry::default();
+
+        cvars
+            .init("mouse.sensitivity", CVarValue::F32(1.0))
+            .unwrap();
+
+        console_state.input_text = "setvar sens 0.5".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+
+        assert_eq!(console_state.input_text, "setvar mouse.sensitivity 0.5");
+    }
+
+    #[test]
+    fn test_autocomplete_prefix_preferred_over_fuzzy() {
+        let mut console_state = ConsoleState::default();
+        let mut cvars = CVarRegistry::default();
+
+        cvars.init("mouse.smooth", CVarValue::Bool(true)).unwrap();
+        cvars.init("smooth_camera", CVarValue::Bool(true)).unwrap();
+
+        console_state.input_text = "setvar smo".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+
+        assert_eq!(console_state.input_text, "setvar smooth_camera");
+    }
+
+    #[test]
+    fn test_autocomplete_fuzzy_no_match() {
+        let mut console_state = ConsoleState::default();
+        let mut cvars = CVarRegistry::default();
+
+        cvars
+            .init("mouse.sensitivity", CVarValue::F32(1.0))
+            .unwrap();
+
+        console_state.input_text = "setvar xyz".to_string();
+        console_state.cursor_position = console_state.input_text.len();
+
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
+
+        assert_eq!(console_state.input_text, "setvar xyz");
+    }
+
+    #[test]
+    fn test_submit_command_clear() {
+        let mut console_state = ConsoleState::default();
+
+        let command = submit_command(&mut console_state, "echo hello", &AliasRegistry::default());
+        assert_eq!(command, ConsoleCommand::new("echo hello"));
+        console_state.push_output(vec![ScriptOutput::info("hello")]);
+        assert_eq!(
+            console_state.log,
+            vec![
+                ScriptOutput::new(Severity::Command, ": echo hello"),
+                ScriptOutput::info("  hello"),
+            ]
+        );
+
+        // clear is run by its own system, like a bound or aliased clear
+        assert_eq!(
+            submit_command(&mut console_state, "clear", &AliasRegistry::default()),
+            ConsoleCommand::new("clear")
+        );
+        console_state.clear_log();
+        assert!(console_state.log.is_empty());
+
+        // The clear command still shows up in the up/down arrow history
+        assert_eq!(console_state.command_history, vec!["echo hello", "clear"]);
+    }
+
+    #[test]
+    fn test_submit_command_keeps_severity() {
+        let mut console_state = ConsoleState::default();
+
+        submit_command(&mut console_state, "bogus", &AliasRegistry::default());
+        console_state.push_output(vec![ScriptOutput::error("Unknown command: bogus")]);
+
+        assert_eq!(console_state.log[0].severity, Severity::Command);
+        assert_eq!(console_state.log[0].text, ": bogus");
+        assert_eq!(console_state.log[1].severity, Severity::Error);
+        assert_eq!(console_state.log[1].text, "  Unknown command: bogus");
+        assert!(console_state.log_dirty);
+    }
+
+    #[test]
+    fn test_submit_command_expands_alias() {
+        let mut console_state = ConsoleState::default();
+        let mut aliases = AliasRegistry::default();
+        aliases.set("build", "addwall 3").unwrap();
+
+        // Expanded here so the alias reaches the console-only wall command
+        let command = submit_command(&mut console_state, "build 4", &aliases);
+        assert_eq!(command, ConsoleCommand::new("addwall 3 4"));
+
+        // The log and history keep what was typed
+        assert_eq!(console_state.log[0].text, ": build 4");
+        assert_eq!(console_state.command_history, vec!["build 4"]);
+    }
+
+    #[test]
+    fn test_insert_at_cursor() {
+        let mut console_state = ConsoleState {
+            input_text: "setvar  1.5".to_string(),
+            cursor_position: 7,
+            history_index: Some(0),
+            ..Default::default()
+        };
+
+        insert_at_cursor(&mut console_state, "mouse.séns");
+
+        assert_eq!(console_state.input_text, "setvar mouse.séns 1.5");
+        assert_eq!(console_state.cursor_position, 17);
+        assert_eq!(console_state.history_index, None);
+
+        // Inserting at the end appends
+        console_state.cursor_position = console_state.input_text.chars().count();
+        insert_at_cursor(&mut console_state, "0");
+        assert_eq!(console_state.input_text, "setvar mouse.séns 1.50");
+    }
+
+    #[test]
+    fn test_sanitize_pasted_text() {
+        assert_eq!(sanitize_pasted_text("echo a\r\nb\nc"), "echo a b c");
+        assert_eq!(sanitize_pasted_text("no newlines"), "no newlines");
+    }
+
     #[test]
     fn test_autocomplete_empty_input() {
         let mut console_state = ConsoleState::default();
@@ -544,7 +1013,7 @@ mod tests {
         console_state.input_text = "".to_string();
         console_state.cursor_position = 0;
 
-        handle_autocomplete(&mut console_state, &cvars);
+        handle_autocomplete(&mut console_state, &cvars, &AliasRegistry::default());
 
         // Should not crash or change anything
         assert_eq!(console_state.input_text, "");
@@ -5,6 +5,7 @@
 //!
 //! Includes the UI for the console.
 
+mod console_commands;
 mod console_plugin;
 mod console_state;
 mod console_ui;
@@ -15,11 +16,17 @@ pub use console_plugin::ConsolePlugin;
 // Bevy Components & Resources
 pub use console_state::ConsoleState;
 
+// Bevy Messages
+pub use console_commands::ConsoleCommand;
+
+pub use console_ui::init_console_cvars;
+
 //
 // internal mod
 //
 pub mod internal {
-    pub const MAX_HISTORY_LINES: usize = 200;
+    /// Log lines kept when `con_maxlines` isn't set
+    pub const DEFAULT_MAX_HISTORY_LINES: usize = 200;
 
     pub use crate::game_state::{GamePlayEntity, GameState};
 
@@ -1,6 +1,14 @@
 mod plugin;
+mod save_game;
+#[cfg(test)]
+mod save_game_test;
 mod states;
 mod systems;
+#[cfg(test)]
+mod systems_test;
 
 pub use plugin::GameStatePlugin;
+pub use save_game::{
+    PendingLoad, SAVE_SLOTS, SaveDir, SaveGame, actor_positions, slot_exists,
+};
 pub use states::{GamePlayEntity, GameState};
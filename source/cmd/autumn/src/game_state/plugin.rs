@@ -1,19 +1,34 @@
 use bevy::prelude::*;
 
+use super::save_game::{PendingLoad, SaveDir, apply_pending_load};
 use super::states::GameState;
 use super::systems::*;
+use crate::hud::startup_ui;
+use crate::world::setup_world;
 
 pub struct GameStatePlugin;
 
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
+            .init_resource::<PendingLoad>()
+            .init_resource::<SaveDir>()
             // Main Menu systems
             .add_systems(OnEnter(GameState::MainMenu), unlock_cursor_on_menu)
             // Playing state systems
-            .add_systems(Update, detect_player_death.run_if(in_state(GameState::Playing)))
+            .add_systems(
+                Update,
+                detect_player_death.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnEnter(GameState::Playing),
+                apply_pending_load.after(setup_world).after(startup_ui),
+            )
             .add_systems(OnExit(GameState::Playing), cleanup_game_entities)
+            // Loading systems
+            .add_systems(OnEnter(GameState::Loading), finish_loading)
             // Game Over systems
-            .add_systems(OnEnter(GameState::GameOver), unlock_cursor_on_menu);
+            .add_systems(OnEnter(GameState::GameOver), unlock_cursor_on_menu)
+            .add_systems(OnExit(GameState::GameOver), reset_progress_after_game_over);
     }
 }
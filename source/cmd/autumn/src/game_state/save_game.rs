@@ -0,0 +1,186 @@
+/// Saved games
+///
+/// A save captures what the player has changed since the map was loaded:
+///
+/// - the map layout, the items still lying around and the living actors
+///   (type and where they stand)
+/// - the active floor
+/// - the player's position, facing and `PlayerStats`
+/// - the toolbar and inventory
+///
+/// Everything else is regenerated when the save is loaded, the same way a new
+/// game builds it: wall and floor entities, actor AI state and health, weapon
+/// durability, the explored map and cvars (which have their own storage in
+/// data/cvars.yaml).
+///
+/// Slots are stored the way the cvars are: one YAML file each, under
+/// data/saves (see `SaveDir`). The `LocalStorage` key/value store belongs to blackbird, which
+/// this game doesn't depend on, and it panics on unreadable files where a
+/// damaged save should only fail to load.
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::actor::{Actor, ActorPosition};
+use crate::camera::Player;
+use crate::hud::{MAX_HEALTH, PlayerStats, Toolbar};
+use crate::item::Inventory;
+use crate::world::{Map, MapFile};
+
+/// Directory the save slots are written to, next to data/cvars.yaml
+const SAVE_DIR: &str = "data/saves";
+
+/// Directory the save menu reads and writes slots in. Games use data/saves;
+/// tests point it somewhere temporary.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SaveDir(pub PathBuf);
+
+impl Default for SaveDir {
+    fn default() -> Self {
+        Self(PathBuf::from(SAVE_DIR))
+    }
+}
+
+/// Slots offered by the save menu
+pub const SAVE_SLOTS: [&str; 3] = ["slot1", "slot2", "slot3"];
+
+/// Where the player stood when the game was saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+/// Everything written to a save slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub map: MapFile,
+    pub active_layer: usize,
+    pub player: SavedPlayer,
+    pub stats: PlayerStats,
+    pub toolbar: Toolbar,
+    pub inventory: Inventory,
+}
+
+/// A save waiting to be applied once the world has been rebuilt from it
+#[derive(Resource, Default)]
+pub struct PendingLoad(pub Option<SaveGame>);
+
+impl SaveGame {
+    /// Capture the current game. Actors are saved where they stand now rather
+    /// than where the map placed them.
+    pub fn capture(
+        map: &Map,
+        player_transform: &Transform,
+        stats: &PlayerStats,
+        toolbar: &Toolbar,
+        inventory: &Inventory,
+        actors: impl IntoIterator<Item = ActorPosition>,
+    ) -> Self {
+        let mut map_file = map.to_map_file();
+        map_file.items = map.item_positions();
+        map_file.actors = actors.into_iter().collect();
+
+        Self {
+            map: map_file,
+            active_layer: map.active_layer,
+            player: SavedPlayer {
+                translation: player_transform.translation,
+                rotation: player_transform.rotation,
+            },
+            stats: stats.clone(),
+            toolbar: toolbar.clone(),
+            inventory: inventory.clone(),
+        }
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|e| format!("Failed to serialize save: {}", e))
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        let save: Self =
+            serde_yaml::from_str(yaml).map_err(|e| format!("Failed to parse save: {}", e))?;
+        save.map.validate_doors()?;
+        save.map.validate_stairs()?;
+        save.map.validate_tile_costs()?;
+        save.map.validate_wall_heights()?;
+        Ok(save)
+    }
+
+    /// Write the save to a slot in `dir`, replacing anything already there
+    pub fn write(&self, dir: &Path, slot: &str) -> Result<(), String> {
+        let path = save_path(dir, slot)?;
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+        fs::write(&path, self.to_yaml()?).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
+    /// Read the save in a slot in `dir`
+    pub fn read(dir: &Path, slot: &str) -> Result<Self, String> {
+        let path = save_path(dir, slot)?;
+        let yaml =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        Self::from_yaml(&yaml)
+    }
+}
+
+/// File backing a save slot. Slot names are limited to letters, digits, `-`
+/// and `_` so they can't point outside the save directory.
+pub fn save_path(dir: &Path, slot: &str) -> Result<PathBuf, String> {
+    let valid = !slot.is_empty()
+        && slot
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid save slot name: {:?}", slot));
+    }
+    Ok(dir.join(format!("{}.yaml", slot)))
+}
+
+/// Whether a slot in `dir` has a save in it
+pub fn slot_exists(dir: &Path, slot: &str) -> bool {
+    save_path(dir, slot).is_ok_and(|path| path.exists())
+}
+
+/// Positions of the living actors, for saving
+pub fn actor_positions<'a>(
+    actors: impl IntoIterator<Item = (&'a Actor, &'a Transform)>,
+) -> Vec<ActorPosition> {
+    actors
+        .into_iter()
+        .filter(|(actor, _)| actor.health > 0.0)
+        .map(|(actor, transform)| ActorPosition {
+            x: transform.translation.x,
+            y: transform.translation.y,
+            actor_type: actor.actor_type.clone(),
+            layer: actor.layer,
+        })
+        .collect()
+}
+
+/// System to restore the player and HUD state from a loaded save. Runs after
+/// `setup_world` has rebuilt the map from the same save.
+pub fn apply_pending_load(
+    mut pending: ResMut<PendingLoad>,
+    mut map: ResMut<Map>,
+    mut player_query: Query<(&mut Transform, &mut Player)>,
+    mut stats: ResMut<PlayerStats>,
+    mut toolbar: ResMut<Toolbar>,
+    mut inventory: ResMut<Inventory>,
+) {
+    let Some(save) = pending.0.take() else {
+        return;
+    };
+
+    map.set_active_layer(save.active_layer);
+    if let Ok((mut transform, mut player)) = player_query.single_mut() {
+        transform.translation = save.player.translation;
+        transform.rotation = save.player.rotation;
+        // The health bar mirrors the player's health as a percentage
+        player.current_health = save.stats.health / MAX_HEALTH * player.max_health;
+    }
+    *stats = save.stats;
+    *toolbar = save.toolbar;
+    *inventory = save.inventory;
+}
@@ -0,0 +1,149 @@
+use super::save_game::{SaveGame, SavedPlayer, save_path};
+use crate::actor::ActorPosition;
+use crate::hud::{PlayerStats, Toolbar};
+use crate::item::{Inventory, ItemPosition};
+use crate::world::map::{DoorDef, LayerDef};
+use crate::world::{Map, MapFile};
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+fn sample_map_file() -> MapFile {
+    MapFile {
+        grid: vec![
+            "XXXXX".to_string(),
+            "XD.>X".to_string(),
+            "XXXXX".to_string(),
+        ],
+        items: vec![ItemPosition {
+            x: 10.0,
+            y: 12.0,
+            item_type: "apple".to_string(),
+        }],
+        actors: vec![ActorPosition {
+            x: 9.5,
+            y: 11.25,
+            actor_type: "goblin".to_string(),
+            layer: 0,
+        }],
+        doors: vec![DoorDef {
+            x: 1,
+            y: 1,
+            target_x: 2,
+            target_y: 1,
+        }],
+        layers: vec![LayerDef {
+            z: 16.0,
+            grid: vec![
+                "XXXXX".to_string(),
+                "X..<X".to_string(),
+                "XXXXX".to_string(),
+            ],
+        }],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    }
+}
+
+fn sample_save() -> SaveGame {
+    let mut toolbar = Toolbar {
+        active_slot: 3,
+        ..Default::default()
+    };
+    toolbar.slot_weapons[4] = Some("axe".to_string());
+    toolbar.assign_item("apple");
+
+    let mut inventory = Inventory::default();
+    inventory.add("apple");
+    inventory.add("apple");
+
+    SaveGame {
+        map: sample_map_file(),
+        active_layer: 1,
+        player: SavedPlayer {
+            translation: Vec3::new(12.0, 10.0, 20.8),
+            rotation: Quat::from_rotation_z(0.75),
+        },
+        stats: PlayerStats {
+            health: 64.0,
+            stamina: 35.5,
+            gold: 120,
+        },
+        toolbar,
+        inventory,
+    }
+}
+
+#[test]
+fn test_save_round_trips_through_yaml() {
+    let save = sample_save();
+    let yaml = save.to_yaml().unwrap();
+    let loaded = SaveGame::from_yaml(&yaml).unwrap();
+    assert_eq!(loaded, save);
+    assert_eq!(loaded.inventory.count("apple"), 2);
+    assert_eq!(loaded.toolbar.slot_item(2), Some("apple"));
+}
+
+#[test]
+fn test_saved_map_rebuilds_the_same_world() {
+    let save = sample_save();
+    let loaded = SaveGame::from_yaml(&save.to_yaml().unwrap()).unwrap();
+
+    let original = Map::from_map_file(&save.map);
+    let rebuilt = Map::from_map_file(&loaded.map);
+    assert_eq!(rebuilt.to_map_file(), original.to_map_file());
+    assert_eq!(rebuilt.layers.len(), 2);
+}
+
+#[test]
+fn test_capture_records_items_and_actor_positions() {
+    let mut map = Map::from_map_file(&sample_map_file());
+    map.set_active_layer(1);
+    map.item_world_positions
+        .push((Vec3::new(4.0, 6.0, 0.0), "potion".to_string()));
+    let player = Transform::from_xyz(12.0, 10.0, 20.8);
+    let actors = vec![ActorPosition {
+        x: 30.0,
+        y: 14.0,
+        actor_type: "goblin".to_string(),
+        layer: 1,
+    }];
+
+    let save = SaveGame::capture(
+        &map,
+        &player,
+        &PlayerStats::default(),
+        &Toolbar::default(),
+        &Inventory::default(),
+        actors.clone(),
+    );
+
+    assert_eq!(save.active_layer, 1);
+    assert_eq!(save.player.translation, player.translation);
+    assert_eq!(save.map.actors, actors);
+    assert_eq!(save.map.items.len(), 1);
+    assert_eq!(save.map.items[0].item_type, "potion");
+    assert_eq!(save.map.layers.len(), 1);
+}
+
+#[test]
+fn test_invalid_save_is_rejected() {
+    let mut save = sample_save();
+    save.map.layers.clear();
+    // The ground floor's stairs now lead nowhere
+    assert!(SaveGame::from_yaml(&save.to_yaml().unwrap()).is_err());
+    assert!(SaveGame::from_yaml("not: [a save").is_err());
+}
+
+#[test]
+fn test_save_slot_names() {
+    let dir = Path::new("data/saves");
+    assert_eq!(
+        save_path(dir, "slot1").unwrap(),
+        Path::new("data/saves/slot1.yaml")
+    );
+    assert!(save_path(dir, "quick_save-2").is_ok());
+    assert!(save_path(dir, "").is_err());
+    assert!(save_path(dir, "../cvars").is_err());
+    assert!(save_path(dir, "a/b").is_err());
+}
@@ -6,6 +6,10 @@ pub enum GameState {
     #[default]
     MainMenu,
     Playing,
+    /// Passed through on the way back into Playing to rebuild the world, for
+    /// the `map` command and loading a save during a game. Leaving Playing
+    /// despawns the current game and entering it again builds the new one.
+    Loading,
     GameOver,
 }
 
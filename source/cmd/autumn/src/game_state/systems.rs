@@ -2,25 +2,52 @@ use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
 use crate::camera::{MouseLookSettings, Player};
+use crate::hud::{PlayerStats, Toolbar};
+use crate::item::Inventory;
 
 use super::states::*;
 
+/// Whether the game is over: the player ran out of health during play
+pub fn should_game_over(state: &GameState, player: &Player) -> bool {
+    *state == GameState::Playing && !player.is_alive()
+}
+
 /// System to detect player death and transition to game over
 pub fn detect_player_death(
     player_query: Query<&Player>,
     current_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if *current_state.get() != GameState::Playing {
-        return;
+    if let Ok(player) = player_query.single()
+        && should_game_over(current_state.get(), player)
+    {
+        info!("Player died! Transitioning to GameOver state");
+        next_state.set(GameState::GameOver);
     }
+}
 
-    if let Ok(player) = player_query.single() {
-        if !player.is_alive() {
-            info!("Player died! Transitioning to GameOver state");
-            next_state.set(GameState::GameOver);
-        }
-    }
+/// Clear the player's gold and carried items for a fresh game, along with
+/// the toolbar slots that held those items
+pub fn reset_progress(stats: &mut PlayerStats, inventory: &mut Inventory, toolbar: &mut Toolbar) {
+    *stats = PlayerStats::default();
+    *inventory = Inventory::default();
+    toolbar.slot_items = Default::default();
+}
+
+/// System to reset the player's progress when leaving the game over screen,
+/// once it has shown the final tally. The map is rebuilt from its file when
+/// Playing is entered again.
+pub fn reset_progress_after_game_over(
+    mut stats: ResMut<PlayerStats>,
+    mut inventory: ResMut<Inventory>,
+    mut toolbar: ResMut<Toolbar>,
+) {
+    reset_progress(&mut stats, &mut inventory, &mut toolbar);
+}
+
+/// System to go straight back to Playing once the old game has been torn down
+pub fn finish_loading(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
 }
 
 /// Clean up game entities when leaving Playing state
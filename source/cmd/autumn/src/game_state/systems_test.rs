@@ -0,0 +1,47 @@
+use super::states::GameState;
+use super::systems::{reset_progress, should_game_over};
+use crate::camera::Player;
+use crate::hud::{PlayerStats, Toolbar};
+use crate::item::Inventory;
+
+#[test]
+fn test_game_over_when_health_runs_out() {
+    let mut player = Player::new(5.0, 100.0);
+    assert!(!should_game_over(&GameState::Playing, &player));
+
+    player.take_damage(99.0);
+    assert!(!should_game_over(&GameState::Playing, &player));
+
+    player.take_damage(5.0);
+    assert!(should_game_over(&GameState::Playing, &player));
+}
+
+#[test]
+fn test_no_game_over_outside_play() {
+    let mut player = Player::new(5.0, 100.0);
+    player.take_damage(100.0);
+    assert!(!should_game_over(&GameState::MainMenu, &player));
+    assert!(!should_game_over(&GameState::GameOver, &player));
+}
+
+#[test]
+fn test_reset_progress() {
+    let mut stats = PlayerStats {
+        health: 0.0,
+        stamina: 12.0,
+        gold: 250,
+    };
+    let mut inventory = Inventory::default();
+    inventory.add("potion");
+    let mut toolbar = Toolbar::default();
+    let slot = toolbar.assign_item("potion").unwrap();
+
+    reset_progress(&mut stats, &mut inventory, &mut toolbar);
+    assert_eq!(stats, PlayerStats::default());
+    assert_eq!(inventory, Inventory::default());
+    assert_eq!(inventory.count("potion"), 0);
+
+    // The toolbar no longer shows the item, but weapons stay put
+    assert_eq!(toolbar.slot_item(slot), None);
+    assert_eq!(toolbar.slot_weapons, Toolbar::default().slot_weapons);
+}
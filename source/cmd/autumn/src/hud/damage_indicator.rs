@@ -0,0 +1,156 @@
+use crate::camera::Player;
+use crate::game_state::GamePlayEntity;
+use crate::scripting::{CVarRegistry, CVarValue};
+use bevy::prelude::*;
+use fallgray_bevy_ui::EntityCommandsUIExt;
+
+/// Seconds an indicator takes to fade out
+pub const INDICATOR_DURATION: f32 = 1.0;
+
+/// Indicators shown at once; a new hit replaces the oldest
+const MAX_INDICATORS: usize = 4;
+
+/// Distance of the indicators from the center of the screen, in pixels
+const INDICATOR_RADIUS: f32 = 120.0;
+
+const INDICATOR_WIDTH: f32 = 48.0;
+const INDICATOR_HEIGHT: f32 = 8.0;
+
+/// Indicator color, in linear RGBA, when `hud.damage_indicator_color` is
+/// unset. Alpha is the opacity of a fresh hit.
+pub const DEFAULT_INDICATOR_COLOR: [f32; 4] = [1.0, 0.01, 0.01, 0.8];
+
+/// A recent hit on the player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamageHit {
+    /// World position the hit came from
+    pub source: Vec2,
+    /// Seconds until the indicator disappears
+    pub remaining: f32,
+}
+
+/// Recent hits on the player, recorded by whatever dealt the damage
+#[derive(Resource, Default)]
+pub struct DamageIndicators {
+    pub hits: Vec<DamageHit>,
+}
+
+impl DamageIndicators {
+    /// Show an indicator pointing at `source`
+    pub fn record(&mut self, source: Vec2) {
+        if self.hits.len() >= MAX_INDICATORS {
+            self.hits.remove(0);
+        }
+        self.hits.push(DamageHit {
+            source,
+            remaining: INDICATOR_DURATION,
+        });
+    }
+
+    /// Age every indicator, dropping the ones that have faded out
+    pub fn tick(&mut self, dt: f32) {
+        for hit in &mut self.hits {
+            hit.remaining -= dt;
+        }
+        self.hits.retain(|hit| hit.remaining > 0.0);
+    }
+}
+
+/// Register the damage indicator cvars
+pub fn init_damage_indicator_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_bool("hud.damage_indicator", true);
+    cvars
+        .init(
+            "hud.damage_indicator_color",
+            CVarValue::Color(DEFAULT_INDICATOR_COLOR),
+        )
+        .unwrap();
+}
+
+/// Screen direction of a hit, in radians clockwise from straight ahead (the
+/// top of the screen), in the range -PI..=PI. `forward` is the player's
+/// facing projected onto the ground plane.
+pub fn indicator_angle(player_pos: Vec2, forward: Vec2, source: Vec2) -> f32 {
+    let to_source = source - player_pos;
+    // Z is up, so the player's right is the forward vector turned clockwise
+    let right = Vec2::new(forward.y, -forward.x);
+    to_source.dot(right).atan2(to_source.dot(forward))
+}
+
+/// One of the pooled indicator nodes
+#[derive(Component)]
+pub struct DamageIndicatorMarker(usize);
+
+/// Spawn the (hidden) indicator nodes around the center of the screen
+pub fn startup_damage_indicators(mut commands: Commands, mut indicators: ResMut<DamageIndicators>) {
+    indicators.hits.clear();
+
+    commands
+        .spawn(GamePlayEntity)
+        .style("absolute width-100% height-100% justify-center align-center")
+        .with_children(|parent| {
+            // Zero-sized anchor at the screen center that indicators are
+            // positioned around
+            parent
+                .spawn_empty()
+                .style("relative width-0 height-0")
+                .with_children(|parent| {
+                    for index in 0..MAX_INDICATORS {
+                        parent
+                            .spawn((DamageIndicatorMarker(index), Visibility::Hidden))
+                            .style("absolute");
+                    }
+                });
+        });
+}
+
+/// System to age indicators and place them around the screen center,
+/// pointing toward where each hit came from
+pub fn update_damage_indicators(
+    time: Res<Time>,
+    cvars: Res<CVarRegistry>,
+    mut indicators: ResMut<DamageIndicators>,
+    player_query: Query<&Transform, With<Player>>,
+    mut markers: Query<(
+        &DamageIndicatorMarker,
+        &mut Node,
+        &mut UiTransform,
+        &mut BackgroundColor,
+        &mut Visibility,
+    )>,
+) {
+    let enabled = cvars.get_bool_or("hud.damage_indicator", true);
+    let [red, green, blue, alpha] =
+        cvars.get_color_or("hud.damage_indicator_color", DEFAULT_INDICATOR_COLOR);
+    if !enabled {
+        indicators.hits.clear();
+    }
+    indicators.tick(time.delta_secs());
+
+    let player = player_query.single().ok().map(|transform| {
+        let forward = transform.forward();
+        (
+            transform.translation.truncate(),
+            Vec2::new(forward.x, forward.y).normalize_or_zero(),
+        )
+    });
+
+    for (marker, mut node, mut ui_transform, mut color, mut visibility) in markers.iter_mut() {
+        let (Some(hit), Some((player_pos, forward))) = (indicators.hits.get(marker.0), player)
+        else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let angle = indicator_angle(player_pos, forward, hit.source);
+        node.width = Val::Px(INDICATOR_WIDTH);
+        node.height = Val::Px(INDICATOR_HEIGHT);
+        node.left = Val::Px(INDICATOR_RADIUS * angle.sin() - INDICATOR_WIDTH / 2.0);
+        node.top = Val::Px(-INDICATOR_RADIUS * angle.cos() - INDICATOR_HEIGHT / 2.0);
+        // An arc segment facing the center, tangent to the circle
+        ui_transform.rotation = Rot2::radians(angle);
+        let fade = (hit.remaining / INDICATOR_DURATION).clamp(0.0, 1.0);
+        color.0 = Color::linear_rgba(red, green, blue, alpha * fade);
+        *visibility = Visibility::Visible;
+    }
+}
@@ -0,0 +1,69 @@
+use super::damage_indicator::{DamageIndicators, INDICATOR_DURATION, indicator_angle};
+use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI};
+
+fn assert_angle(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-5,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_hit_from_ahead_points_up() {
+    let angle = indicator_angle(Vec2::ZERO, Vec2::X, Vec2::new(10.0, 0.0));
+    assert_angle(angle, 0.0);
+}
+
+#[test]
+fn test_hit_from_sides() {
+    // Facing +X with Z up, -Y is on the player's right
+    assert_angle(
+        indicator_angle(Vec2::ZERO, Vec2::X, Vec2::new(0.0, -5.0)),
+        FRAC_PI_2,
+    );
+    assert_angle(
+        indicator_angle(Vec2::ZERO, Vec2::X, Vec2::new(0.0, 5.0)),
+        -FRAC_PI_2,
+    );
+}
+
+#[test]
+fn test_hit_from_behind_points_down() {
+    let angle = indicator_angle(Vec2::ZERO, Vec2::X, Vec2::new(-10.0, 0.0));
+    assert_angle(angle.abs(), PI);
+}
+
+#[test]
+fn test_angle_follows_player_position_and_facing() {
+    // Facing +Y from (10, 10): a source further along +Y is straight ahead,
+    // one along +X is to the right
+    let player = Vec2::new(10.0, 10.0);
+    assert_angle(indicator_angle(player, Vec2::Y, Vec2::new(10.0, 30.0)), 0.0);
+    assert_angle(
+        indicator_angle(player, Vec2::Y, Vec2::new(30.0, 10.0)),
+        FRAC_PI_2,
+    );
+}
+
+#[test]
+fn test_indicators_fade_out() {
+    let mut indicators = DamageIndicators::default();
+    indicators.record(Vec2::ZERO);
+    indicators.tick(INDICATOR_DURATION * 0.5);
+    assert_eq!(indicators.hits.len(), 1);
+    indicators.tick(INDICATOR_DURATION * 0.5);
+    assert!(indicators.hits.is_empty());
+}
+
+#[test]
+fn test_new_hits_replace_the_oldest() {
+    let mut indicators = DamageIndicators::default();
+    for x in 0..6 {
+        indicators.record(Vec2::new(x as f32, 0.0));
+    }
+    assert_eq!(indicators.hits.len(), 4);
+    assert_eq!(indicators.hits[0].source, Vec2::new(2.0, 0.0));
+}
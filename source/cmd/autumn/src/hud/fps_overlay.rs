@@ -0,0 +1,76 @@
+use crate::game_state::GamePlayEntity;
+use crate::scripting::CVarRegistry;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use fallgray_bevy_ui::EntityCommandsUIExt;
+
+/// Weight of the newest frame in the running average. Lower is steadier.
+pub const FPS_SMOOTHING: f32 = 0.1;
+
+/// Register the FPS readout cvar
+pub fn init_fps_overlay_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_bool("cl_showfps", false);
+}
+
+/// Exponential moving average of frame times in milliseconds. The first
+/// sample is taken as-is so the readout doesn't start from zero.
+pub fn smooth_frame_time(previous_ms: Option<f32>, frame_ms: f32) -> f32 {
+    match previous_ms {
+        Some(previous) => previous + (frame_ms - previous) * FPS_SMOOTHING,
+        None => frame_ms,
+    }
+}
+
+/// Frames per second for a frame time in milliseconds
+pub fn fps_from_frame_time(frame_ms: f32) -> f32 {
+    if frame_ms > 0.0 {
+        1000.0 / frame_ms
+    } else {
+        0.0
+    }
+}
+
+/// Text node showing the smoothed frame rate
+#[derive(Component)]
+pub struct FpsOverlay;
+
+/// Spawn the (hidden) FPS readout in the top right corner, beneath the
+/// console when it is open
+pub fn startup_fps_overlay(mut commands: Commands) {
+    commands
+        .spawn((GamePlayEntity, FpsOverlay, Visibility::Hidden))
+        .text("")
+        .styles(&vec![
+            "absolute top-8 right-8 p4 z900",
+            "font-size-14 fg-white",
+            "bg-rgba(0.0,0.0,0.0,0.5)",
+        ]);
+}
+
+/// System to update the FPS readout from the frame time diagnostic while
+/// `cl_showfps` is on
+pub fn update_fps_overlay(
+    cvars: Res<CVarRegistry>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut smoothed_ms: Local<Option<f32>>,
+    mut overlay_query: Query<(&mut Text, &mut Visibility), With<FpsOverlay>>,
+) {
+    let Ok((mut text, mut visibility)) = overlay_query.single_mut() else {
+        return;
+    };
+
+    let enabled = cvars.get_bool_or("cl_showfps", false);
+    let frame_ms = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|diagnostic| diagnostic.value());
+    let (true, Some(frame_ms)) = (enabled, frame_ms) else {
+        *visibility = Visibility::Hidden;
+        *smoothed_ms = None;
+        return;
+    };
+
+    let average = smooth_frame_time(*smoothed_ms, frame_ms as f32);
+    *smoothed_ms = Some(average);
+    text.0 = format!("{:.0} fps  {:.1} ms", fps_from_frame_time(average), average);
+    *visibility = Visibility::Visible;
+}
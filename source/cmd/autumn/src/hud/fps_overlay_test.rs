@@ -0,0 +1,45 @@
+use super::fps_overlay::{FPS_SMOOTHING, fps_from_frame_time, smooth_frame_time};
+
+fn smooth_all(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .fold(None, |average, &sample| {
+            Some(smooth_frame_time(average, sample))
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_first_sample_is_taken_as_is() {
+    assert_eq!(smooth_frame_time(None, 16.0), 16.0);
+}
+
+#[test]
+fn test_steady_frames_stay_steady() {
+    let average = smooth_all(&[16.0; 30]);
+    assert!((average - 16.0).abs() < 1e-4);
+    assert!((fps_from_frame_time(average) - 62.5).abs() < 0.01);
+}
+
+#[test]
+fn test_single_spike_is_damped() {
+    let mut samples = vec![10.0; 20];
+    samples.push(100.0);
+    let average = smooth_all(&samples);
+    assert!((average - (10.0 + 90.0 * FPS_SMOOTHING)).abs() < 1e-3);
+    assert!(average < 20.0);
+}
+
+#[test]
+fn test_average_follows_a_lasting_change() {
+    let mut samples = vec![10.0; 10];
+    samples.extend([20.0; 60]);
+    let average = smooth_all(&samples);
+    assert!((average - 20.0).abs() < 0.1, "average was {}", average);
+}
+
+#[test]
+fn test_fps_from_frame_time() {
+    assert_eq!(fps_from_frame_time(20.0), 50.0);
+    assert_eq!(fps_from_frame_time(0.0), 0.0);
+}
@@ -1,10 +1,16 @@
 use crate::camera::Player;
 use crate::game_state::GamePlayEntity;
 use crate::rendering::load_image_texture;
+use crate::weapon::WeaponSprite;
 use fallgray_bevy_ui::EntityCommandsUIExt;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource)]
+/// Upper bound for the health bar. Health in `PlayerStats` is a percentage of
+/// the player's max health.
+pub const MAX_HEALTH: f32 = 100.0;
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlayerStats {
     pub health: f32,  // 0.0 to 100.0
     pub stamina: f32, // 0.0 to 100.0
@@ -30,6 +36,10 @@ pub struct FatigueBar;
 #[derive(Component)]
 pub struct GoldText;
 
+/// Readout of the equipped weapon's durability (empty for unbreakable weapons)
+#[derive(Component)]
+pub struct DurabilityText;
+
 pub fn startup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
     info!("Spawning playing state UI");
     // Initialize player stats
@@ -106,12 +116,16 @@ pub fn startup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
     // Gold text (keeping at top for now)
     commands
         .spawn(GamePlayEntity)
-        .style("width-100% height-100% justify-start align-start p20 absolute")
+        .style("width-100% height-100% flex-col justify-start align-start p20 absolute")
         .with_children(|parent| {
             parent
                 .spawn(GoldText)
                 .text("Gold: 0")
                 .style("font-size-16 fg-white");
+            parent
+                .spawn(DurabilityText)
+                .text("")
+                .style("font-size-16 fg-white");
         });
 }
 
@@ -120,11 +134,14 @@ pub fn update_ui(
     mut stats: ResMut<PlayerStats>,
     mut health_query: Query<&mut Node, (With<HealthBar>, Without<FatigueBar>)>,
     mut fatigue_query: Query<&mut Node, (With<FatigueBar>, Without<HealthBar>)>,
-    mut gold_query: Query<&mut Text, With<GoldText>>,
+    mut gold_query: Query<&mut Text, (With<GoldText>, Without<DurabilityText>)>,
+    mut durability_query: Query<&mut Text, (With<DurabilityText>, Without<GoldText>)>,
+    weapon_query: Query<&WeaponSprite>,
 ) {
     // Sync Player health to PlayerStats
     if let Ok(player) = player_query.single() {
-        stats.health = (player.current_health / player.max_health * 100.0).clamp(0.0, 100.0);
+        stats.health =
+            (player.current_health / player.max_health * MAX_HEALTH).clamp(0.0, MAX_HEALTH);
     }
 
     // Update health bar width
@@ -141,4 +158,13 @@ pub fn update_ui(
     if let Ok(mut text) = gold_query.single_mut() {
         **text = format!("Gold: {}", stats.gold);
     }
+
+    // Update weapon durability text
+    if let Ok(mut text) = durability_query.single_mut() {
+        **text = match weapon_query.iter().find_map(|weapon| weapon.durability) {
+            Some(durability) if durability.is_broken() => "Weapon: broken".to_string(),
+            Some(durability) => format!("Weapon: {:.0}/{:.0}", durability.current, durability.max),
+            None => String::new(),
+        };
+    }
 }
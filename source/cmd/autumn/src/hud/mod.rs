@@ -1,5 +1,26 @@
+mod damage_indicator;
+#[cfg(test)]
+mod damage_indicator_test;
+mod fps_overlay;
+#[cfg(test)]
+mod fps_overlay_test;
 mod hud_overlay;
+mod stamina;
+#[cfg(test)]
+mod stamina_test;
+mod status_icons;
+#[cfg(test)]
+mod status_icons_test;
 mod toolbar;
+#[cfg(test)]
+mod toolbar_test;
 
-pub use hud_overlay::{startup_ui, update_ui, PlayerStats};
+pub use damage_indicator::{
+    DamageIndicators, init_damage_indicator_cvars, startup_damage_indicators,
+    update_damage_indicators,
+};
+pub use fps_overlay::{init_fps_overlay_cvars, startup_fps_overlay, update_fps_overlay};
+pub use hud_overlay::{startup_ui, update_ui, PlayerStats, MAX_HEALTH};
+pub use stamina::{init_stamina_cvars, update_player_stamina, MAX_STAMINA};
+pub use status_icons::{startup_status_icons, update_status_icons};
 pub use toolbar::{Toolbar, ToolbarPlugin};
@@ -0,0 +1,122 @@
+use super::hud_overlay::PlayerStats;
+use crate::camera::Player;
+use crate::combat::AttackState;
+use crate::scripting::CVarRegistry;
+use crate::weapon::WeaponSprite;
+use bevy::prelude::*;
+
+/// Upper bound for the stamina bar
+pub const MAX_STAMINA: f32 = 100.0;
+
+const DEFAULT_STAMINA_REGEN: f32 = 15.0;
+const DEFAULT_SPRINT_COST: f32 = 25.0;
+const DEFAULT_ATTACK_COST: f32 = 20.0;
+const DEFAULT_HEALTH_REGEN: f32 = 1.0;
+const DEFAULT_HEALTH_REGEN_DELAY: f32 = 5.0;
+
+/// Per-second stamina rates, read from the `player.*` cvars
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaminaRates {
+    /// Stamina recovered per second while idle
+    pub regen: f32,
+    /// Stamina spent per second while sprinting
+    pub sprint_cost: f32,
+    /// Stamina spent per second while a weapon attack is in progress
+    pub attack_cost: f32,
+}
+
+impl StaminaRates {
+    pub fn from_cvars(cvars: &CVarRegistry) -> Self {
+        Self {
+            regen: cvars.get_f32_or("player.stamina_regen", DEFAULT_STAMINA_REGEN),
+            sprint_cost: cvars.get_f32_or("player.sprint_cost", DEFAULT_SPRINT_COST),
+            attack_cost: cvars.get_f32_or("player.attack_cost", DEFAULT_ATTACK_COST),
+        }
+    }
+}
+
+impl Default for StaminaRates {
+    fn default() -> Self {
+        Self {
+            regen: DEFAULT_STAMINA_REGEN,
+            sprint_cost: DEFAULT_SPRINT_COST,
+            attack_cost: DEFAULT_ATTACK_COST,
+        }
+    }
+}
+
+/// Register the stamina and health regeneration cvars
+pub fn init_stamina_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("player.stamina_regen", DEFAULT_STAMINA_REGEN);
+    cvars.init_f32("player.sprint_cost", DEFAULT_SPRINT_COST);
+    cvars.init_f32("player.attack_cost", DEFAULT_ATTACK_COST);
+
+    // Health regained per second once out of combat, and how long that takes
+    cvars.init_f32("player.health_regen", DEFAULT_HEALTH_REGEN);
+    cvars.init_f32("player.health_regen_delay", DEFAULT_HEALTH_REGEN_DELAY);
+}
+
+/// Stamina after one frame of activity.
+///
+/// Any activity drains stamina (sprinting and attacking stack); otherwise it
+/// regenerates. The result is kept within `0.0..=MAX_STAMINA`.
+pub fn next_stamina(
+    stamina: f32,
+    sprinting: bool,
+    attacking: bool,
+    rates: &StaminaRates,
+    dt: f32,
+) -> f32 {
+    let mut drain = 0.0;
+    if sprinting {
+        drain += rates.sprint_cost;
+    }
+    if attacking {
+        drain += rates.attack_cost;
+    }
+
+    let rate = if drain > 0.0 { -drain } else { rates.regen };
+    (stamina + rate * dt).clamp(0.0, MAX_STAMINA)
+}
+
+/// Drain or regenerate stamina, and slowly heal the player when out of combat.
+///
+/// The player counts as in combat while attacking and for
+/// `player.health_regen_delay` seconds after taking damage.
+pub fn update_player_stamina(
+    time: Res<Time>,
+    cvars: Res<CVarRegistry>,
+    mut stats: ResMut<PlayerStats>,
+    mut player_query: Query<&mut Player>,
+    weapon_query: Query<&WeaponSprite>,
+    mut last_health: Local<Option<f32>>,
+    mut time_since_combat: Local<f32>,
+) {
+    let Ok(mut player) = player_query.single_mut() else {
+        return;
+    };
+    let dt = time.delta_secs();
+
+    let attacking = weapon_query
+        .iter()
+        .any(|weapon| !matches!(weapon.attack_state, AttackState::Idle));
+
+    let rates = StaminaRates::from_cvars(&cvars);
+    stats.stamina = next_stamina(stats.stamina, player.sprinting, attacking, &rates, dt);
+
+    // Any damage taken since last frame restarts the out-of-combat timer
+    let took_damage = last_health.is_some_and(|health| player.current_health < health);
+    if took_damage || attacking {
+        *time_since_combat = 0.0;
+    } else {
+        *time_since_combat += dt;
+    }
+
+    let regen_delay = cvars.get_f32_or("player.health_regen_delay", DEFAULT_HEALTH_REGEN_DELAY);
+    if player.is_alive() && *time_since_combat >= regen_delay {
+        let regen = cvars.get_f32_or("player.health_regen", DEFAULT_HEALTH_REGEN);
+        player.heal(regen * dt);
+    }
+
+    *last_health = Some(player.current_health);
+}
@@ -0,0 +1,33 @@
+use super::stamina::{MAX_STAMINA, StaminaRates, next_stamina};
+
+fn rates() -> StaminaRates {
+    StaminaRates {
+        regen: 10.0,
+        sprint_cost: 20.0,
+        attack_cost: 30.0,
+    }
+}
+
+#[test]
+fn test_idle_regenerates() {
+    assert_eq!(next_stamina(50.0, false, false, &rates(), 0.5), 55.0);
+}
+
+#[test]
+fn test_sprinting_drains() {
+    assert_eq!(next_stamina(50.0, true, false, &rates(), 0.5), 40.0);
+}
+
+#[test]
+fn test_sprint_and_attack_costs_stack() {
+    assert_eq!(next_stamina(50.0, true, true, &rates(), 0.5), 25.0);
+}
+
+#[test]
+fn test_stamina_clamped_to_range() {
+    assert_eq!(next_stamina(5.0, true, true, &rates(), 1.0), 0.0);
+    assert_eq!(
+        next_stamina(MAX_STAMINA - 1.0, false, false, &rates(), 1.0),
+        MAX_STAMINA
+    );
+}
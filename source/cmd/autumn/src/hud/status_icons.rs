@@ -0,0 +1,119 @@
+use crate::camera::Player;
+use crate::combat::{StatusEffect, StatusEffectType, StatusEffects};
+use crate::game_state::GamePlayEntity;
+use bevy::prelude::*;
+use fallgray_bevy_ui::EntityCommandsUIExt;
+
+/// Icons in the row, one per effect type
+const MAX_ICONS: usize = 4;
+
+/// An active effect as shown in the HUD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatusIcon {
+    pub effect_type: StatusEffectType,
+    /// Seconds left on the longest running effect of this type
+    pub remaining: f32,
+}
+
+/// Icons for a set of active effects: one per effect type, in a fixed order
+/// so icons don't jump around as effects come and go. Expired effects are
+/// left out.
+pub fn status_icons<'a>(effects: impl IntoIterator<Item = &'a StatusEffect>) -> Vec<StatusIcon> {
+    let mut icons: Vec<StatusIcon> = Vec::new();
+    for effect in effects {
+        if effect.duration <= 0.0 {
+            continue;
+        }
+        match icons
+            .iter_mut()
+            .find(|icon| icon.effect_type == effect.effect_type)
+        {
+            Some(icon) => icon.remaining = icon.remaining.max(effect.duration),
+            None => icons.push(StatusIcon {
+                effect_type: effect.effect_type,
+                remaining: effect.duration,
+            }),
+        }
+    }
+    icons.sort_by_key(|icon| icon.effect_type);
+    icons
+}
+
+/// Letter and background color for an effect's icon
+pub fn icon_appearance(effect_type: StatusEffectType) -> (&'static str, Color) {
+    match effect_type {
+        StatusEffectType::Poison => ("P", Color::srgba(0.2, 0.7, 0.1, 0.9)),
+        StatusEffectType::Burn => ("B", Color::srgba(0.9, 0.4, 0.0, 0.9)),
+        StatusEffectType::Stun => ("S", Color::srgba(0.8, 0.8, 0.1, 0.9)),
+        StatusEffectType::Frozen => ("F", Color::srgba(0.3, 0.6, 1.0, 0.9)),
+    }
+}
+
+/// One of the pooled icon nodes
+#[derive(Component)]
+pub struct StatusIconMarker(usize);
+
+#[derive(Component)]
+pub struct StatusIconLetter(usize);
+
+/// Remaining duration drawn over the corner of an icon
+#[derive(Component)]
+pub struct StatusIconDuration(usize);
+
+/// Spawn the (hidden) icon row above the health and fatigue bars
+pub fn startup_status_icons(mut commands: Commands) {
+    commands
+        .spawn(GamePlayEntity)
+        .style("absolute bottom-88 left-8 flex-row gap4")
+        .with_children(|parent| {
+            for index in 0..MAX_ICONS {
+                parent
+                    .spawn((StatusIconMarker(index), Visibility::Hidden))
+                    .styles(&vec![
+                        "relative width-32 height-32 flex-col-center",
+                        "outline-width-1 outline-rgb(0.1,0.1,0.1)",
+                    ])
+                    .with_children(|parent| {
+                        parent
+                            .spawn(StatusIconLetter(index))
+                            .text("")
+                            .style("font-size-18 fg-white");
+                        parent
+                            .spawn(StatusIconDuration(index))
+                            .text("")
+                            .style("absolute bottom-0 right-2 font-size-10 fg-white");
+                    });
+            }
+        });
+}
+
+/// System to show an icon for each effect active on the player
+pub fn update_status_icons(
+    effects: Query<&StatusEffects, With<Player>>,
+    mut icons: Query<(&StatusIconMarker, &mut BackgroundColor, &mut Visibility)>,
+    mut letters: Query<(&StatusIconLetter, &mut Text), Without<StatusIconDuration>>,
+    mut durations: Query<(&StatusIconDuration, &mut Text), Without<StatusIconLetter>>,
+) {
+    let active = status_icons(effects.iter().flat_map(StatusEffects::iter));
+
+    for (marker, mut color, mut visibility) in icons.iter_mut() {
+        let Some(icon) = active.get(marker.0) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        color.0 = icon_appearance(icon.effect_type).1;
+        *visibility = Visibility::Visible;
+    }
+
+    for (letter, mut text) in letters.iter_mut() {
+        if let Some(icon) = active.get(letter.0) {
+            text.0 = icon_appearance(icon.effect_type).0.to_string();
+        }
+    }
+
+    for (duration, mut text) in durations.iter_mut() {
+        if let Some(icon) = active.get(duration.0) {
+            text.0 = format!("{}", icon.remaining.ceil());
+        }
+    }
+}
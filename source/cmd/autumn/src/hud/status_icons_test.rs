@@ -0,0 +1,109 @@
+use super::status_icons::{
+    StatusIcon, StatusIconLetter, StatusIconMarker, startup_status_icons, status_icons,
+    update_status_icons,
+};
+use crate::camera::Player;
+use crate::combat::{StatusEffect, StatusEffectType, add_status_effect};
+use bevy::prelude::*;
+
+fn effect(effect_type: StatusEffectType, duration: f32) -> StatusEffect {
+    StatusEffect {
+        effect_type,
+        duration,
+        tick_interval: 1.0,
+        time_since_tick: 0.0,
+        damage_per_tick: 1,
+    }
+}
+
+#[test]
+fn test_no_effects_no_icons() {
+    assert!(status_icons(&[]).is_empty());
+}
+
+#[test]
+fn test_icons_follow_fixed_order() {
+    let effects = [
+        effect(StatusEffectType::Stun, 2.0),
+        effect(StatusEffectType::Poison, 5.0),
+        effect(StatusEffectType::Burn, 3.0),
+    ];
+    let icons = status_icons(&effects);
+    assert_eq!(
+        icons,
+        vec![
+            StatusIcon {
+                effect_type: StatusEffectType::Poison,
+                remaining: 5.0
+            },
+            StatusIcon {
+                effect_type: StatusEffectType::Burn,
+                remaining: 3.0
+            },
+            StatusIcon {
+                effect_type: StatusEffectType::Stun,
+                remaining: 2.0
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_same_type_shows_longest_remaining() {
+    let effects = [
+        effect(StatusEffectType::Burn, 1.5),
+        effect(StatusEffectType::Burn, 4.0),
+        effect(StatusEffectType::Burn, 2.0),
+    ];
+    let icons = status_icons(&effects);
+    assert_eq!(icons.len(), 1);
+    assert_eq!(icons[0].remaining, 4.0);
+}
+
+#[test]
+fn test_expired_effects_have_no_icon() {
+    let effects = [
+        effect(StatusEffectType::Poison, 0.0),
+        effect(StatusEffectType::Stun, -0.1),
+        effect(StatusEffectType::Burn, 0.5),
+    ];
+    let icons = status_icons(&effects);
+    assert_eq!(icons.len(), 1);
+    assert_eq!(icons[0].effect_type, StatusEffectType::Burn);
+}
+
+fn visible_letters(app: &mut App) -> Vec<String> {
+    let world = app.world_mut();
+    let shown = world
+        .query_filtered::<&Visibility, With<StatusIconMarker>>()
+        .iter(world)
+        .filter(|visibility| **visibility == Visibility::Visible)
+        .count();
+    let mut letters: Vec<String> = world
+        .query_filtered::<&Text, With<StatusIconLetter>>()
+        .iter(world)
+        .map(|text| text.0.clone())
+        .filter(|letter| !letter.is_empty())
+        .collect();
+    letters.truncate(shown);
+    letters
+}
+
+#[test]
+fn test_icon_appears_when_player_gets_effect() {
+    let mut app = App::new();
+    app.add_systems(Startup, startup_status_icons)
+        .add_systems(Update, update_status_icons);
+    let player = app.world_mut().spawn(Player::new(1.0, 100.0)).id();
+    app.update();
+    assert!(visible_letters(&mut app).is_empty());
+
+    add_status_effect(
+        &mut app.world_mut().commands(),
+        player,
+        StatusEffect::poison(5.0),
+    );
+    app.world_mut().flush();
+    app.update();
+    assert_eq!(visible_letters(&mut app), vec!["P".to_string()]);
+}
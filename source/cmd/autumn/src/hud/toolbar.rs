@@ -3,17 +3,77 @@ use crate::game_state::GamePlayEntity;
 use crate::game_state::GameState;
 use crate::rendering::load_image_texture;
 use fallgray_bevy_ui::EntityCommandsUIExt;
+use bevy::input::mouse::{AccumulatedMouseScroll, MouseScrollUnit};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Resource tracking the currently active toolbar slot
-#[derive(Resource)]
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Toolbar {
     pub active_slot: usize, // 1-9, 0 for 10th slot
+
+    /// Weapon type held in each slot, indexed by slot number (0 is the 10th slot)
+    pub slot_weapons: [Option<String>; 10],
+
+    /// Consumable item type held in each slot, indexed like `slot_weapons`
+    pub slot_items: [Option<String>; 10],
 }
 
 impl Default for Toolbar {
     fn default() -> Self {
-        Self { active_slot: 1 }
+        let mut slot_weapons: [Option<String>; 10] = Default::default();
+        slot_weapons[1] = Some("sword".to_string());
+        Self {
+            active_slot: 1,
+            slot_weapons,
+            slot_items: Default::default(),
+        }
+    }
+}
+
+impl Toolbar {
+    /// Weapon type held in a slot, if any
+    pub fn slot_weapon(&self, slot: usize) -> Option<&str> {
+        self.slot_weapons.get(slot)?.as_deref()
+    }
+
+    /// Weapon type in the active slot. None means the weapon is put away.
+    pub fn active_weapon(&self) -> Option<&str> {
+        self.slot_weapon(self.active_slot)
+    }
+
+    /// Consumable item type held in a slot, if any
+    pub fn slot_item(&self, slot: usize) -> Option<&str> {
+        self.slot_items.get(slot)?.as_deref()
+    }
+
+    /// Consumable item type in the active slot
+    pub fn active_item(&self) -> Option<&str> {
+        self.slot_item(self.active_slot)
+    }
+
+    /// Put a consumable item type in a slot, or empty it with None. Returns
+    /// false if the slot number is out of range.
+    pub fn set_slot_item(&mut self, slot: usize, item_type: Option<String>) -> bool {
+        let Some(entry) = self.slot_items.get_mut(slot) else {
+            return false;
+        };
+        *entry = item_type;
+        true
+    }
+
+    /// Place a newly picked up item type in the first slot (in toolbar order)
+    /// holding neither a weapon nor an item. Items already on the toolbar stay
+    /// where they are. Returns the slot, or None if the toolbar is full.
+    pub fn assign_item(&mut self, item_type: &str) -> Option<usize> {
+        if let Some(slot) = (0..10).find(|&slot| self.slot_item(slot) == Some(item_type)) {
+            return Some(slot);
+        }
+        let slot = [1, 2, 3, 4, 5, 6, 7, 8, 9, 0]
+            .into_iter()
+            .find(|&slot| self.slot_weapon(slot).is_none() && self.slot_item(slot).is_none())?;
+        self.slot_items[slot] = Some(item_type.to_string());
+        Some(slot)
     }
 }
 
@@ -134,8 +194,11 @@ fn update_toolbar_ui(
 
 fn update_toolbar_input(
     input: Res<ButtonInput<KeyCode>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    gamepads: Query<&Gamepad>,
     mut toolbar: ResMut<Toolbar>,
     console_state: Res<ConsoleState>,
+    mut pending_scroll: Local<f32>,
 ) {
     // Don't process toolbar input if console is open
     if console_state.visible {
@@ -173,6 +236,22 @@ fn update_toolbar_input(
     if input.just_pressed(KeyCode::Digit0) {
         toolbar.active_slot = 0;
     }
+
+    // The mouse wheel cycles through the slots, scrolling down moves right
+    let steps = wheel_slot_steps(mouse_scroll.unit, mouse_scroll.delta.y, &mut pending_scroll);
+    if steps != 0 {
+        toolbar.active_slot = cycle_slot(toolbar.active_slot, steps);
+    }
+
+    // Gamepad bumpers cycle through the slots
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::LeftTrigger) {
+            toolbar.active_slot = cycle_slot(toolbar.active_slot, -1);
+        }
+        if gamepad.just_pressed(GamepadButton::RightTrigger) {
+            toolbar.active_slot = cycle_slot(toolbar.active_slot, 1);
+        }
+    }
 }
 
 fn update_toolbar_click(
@@ -190,3 +269,36 @@ fn update_toolbar_click(
         }
     }
 }
+
+/// Pixels of smooth (touchpad) scrolling that count as one wheel notch
+const SCROLL_PIXELS_PER_SLOT: f32 = 20.0;
+
+/// Slots to move for a frame's worth of wheel scrolling. Scrolling up
+/// (positive delta) moves left along the toolbar. Partial notches are kept
+/// in `pending` so slow touchpad scrolling still adds up.
+pub(super) fn wheel_slot_steps(unit: MouseScrollUnit, delta_y: f32, pending: &mut f32) -> i32 {
+    let notches = *pending
+        + match unit {
+            MouseScrollUnit::Line => delta_y,
+            MouseScrollUnit::Pixel => delta_y / SCROLL_PIXELS_PER_SLOT,
+        };
+    let whole = notches.trunc();
+    *pending = notches - whole;
+    -(whole as i32)
+}
+
+/// Step from one slot to the next in toolbar order (1-9, then 0), wrapping around
+pub(super) fn cycle_slot(active_slot: usize, direction: i32) -> usize {
+    // Position of the slot along the toolbar: slot 0 is the 10th slot
+    let position = if active_slot == 0 {
+        9
+    } else {
+        active_slot as i32 - 1
+    };
+    let new_position = (position + direction).rem_euclid(10);
+    if new_position == 9 {
+        0
+    } else {
+        new_position as usize + 1
+    }
+}
@@ -0,0 +1,85 @@
+use super::toolbar::{Toolbar, cycle_slot, wheel_slot_steps};
+use bevy::input::mouse::MouseScrollUnit;
+
+#[test]
+fn test_default_slot_holds_sword() {
+    let toolbar = Toolbar::default();
+    assert_eq!(toolbar.active_slot, 1);
+    assert_eq!(toolbar.active_weapon(), Some("sword"));
+}
+
+#[test]
+fn test_empty_slot_has_no_weapon() {
+    let toolbar = Toolbar {
+        active_slot: 2,
+        ..Default::default()
+    };
+    assert_eq!(toolbar.active_weapon(), None);
+}
+
+#[test]
+fn test_each_slot_resolves_its_own_weapon() {
+    let mut toolbar = Toolbar::default();
+    toolbar.slot_weapons[4] = Some("axe".to_string());
+    toolbar.slot_weapons[0] = Some("spear".to_string());
+
+    assert_eq!(toolbar.slot_weapon(1), Some("sword"));
+    assert_eq!(toolbar.slot_weapon(4), Some("axe"));
+
+    // Slot 0 is the 10th slot on the bar
+    toolbar.active_slot = 0;
+    assert_eq!(toolbar.active_weapon(), Some("spear"));
+}
+
+#[test]
+fn test_cleared_and_invalid_slots() {
+    let mut toolbar = Toolbar::default();
+    toolbar.slot_weapons[1] = None;
+    assert_eq!(toolbar.active_weapon(), None);
+
+    assert_eq!(toolbar.slot_weapon(10), None);
+}
+
+#[test]
+fn test_scrolling_wraps_around_the_toolbar() {
+    // Slots run 1-9 then 0, so 0 is the last slot on the bar
+    assert_eq!(cycle_slot(9, 1), 0);
+    assert_eq!(cycle_slot(0, 1), 1);
+    assert_eq!(cycle_slot(1, -1), 0);
+    assert_eq!(cycle_slot(0, -1), 9);
+
+    // Several notches in one frame
+    assert_eq!(cycle_slot(8, 3), 1);
+    assert_eq!(cycle_slot(2, -3), 9);
+}
+
+#[test]
+fn test_wheel_direction_and_notches() {
+    let mut pending = 0.0;
+    assert_eq!(
+        wheel_slot_steps(MouseScrollUnit::Line, -1.0, &mut pending),
+        1
+    );
+    assert_eq!(
+        wheel_slot_steps(MouseScrollUnit::Line, 1.0, &mut pending),
+        -1
+    );
+    assert_eq!(
+        wheel_slot_steps(MouseScrollUnit::Line, -2.0, &mut pending),
+        2
+    );
+    assert_eq!(
+        wheel_slot_steps(MouseScrollUnit::Line, 0.0, &mut pending),
+        0
+    );
+    assert_eq!(pending, 0.0);
+}
+
+#[test]
+fn test_touchpad_scrolling_adds_up() {
+    let mut pending = 0.0;
+    let steps: Vec<i32> = (0..8)
+        .map(|_| wheel_slot_steps(MouseScrollUnit::Pixel, -5.0, &mut pending))
+        .collect();
+    assert_eq!(steps, vec![0, 0, 0, 1, 0, 0, 0, 1]);
+}
@@ -6,7 +6,7 @@ pub struct Item {
     pub interaction_radius: f32,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct ItemPosition {
     pub x: f32,
     pub y: f32,
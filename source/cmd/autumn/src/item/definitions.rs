@@ -1,3 +1,4 @@
+use crate::hud::{MAX_HEALTH, MAX_STAMINA, PlayerStats};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,6 +9,42 @@ pub struct ItemDefinition {
     pub script: String,
     pub scale: f32,
     pub effects: Vec<String>,
+    /// Items with a consumable effect go into the inventory when picked up
+    /// and apply the effect when used from the toolbar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumable: Option<ConsumableEffect>,
+}
+
+impl ItemDefinition {
+    /// Problems with the definition that would otherwise only show up once
+    /// the item is spawned
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.image.trim().is_empty() {
+            problems.push("image is missing".to_string());
+        }
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            problems.push(format!("scale must be greater than 0 (got {})", self.scale));
+        }
+        problems
+    }
+}
+
+/// Change to the player's stats when an item is used, e.g. `{ health: 25 }`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ConsumableEffect {
+    #[serde(default)]
+    pub health: f32,
+    #[serde(default)]
+    pub stamina: f32,
+}
+
+impl ConsumableEffect {
+    /// Add the effect to the stats, keeping each within its bar
+    pub fn apply(&self, stats: &mut PlayerStats) {
+        stats.health = (stats.health + self.health).clamp(0.0, MAX_HEALTH);
+        stats.stamina = (stats.stamina + self.stamina).clamp(0.0, MAX_STAMINA);
+    }
 }
 
 #[derive(Deserialize)]
@@ -19,3 +56,26 @@ pub struct ItemDefinitionsFile {
 pub struct ItemDefinitions {
     pub items: HashMap<String, ItemDefinition>,
 }
+
+impl ItemDefinitions {
+    /// Parse and validate item definitions. Every problem found is reported,
+    /// one line per item.
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        let file: ItemDefinitionsFile = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+
+        let mut names: Vec<&String> = file.items.keys().collect();
+        names.sort();
+        let errors: Vec<String> = names
+            .into_iter()
+            .filter_map(|name| {
+                let problems = file.items[name].problems();
+                (!problems.is_empty()).then(|| format!("{}: {}", name, problems.join(", ")))
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        Ok(Self { items: file.items })
+    }
+}
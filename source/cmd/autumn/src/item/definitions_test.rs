@@ -0,0 +1,111 @@
+use super::definitions::{ConsumableEffect, ItemDefinition, ItemDefinitions};
+use crate::hud::PlayerStats;
+
+fn stats(health: f32, stamina: f32) -> PlayerStats {
+    PlayerStats {
+        health,
+        stamina,
+        gold: 0,
+    }
+}
+
+#[test]
+fn test_consumable_adds_to_stats() {
+    let mut stats = stats(50.0, 20.0);
+    ConsumableEffect {
+        health: 25.0,
+        stamina: 0.0,
+    }
+    .apply(&mut stats);
+    assert_eq!(stats.health, 75.0);
+    assert_eq!(stats.stamina, 20.0);
+
+    ConsumableEffect {
+        health: 0.0,
+        stamina: 50.0,
+    }
+    .apply(&mut stats);
+    assert_eq!(stats.stamina, 70.0);
+}
+
+#[test]
+fn test_consumable_clamps_to_max() {
+    let mut stats = stats(90.0, 80.0);
+    ConsumableEffect {
+        health: 25.0,
+        stamina: 50.0,
+    }
+    .apply(&mut stats);
+    assert_eq!(stats.health, 100.0);
+    assert_eq!(stats.stamina, 100.0);
+}
+
+#[test]
+fn test_harmful_consumable_stops_at_zero() {
+    let mut stats = stats(10.0, 5.0);
+    ConsumableEffect {
+        health: -30.0,
+        stamina: -30.0,
+    }
+    .apply(&mut stats);
+    assert_eq!(stats.health, 0.0);
+    assert_eq!(stats.stamina, 0.0);
+}
+
+#[test]
+fn test_consumable_parses_from_yaml() {
+    let yaml = r#"
+image: apple.png
+script: ""
+scale: 1.0
+effects: []
+consumable: { health: 25 }
+"#;
+    let item: ItemDefinition = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        item.consumable,
+        Some(ConsumableEffect {
+            health: 25.0,
+            stamina: 0.0,
+        })
+    );
+
+    let yaml = "image: key.png\nscript: \"\"\nscale: 1.0\neffects: []\n";
+    let item: ItemDefinition = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(item.consumable, None);
+}
+
+#[test]
+fn test_item_definitions_validate_scale_and_image() {
+    let yaml = r#"
+items:
+  key:
+    image: key.png
+    script: ""
+    scale: 1.0
+    effects: []
+  coin:
+    image: ""
+    script: ""
+    scale: -1.0
+    effects: []
+"#;
+    let error = ItemDefinitions::from_yaml(yaml).err().unwrap();
+    assert_eq!(
+        error,
+        "coin: image is missing, scale must be greater than 0 (got -1)"
+    );
+
+    let valid = yaml.replace("image: \"\"", "image: coin.png");
+    let valid = valid.replace("scale: -1.0", "scale: 0.5");
+    let definitions = ItemDefinitions::from_yaml(&valid).unwrap();
+    assert_eq!(definitions.items.len(), 2);
+}
+
+#[test]
+fn test_shipped_items_include_a_consumable() {
+    let yaml = std::fs::read_to_string("data/items.yaml").expect("items.yaml should exist");
+    let definitions = ItemDefinitions::from_yaml(&yaml).unwrap();
+    assert!(definitions.items["apple"].consumable.is_some());
+    assert!(definitions.items["coin-gold"].consumable.is_none());
+}
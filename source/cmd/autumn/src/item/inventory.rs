@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Consumable items the player is carrying, counted by item type
+#[derive(Resource, Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    counts: BTreeMap<String, u32>,
+}
+
+impl Inventory {
+    /// Add one item of the given type
+    pub fn add(&mut self, item_type: &str) {
+        *self.counts.entry(item_type.to_string()).or_insert(0) += 1;
+    }
+
+    /// Number of items of the given type being carried
+    pub fn count(&self, item_type: &str) -> u32 {
+        self.counts.get(item_type).copied().unwrap_or(0)
+    }
+
+    /// Take one item of the given type, returning false if there are none
+    pub fn remove_one(&mut self, item_type: &str) -> bool {
+        let Some(count) = self.counts.get_mut(item_type) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(item_type);
+        }
+        true
+    }
+}
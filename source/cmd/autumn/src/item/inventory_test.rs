@@ -0,0 +1,90 @@
+use super::definitions::ItemDefinitions;
+use super::inventory::Inventory;
+use super::systems::{UseItemContext, process_use_item_command};
+use crate::hud::{PlayerStats, Toolbar};
+use crate::scripting::Severity;
+
+fn apple_definitions() -> ItemDefinitions {
+    ItemDefinitions::from_yaml(
+        r#"
+items:
+  apple:
+    image: apple.png
+    script: ""
+    scale: 1.0
+    effects: []
+    consumable: { health: 25 }
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_inventory_counts_items() {
+    let mut inventory = Inventory::default();
+    assert_eq!(inventory.count("apple"), 0);
+
+    inventory.add("apple");
+    inventory.add("apple");
+    assert_eq!(inventory.count("apple"), 2);
+
+    assert!(inventory.remove_one("apple"));
+    assert!(inventory.remove_one("apple"));
+    assert!(!inventory.remove_one("apple"));
+    assert_eq!(inventory.count("apple"), 0);
+}
+
+#[test]
+fn test_picked_up_items_fill_empty_slots() {
+    let mut toolbar = Toolbar::default();
+
+    // Slot 1 holds the sword, so the first item lands in slot 2
+    assert_eq!(toolbar.assign_item("apple"), Some(2));
+    assert_eq!(toolbar.assign_item("potion"), Some(3));
+    assert_eq!(toolbar.assign_item("apple"), Some(2));
+
+    toolbar.active_slot = 3;
+    assert_eq!(toolbar.active_item(), Some("potion"));
+}
+
+#[test]
+fn test_full_toolbar_has_no_slot() {
+    let mut toolbar = Toolbar::default();
+    for slot in 0..10 {
+        toolbar.slot_weapons[slot] = Some("sword".to_string());
+    }
+    assert_eq!(toolbar.assign_item("apple"), None);
+}
+
+#[test]
+fn test_use_item_applies_effect_and_empties_slot() {
+    let definitions = apple_definitions();
+    let mut toolbar = Toolbar::default();
+    let mut inventory = Inventory::default();
+    let mut stats = PlayerStats {
+        health: 50.0,
+        stamina: 100.0,
+        gold: 0,
+    };
+    toolbar.active_slot = toolbar.assign_item("apple").unwrap();
+    inventory.add("apple");
+
+    let mut ctx = UseItemContext {
+        toolbar: &mut toolbar,
+        inventory: &mut inventory,
+        item_definitions: &definitions,
+        stats: &mut stats,
+        player: None,
+    };
+    let output = process_use_item_command("use_item", &mut ctx).unwrap();
+    assert_eq!(output[0].severity, Severity::Info);
+
+    // The last one is gone, so a second use finds nothing in the slot
+    let output = process_use_item_command("use_item", &mut ctx).unwrap();
+    assert_eq!(output[0].severity, Severity::Warning);
+    assert!(process_use_item_command("repair", &mut ctx).is_none());
+
+    assert_eq!(stats.health, 75.0);
+    assert_eq!(inventory.count("apple"), 0);
+    assert_eq!(toolbar.active_item(), None);
+}
@@ -1,8 +1,15 @@
 pub mod components;
 pub mod definitions;
+#[cfg(test)]
+mod definitions_test;
+pub mod inventory;
+#[cfg(test)]
+mod inventory_test;
 pub mod plugin;
 pub mod systems;
 
 pub use components::{Item, ItemPosition};
-pub use definitions::{ItemDefinitions, ItemDefinitionsFile};
+pub use definitions::ItemDefinitions;
+pub use inventory::Inventory;
 pub use plugin::ItemPlugin;
+pub use systems::{UseItemContext, process_use_item_command};
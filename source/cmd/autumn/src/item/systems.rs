@@ -2,10 +2,11 @@ use bevy::prelude::*;
 use crate::camera::Player;
 use crate::world::check_circle_collision;
 use crate::world::Map;
-use crate::scripting::{self, CVarRegistry};
-use crate::hud::PlayerStats;
+use crate::scripting::{self, AliasRegistry, BindRegistry, CVarRegistry, ScriptOutput};
+use crate::hud::{PlayerStats, Toolbar, MAX_HEALTH};
 use super::components::Item;
 use super::definitions::ItemDefinitions;
+use super::inventory::Inventory;
 
 /// System to check for item collision and pickup
 pub fn update_check_item_collision(
@@ -14,8 +15,12 @@ pub fn update_check_item_collision(
     item_query: Query<(Entity, &Transform, &Item)>,
     mut stats: ResMut<PlayerStats>,
     mut cvars: ResMut<CVarRegistry>,
+    mut aliases: ResMut<AliasRegistry>,
+    mut binds: ResMut<BindRegistry>,
     mut map: ResMut<Map>,
     item_definitions: Res<ItemDefinitions>,
+    mut inventory: ResMut<Inventory>,
+    mut toolbar: ResMut<Toolbar>,
 ) {
     let Ok(player_transform) = player_query.single() else {
         return;
@@ -37,10 +42,23 @@ pub fn update_check_item_collision(
                 .map(|(_, item_type)| item_type.as_str())
                 .unwrap_or("apple");
 
-            // Get the item definition and process the script
-            if let Some(item_def) = item_definitions.items.get(item_type) {
+            // Consumables are carried for later; anything else takes effect now
+            let item_def = item_definitions.items.get(item_type);
+            if item_def.is_some_and(|item_def| item_def.consumable.is_some()) {
+                // With no free toolbar slot the item stays where it is
+                if toolbar.assign_item(item_type).is_none() {
+                    continue;
+                }
+                inventory.add(item_type);
+            } else if let Some(item_def) = item_def {
                 println!("Item script: {}", item_def.script);
-                let output = scripting::process_script(&item_def.script, &mut stats, &mut cvars);
+                let output = scripting::process_script(
+                    &item_def.script,
+                    &mut stats,
+                    &mut cvars,
+                    &mut aliases,
+                    &mut binds,
+                );
                 for line in &output {
                     println!("{}", line);
                 }
@@ -53,8 +71,58 @@ pub fn update_check_item_collision(
             let grid_x = (item_pos.x / 2.0).floor() as i32;
             let grid_y = (item_pos.y / 2.0).floor() as i32;
             map.unregister_item(grid_x, grid_y);
-
-            println!("Collected item! Fatigue: {}", stats.stamina);
         }
     }
 }
+
+/// Everything `use_item` needs to apply a consumable
+pub struct UseItemContext<'a> {
+    pub toolbar: &'a mut Toolbar,
+    pub inventory: &'a mut Inventory,
+    pub item_definitions: &'a ItemDefinitions,
+    pub stats: &'a mut PlayerStats,
+    pub player: Option<&'a mut Player>,
+}
+
+/// Handle the console-only `use_item` command (bound to E by default), which
+/// uses the consumable in the active toolbar slot, applying its effect and
+/// taking it out of the inventory. Returns None for any other command.
+pub fn process_use_item_command(
+    script: &str,
+    ctx: &mut UseItemContext,
+) -> Option<Vec<ScriptOutput>> {
+    if script.split_whitespace().next() != Some("use_item") {
+        return None;
+    }
+    Some(vec![use_active_item(ctx)])
+}
+
+fn use_active_item(ctx: &mut UseItemContext) -> ScriptOutput {
+    let Some(item_type) = ctx.toolbar.active_item().map(str::to_string) else {
+        return ScriptOutput::warning("No item in the active slot");
+    };
+    let Some(effect) = ctx
+        .item_definitions
+        .items
+        .get(&item_type)
+        .and_then(|item_def| item_def.consumable)
+    else {
+        return ScriptOutput::error(format!("{} can't be used", item_type));
+    };
+    if !ctx.inventory.remove_one(&item_type) {
+        return ScriptOutput::warning(format!("No {} left", item_type));
+    }
+
+    effect.apply(ctx.stats);
+    // Health is tracked on the player; the stats mirror it as a percentage
+    if let Some(player) = ctx.player.as_deref_mut() {
+        player.current_health = ctx.stats.health / MAX_HEALTH * player.max_health;
+    }
+
+    let remaining = ctx.inventory.count(&item_type);
+    if remaining == 0 {
+        let slot = ctx.toolbar.active_slot;
+        ctx.toolbar.set_slot_item(slot, None);
+    }
+    ScriptOutput::info(format!("Used {} ({} left)", item_type, remaining))
+}
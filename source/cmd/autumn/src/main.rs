@@ -15,16 +15,23 @@ mod world;
 use actor::ActorPlugin;
 use ai::AIPlugin;
 use bevy::prelude::*;
-use camera::{CameraPlugin, PlayerLightPlugin, update_camera_shake};
+use camera::{CameraPlugin, PlayerLightPlugin, remove_camera_shake_roll, update_camera_shake};
 use clap::Parser;
 pub use cli::Args;
-use combat::{update_blood_particles, update_damage_numbers, update_status_effects};
+use combat::{
+    update_blood_particles, update_damage_numbers, update_player_status_effects,
+    update_status_effects,
+};
 use console::*;
 use game_state::{GameState, GameStatePlugin};
-use hud::{startup_ui, update_ui};
+use hud::{
+    startup_damage_indicators, startup_fps_overlay, startup_status_icons, startup_ui,
+    update_damage_indicators, update_fps_overlay, update_player_stamina, update_status_icons,
+    update_ui,
+};
 use item::ItemPlugin;
 use menu::MenuPlugin;
-use rendering::update_billboards;
+use rendering::{update_billboard_alpha_mode, update_billboards, update_distance_culling};
 use scripting::ScriptingPlugin;
 use weapon::WeaponPlugin;
 use world::{MapEditorPlugin, WorldPlugin};
@@ -61,7 +68,11 @@ fn bevy_main(args: Args) {
                     ..default()
                 }),
         )
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
         .add_systems(Startup, (log_startup, setup_ui_camera))
+        .init_resource::<combat::DamageNumberPool>()
+        .init_resource::<combat::BloodParticlePool>()
+        .init_resource::<hud::DamageIndicators>()
         .add_plugins(ScriptingPlugin)
         .add_plugins(GameStatePlugin)
         .add_plugins(MenuPlugin)
@@ -77,16 +88,34 @@ fn bevy_main(args: Args) {
         .add_plugins(MapEditorPlugin)
         // Main Menu systems
         // Playing state systems
-        .add_systems(OnEnter(GameState::Playing), startup_ui)
+        .add_systems(
+            OnEnter(GameState::Playing),
+            (
+                startup_ui,
+                startup_damage_indicators,
+                startup_fps_overlay,
+                startup_status_icons,
+                combat::init_blood_particle_pool,
+            ),
+        )
+        .add_systems(OnExit(GameState::Playing), combat::clear_effect_pools)
         .add_systems(
             Update,
             (
+                remove_camera_shake_roll.before(camera::update_camera_control_system),
                 update_camera_shake.after(camera::update_camera_control_system),
                 update_damage_numbers,
                 update_blood_particles,
                 update_status_effects,
+                update_player_status_effects,
+                update_player_stamina.before(update_ui),
                 update_ui,
+                update_damage_indicators,
+                update_fps_overlay,
+                update_status_icons,
                 update_billboards,
+                update_billboard_alpha_mode,
+                update_distance_culling,
             )
                 .run_if(in_state(GameState::Playing)),
         )
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use crate::scripting::CVarRegistry;
+
+use super::ui::MainMenuUI;
+
+/// Seconds for the background gradient to make one full turn
+pub const BACKGROUND_CYCLE_SECONDS: f32 = 60.0;
+
+/// Register the menu background cvar
+pub fn init_menu_background_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_bool("r_menu_background", true);
+}
+
+/// Full-screen gradient slowly turning behind the main menu
+#[derive(Component, Default)]
+pub(super) struct MenuBackground {
+    /// Progress through the cycle, 0 to 1
+    pub phase: f32,
+}
+
+/// Advance the animation by `delta_secs`. Depends only on elapsed time, so
+/// the background turns at the same speed at any frame rate.
+pub(super) fn advance_phase(phase: f32, delta_secs: f32) -> f32 {
+    (phase + delta_secs / BACKGROUND_CYCLE_SECONDS).rem_euclid(1.0)
+}
+
+/// The gradient at a point in the cycle
+fn background_gradient(phase: f32) -> BackgroundGradient {
+    BackgroundGradient::from(LinearGradient::new(
+        phase * TAU,
+        vec![
+            ColorStop::auto(Color::srgb(0.12, 0.16, 0.3)),
+            ColorStop::auto(Color::srgb(0.3, 0.14, 0.08)),
+        ],
+    ))
+}
+
+/// Spawn the background behind the main menu. Tagged as part of the main
+/// menu so it is cleaned up with it.
+pub(super) fn spawn_menu_background(mut commands: Commands) {
+    commands.spawn((
+        MainMenuUI,
+        MenuBackground::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        GlobalZIndex(-1),
+        background_gradient(0.0),
+    ));
+}
+
+/// System to turn the menu background. With `r_menu_background` off the
+/// background is hidden and left still.
+pub(super) fn update_menu_background(
+    time: Res<Time>,
+    cvars: Res<CVarRegistry>,
+    mut query: Query<(
+        &mut MenuBackground,
+        &mut BackgroundGradient,
+        &mut Visibility,
+    )>,
+) {
+    let enabled = cvars.get_bool_or("r_menu_background", true);
+
+    for (mut background, mut gradient, mut visibility) in query.iter_mut() {
+        if !enabled {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+        background.phase = advance_phase(background.phase, time.delta_secs());
+        *gradient = background_gradient(background.phase);
+    }
+}
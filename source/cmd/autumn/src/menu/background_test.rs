@@ -0,0 +1,25 @@
+use super::background::{BACKGROUND_CYCLE_SECONDS, advance_phase};
+
+#[test]
+fn test_phase_advances_with_elapsed_time() {
+    assert_eq!(advance_phase(0.0, BACKGROUND_CYCLE_SECONDS / 4.0), 0.25);
+    assert_eq!(advance_phase(0.25, 0.0), 0.25);
+}
+
+#[test]
+fn test_phase_is_frame_rate_independent() {
+    // One second at 30 and at 144 frames per second
+    let slow = (0..30).fold(0.0, |phase, _| advance_phase(phase, 1.0 / 30.0));
+    let fast = (0..144).fold(0.0, |phase, _| advance_phase(phase, 1.0 / 144.0));
+    let single = advance_phase(0.0, 1.0);
+
+    assert!((slow - single).abs() < 1e-5);
+    assert!((fast - single).abs() < 1e-5);
+}
+
+#[test]
+fn test_phase_wraps_around() {
+    let phase = advance_phase(0.9, BACKGROUND_CYCLE_SECONDS * 0.2);
+    assert!((phase - 0.1).abs() < 1e-5);
+    assert!((0.0..1.0).contains(&advance_phase(0.5, BACKGROUND_CYCLE_SECONDS * 3.5)));
+}
@@ -1,4 +1,13 @@
+mod background;
+#[cfg(test)]
+mod background_test;
 mod plugin;
+mod save_menu;
+#[cfg(test)]
+mod save_menu_test;
 mod ui;
+#[cfg(test)]
+mod ui_test;
 
+pub use background::init_menu_background_cvars;
 pub use plugin::MenuPlugin;
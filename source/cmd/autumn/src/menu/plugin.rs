@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use super::background::*;
+use super::save_menu::*;
 use super::ui::*;
 use crate::game_state::GameState;
 
@@ -9,8 +11,15 @@ impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app
             // Main Menu systems
-            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+            .add_systems(
+                OnEnter(GameState::MainMenu),
+                (spawn_main_menu, spawn_menu_background),
+            )
             .add_systems(OnExit(GameState::MainMenu), cleanup_main_menu)
+            .add_systems(
+                Update,
+                update_menu_background.run_if(in_state(GameState::MainMenu)),
+            )
             // Game Over systems
             .add_systems(OnEnter(GameState::GameOver), spawn_game_over)
             .add_systems(OnExit(GameState::GameOver), cleanup_game_over)
@@ -19,6 +28,21 @@ impl Plugin for MenuPlugin {
                 Update,
                 (handle_menu_buttons, update_button_visuals)
                     .run_if(not(in_state(GameState::Playing))),
+            )
+            // Save menu systems
+            .add_systems(
+                Update,
+                handle_load_buttons
+                    .run_if(in_state(GameState::MainMenu).or(in_state(GameState::Playing))),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_save_menu,
+                    handle_save_buttons,
+                    update_save_menu_button_visuals,
+                )
+                    .run_if(in_state(GameState::Playing)),
             );
     }
 }
@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
+
+use crate::actor::Actor;
+use crate::camera::{MouseLookSettings, Player};
+use crate::console::ConsoleState;
+use crate::game_state::{
+    GamePlayEntity, GameState, PendingLoad, SAVE_SLOTS, SaveDir, SaveGame, actor_positions,
+    slot_exists,
+};
+use crate::hud::{PlayerStats, Toolbar};
+use crate::item::Inventory;
+use crate::world::Map;
+use fallgray_bevy_ui::EntityCommandsUIExt;
+
+use super::ui::MainMenuUI;
+
+#[derive(Component)]
+pub(super) struct SaveMenuUI;
+
+/// Any button on the save menu, for hover effects while playing
+#[derive(Component)]
+pub(super) struct SaveMenuButton;
+
+#[derive(Component)]
+pub(super) struct SaveSlotButton(String);
+
+#[derive(Component)]
+pub(super) struct LoadSlotButton(pub(super) String);
+
+#[derive(Component)]
+pub(super) struct CloseSaveMenuButton;
+
+type SaveMenuButtonQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (With<SaveMenuButton>, Changed<Interaction>),
+>;
+
+const BUTTON_STYLE: [&str; 3] = [
+    "px-16 py-8",
+    "bg-rgba(0.3,0.3,0.3,0.9)",
+    "outline-width-2 outline-rgb(0.5,0.5,0.5)",
+];
+
+/// Spawn the slot picker for the slots in `save_dir`. Saving is only offered
+/// during a game; from the main menu the slots can only be loaded.
+pub(super) fn spawn_save_menu(
+    commands: &mut Commands,
+    save_dir: &SaveDir,
+    allow_save: bool,
+) -> Entity {
+    commands
+        .spawn((SaveMenuUI, GlobalZIndex(50)))
+        .styles(&vec![
+            "absolute width-100% height-100%",
+            "flex-col-center",
+            "bg-rgba(0.0,0.0,0.0,0.8)",
+        ])
+        .with_children(|parent| {
+            let title = if allow_save {
+                "SAVE / LOAD"
+            } else {
+                "LOAD GAME"
+            };
+            parent
+                .spawn_empty()
+                .text(title)
+                .styles(&vec!["font-size-48", "fg-white", "mb-32"]);
+
+            for (index, &slot) in SAVE_SLOTS.iter().enumerate() {
+                let exists = slot_exists(&save_dir.0, slot);
+                parent
+                    .spawn_empty()
+                    .style("flex-row-center gap10 mb-12")
+                    .with_children(|parent| {
+                        let label = format!(
+                            "Slot {} - {}",
+                            index + 1,
+                            if exists { "saved" } else { "empty" }
+                        );
+                        parent
+                            .spawn_empty()
+                            .text(&label)
+                            .style("font-size-24 fg-white width-240");
+
+                        if allow_save {
+                            spawn_button(
+                                parent,
+                                (SaveSlotButton(slot.to_string()), SaveMenuButton),
+                                "Save",
+                            );
+                        }
+                        if exists {
+                            spawn_button(
+                                parent,
+                                (LoadSlotButton(slot.to_string()), SaveMenuButton),
+                                "Load",
+                            );
+                        }
+                    });
+            }
+
+            parent.spawn_empty().style("mt-20").with_children(|parent| {
+                spawn_button(parent, (CloseSaveMenuButton, SaveMenuButton), "Close");
+            });
+        })
+        .id()
+}
+
+fn spawn_button(parent: &mut ChildSpawnerCommands, marker: impl Bundle, label: &str) {
+    parent
+        .spawn((marker, Interaction::default()))
+        .styles(&BUTTON_STYLE.to_vec())
+        .with_children(|parent| {
+            parent
+                .spawn_empty()
+                .text(label)
+                .style("font-size-20 fg-white");
+        });
+}
+
+/// System to open and close the save menu with F5 during a game. The cursor
+/// is freed so the slots can be clicked.
+pub(super) fn toggle_save_menu(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    console_state: Res<ConsoleState>,
+    menus: Query<Entity, With<SaveMenuUI>>,
+    mut mouse_look: ResMut<MouseLookSettings>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
+    save_dir: Res<SaveDir>,
+) {
+    if console_state.visible || !input.just_pressed(KeyCode::F5) {
+        return;
+    }
+
+    if !menus.is_empty() {
+        for entity in menus.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let menu = spawn_save_menu(&mut commands, &save_dir, true);
+    commands.entity(menu).insert(GamePlayEntity);
+
+    mouse_look.cursor_locked = false;
+    if let Ok(mut cursor) = cursor_query.single_mut() {
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+}
+
+/// System to save the current game to the clicked slot
+pub(super) fn handle_save_buttons(
+    mut commands: Commands,
+    save_query: Query<(&Interaction, &SaveSlotButton), Changed<Interaction>>,
+    menus: Query<Entity, With<SaveMenuUI>>,
+    map: Res<Map>,
+    player_query: Query<&Transform, With<Player>>,
+    actor_query: Query<(&Actor, &Transform)>,
+    stats: Res<PlayerStats>,
+    toolbar: Res<Toolbar>,
+    inventory: Res<Inventory>,
+    save_dir: Res<SaveDir>,
+) {
+    let Some(slot) = save_query
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button.0.as_str())
+    else {
+        return;
+    };
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    let save = SaveGame::capture(
+        &map,
+        player_transform,
+        &stats,
+        &toolbar,
+        &inventory,
+        actor_positions(actor_query.iter()),
+    );
+    match save.write(&save_dir.0, slot) {
+        Ok(()) => info!("Saved game to {}", slot),
+        Err(e) => error!("Failed to save game: {}", e),
+    }
+
+    for entity in menus.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System to handle the load and close buttons. Loading rebuilds the world
+/// from the save by passing through the Loading state into Playing, which
+/// also works when a game is already being played.
+pub(super) fn handle_load_buttons(
+    mut commands: Commands,
+    load_query: Query<(&Interaction, &LoadSlotButton), Changed<Interaction>>,
+    close_query: Query<&Interaction, (With<CloseSaveMenuButton>, Changed<Interaction>)>,
+    menus: Query<Entity, With<SaveMenuUI>>,
+    mut pending: ResMut<PendingLoad>,
+    mut next_state: ResMut<NextState<GameState>>,
+    save_dir: Res<SaveDir>,
+) {
+    let pressed_close = close_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    if pressed_close {
+        for entity in menus.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let Some(slot) = load_query
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button.0.as_str())
+    else {
+        return;
+    };
+
+    match SaveGame::read(&save_dir.0, slot) {
+        Ok(save) => {
+            info!("Loading game from {}", slot);
+            pending.0 = Some(save);
+            next_state.set(GameState::Loading);
+        }
+        Err(e) => error!("Failed to load game: {}", e),
+    }
+}
+
+/// Hover effects for the save menu while playing, when the main menu's
+/// button visuals aren't running
+pub(super) fn update_save_menu_button_visuals(mut query: SaveMenuButtonQuery) {
+    for (interaction, mut color) in query.iter_mut() {
+        *color = match *interaction {
+            Interaction::Pressed => Color::srgba(0.2, 0.2, 0.2, 0.9).into(),
+            Interaction::Hovered => Color::srgba(0.4, 0.4, 0.4, 0.9).into(),
+            Interaction::None => Color::srgba(0.3, 0.3, 0.3, 0.9).into(),
+        };
+    }
+}
+
+/// Open the load-only slot picker over the main menu
+pub(super) fn open_load_menu(commands: &mut Commands, save_dir: &SaveDir) {
+    let menu = spawn_save_menu(commands, save_dir, false);
+    commands.entity(menu).insert(MainMenuUI);
+}
@@ -0,0 +1,79 @@
+use super::save_menu::{LoadSlotButton, handle_load_buttons};
+use crate::camera::MouseLookSettings;
+use crate::game_state::{GameState, GameStatePlugin, PendingLoad, SaveDir, SaveGame};
+use crate::hud::{PlayerStats, Toolbar};
+use crate::item::Inventory;
+use crate::world::{Map, MapFile};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use std::collections::BTreeMap;
+
+fn small_map() -> Map {
+    Map::from_map_file(&MapFile {
+        grid: vec!["XXX".to_string(), "X.X".to_string(), "XXX".to_string()],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    })
+}
+
+#[test]
+fn test_load_during_a_game_applies_the_save() {
+    let save_dir = std::env::temp_dir().join(format!("save_menu_test_{}", std::process::id()));
+    let slot = "slot1";
+    let saved_stats = PlayerStats {
+        health: 42.0,
+        stamina: 10.0,
+        gold: 77,
+    };
+    let save = SaveGame::capture(
+        &small_map(),
+        &Transform::default(),
+        &saved_stats,
+        &Toolbar::default(),
+        &Inventory::default(),
+        vec![],
+    );
+    save.write(&save_dir, slot).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(StatesPlugin)
+        .insert_resource(small_map())
+        .insert_resource(SaveDir(save_dir.clone()))
+        .init_resource::<PlayerStats>()
+        .init_resource::<Toolbar>()
+        .init_resource::<Inventory>()
+        .init_resource::<MouseLookSettings>()
+        .add_plugins(GameStatePlugin)
+        .add_systems(
+            Update,
+            handle_load_buttons.run_if(in_state(GameState::Playing)),
+        );
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    app.update();
+    assert_eq!(
+        *app.world().resource::<PlayerStats>(),
+        PlayerStats::default()
+    );
+
+    app.world_mut()
+        .spawn((LoadSlotButton(slot.to_string()), Interaction::Pressed));
+    app.update();
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<State<GameState>>().get(),
+        &GameState::Playing
+    );
+    assert!(app.world().resource::<PendingLoad>().0.is_none());
+    assert_eq!(*app.world().resource::<PlayerStats>(), saved_stats);
+
+    std::fs::remove_dir_all(&save_dir).ok();
+}
@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 
-use crate::game_state::GameState;
+use crate::game_state::{GameState, SaveDir};
+use crate::hud::PlayerStats;
+use crate::world::CurrentMap;
 use fallgray_bevy_ui::EntityCommandsUIExt;
 
+use super::save_menu::open_load_menu;
+
 #[derive(Component)]
 pub(super) struct MainMenuUI;
 
@@ -12,6 +16,9 @@ pub(super) struct GameOverUI;
 #[derive(Component)]
 pub(super) struct NewGameButton;
 
+#[derive(Component)]
+pub(super) struct LoadGameButton;
+
 #[derive(Component)]
 pub(super) struct RestartButton;
 
@@ -52,6 +59,22 @@ pub(super) fn spawn_main_menu(mut commands: Commands) {
                         .style("font-size-24 fg-white");
                 });
 
+            // Load Game Button
+            parent
+                .spawn((LoadGameButton, Interaction::default()))
+                .styles(&vec![
+                    "px-40 py-12",
+                    "bg-rgba(0.3,0.3,0.3,0.9)",
+                    "outline-width-2 outline-rgb(0.5,0.5,0.5)",
+                    "mb-12",
+                ])
+                .with_children(|parent| {
+                    parent
+                        .spawn_empty()
+                        .text("Load Game")
+                        .style("font-size-24 fg-white");
+                });
+
             // Quit Button
             parent
                 .spawn((QuitButton, Interaction::default()))
@@ -70,7 +93,8 @@ pub(super) fn spawn_main_menu(mut commands: Commands) {
 }
 
 /// Spawn game over UI
-pub(super) fn spawn_game_over(mut commands: Commands) {
+pub(super) fn spawn_game_over(mut commands: Commands, stats: Option<Res<PlayerStats>>) {
+    let gold = stats.map_or(0, |stats| stats.gold);
     info!("Spawning game over UI");
     commands
         .spawn(GameOverUI)
@@ -84,9 +108,15 @@ pub(super) fn spawn_game_over(mut commands: Commands) {
             parent.spawn_empty().text("GAME OVER").styles(&vec![
                 "font-size-64",
                 "fg-rgb(1.0,0.0,0.3)",
-                "mb-40",
+                "mb-20",
             ]);
 
+            // Final tally
+            parent
+                .spawn_empty()
+                .text(&format!("Gold: {}", gold))
+                .styles(&vec!["font-size-32", "fg-white", "mb-40"]);
+
             // Restart Button
             parent
                 .spawn((RestartButton, Interaction::default()))
@@ -144,22 +174,37 @@ pub(super) fn cleanup_game_over(mut commands: Commands, query: Query<Entity, Wit
 
 /// Handle button interactions
 pub(super) fn handle_menu_buttons(
+    mut commands: Commands,
     new_game_query: Query<&Interaction, (With<NewGameButton>, Changed<Interaction>)>,
+    load_game_query: Query<&Interaction, (With<LoadGameButton>, Changed<Interaction>)>,
     restart_query: Query<&Interaction, (With<RestartButton>, Changed<Interaction>)>,
     quit_query: Query<&Interaction, (With<QuitButton>, Changed<Interaction>)>,
     current_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut exit: MessageWriter<bevy::app::AppExit>,
+    save_dir: Res<SaveDir>,
+    mut current_map: ResMut<CurrentMap>,
 ) {
-    // Check New Game button
+    // Check New Game button. A new game always starts on the default map,
+    // whatever the `map` command loaded last; Restart keeps it.
     if let Ok(interaction) = new_game_query.single() {
         if *interaction == Interaction::Pressed {
             info!("New Game button pressed! Transitioning to Playing state");
+            *current_map = CurrentMap::default();
             next_state.set(GameState::Playing);
             return;
         }
     }
 
+    // Check Load Game button
+    if let Ok(interaction) = load_game_query.single() {
+        if *interaction == Interaction::Pressed {
+            info!("Load Game button pressed! Opening save slots");
+            open_load_menu(&mut commands, &save_dir);
+            return;
+        }
+    }
+
     // Check Restart button
     if let Ok(interaction) = restart_query.single() {
         if *interaction == Interaction::Pressed {
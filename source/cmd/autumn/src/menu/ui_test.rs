@@ -0,0 +1,25 @@
+use super::ui::{NewGameButton, handle_menu_buttons};
+use crate::game_state::{GameState, SaveDir};
+use crate::world::{CurrentMap, map_path};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+
+#[test]
+fn test_new_game_starts_on_the_default_map() {
+    let mut app = App::new();
+    app.add_plugins(StatesPlugin)
+        .init_state::<GameState>()
+        .add_message::<AppExit>()
+        .init_resource::<SaveDir>()
+        .insert_resource(CurrentMap(map_path("arena").unwrap()))
+        .add_systems(Update, handle_menu_buttons);
+    app.world_mut().spawn((NewGameButton, Interaction::Pressed));
+    app.update();
+    app.update();
+
+    assert_eq!(*app.world().resource::<CurrentMap>(), CurrentMap::default());
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Playing
+    );
+}
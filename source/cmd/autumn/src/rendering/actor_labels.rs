@@ -0,0 +1,138 @@
+use crate::actor::Actor;
+use crate::game_state::GamePlayEntity;
+use crate::scripting::CVarRegistry;
+use crate::world::Map;
+use bevy::prelude::*;
+use fallgray_bevy_ui::EntityCommandsUIExt;
+
+/// Gap between the top of an actor's sprite and its label
+const LABEL_GAP: f32 = 0.5;
+
+/// Labels are fully opaque up to this distance from the camera...
+pub const LABEL_FADE_START: f32 = 24.0;
+
+/// ...and fade out completely by this distance
+pub const LABEL_FADE_END: f32 = 48.0;
+
+/// Register the actor label cvar. Labels are a debugging aid, so they start off.
+pub fn init_actor_label_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_bool("cl_actor_labels", false);
+}
+
+/// Label for one actor, drawn as a UI node that tracks the actor on screen
+#[derive(Component)]
+pub struct ActorLabel {
+    pub actor: Entity,
+}
+
+/// Marks actors that already have a label
+#[derive(Component)]
+pub struct HasActorLabel;
+
+/// World position just above the top edge of an actor's billboard. The sprite
+/// is centered on the transform and extends `actor_scale` up, stretched by
+/// any scale animation.
+pub fn label_anchor(transform: &Transform, actor_scale: f32) -> Vec3 {
+    transform.translation + Vec3::Z * (actor_scale * transform.scale.z + LABEL_GAP)
+}
+
+/// Label opacity at a given distance from the camera
+pub fn label_alpha(distance: f32) -> f32 {
+    1.0 - ((distance - LABEL_FADE_START) / (LABEL_FADE_END - LABEL_FADE_START)).clamp(0.0, 1.0)
+}
+
+/// Text shown on an actor's label
+fn label_text(actor: &Actor) -> String {
+    format!(
+        "{} {}/{}",
+        actor.actor_type,
+        actor.health.max(0.0).ceil(),
+        actor.max_health.ceil()
+    )
+}
+
+/// System to add or remove actor labels as `cl_actor_labels` is toggled
+pub fn spawn_actor_labels(
+    mut commands: Commands,
+    cvars: Res<CVarRegistry>,
+    unlabeled: Query<Entity, (With<Actor>, Without<HasActorLabel>)>,
+    labeled: Query<Entity, (With<Actor>, With<HasActorLabel>)>,
+    labels: Query<Entity, With<ActorLabel>>,
+) {
+    let enabled = cvars.get_bool_or("cl_actor_labels", false);
+
+    if !enabled {
+        for label in labels.iter() {
+            commands.entity(label).despawn();
+        }
+        for actor in labeled.iter() {
+            commands.entity(actor).remove::<HasActorLabel>();
+        }
+        return;
+    }
+
+    for actor in unlabeled.iter() {
+        commands.entity(actor).insert(HasActorLabel);
+        commands
+            .spawn((GamePlayEntity, ActorLabel { actor }, Visibility::Hidden))
+            .text("")
+            .style("absolute font-size-14 fg-white");
+    }
+}
+
+/// System to keep labels over their actors, facing the camera like a
+/// billboard and fading with distance. Labels are hidden when the actor is
+/// off screen or behind a wall.
+pub fn update_actor_labels(
+    mut commands: Commands,
+    map: Res<Map>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    actors: Query<(&Actor, &Transform)>,
+    mut labels: Query<(
+        Entity,
+        &ActorLabel,
+        &mut Text,
+        &mut TextColor,
+        &mut Node,
+        &mut UiTransform,
+        &mut Visibility,
+    )>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let camera_pos = camera_transform.translation();
+
+    for (entity, label, mut text, mut color, mut node, mut ui_transform, mut visibility) in
+        labels.iter_mut()
+    {
+        // The actor is gone, so its label goes too
+        let Ok((actor, transform)) = actors.get(label.actor) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let anchor = label_anchor(transform, actor.scale);
+        let alpha = label_alpha(anchor.distance(camera_pos));
+        let in_view = map.has_line_of_sight(camera_pos.truncate(), anchor.truncate());
+        let Some(screen_pos) = camera
+            .world_to_viewport(camera_transform, anchor)
+            .ok()
+            .filter(|_| alpha > 0.0 && in_view)
+        else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let content = label_text(actor);
+        if text.0 != content {
+            text.0 = content;
+        }
+        color.0 = color.0.with_alpha(alpha);
+        node.left = Val::Px(screen_pos.x);
+        node.top = Val::Px(screen_pos.y);
+        // Center the label horizontally and sit it on the anchor point
+        ui_transform.translation = Val2::percent(-50.0, -100.0);
+        *visibility = Visibility::Visible;
+    }
+}
@@ -0,0 +1,30 @@
+use super::actor_labels::{LABEL_FADE_END, LABEL_FADE_START, label_alpha, label_anchor};
+use bevy::prelude::*;
+
+#[test]
+fn test_anchor_sits_above_sprite() {
+    let transform = Transform::from_xyz(10.0, 20.0, 3.0);
+    let anchor = label_anchor(&transform, 3.0);
+    assert_eq!(anchor.x, 10.0);
+    assert_eq!(anchor.y, 20.0);
+    // Sprite top is at 3 + 3 = 6, and the label floats just above it
+    assert!(anchor.z > 6.0 && anchor.z < 7.0);
+}
+
+#[test]
+fn test_anchor_follows_scale_animation() {
+    let resting = Transform::from_xyz(0.0, 0.0, 2.0);
+    let stretched = resting.with_scale(Vec3::splat(1.1));
+    let rise = label_anchor(&stretched, 2.0).z - label_anchor(&resting, 2.0).z;
+    assert!((rise - 0.2).abs() < 1e-5);
+}
+
+#[test]
+fn test_label_fades_with_distance() {
+    assert_eq!(label_alpha(0.0), 1.0);
+    assert_eq!(label_alpha(LABEL_FADE_START), 1.0);
+    let halfway = (LABEL_FADE_START + LABEL_FADE_END) / 2.0;
+    assert!((label_alpha(halfway) - 0.5).abs() < 1e-5);
+    assert_eq!(label_alpha(LABEL_FADE_END), 0.0);
+    assert_eq!(label_alpha(LABEL_FADE_END * 2.0), 0.0);
+}
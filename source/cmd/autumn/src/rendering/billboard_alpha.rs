@@ -0,0 +1,51 @@
+use super::systems::Billboard;
+use crate::scripting::CVarRegistry;
+use bevy::prelude::*;
+
+/// Default alpha cutoff for billboard sprites
+pub const DEFAULT_ALPHA_CUTOFF: f32 = 0.5;
+
+/// Register the billboard rendering cvars.
+///
+/// `r_alpha_cutoff` above zero renders billboards with `AlphaMode::Mask`:
+/// pixels are either fully drawn or discarded, so overlapping sprites always
+/// occlude correctly, at the cost of hard edges and no partial transparency.
+/// Setting it to zero falls back to `AlphaMode::Blend` with soft edges. Bevy's
+/// transparent pass already draws blended meshes back to front by the
+/// distance of their origin, which is right for billboards that don't
+/// touch, but sprites that intersect can still show through each other.
+pub fn init_billboard_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("r_alpha_cutoff", DEFAULT_ALPHA_CUTOFF);
+}
+
+/// Alpha mode for billboards given the `r_alpha_cutoff` value
+pub fn billboard_alpha_mode(cutoff: f32) -> AlphaMode {
+    if cutoff > 0.0 {
+        AlphaMode::Mask(cutoff.min(1.0))
+    } else {
+        AlphaMode::Blend
+    }
+}
+
+/// Keep billboard materials in line with `r_alpha_cutoff`. Materials are
+/// only touched when the mode actually changes.
+pub fn update_billboard_alpha_mode(
+    cvars: Res<CVarRegistry>,
+    billboard_query: Query<&MeshMaterial3d<StandardMaterial>, With<Billboard>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let cutoff = cvars.get_f32_or("r_alpha_cutoff", DEFAULT_ALPHA_CUTOFF);
+    let alpha_mode = billboard_alpha_mode(cutoff);
+
+    for material in billboard_query.iter() {
+        let Some(current) = materials.get(material.id()) else {
+            continue;
+        };
+        if current.alpha_mode == alpha_mode {
+            continue;
+        }
+        if let Some(current) = materials.get_mut(material.id()) {
+            current.alpha_mode = alpha_mode;
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use super::billboard_alpha::billboard_alpha_mode;
+use bevy::prelude::*;
+
+#[test]
+fn test_alpha_mode_from_cutoff() {
+    assert_eq!(billboard_alpha_mode(0.5), AlphaMode::Mask(0.5));
+    assert_eq!(billboard_alpha_mode(2.0), AlphaMode::Mask(1.0));
+    assert_eq!(billboard_alpha_mode(0.0), AlphaMode::Blend);
+}
@@ -0,0 +1,76 @@
+use super::systems::Billboard;
+use crate::scripting::CVarRegistry;
+use bevy::prelude::*;
+
+/// Default `r_cull_distance`. Zero turns culling off.
+pub const DEFAULT_CULL_DISTANCE: f32 = 0.0;
+
+/// Billboards with this are never culled, e.g. actors that must keep acting
+/// when the player is far away
+#[derive(Component)]
+pub struct CullExempt;
+
+/// Set on billboards hidden for being too far from the camera. Actor AI and
+/// attacks don't run while it is present.
+#[derive(Component)]
+pub struct Culled;
+
+/// Register the distance culling cvars.
+///
+/// `r_cull_distance` hides billboards farther than that from the camera and
+/// pauses their actors' AI until the player comes back within range.
+pub fn init_culling_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("r_cull_distance", DEFAULT_CULL_DISTANCE);
+}
+
+/// Whether a billboard at `position` is beyond the cull distance. Anything
+/// exactly at the distance is still drawn.
+pub fn is_culled(cull_distance: f32, camera_pos: Vec3, position: Vec3) -> bool {
+    cull_distance > 0.0 && camera_pos.distance_squared(position) > cull_distance * cull_distance
+}
+
+type CullingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut Visibility,
+        Has<Culled>,
+    ),
+    (With<Billboard>, Without<CullExempt>),
+>;
+
+/// System to hide billboards beyond `r_cull_distance` and show them again
+/// once the camera is back in range
+pub fn update_distance_culling(
+    mut commands: Commands,
+    cvars: Res<CVarRegistry>,
+    camera_query: Query<&Transform, With<Camera3d>>,
+    mut billboard_query: CullingQuery,
+) {
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+
+    let cull_distance = cvars.get_f32_or("r_cull_distance", DEFAULT_CULL_DISTANCE);
+
+    for (entity, transform, mut visibility, was_culled) in billboard_query.iter_mut() {
+        let culled = is_culled(
+            cull_distance,
+            camera_transform.translation,
+            transform.translation,
+        );
+        if culled == was_culled {
+            continue;
+        }
+
+        if culled {
+            *visibility = Visibility::Hidden;
+            commands.entity(entity).insert(Culled);
+        } else {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Culled>();
+        }
+    }
+}
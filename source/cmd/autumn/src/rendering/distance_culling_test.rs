@@ -0,0 +1,25 @@
+use super::distance_culling::is_culled;
+use bevy::prelude::*;
+
+#[test]
+fn test_culls_beyond_distance() {
+    let camera = Vec3::new(10.0, 10.0, 4.0);
+    assert!(!is_culled(50.0, camera, Vec3::new(59.0, 10.0, 4.0)));
+    assert!(!is_culled(50.0, camera, Vec3::new(60.0, 10.0, 4.0)));
+    assert!(is_culled(50.0, camera, Vec3::new(60.01, 10.0, 4.0)));
+    assert!(is_culled(50.0, camera, Vec3::new(-40.0, -40.0, 4.0)));
+}
+
+#[test]
+fn test_distance_includes_height() {
+    let camera = Vec3::new(0.0, 0.0, 30.0);
+    assert!(!is_culled(25.0, camera, Vec3::new(0.0, 0.0, 10.0)));
+    assert!(is_culled(25.0, camera, Vec3::new(20.0, 0.0, 10.0)));
+}
+
+#[test]
+fn test_zero_distance_disables_culling() {
+    let far = Vec3::new(10_000.0, 0.0, 0.0);
+    assert!(!is_culled(0.0, Vec3::ZERO, far));
+    assert!(!is_culled(-5.0, Vec3::ZERO, far));
+}
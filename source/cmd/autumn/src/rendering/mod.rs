@@ -1,5 +1,19 @@
+mod actor_labels;
+#[cfg(test)]
+mod actor_labels_test;
+mod billboard_alpha;
+#[cfg(test)]
+mod billboard_alpha_test;
+mod distance_culling;
+#[cfg(test)]
+mod distance_culling_test;
 mod systems;
+#[cfg(test)]
+mod systems_test;
 mod texture_loader;
 
-pub use systems::{update_billboards, Billboard};
+pub use actor_labels::{init_actor_label_cvars, spawn_actor_labels, update_actor_labels};
+pub use billboard_alpha::{init_billboard_cvars, update_billboard_alpha_mode};
+pub use distance_culling::{CullExempt, Culled, init_culling_cvars, update_distance_culling};
+pub use systems::{update_billboards, Billboard, BillboardMode};
 pub use texture_loader::{load_image_texture, load_weapon_texture};
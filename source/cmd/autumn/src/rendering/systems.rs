@@ -1,32 +1,78 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Component marker for entities that should face the camera
 #[derive(Component)]
 pub struct Billboard;
 
+/// How a billboard turns to face the camera. Billboards without one are
+/// `Upright`. Actor definitions pick one with `billboard`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BillboardMode {
+    /// Turn only around the world up (Z) axis, so sprites stay standing
+    /// however far the camera looks up or down
+    #[default]
+    Upright,
+    /// Turn fully towards the camera, tilting with its height
+    FaceCamera,
+}
+
+/// Horizontal distance below which the camera is treated as directly above
+/// or below the billboard, where there is no direction to turn towards
+const MIN_FACING_DISTANCE: f32 = 1e-4;
+
+/// Rotation that points a billboard's normal (its local X axis) at the
+/// camera. When the camera is directly overhead the billboard keeps the yaw
+/// it had, rather than snapping to an arbitrary one.
+pub fn billboard_rotation(
+    mode: BillboardMode,
+    billboard_pos: Vec3,
+    camera_pos: Vec3,
+    current: Quat,
+) -> Quat {
+    let to_camera = camera_pos - billboard_pos;
+    let horizontal = to_camera.truncate().length();
+
+    let yaw = if horizontal > MIN_FACING_DISTANCE {
+        to_camera.y.atan2(to_camera.x)
+    } else {
+        current.to_euler(EulerRot::ZYX).0
+    };
+
+    match mode {
+        BillboardMode::Upright => Quat::from_rotation_z(yaw),
+        BillboardMode::FaceCamera => {
+            // A positive rotation around Y tips X downwards, so negate the
+            // pitch to tip the normal up towards a camera above
+            let pitch = to_camera.z.atan2(horizontal);
+            Quat::from_rotation_z(yaw) * Quat::from_rotation_y(-pitch)
+        }
+    }
+}
+
+type BillboardQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Transform, Option<&'static BillboardMode>),
+    (With<Billboard>, Without<Camera3d>),
+>;
+
 /// System to update billboard transforms to face the camera
 pub fn update_billboards(
     camera_query: Query<&Transform, With<Camera3d>>,
-    mut billboard_query: Query<&mut Transform, (With<Billboard>, Without<Camera3d>)>,
+    mut billboard_query: BillboardQuery,
 ) {
     if let Ok(camera_transform) = camera_query.single() {
         let camera_pos = camera_transform.translation;
 
-        for mut billboard_transform in billboard_query.iter_mut() {
-            let billboard_pos = billboard_transform.translation;
-
-            // Calculate direction from billboard to camera (in XY plane)
-            let to_camera = Vec2::new(
-                camera_pos.x - billboard_pos.x,
-                camera_pos.y - billboard_pos.y,
+        for (mut billboard_transform, mode) in billboard_query.iter_mut() {
+            billboard_transform.rotation = billboard_rotation(
+                mode.copied().unwrap_or_default(),
+                billboard_transform.translation,
+                camera_pos,
+                billboard_transform.rotation,
             );
-
-            // The plane's normal starts pointing in X direction (Dir3::X)
-            // Calculate angle around Z axis to rotate the normal to face the camera
-            let angle = to_camera.y.atan2(to_camera.x);
-
-            // Rotate around Z axis so the plane normal points toward camera
-            billboard_transform.rotation = Quat::from_rotation_z(angle);
         }
     }
 }
@@ -0,0 +1,88 @@
+use super::systems::{BillboardMode, billboard_rotation};
+use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+fn yaw(rotation: Quat) -> f32 {
+    rotation.to_euler(EulerRot::ZYX).0
+}
+
+fn normal(rotation: Quat) -> Vec3 {
+    rotation * Vec3::X
+}
+
+#[test]
+fn test_upright_yaw_follows_camera_around() {
+    let cases = [
+        (Vec3::new(10.0, 0.0, 5.0), 0.0),
+        (Vec3::new(0.0, 10.0, 5.0), FRAC_PI_2),
+        (Vec3::new(10.0, 10.0, -3.0), FRAC_PI_4),
+        (Vec3::new(0.0, -10.0, 40.0), -FRAC_PI_2),
+    ];
+    for (camera, expected) in cases {
+        let rotation =
+            billboard_rotation(BillboardMode::Upright, Vec3::ZERO, camera, Quat::IDENTITY);
+        assert!((yaw(rotation) - expected).abs() < 1e-5, "{:?}", camera);
+    }
+
+    let behind = billboard_rotation(
+        BillboardMode::Upright,
+        Vec3::ZERO,
+        Vec3::new(-10.0, 0.0, 0.0),
+        Quat::IDENTITY,
+    );
+    assert!((yaw(behind).abs() - PI).abs() < 1e-5);
+}
+
+#[test]
+fn test_upright_ignores_camera_height() {
+    let billboard = Vec3::new(2.0, 3.0, 1.0);
+    let level = billboard_rotation(
+        BillboardMode::Upright,
+        billboard,
+        Vec3::new(8.0, 3.0, 1.0),
+        Quat::IDENTITY,
+    );
+    let steep = billboard_rotation(
+        BillboardMode::Upright,
+        billboard,
+        Vec3::new(8.0, 3.0, 200.0),
+        Quat::IDENTITY,
+    );
+    assert!(level.angle_between(steep) < 1e-5);
+    assert!(normal(steep).z.abs() < 1e-6);
+}
+
+#[test]
+fn test_camera_directly_above_keeps_current_yaw() {
+    let current = Quat::from_rotation_z(1.2);
+    let rotation = billboard_rotation(
+        BillboardMode::Upright,
+        Vec3::new(4.0, 4.0, 0.0),
+        Vec3::new(4.0, 4.0, 30.0),
+        current,
+    );
+    assert!((yaw(rotation) - 1.2).abs() < 1e-5);
+    assert!(normal(rotation).z.abs() < 1e-6);
+}
+
+#[test]
+fn test_face_camera_tilts_towards_camera() {
+    let camera = Vec3::new(10.0, 0.0, 10.0);
+    let rotation = billboard_rotation(
+        BillboardMode::FaceCamera,
+        Vec3::ZERO,
+        camera,
+        Quat::IDENTITY,
+    );
+    assert!(normal(rotation).distance(camera.normalize()) < 1e-5);
+
+    // Directly above, the billboard lies flat facing up
+    let rotation = billboard_rotation(
+        BillboardMode::FaceCamera,
+        Vec3::ZERO,
+        Vec3::new(0.0, 0.0, 10.0),
+        Quat::from_rotation_z(0.5),
+    );
+    assert!(normal(rotation).distance(Vec3::Z) < 1e-5);
+    assert!((yaw(rotation) - 0.5).abs() < 1e-5);
+}
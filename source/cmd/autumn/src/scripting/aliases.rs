@@ -0,0 +1,88 @@
+/// Console command aliases
+///
+/// An alias maps a single word to a command string, e.g. `alias heal "add_gold 10"`.
+/// When a script line starts with an alias name, the alias is expanded and the
+/// resulting command is processed in its place.
+///
+use super::cvars::CVarRegistry;
+use super::process_script::MAX_ALIAS_DEPTH;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Resource that stores all user-defined command aliases
+#[derive(Resource, Default)]
+pub struct AliasRegistry {
+    aliases: HashMap<String, String>,
+}
+
+impl AliasRegistry {
+    /// Define or redefine an alias. Built-in command names cannot be aliased.
+    pub fn set(&mut self, name: &str, command: &str) -> Result<(), String> {
+        if !CVarRegistry::is_valid_name(name) {
+            return Err(format!("Invalid alias name: {}", name));
+        }
+        if super::COMMAND_NAMES.contains(&name) {
+            return Err(format!("Cannot alias built-in command: {}", name));
+        }
+
+        self.aliases.insert(name.to_string(), command.to_string());
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.aliases.get(name)
+    }
+
+    /// Remove an alias, returning true if it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.aliases.remove(name).is_some()
+    }
+
+    /// Expand the alias a command line starts with, passing along the rest of
+    /// the line as typed, until its first word is no longer an alias. Both the
+    /// console (before routing a command) and `process_script` expand through
+    /// here, so an alias of a console-only command reaches the system that
+    /// runs it.
+    ///
+    /// Returns an error if the line is still an alias after `MAX_ALIAS_DEPTH`
+    /// expansions, which happens when aliases refer to each other in a loop.
+    pub fn expand(&self, line: &str) -> Result<String, String> {
+        let mut expanded = line.trim().to_string();
+        for _ in 0..MAX_ALIAS_DEPTH {
+            let (name, args) = expanded
+                .split_once(char::is_whitespace)
+                .unwrap_or((&expanded, ""));
+            let Some(alias) = self.get(name) else {
+                return Ok(expanded);
+            };
+            expanded = if args.is_empty() {
+                alias.clone()
+            } else {
+                format!("{} {}", alias, args)
+            };
+        }
+
+        let name = expanded.split_whitespace().next().unwrap_or_default();
+        if self.exists(name) {
+            return Err(format!("Alias recursion limit reached: {}", name));
+        }
+        Ok(expanded)
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.aliases.contains_key(name)
+    }
+
+    pub fn list(&self) -> Vec<(String, String)> {
+        let mut result: Vec<(String, String)> = self
+            .aliases
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        // Sort alphabetically by alias name
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+
+        result
+    }
+}
@@ -1,4 +1,5 @@
 use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -6,15 +7,15 @@ pub fn cmd_add_gold(
     tokens: &[&str],
     stats: &mut ResMut<PlayerStats>,
     _cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     if tokens.len() < 2 {
-        return "usage: add_gold <amount>".to_string();
+        return ScriptOutput::warning("usage: add_gold <amount>");
     }
 
     let Ok(amount) = tokens[1].parse::<i32>() else {
-        return format!("Invalid gold amount: {}", tokens[1]);
+        return ScriptOutput::error(format!("Invalid gold amount: {}", tokens[1]));
     };
 
     stats.gold += amount;
-    format!("Added {} gold, new value: {}", amount, stats.gold)
+    ScriptOutput::info(format!("Added {} gold, new value: {}", amount, stats.gold))
 }
@@ -1,4 +1,5 @@
 use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -6,15 +7,18 @@ pub fn cmd_add_stamina(
     tokens: &[&str],
     stats: &mut ResMut<PlayerStats>,
     _cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     if tokens.len() < 2 {
-        return "usage: add_stamina <amount>".to_string();
+        return ScriptOutput::warning("usage: add_stamina <amount>");
     }
 
     let Ok(amount) = tokens[1].parse::<f32>() else {
-        return format!("Invalid stamina amount: {}", tokens[1]);
+        return ScriptOutput::error(format!("Invalid stamina amount: {}", tokens[1]));
     };
 
     stats.stamina = (stats.stamina + amount).min(100.0);
-    format!("Added {} stamina, new value: {}", amount, stats.stamina)
+    ScriptOutput::info(format!(
+        "Added {} stamina, new value: {}",
+        amount, stats.stamina
+    ))
 }
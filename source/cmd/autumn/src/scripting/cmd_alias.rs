@@ -0,0 +1,44 @@
+use super::aliases::AliasRegistry;
+use super::script_output::ScriptOutput;
+
+/// Handle the alias command - defines a new alias or lists existing ones
+pub fn cmd_alias(tokens: &[&str], aliases: &mut AliasRegistry) -> ScriptOutput {
+    match tokens.len() {
+        1 => {
+            let list = aliases.list();
+            if list.is_empty() {
+                return ScriptOutput::info("No aliases defined");
+            }
+
+            let mut output = format!("{} aliases:", list.len());
+            for (name, command) in list {
+                output.push_str(&format!("\n  {} = \"{}\"", name, command));
+            }
+            ScriptOutput::info(output)
+        }
+        2 => match aliases.get(tokens[1]) {
+            Some(command) => ScriptOutput::info(format!("{} = \"{}\"", tokens[1], command)),
+            None => ScriptOutput::error(format!("Alias not found: {}", tokens[1])),
+        },
+        _ => {
+            let command = tokens[2..].join(" ");
+            match aliases.set(tokens[1], &command) {
+                Ok(()) => ScriptOutput::info(format!("{} = \"{}\"", tokens[1], command)),
+                Err(e) => ScriptOutput::error(e),
+            }
+        }
+    }
+}
+
+/// Handle the unalias command - removes an alias
+pub fn cmd_unalias(tokens: &[&str], aliases: &mut AliasRegistry) -> ScriptOutput {
+    if tokens.len() < 2 {
+        return ScriptOutput::warning("usage: unalias <name>");
+    }
+
+    if aliases.remove(tokens[1]) {
+        ScriptOutput::info(format!("Removed alias: {}", tokens[1]))
+    } else {
+        ScriptOutput::error(format!("Alias not found: {}", tokens[1]))
+    }
+}
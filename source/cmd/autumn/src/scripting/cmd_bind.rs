@@ -0,0 +1,45 @@
+use super::key_binds::BindRegistry;
+use super::script_output::ScriptOutput;
+
+/// Handle the bind command - binds a console command to a key
+pub fn cmd_bind(tokens: &[&str], binds: &mut BindRegistry) -> ScriptOutput {
+    if tokens.len() < 3 {
+        return ScriptOutput::warning("usage: bind <key> <command>");
+    }
+
+    let command = tokens[2..].join(" ");
+    match binds.bind(tokens[1], &command) {
+        Ok(()) => ScriptOutput::info(format!("{} = \"{}\"", tokens[1].to_lowercase(), command)),
+        Err(e) => ScriptOutput::error(e),
+    }
+}
+
+/// Handle the unbind command - removes the binding for a key
+pub fn cmd_unbind(tokens: &[&str], binds: &mut BindRegistry) -> ScriptOutput {
+    if tokens.len() < 2 {
+        return ScriptOutput::warning("usage: unbind <key>");
+    }
+
+    match binds.unbind(tokens[1]) {
+        Ok(true) => ScriptOutput::info(format!("Unbound key: {}", tokens[1].to_lowercase())),
+        Ok(false) => {
+            ScriptOutput::warning(format!("Key is not bound: {}", tokens[1].to_lowercase()))
+        }
+        Err(e) => ScriptOutput::error(e),
+    }
+}
+
+/// Handle the bindlist command - lists all key bindings
+pub fn cmd_bindlist(_tokens: &[&str], binds: &mut BindRegistry) -> ScriptOutput {
+    let list = binds.list();
+
+    if list.is_empty() {
+        return ScriptOutput::info("No keys bound");
+    }
+
+    let mut output = format!("{} bindings:", list.len());
+    for bind in list {
+        output.push_str(&format!("\n  {} = \"{}\"", bind.key_name, bind.command));
+    }
+    ScriptOutput::info(output)
+}
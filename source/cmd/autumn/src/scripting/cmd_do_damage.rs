@@ -1,12 +1,13 @@
+use super::script_output::ScriptOutput;
 use crate::actor::Actor;
 
-pub fn cmd_do_damage(tokens: &[&str], actor: &mut Actor) -> String {
+pub fn cmd_do_damage(tokens: &[&str], actor: &mut Actor) -> ScriptOutput {
     if tokens.len() < 2 {
-        return "usage: do_damage <amount>".to_string();
+        return ScriptOutput::warning("usage: do_damage <amount>");
     }
 
     let Ok(amount) = tokens[1].parse::<f32>() else {
-        return format!("Invalid damage amount: {}", tokens[1]);
+        return ScriptOutput::error(format!("Invalid damage amount: {}", tokens[1]));
     };
 
     actor.health -= amount;
@@ -14,8 +15,8 @@ pub fn cmd_do_damage(tokens: &[&str], actor: &mut Actor) -> String {
         actor.health = 0.0;
     }
 
-    format!(
+    ScriptOutput::info(format!(
         "Dealt {} damage to {}, health: {}/{}",
         amount, actor.actor_type, actor.health, actor.max_health
-    )
+    ))
 }
@@ -0,0 +1,6 @@
+use super::script_output::ScriptOutput;
+
+/// Handle the echo command - returns its arguments joined by spaces
+pub fn cmd_echo(tokens: &[&str]) -> ScriptOutput {
+    ScriptOutput::info(tokens[1..].join(" "))
+}
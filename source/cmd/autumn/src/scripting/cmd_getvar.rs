@@ -1,4 +1,5 @@
 use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -7,15 +8,15 @@ pub fn cmd_getvar(
     tokens: &[&str],
     _stats: &mut ResMut<PlayerStats>,
     cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     if tokens.len() < 2 {
-        return "usage: getvar <variable>".to_string();
+        return ScriptOutput::warning("usage: getvar <variable>");
     }
 
     let var_name = tokens[1];
 
     match cvars.get(var_name) {
-        Some(value) => format!("{}", value),
-        None => format!("Variable not found: {}", var_name),
+        Some(value) => ScriptOutput::info(format!("{}", value)),
+        None => ScriptOutput::error(format!("Variable not found: {}", var_name)),
     }
 }
@@ -0,0 +1,27 @@
+use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
+
+/// Handle the help command - describes a console variable
+pub fn cmd_help(tokens: &[&str], cvars: &CVarRegistry) -> ScriptOutput {
+    if tokens.len() < 2 {
+        return ScriptOutput::warning("usage: help <variable>");
+    }
+
+    let var_name = tokens[1];
+    let Some(value) = cvars.get(var_name) else {
+        return ScriptOutput::error(format!("Variable not found: {}", var_name));
+    };
+
+    let mut output = format!("{} ({})", var_name, value.type_name());
+    if cvars.is_read_only(var_name) {
+        output.push_str(" read-only");
+    }
+    if let Some(help) = cvars.help(var_name) {
+        output.push_str(&format!("\n  {}", help));
+    }
+    output.push_str(&format!("\n  value: {}", value));
+    if let Some(default) = cvars.default_value(var_name) {
+        output.push_str(&format!("\n  default: {}", default));
+    }
+    ScriptOutput::info(output)
+}
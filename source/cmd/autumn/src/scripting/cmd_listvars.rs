@@ -1,4 +1,5 @@
 use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -7,16 +8,19 @@ pub fn cmd_listvars(
     _tokens: &[&str],
     _stats: &mut ResMut<PlayerStats>,
     cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     let vars = cvars.list();
 
     if vars.is_empty() {
-        return "No variables defined".to_string();
+        return ScriptOutput::info("No variables defined");
     }
 
     let mut output = format!("{} variables:", vars.len());
     for (name, value) in vars {
         output.push_str(&format!("\n  {} = {}", name, value));
+        if let Some(help) = cvars.help(&name) {
+            output.push_str(&format!("  - {}", help));
+        }
     }
-    output
+    ScriptOutput::info(output)
 }
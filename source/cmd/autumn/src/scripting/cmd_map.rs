@@ -0,0 +1,46 @@
+use super::script_output::ScriptOutput;
+use crate::game_state::GameState;
+use crate::world::{CurrentMap, Map, map_path};
+use bevy::prelude::*;
+
+/// What the `map` command needs to switch maps.
+///
+/// The world is rebuilt by passing through the Loading state back into
+/// Playing: the current game's entities are despawned on leaving Playing and
+/// `setup_world` builds the new map on entering it again. Setting Playing
+/// directly would do nothing, as it is already the current state.
+pub struct MapCommandContext<'a> {
+    pub current_map: &'a mut CurrentMap,
+    pub next_state: &'a mut NextState<GameState>,
+}
+
+/// Run `script` if it is a `map` command, returning `None` for anything else
+pub fn process_map_command(script: &str, ctx: &mut MapCommandContext) -> Option<Vec<ScriptOutput>> {
+    let tokens: Vec<&str> = script.split_whitespace().collect();
+    if tokens.first() != Some(&"map") {
+        return None;
+    }
+
+    let output = match tokens.as_slice() {
+        [_] => ScriptOutput::info(format!("Current map: {}", ctx.current_map.0.display())),
+        [_, name] => cmd_map(name, ctx),
+        _ => ScriptOutput::warning("usage: map <name>"),
+    };
+    Some(vec![output])
+}
+
+fn cmd_map(name: &str, ctx: &mut MapCommandContext) -> ScriptOutput {
+    let path = match map_path(name) {
+        Ok(path) => path,
+        Err(e) => return ScriptOutput::error(e),
+    };
+
+    // Check the map is usable before tearing down the current game
+    if let Err(e) = Map::load_map_file(&path) {
+        return ScriptOutput::error(e);
+    }
+
+    ctx.current_map.0 = path;
+    ctx.next_state.set(GameState::Loading);
+    ScriptOutput::info(format!("Loading map {}", ctx.current_map.0.display()))
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use super::super::cmd_map::{MapCommandContext, process_map_command};
+    use crate::camera::MouseLookSettings;
+    use crate::game_state::{GamePlayEntity, GameState, GameStatePlugin};
+    use crate::hud::{PlayerStats, Toolbar};
+    use crate::item::Inventory;
+    use crate::scripting::Severity;
+    use crate::world::map::MAPS_DIR;
+    use crate::world::{CurrentMap, Map, map_path};
+    use bevy::prelude::*;
+    use bevy::state::app::StatesPlugin;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_map_path_in_maps_dir() {
+        assert_eq!(
+            map_path("crypt"),
+            Ok(PathBuf::from(MAPS_DIR).join("crypt.yaml"))
+        );
+        assert_eq!(
+            map_path("crypt.yaml"),
+            Ok(PathBuf::from(MAPS_DIR).join("crypt.yaml"))
+        );
+        assert_eq!(
+            map_path("act_1/level-2"),
+            Ok(PathBuf::from(MAPS_DIR).join("act_1/level-2.yaml"))
+        );
+    }
+
+    #[test]
+    fn test_map_path_rejects_escapes() {
+        for name in [
+            "",
+            "../map",
+            "act_1/../../cvars",
+            "/etc/passwd",
+            "act_1//level",
+            "./crypt",
+            "C:\\maps\\crypt",
+            "crypt level",
+            "crypt/",
+        ] {
+            assert!(map_path(name).is_err(), "{:?} should be rejected", name);
+        }
+    }
+
+    #[test]
+    fn test_default_map_still_loads() {
+        let path = map_path("default").unwrap();
+        assert_eq!(path, CurrentMap::default().0);
+        assert!(Map::load_map_file(&path).is_ok());
+    }
+
+    #[test]
+    fn test_map_command() {
+        let mut current_map = CurrentMap(PathBuf::from(MAPS_DIR).join("crypt.yaml"));
+        let mut next_state = NextState::<GameState>::default();
+        let mut ctx = MapCommandContext {
+            current_map: &mut current_map,
+            next_state: &mut next_state,
+        };
+
+        assert!(process_map_command("setvar x 1", &mut ctx).is_none());
+
+        let output = process_map_command("map ../secrets", &mut ctx).unwrap();
+        assert_eq!(output[0].severity, Severity::Error);
+        let output = process_map_command("map no_such_map", &mut ctx).unwrap();
+        assert_eq!(output[0].severity, Severity::Error);
+        let output = process_map_command("map a b", &mut ctx).unwrap();
+        assert_eq!(output[0].severity, Severity::Warning);
+        assert!(matches!(ctx.next_state, NextState::Unchanged));
+
+        let output = process_map_command("map default", &mut ctx).unwrap();
+        assert_eq!(output[0].severity, Severity::Info);
+        assert_eq!(*ctx.current_map, CurrentMap::default());
+        assert!(matches!(
+            ctx.next_state,
+            NextState::Pending(GameState::Loading)
+        ));
+    }
+
+    /// Stands in for one of the entities `setup_world` spawns, remembering
+    /// which map it was built from
+    #[derive(Component)]
+    struct BuiltFrom(PathBuf);
+
+    /// A cut-down `setup_world`: load the current map and spawn a game entity
+    fn build_world(mut commands: Commands, current_map: Res<CurrentMap>) {
+        let map_file = Map::load_map_file(&current_map.0).unwrap();
+        commands.insert_resource(Map::from_map_file(&map_file));
+        commands.spawn((GamePlayEntity, BuiltFrom(current_map.0.clone())));
+    }
+
+    fn built_from(app: &mut App) -> Vec<PathBuf> {
+        let world = app.world_mut();
+        world
+            .query::<&BuiltFrom>()
+            .iter(world)
+            .map(|built| built.0.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_map_command_rebuilds_world() {
+        let small_yaml = "map:\n  grid:\n  - XXX\n  - X.X\n  - XXX\n  items: []\n  actors: []\n";
+        let small_path =
+            std::env::temp_dir().join(format!("map_reload_test_{}.yaml", std::process::id()));
+        std::fs::write(&small_path, small_yaml).unwrap();
+        let small_map = Map::load_map_file(&small_path).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .insert_resource(CurrentMap(small_path.clone()))
+            .insert_resource(Map::from_map_file(&small_map))
+            .init_resource::<PlayerStats>()
+            .init_resource::<Toolbar>()
+            .init_resource::<Inventory>()
+            .init_resource::<MouseLookSettings>()
+            .add_plugins(GameStatePlugin)
+            .add_systems(OnEnter(GameState::Playing), build_world);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+        assert_eq!(built_from(&mut app), vec![small_path.clone()]);
+
+        app.world_mut()
+            .resource_scope(|world, mut current_map: Mut<CurrentMap>| {
+                let mut next_state = world.resource_mut::<NextState<GameState>>();
+                let mut ctx = MapCommandContext {
+                    current_map: &mut current_map,
+                    next_state: &mut next_state,
+                };
+                let output = process_map_command("map default", &mut ctx).unwrap();
+                assert_eq!(output[0].severity, Severity::Info);
+            });
+        app.update();
+        app.update();
+
+        // The small map's entities are gone and the default map is loaded
+        let default_path = CurrentMap::default().0;
+        assert_eq!(
+            app.world().resource::<State<GameState>>().get(),
+            &GameState::Playing
+        );
+        assert_eq!(built_from(&mut app), vec![default_path.clone()]);
+        let default_map = Map::load_map_file(&default_path).unwrap();
+        assert_eq!(
+            app.world().resource::<Map>().to_map_file(),
+            Map::from_map_file(&default_map).to_map_file()
+        );
+
+        std::fs::remove_file(&small_path).ok();
+    }
+}
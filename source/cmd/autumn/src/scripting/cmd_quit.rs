@@ -1,4 +1,5 @@
 use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -6,7 +7,7 @@ pub fn cmd_quit(
     _tokens: &[&str],
     _stats: &mut ResMut<PlayerStats>,
     _cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     println!("Exiting...");
     std::process::exit(0);
 }
@@ -1,4 +1,5 @@
 use super::cvars::CVarRegistry;
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -7,9 +8,9 @@ pub fn cmd_savecvars(
     _tokens: &[&str],
     _stats: &mut ResMut<PlayerStats>,
     cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     match cvars.save_to_yaml("data/cvars.yaml") {
-        Ok(_) => "CVars saved to data/cvars.yaml".to_string(),
-        Err(e) => format!("Failed to save cvars: {}", e),
+        Ok(_) => ScriptOutput::info("CVars saved to data/cvars.yaml"),
+        Err(e) => ScriptOutput::error(format!("Failed to save cvars: {}", e)),
     }
 }
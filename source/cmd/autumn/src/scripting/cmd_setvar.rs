@@ -1,4 +1,5 @@
 use super::cvars::{CVarRegistry, CVarValue};
+use super::script_output::ScriptOutput;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
@@ -25,13 +26,48 @@ pub fn parse_value_for_type(
             )),
         },
         CVarValue::String(_) => Ok(CVarValue::String(value_str.to_string())),
+        CVarValue::Color(_) => parse_color(value_str).map(CVarValue::Color),
+    }
+}
+
+/// Parse `#rrggbb`, `#rrggbbaa` or `r,g,b,a` (channels 0-1) into a color.
+/// Alpha defaults to 1 when left out.
+fn parse_color(value_str: &str) -> Result<[f32; 4], String> {
+    let invalid = || {
+        format!(
+            "Invalid color value: {} (use #rrggbb, #rrggbbaa or r,g,b,a)",
+            value_str
+        )
+    };
+
+    let channels: Vec<f32> = if let Some(hex) = value_str.strip_prefix('#') {
+        if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+            return Err(invalid());
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map(|v| v as f32 / 255.0))
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?
+    } else {
+        value_str
+            .split(',')
+            .map(|part| part.trim().parse::<f32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?
+    };
+
+    match channels[..] {
+        [r, g, b] => Ok([r, g, b, 1.0]),
+        [r, g, b, a] => Ok([r, g, b, a]),
+        _ => Err(invalid()),
     }
 }
 
 /// Worker function that handles setvar logic without Bevy dependencies
-pub fn cmd_setvar_worker(tokens: &[&str], cvars: &mut CVarRegistry) -> String {
+pub fn cmd_setvar_worker(tokens: &[&str], cvars: &mut CVarRegistry) -> ScriptOutput {
     if tokens.len() < 3 {
-        return "usage: setvar <variable> <value>".to_string();
+        return ScriptOutput::warning("usage: setvar <variable> <value>");
     }
 
     let var_name = tokens[1];
@@ -40,18 +76,18 @@ pub fn cmd_setvar_worker(tokens: &[&str], cvars: &mut CVarRegistry) -> String {
     // Look up the existing variable to determine its type
     let existing_var = match cvars.get(var_name) {
         Some(v) => v.clone(),
-        None => return format!("Variable '{}' does not exist", var_name),
+        None => return ScriptOutput::error(format!("Variable '{}' does not exist", var_name)),
     };
 
     // Convert the string input to the appropriate type based on existing variable type
     let new_value = match parse_value_for_type(value_str, &existing_var) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return ScriptOutput::error(e),
     };
 
     match cvars.set(var_name, new_value) {
-        Ok(_) => format!("{} = {}", var_name, value_str),
-        Err(e) => e,
+        Ok(_) => ScriptOutput::info(format!("{} = {}", var_name, value_str)),
+        Err(e) => ScriptOutput::error(e),
     }
 }
 
@@ -60,6 +96,6 @@ pub fn cmd_setvar(
     tokens: &[&str],
     _stats: &mut ResMut<PlayerStats>,
     cvars: &mut ResMut<CVarRegistry>,
-) -> String {
+) -> ScriptOutput {
     cmd_setvar_worker(tokens, cvars)
 }
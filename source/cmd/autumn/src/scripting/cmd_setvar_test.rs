@@ -2,6 +2,7 @@
 mod tests {
     use super::super::cmd_setvar::cmd_setvar_worker;
     use super::super::cvars::{CVarRegistry, CVarValue};
+    use super::super::script_output::Severity;
 
     // Helper to verify type is preserved after parsing
     fn verify_type_match(original: &CVarValue, parsed: &CVarValue) -> bool {
@@ -154,4 +155,53 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_color_round_trips_through_setvar() {
+        let mut cvars = setup_cvars();
+        cvars
+            .init("color_var", CVarValue::Color([1.0, 1.0, 1.0, 1.0]))
+            .unwrap();
+        assert_eq!(cvars.get("color_var").unwrap().to_string(), "#ffffffff");
+
+        cmd_setvar_worker(&["setvar", "color_var", "#ff8000"], &mut cvars);
+        assert_eq!(cvars.get("color_var").unwrap().to_string(), "#ff8000ff");
+
+        cmd_setvar_worker(&["setvar", "color_var", "#00ff0080"], &mut cvars);
+        assert_eq!(cvars.get("color_var").unwrap().to_string(), "#00ff0080");
+
+        cmd_setvar_worker(&["setvar", "color_var", "0,0,1,0.5"], &mut cvars);
+        assert_eq!(
+            cvars.get("color_var").unwrap().as_color(),
+            Some([0.0, 0.0, 1.0, 0.5])
+        );
+        assert_eq!(cvars.get("color_var").unwrap().to_string(), "#0000ff80");
+
+        // Getting the value back and setting it again changes nothing
+        let hex = cvars.get("color_var").unwrap().to_string();
+        cmd_setvar_worker(&["setvar", "color_var", &hex], &mut cvars);
+        assert_eq!(cvars.get("color_var").unwrap().to_string(), hex);
+    }
+
+    #[test]
+    fn test_invalid_color_is_rejected() {
+        let mut cvars = setup_cvars();
+        cvars
+            .init("color_var", CVarValue::Color([1.0, 0.0, 0.0, 1.0]))
+            .unwrap();
+
+        for value in ["#ff00", "#gg0000", "red", "1,0", "1,0,0,0,1"] {
+            let output = cmd_setvar_worker(&["setvar", "color_var", value], &mut cvars);
+            assert_eq!(output.severity, Severity::Error, "{}", value);
+            assert!(output.text.starts_with("Invalid color value"), "{}", value);
+        }
+        assert_eq!(cvars.get("color_var").unwrap().to_string(), "#ff0000ff");
+
+        // Colors and other types don't mix
+        assert!(
+            cvars
+                .set("string_var", CVarValue::Color([0.0, 0.0, 0.0, 1.0]))
+                .is_err()
+        );
+    }
 }
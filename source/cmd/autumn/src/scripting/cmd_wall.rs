@@ -0,0 +1,137 @@
+use super::script_output::ScriptOutput;
+use crate::world::{Map, TileType};
+use bevy::prelude::*;
+
+/// Height used by `addwall` when none is given (same as an 'X' map tile)
+pub const DEFAULT_WALL_HEIGHT: f32 = 16.0;
+
+/// Everything the wall commands need to change the world.
+///
+/// `process_script` only carries resources, so the console special-cases
+/// these commands and runs them with this context instead.
+pub struct WallCommandContext<'a, 'w, 's, 'r> {
+    pub commands: &'a mut Commands<'w, 's>,
+    pub asset_server: &'a Res<'r, AssetServer>,
+    pub meshes: &'a mut Assets<Mesh>,
+    pub materials: &'a mut Assets<StandardMaterial>,
+    pub map: Option<&'a mut Map>,
+}
+
+/// Run `script` if it is a wall command, returning `None` for anything else
+pub fn process_wall_command(
+    script: &str,
+    ctx: &mut WallCommandContext,
+) -> Option<Vec<ScriptOutput>> {
+    let tokens: Vec<&str> = script.split_whitespace().collect();
+    let output = match tokens.first() {
+        Some(&"addwall") => cmd_addwall(&tokens, ctx),
+        Some(&"delwall") => cmd_delwall(&tokens, ctx),
+        _ => return None,
+    };
+    Some(vec![output])
+}
+
+/// Parse `addwall <gx> <gy> [height]`
+pub fn parse_addwall_args(tokens: &[&str]) -> Result<(i32, i32, f32), ScriptOutput> {
+    if tokens.len() < 3 || tokens.len() > 4 {
+        return Err(ScriptOutput::warning("usage: addwall <gx> <gy> [height]"));
+    }
+
+    let (grid_x, grid_y) = parse_grid_coords(tokens[1], tokens[2])?;
+    let height = match tokens.get(3) {
+        Some(token) => match token.parse::<f32>() {
+            Ok(height) if height > 0.0 => height,
+            _ => {
+                return Err(ScriptOutput::error(format!(
+                    "Invalid wall height: {}",
+                    token
+                )));
+            }
+        },
+        None => DEFAULT_WALL_HEIGHT,
+    };
+    Ok((grid_x, grid_y, height))
+}
+
+/// Parse `delwall <gx> <gy>`
+pub fn parse_delwall_args(tokens: &[&str]) -> Result<(i32, i32), ScriptOutput> {
+    if tokens.len() != 3 {
+        return Err(ScriptOutput::warning("usage: delwall <gx> <gy>"));
+    }
+    parse_grid_coords(tokens[1], tokens[2])
+}
+
+fn parse_grid_coords(x: &str, y: &str) -> Result<(i32, i32), ScriptOutput> {
+    let grid_x = x
+        .parse::<i32>()
+        .map_err(|_| ScriptOutput::error(format!("Invalid grid x: {}", x)))?;
+    let grid_y = y
+        .parse::<i32>()
+        .map_err(|_| ScriptOutput::error(format!("Invalid grid y: {}", y)))?;
+    Ok((grid_x, grid_y))
+}
+
+fn describe_tile(map: &Map, layer: usize, grid_x: i32, grid_y: i32) -> String {
+    let tile = map
+        .layer_collision(layer)
+        .and_then(|collision| collision.get(&(grid_x, grid_y)));
+    match tile {
+        Some(TileType::Wall { height }) => format!("wall (height {})", height),
+        _ => "empty".to_string(),
+    }
+}
+
+fn cmd_addwall(tokens: &[&str], ctx: &mut WallCommandContext) -> ScriptOutput {
+    let (grid_x, grid_y, height) = match parse_addwall_args(tokens) {
+        Ok(args) => args,
+        Err(output) => return output,
+    };
+    let Some(map) = ctx.map.as_deref_mut() else {
+        return ScriptOutput::error("addwall requires a loaded map");
+    };
+
+    // Walls are added to the floor the player is on
+    let layer = map.active_layer;
+    let before = describe_tile(map, layer, grid_x, grid_y);
+
+    // Replace rather than stack walls so the old entity isn't orphaned
+    map.remove_wall(ctx.commands, layer, grid_x, grid_y);
+    map.spawn_wall(
+        ctx.commands,
+        ctx.asset_server,
+        ctx.meshes,
+        ctx.materials,
+        layer,
+        grid_x,
+        grid_y,
+        height,
+    );
+
+    let after = describe_tile(map, layer, grid_x, grid_y);
+    ScriptOutput::info(format!(
+        "Collision ({}, {}): {} -> {}",
+        grid_x, grid_y, before, after
+    ))
+}
+
+fn cmd_delwall(tokens: &[&str], ctx: &mut WallCommandContext) -> ScriptOutput {
+    let (grid_x, grid_y) = match parse_delwall_args(tokens) {
+        Ok(args) => args,
+        Err(output) => return output,
+    };
+    let Some(map) = ctx.map.as_deref_mut() else {
+        return ScriptOutput::error("delwall requires a loaded map");
+    };
+
+    let layer = map.active_layer;
+    let before = describe_tile(map, layer, grid_x, grid_y);
+    if !map.remove_wall(ctx.commands, layer, grid_x, grid_y) {
+        return ScriptOutput::warning(format!("No wall at ({}, {})", grid_x, grid_y));
+    }
+
+    let after = describe_tile(map, layer, grid_x, grid_y);
+    ScriptOutput::info(format!(
+        "Collision ({}, {}): {} -> {}",
+        grid_x, grid_y, before, after
+    ))
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use super::super::cmd_wall::{DEFAULT_WALL_HEIGHT, parse_addwall_args, parse_delwall_args};
+
+    #[test]
+    fn test_addwall_default_height() {
+        assert_eq!(
+            parse_addwall_args(&["addwall", "3", "-4"]),
+            Ok((3, -4, DEFAULT_WALL_HEIGHT))
+        );
+    }
+
+    #[test]
+    fn test_addwall_explicit_height() {
+        assert_eq!(
+            parse_addwall_args(&["addwall", "0", "7", "8"]),
+            Ok((0, 7, 8.0))
+        );
+    }
+
+    #[test]
+    fn test_addwall_rejects_bad_input() {
+        assert!(parse_addwall_args(&["addwall", "3"]).is_err());
+        assert!(parse_addwall_args(&["addwall", "x", "4"]).is_err());
+        assert!(parse_addwall_args(&["addwall", "1.5", "4"]).is_err());
+        assert!(parse_addwall_args(&["addwall", "1", "4", "tall"]).is_err());
+        assert!(parse_addwall_args(&["addwall", "1", "4", "-2"]).is_err());
+        assert!(parse_addwall_args(&["addwall", "1", "4", "8", "9"]).is_err());
+    }
+
+    #[test]
+    fn test_delwall_coords() {
+        assert_eq!(parse_delwall_args(&["delwall", "-1", "12"]), Ok((-1, 12)));
+        assert!(parse_delwall_args(&["delwall", "1"]).is_err());
+        assert!(parse_delwall_args(&["delwall", "1", "2", "3"]).is_err());
+        assert!(parse_delwall_args(&["delwall", "a", "2"]).is_err());
+    }
+}
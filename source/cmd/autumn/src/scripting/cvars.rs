@@ -9,7 +9,7 @@
 ///
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Represents a console variable value
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,6 +20,8 @@ pub enum CVarValue {
     Int32(i32),
     String(String),
     Bool(bool),
+    /// Linear RGBA, each channel 0-1
+    Color([f32; 4]),
 }
 
 impl CVarValue {
@@ -44,12 +46,31 @@ impl CVarValue {
         }
     }
 
+    pub fn as_color(&self) -> Option<[f32; 4]> {
+        match self {
+            CVarValue::Color(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// Type name as written in cvars.yaml
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            CVarValue::F32(_) => "f32",
+            CVarValue::Int32(_) => "int32",
+            CVarValue::String(_) => "string",
+            CVarValue::Bool(_) => "bool",
+            CVarValue::Color(_) => "color",
+        }
+    }
+
     pub fn as_string(&self) -> String {
         match self {
             CVarValue::F32(v) => v.to_string(),
             CVarValue::Int32(v) => v.to_string(),
             CVarValue::String(s) => s.clone(),
             CVarValue::Bool(b) => b.to_string(),
+            CVarValue::Color(_) => self.to_string(),
         }
     }
 }
@@ -61,6 +82,13 @@ impl std::fmt::Display for CVarValue {
             CVarValue::Int32(v) => write!(f, "{}", v),
             CVarValue::String(s) => write!(f, "{}", s),
             CVarValue::Bool(b) => write!(f, "{}", b),
+            CVarValue::Color(c) => {
+                write!(f, "#")?;
+                for channel in c {
+                    write!(f, "{:02x}", (channel.clamp(0.0, 1.0) * 255.0).round() as u8)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -69,12 +97,28 @@ impl std::fmt::Display for CVarValue {
 #[derive(Resource, Default)]
 pub struct CVarRegistry {
     vars: HashMap<String, CVarValue>,
+
+    /// Value each variable was initialized with
+    defaults: HashMap<String, CVarValue>,
+
+    /// Description shown by the help command, for variables that have one
+    help: HashMap<String, String>,
+
+    /// Variables left out of `save_to_yaml`, such as cheats
+    non_archived: HashSet<String>,
+
+    /// Engine-managed variables that `set` refuses to change
+    read_only: HashSet<String>,
 }
 
 impl CVarRegistry {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            defaults: HashMap::new(),
+            help: HashMap::new(),
+            non_archived: HashSet::new(),
+            read_only: HashSet::new(),
         }
     }
 
@@ -113,10 +157,23 @@ impl CVarRegistry {
             return Err(format!("Variable already exists: {}", name));
         }
 
+        self.defaults.insert(name.to_string(), value.clone());
         self.vars.insert(name.to_string(), value);
         Ok(())
     }
 
+    /// Initialize a variable along with a description for the help command
+    pub fn init_with_help(
+        &mut self,
+        name: &str,
+        value: CVarValue,
+        help: &str,
+    ) -> Result<(), String> {
+        self.init(name, value)?;
+        self.help.insert(name.to_string(), help.to_string());
+        Ok(())
+    }
+
     pub fn init_f32(&mut self, name: &str, value: f32) {
         self.init(name, CVarValue::F32(value)).unwrap();
     }
@@ -125,7 +182,42 @@ impl CVarRegistry {
         self.init(name, CVarValue::Bool(value)).unwrap();
     }
 
+    /// Initialize a cheat toggle. Cheats always start off each session, so
+    /// they are never written to the saved cvars file.
+    pub fn init_cheat_bool(&mut self, name: &str, value: bool) {
+        self.init_bool(name, value);
+        self.non_archived.insert(name.to_string());
+    }
+
+    /// Initialize an engine-managed variable. Users can read it but `set`
+    /// refuses to change it; the engine updates it with `set_internal`.
+    /// Being derived state, it is never written to the saved cvars file.
+    pub fn init_read_only(&mut self, name: &str, value: CVarValue) -> Result<(), String> {
+        self.init(name, value)?;
+        self.non_archived.insert(name.to_string());
+        self.read_only.insert(name.to_string());
+        Ok(())
+    }
+
+    pub fn is_read_only(&self, name: &str) -> bool {
+        self.read_only.contains(name)
+    }
+
+    /// Whether the variable is written out by `save_to_yaml`
+    pub fn is_archived(&self, name: &str) -> bool {
+        !self.non_archived.contains(name)
+    }
+
     pub fn set(&mut self, name: &str, value: CVarValue) -> Result<(), String> {
+        if self.is_read_only(name) {
+            return Err(format!("Variable '{}' is read-only", name));
+        }
+        self.set_internal(name, value)
+    }
+
+    /// Set a variable even if it is read-only. Only for engine code keeping
+    /// derived values up to date.
+    pub fn set_internal(&mut self, name: &str, value: CVarValue) -> Result<(), String> {
         let existing = self
             .vars
             .get(name)
@@ -137,6 +229,7 @@ impl CVarRegistry {
             (CVarValue::Int32(_), CVarValue::Int32(_)) => {}
             (CVarValue::String(_), CVarValue::String(_)) => {}
             (CVarValue::Bool(_), CVarValue::Bool(_)) => {}
+            (CVarValue::Color(_), CVarValue::Color(_)) => {}
             _ => {
                 return Err(format!(
                     "Type mismatch for variable '{}': cannot change from {:?} to {:?}",
@@ -175,6 +268,52 @@ impl CVarRegistry {
         self.vars.get(name).and_then(|v| v.as_bool()).unwrap()
     }
 
+    /// Value of an f32 variable, or `default` if it isn't registered or holds
+    /// another type
+    pub fn get_f32_or(&self, name: &str, default: f32) -> f32 {
+        self.vars
+            .get(name)
+            .and_then(|v| v.as_f32())
+            .unwrap_or(default)
+    }
+
+    /// Value of an int32 variable, or `default` if it isn't registered or
+    /// holds another type
+    pub fn get_i32_or(&self, name: &str, default: i32) -> i32 {
+        self.vars
+            .get(name)
+            .and_then(|v| v.as_i32())
+            .unwrap_or(default)
+    }
+
+    /// Value of a bool variable, or `default` if it isn't registered or holds
+    /// another type
+    pub fn get_bool_or(&self, name: &str, default: bool) -> bool {
+        self.vars
+            .get(name)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(default)
+    }
+
+    /// Value of a color variable, or `default` if it isn't registered or
+    /// holds another type
+    pub fn get_color_or(&self, name: &str, default: [f32; 4]) -> [f32; 4] {
+        self.vars
+            .get(name)
+            .and_then(|v| v.as_color())
+            .unwrap_or(default)
+    }
+
+    /// Description given when the variable was initialized, if any
+    pub fn help(&self, name: &str) -> Option<&str> {
+        self.help.get(name).map(|help| help.as_str())
+    }
+
+    /// Value the variable was initialized with
+    pub fn default_value(&self, name: &str) -> Option<&CVarValue> {
+        self.defaults.get(name)
+    }
+
     pub fn exists(&self, name: &str) -> bool {
         self.vars.contains_key(name)
     }
@@ -194,7 +333,11 @@ impl CVarRegistry {
 
     pub fn save_to_yaml(&self, path: &str) -> Result<(), String> {
         // Sort variables alphabetically by name and create a YAML mapping
-        let mut sorted_vars: Vec<(&String, &CVarValue)> = self.vars.iter().collect();
+        let mut sorted_vars: Vec<(&String, &CVarValue)> = self
+            .vars
+            .iter()
+            .filter(|(name, _)| self.is_archived(name))
+            .collect();
         sorted_vars.sort_by(|a, b| a.0.cmp(b.0));
 
         // Create a YAML mapping that preserves insertion order
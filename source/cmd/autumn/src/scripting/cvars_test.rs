@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use super::super::cmd_help::cmd_help;
+    use super::super::cmd_setvar::cmd_setvar_worker;
     use super::super::cvars::{CVarRegistry, CVarValue};
+    use super::super::script_output::ScriptOutput;
 
     #[test]
     fn test_cvarvalue_as_f32() {
@@ -409,4 +412,154 @@ mod tests {
         assert!(debug_str.contains("F32"));
         assert!(debug_str.contains("3.14"));
     }
+
+    #[test]
+    fn test_cheat_cvars_are_not_saved() {
+        let mut registry = CVarRegistry::new();
+        registry.init_f32("mouse.sensitivity", 0.5);
+        registry.init_cheat_bool("noclip", false);
+        registry.set("noclip", CVarValue::Bool(true)).unwrap();
+
+        assert!(registry.is_archived("mouse.sensitivity"));
+        assert!(!registry.is_archived("noclip"));
+        assert!(registry.get_bool("noclip"));
+
+        let path =
+            std::env::temp_dir().join(format!("cvars_cheat_test_{}.yaml", std::process::id()));
+        registry.save_to_yaml(path.to_str().unwrap()).unwrap();
+        let yaml = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(yaml.contains("mouse.sensitivity"));
+        assert!(!yaml.contains("noclip"));
+    }
+
+    #[test]
+    fn test_help_text_is_stored() {
+        let mut registry = CVarRegistry::new();
+        registry
+            .init_with_help("r_fov", CVarValue::F32(90.0), "Field of view in degrees")
+            .unwrap();
+        registry.init_f32("r_gamma", 1.0);
+
+        assert_eq!(registry.help("r_fov"), Some("Field of view in degrees"));
+        assert_eq!(registry.help("r_gamma"), None);
+        assert_eq!(registry.help("missing"), None);
+
+        // A failed init doesn't replace the existing description
+        assert!(
+            registry
+                .init_with_help("r_fov", CVarValue::F32(60.0), "Other")
+                .is_err()
+        );
+        assert_eq!(registry.help("r_fov"), Some("Field of view in degrees"));
+    }
+
+    #[test]
+    fn test_default_value_survives_set() {
+        let mut registry = CVarRegistry::new();
+        registry.init_f32("r_gamma", 1.0);
+        registry.set_f32("r_gamma", 2.2);
+
+        assert_eq!(registry.get_f32("r_gamma"), 2.2);
+        assert_eq!(
+            registry.default_value("r_gamma").and_then(|v| v.as_f32()),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_help_command() {
+        let mut registry = CVarRegistry::new();
+        registry
+            .init_with_help("r_fov", CVarValue::F32(90.0), "Field of view in degrees")
+            .unwrap();
+        registry.set_f32("r_fov", 75.0);
+
+        assert_eq!(
+            cmd_help(&["help", "r_fov"], &registry),
+            ScriptOutput::info(
+                "r_fov (f32)\n  Field of view in degrees\n  value: 75\n  default: 90"
+            )
+        );
+    }
+
+    #[test]
+    fn test_help_command_unknown_variable() {
+        let registry = CVarRegistry::new();
+        assert_eq!(
+            cmd_help(&["help", "nope"], &registry),
+            ScriptOutput::error("Variable not found: nope")
+        );
+        assert_eq!(
+            cmd_help(&["help"], &registry),
+            ScriptOutput::warning("usage: help <variable>")
+        );
+    }
+
+    #[test]
+    fn test_read_only_cvar_rejects_set() {
+        let mut registry = CVarRegistry::new();
+        registry
+            .init_read_only("r_resolution", CVarValue::String("1280x720".to_string()))
+            .unwrap();
+        registry.init_f32("r_gamma", 1.0);
+
+        assert!(registry.is_read_only("r_resolution"));
+        assert!(!registry.is_read_only("r_gamma"));
+        assert_eq!(
+            registry.set("r_resolution", CVarValue::String("640x480".to_string())),
+            Err("Variable 'r_resolution' is read-only".to_string())
+        );
+        assert_eq!(registry.get_string("r_resolution"), "1280x720");
+
+        // Engine code can still keep it up to date
+        registry
+            .set_internal("r_resolution", CVarValue::String("1920x1080".to_string()))
+            .unwrap();
+        assert_eq!(registry.get_string("r_resolution"), "1920x1080");
+
+        // Type checks still apply
+        assert!(
+            registry
+                .set_internal("r_resolution", CVarValue::Int32(1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_setvar_on_read_only_cvar_reports_error() {
+        let mut registry = CVarRegistry::new();
+        registry
+            .init_read_only("stat.kills", CVarValue::Int32(3))
+            .unwrap();
+
+        assert_eq!(
+            cmd_setvar_worker(&["setvar", "stat.kills", "99"], &mut registry),
+            ScriptOutput::error("Variable 'stat.kills' is read-only")
+        );
+        assert_eq!(registry.get_i32("stat.kills"), 3);
+        assert!(!registry.is_archived("stat.kills"));
+    }
+
+    #[test]
+    fn test_get_or_falls_back_to_default() {
+        let mut registry = CVarRegistry::new();
+        registry.init_f32("player.speed", 2.5);
+
+        assert_eq!(registry.get_f32_or("player.speed", 1.0), 2.5);
+        // Missing and mistyped variables both give the default
+        assert_eq!(registry.get_f32_or("player.jump", 1.0), 1.0);
+        assert_eq!(registry.get_i32_or("player.speed", 7), 7);
+        assert!(registry.get_bool_or("cl_missing", true));
+
+        registry
+            .init("hud.color", CVarValue::Color([0.0, 1.0, 0.0, 1.0]))
+            .unwrap();
+        assert_eq!(
+            registry.get_color_or("hud.color", [1.0; 4]),
+            [0.0, 1.0, 0.0, 1.0]
+        );
+        assert_eq!(registry.get_color_or("player.speed", [1.0; 4]), [1.0; 4]);
+    }
 }
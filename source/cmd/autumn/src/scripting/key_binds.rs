@@ -0,0 +1,179 @@
+/// Key bindings for console commands
+///
+/// Quake-style `bind <key> <command>` support: each bound key runs its
+/// command through `process_script` when pressed while the console is closed.
+///
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Bindings every game starts with, as (key name, command)
+pub const DEFAULT_BINDS: &[(&str, &str)] = &[("e", "use_item")];
+
+/// A console command bound to a key
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBind {
+    pub key_name: String, // Normalized (lowercase) key name, used for display
+    pub command: String,
+}
+
+/// Resource that stores all key bindings
+#[derive(Resource, Default)]
+pub struct BindRegistry {
+    binds: HashMap<KeyCode, KeyBind>,
+}
+
+impl BindRegistry {
+    /// Bind a command to the named key, replacing any existing binding
+    pub fn bind(&mut self, key_name: &str, command: &str) -> Result<(), String> {
+        let key_code =
+            parse_key_name(key_name).ok_or_else(|| format!("Unknown key: {}", key_name))?;
+        if key_code == KeyCode::Backquote {
+            return Err("Cannot bind the console toggle key".to_string());
+        }
+
+        self.binds.insert(
+            key_code,
+            KeyBind {
+                key_name: key_name.to_lowercase(),
+                command: command.to_string(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove the binding for the named key, returning true if it existed
+    pub fn unbind(&mut self, key_name: &str) -> Result<bool, String> {
+        let key_code =
+            parse_key_name(key_name).ok_or_else(|| format!("Unknown key: {}", key_name))?;
+        Ok(self.binds.remove(&key_code).is_some())
+    }
+
+    #[cfg(test)]
+    pub fn get(&self, key_code: KeyCode) -> Option<&KeyBind> {
+        self.binds.get(&key_code)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&KeyCode, &KeyBind)> {
+        self.binds.iter()
+    }
+
+    pub fn list(&self) -> Vec<KeyBind> {
+        let mut result: Vec<KeyBind> = self.binds.values().cloned().collect();
+
+        // Sort alphabetically by key name
+        result.sort_by(|a, b| a.key_name.cmp(&b.key_name));
+
+        result
+    }
+}
+
+/// Convert a user-facing key name (e.g. "f1", "k", "space") into a KeyCode.
+/// Key names are case-insensitive.
+pub fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let name = name.to_lowercase();
+
+    // Single letters and digits
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        let key_code = match ch {
+            'a' => KeyCode::KeyA,
+            'b' => KeyCode::KeyB,
+            'c' => KeyCode::KeyC,
+            'd' => KeyCode::KeyD,
+            'e' => KeyCode::KeyE,
+            'f' => KeyCode::KeyF,
+            'g' => KeyCode::KeyG,
+            'h' => KeyCode::KeyH,
+            'i' => KeyCode::KeyI,
+            'j' => KeyCode::KeyJ,
+            'k' => KeyCode::KeyK,
+            'l' => KeyCode::KeyL,
+            'm' => KeyCode::KeyM,
+            'n' => KeyCode::KeyN,
+            'o' => KeyCode::KeyO,
+            'p' => KeyCode::KeyP,
+            'q' => KeyCode::KeyQ,
+            'r' => KeyCode::KeyR,
+            's' => KeyCode::KeyS,
+            't' => KeyCode::KeyT,
+            'u' => KeyCode::KeyU,
+            'v' => KeyCode::KeyV,
+            'w' => KeyCode::KeyW,
+            'x' => KeyCode::KeyX,
+            'y' => KeyCode::KeyY,
+            'z' => KeyCode::KeyZ,
+            '0' => KeyCode::Digit0,
+            '1' => KeyCode::Digit1,
+            '2' => KeyCode::Digit2,
+            '3' => KeyCode::Digit3,
+            '4' => KeyCode::Digit4,
+            '5' => KeyCode::Digit5,
+            '6' => KeyCode::Digit6,
+            '7' => KeyCode::Digit7,
+            '8' => KeyCode::Digit8,
+            '9' => KeyCode::Digit9,
+            '-' => KeyCode::Minus,
+            '=' => KeyCode::Equal,
+            '[' => KeyCode::BracketLeft,
+            ']' => KeyCode::BracketRight,
+            ';' => KeyCode::Semicolon,
+            '\'' => KeyCode::Quote,
+            ',' => KeyCode::Comma,
+            '.' => KeyCode::Period,
+            '/' => KeyCode::Slash,
+            '\\' => KeyCode::Backslash,
+            '`' => KeyCode::Backquote,
+            _ => return None,
+        };
+        return Some(key_code);
+    }
+
+    let key_code = match name.as_str() {
+        "f1" => KeyCode::F1,
+        "f2" => KeyCode::F2,
+        "f3" => KeyCode::F3,
+        "f4" => KeyCode::F4,
+        "f5" => KeyCode::F5,
+        "f6" => KeyCode::F6,
+        "f7" => KeyCode::F7,
+        "f8" => KeyCode::F8,
+        "f9" => KeyCode::F9,
+        "f10" => KeyCode::F10,
+        "f11" => KeyCode::F11,
+        "f12" => KeyCode::F12,
+        "space" => KeyCode::Space,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "escape" | "esc" => KeyCode::Escape,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" | "uparrow" => KeyCode::ArrowUp,
+        "down" | "downarrow" => KeyCode::ArrowDown,
+        "left" | "leftarrow" => KeyCode::ArrowLeft,
+        "right" | "rightarrow" => KeyCode::ArrowRight,
+        "shift" | "lshift" => KeyCode::ShiftLeft,
+        "rshift" => KeyCode::ShiftRight,
+        "ctrl" | "lctrl" => KeyCode::ControlLeft,
+        "rctrl" => KeyCode::ControlRight,
+        "alt" | "lalt" => KeyCode::AltLeft,
+        "ralt" => KeyCode::AltRight,
+        "kp0" => KeyCode::Numpad0,
+        "kp1" => KeyCode::Numpad1,
+        "kp2" => KeyCode::Numpad2,
+        "kp3" => KeyCode::Numpad3,
+        "kp4" => KeyCode::Numpad4,
+        "kp5" => KeyCode::Numpad5,
+        "kp6" => KeyCode::Numpad6,
+        "kp7" => KeyCode::Numpad7,
+        "kp8" => KeyCode::Numpad8,
+        "kp9" => KeyCode::Numpad9,
+        "kp_enter" => KeyCode::NumpadEnter,
+        _ => return None,
+    };
+    Some(key_code)
+}
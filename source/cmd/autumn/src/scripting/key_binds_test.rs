@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use super::super::aliases::AliasRegistry;
+    use super::super::cmd_bind::{cmd_bind, cmd_bindlist, cmd_unbind};
+    use super::super::key_binds::{BindRegistry, parse_key_name};
+    use super::super::script_output::ScriptOutput;
+    use super::super::scripting_plugin::update_key_binds;
+    use crate::console::{ConsoleCommand, ConsoleState};
+    use bevy::prelude::*;
+
+    #[test]
+    fn test_parse_key_name() {
+        let tests = vec![
+            ("a", Some(KeyCode::KeyA)),
+            ("K", Some(KeyCode::KeyK)),
+            ("5", Some(KeyCode::Digit5)),
+            ("f1", Some(KeyCode::F1)),
+            ("F12", Some(KeyCode::F12)),
+            ("space", Some(KeyCode::Space)),
+            ("PgUp", Some(KeyCode::PageUp)),
+            ("up", Some(KeyCode::ArrowUp)),
+            ("kp7", Some(KeyCode::Numpad7)),
+            ("/", Some(KeyCode::Slash)),
+            ("", None),
+            ("f13", None),
+            ("banana", None),
+        ];
+
+        for (name, expected) in tests {
+            assert_eq!(parse_key_name(name), expected, "key name: {:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_bind_and_get() {
+        let mut binds = BindRegistry::default();
+        binds.bind("F5", "savecvars").unwrap();
+
+        let bind = binds.get(KeyCode::F5).unwrap();
+        assert_eq!(bind.key_name, "f5");
+        assert_eq!(bind.command, "savecvars");
+
+        // Rebinding replaces the existing command
+        binds.bind("f5", "echo saved").unwrap();
+        assert_eq!(binds.get(KeyCode::F5).unwrap().command, "echo saved");
+        assert_eq!(binds.list().len(), 1);
+    }
+
+    #[test]
+    fn test_bind_rejects_unknown_and_console_keys() {
+        let mut binds = BindRegistry::default();
+        assert!(binds.bind("banana", "echo hi").is_err());
+        assert!(binds.bind("`", "echo hi").is_err());
+        assert!(binds.list().is_empty());
+    }
+
+    #[test]
+    fn test_unbind() {
+        let mut binds = BindRegistry::default();
+        binds.bind("k", "echo hi").unwrap();
+
+        assert_eq!(binds.unbind("K"), Ok(true));
+        assert!(binds.get(KeyCode::KeyK).is_none());
+        assert_eq!(binds.unbind("k"), Ok(false));
+        assert!(binds.unbind("banana").is_err());
+    }
+
+    #[test]
+    fn test_bind_commands() {
+        let mut binds = BindRegistry::default();
+
+        assert_eq!(
+            cmd_bind(&["bind", "k"], &mut binds),
+            ScriptOutput::warning("usage: bind <key> <command>")
+        );
+        assert_eq!(
+            cmd_bind(&["bind", "K", "echo hello"], &mut binds),
+            ScriptOutput::info(r#"k = "echo hello""#)
+        );
+        assert_eq!(
+            cmd_bind(&["bind", "f1", "add_gold", "10"], &mut binds),
+            ScriptOutput::info(r#"f1 = "add_gold 10""#)
+        );
+        assert_eq!(
+            cmd_bindlist(&["bindlist"], &mut binds),
+            ScriptOutput::info("2 bindings:\n  f1 = \"add_gold 10\"\n  k = \"echo hello\"")
+        );
+        assert_eq!(
+            cmd_unbind(&["unbind", "k"], &mut binds),
+            ScriptOutput::info("Unbound key: k")
+        );
+        assert_eq!(
+            cmd_unbind(&["unbind", "k"], &mut binds),
+            ScriptOutput::warning("Key is not bound: k")
+        );
+        assert_eq!(
+            cmd_unbind(&["unbind", "f1"], &mut binds),
+            ScriptOutput::info("Unbound key: f1")
+        );
+        assert_eq!(
+            cmd_bindlist(&["bindlist"], &mut binds),
+            ScriptOutput::info("No keys bound")
+        );
+    }
+
+    fn make_bind_app(binds: BindRegistry) -> App {
+        let mut app = App::new();
+        app.add_message::<ConsoleCommand>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ConsoleState>()
+            .init_resource::<AliasRegistry>()
+            .insert_resource(binds)
+            .add_systems(Update, update_key_binds);
+        app
+    }
+
+    fn sent_commands(app: &App) -> Vec<ConsoleCommand> {
+        let messages = app.world().resource::<Messages<ConsoleCommand>>();
+        messages.get_cursor().read(messages).cloned().collect()
+    }
+
+    #[test]
+    fn test_pressed_bind_sends_console_command() {
+        let mut binds = BindRegistry::default();
+        cmd_bind(&["bind", "k", "echo", "hi"], &mut binds);
+        let mut app = make_bind_app(binds);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyK);
+        app.update();
+
+        assert_eq!(sent_commands(&app), vec![ConsoleCommand::new("echo hi")]);
+    }
+
+    #[test]
+    fn test_pressed_bind_expands_alias() {
+        let mut binds = BindRegistry::default();
+        cmd_bind(&["bind", "k", "fix"], &mut binds);
+        let mut app = make_bind_app(binds);
+        app.world_mut()
+            .resource_mut::<AliasRegistry>()
+            .set("fix", "delwall")
+            .unwrap();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyK);
+        app.update();
+
+        // Sent expanded so the console-only delwall command runs
+        assert_eq!(sent_commands(&app), vec![ConsoleCommand::new("delwall")]);
+    }
+
+    #[test]
+    fn test_bind_ignored_while_console_open() {
+        let mut binds = BindRegistry::default();
+        cmd_bind(&["bind", "k", "echo", "hi"], &mut binds);
+        let mut app = make_bind_app(binds);
+
+        app.world_mut().resource_mut::<ConsoleState>().visible = true;
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyK);
+        app.update();
+
+        assert!(sent_commands(&app).is_empty());
+    }
+}
@@ -1,20 +1,42 @@
+mod aliases;
 mod cmd_add_gold;
 mod cmd_add_stamina;
+mod cmd_alias;
+mod cmd_bind;
 mod cmd_do_damage;
+mod cmd_echo;
 mod cmd_getvar;
+mod cmd_help;
 mod cmd_listvars;
+mod cmd_map;
 mod cmd_quit;
 mod cmd_savecvars;
 mod cmd_setvar;
+mod cmd_wall;
 mod cvars;
+mod key_binds;
 mod process_script;
+mod script_output;
 mod scripting_plugin;
 
+#[cfg(test)]
+mod cmd_map_test;
 #[cfg(test)]
 mod cmd_setvar_test;
 #[cfg(test)]
+mod cmd_wall_test;
+#[cfg(test)]
 mod cvars_test;
+#[cfg(test)]
+mod key_binds_test;
 
+pub use aliases::*;
+pub use cmd_map::{MapCommandContext, process_map_command};
+#[cfg(test)]
+pub use cmd_setvar::cmd_setvar_worker;
+pub use cmd_wall::{WallCommandContext, process_wall_command};
 pub use cvars::*;
+pub use key_binds::*;
 pub use process_script::*;
+pub use script_output::*;
 pub use scripting_plugin::ScriptingPlugin;
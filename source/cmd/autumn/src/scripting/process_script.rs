@@ -1,31 +1,86 @@
+use super::aliases::AliasRegistry;
 use super::cvars::CVarRegistry;
+use super::key_binds::BindRegistry;
+use super::script_output::ScriptOutput;
 use crate::actor::Actor;
 use crate::hud::PlayerStats;
 use bevy::prelude::*;
 
 use super::cmd_add_gold::cmd_add_gold;
 use super::cmd_add_stamina::cmd_add_stamina;
+use super::cmd_alias::{cmd_alias, cmd_unalias};
+use super::cmd_bind::{cmd_bind, cmd_bindlist, cmd_unbind};
 use super::cmd_do_damage::cmd_do_damage;
+use super::cmd_echo::cmd_echo;
 use super::cmd_getvar::cmd_getvar;
+use super::cmd_help::cmd_help;
 use super::cmd_listvars::cmd_listvars;
 use super::cmd_quit::cmd_quit;
 use super::cmd_savecvars::cmd_savecvars;
 use super::cmd_setvar::cmd_setvar;
 
+/// Names of all built-in commands, in alphabetical order
+pub const COMMAND_NAMES: &[&str] = &[
+    "add_gold",
+    "add_stamina",
+    "addwall",
+    "alias",
+    "bind",
+    "bindlist",
+    "clear",
+    "delwall",
+    "do_damage",
+    "echo",
+    "getvar",
+    "help",
+    "listvars",
+    "map",
+    "quit",
+    "repair",
+    "savecvars",
+    "setvar",
+    "unalias",
+    "unbind",
+    "use_item",
+    "weapon_dump",
+    "weapon_reset",
+];
+
+/// Commands that need entities, game state or the console itself and so are
+/// only run by the console, never by `process_script`
+pub const CONSOLE_COMMAND_NAMES: &[&str] = &[
+    "addwall",
+    "clear",
+    "delwall",
+    "map",
+    "repair",
+    "use_item",
+    "weapon_dump",
+    "weapon_reset",
+];
+
+/// Maximum number of nested alias expansions before giving up. This guards
+/// against aliases that (directly or indirectly) refer to themselves.
+pub(super) const MAX_ALIAS_DEPTH: usize = 16;
+
 pub fn process_script(
     script: &str,
     stats: &mut ResMut<PlayerStats>,
     cvars: &mut ResMut<CVarRegistry>,
-) -> Vec<String> {
-    process_script_with_actor(script, stats, cvars, None)
+    aliases: &mut ResMut<AliasRegistry>,
+    binds: &mut ResMut<BindRegistry>,
+) -> Vec<ScriptOutput> {
+    process_script_with_actor(script, stats, cvars, aliases, binds, None)
 }
 
 pub fn process_script_with_actor(
     script: &str,
     stats: &mut ResMut<PlayerStats>,
     cvars: &mut ResMut<CVarRegistry>,
+    aliases: &mut ResMut<AliasRegistry>,
+    binds: &mut ResMut<BindRegistry>,
     mut actor: Option<&mut Actor>,
-) -> Vec<String> {
+) -> Vec<ScriptOutput> {
     let mut output = Vec::new();
 
     for line in script.lines() {
@@ -38,7 +93,16 @@ pub fn process_script_with_actor(
             continue;
         }
 
-        let tokens = tokenize_command(trimmed);
+        // Expand aliases, passing along the rest of the line
+        let expanded = match aliases.expand(trimmed) {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                output.push(ScriptOutput::error(e));
+                continue;
+            }
+        };
+
+        let tokens = tokenize_command(&expanded);
         if tokens.is_empty() {
             continue;
         }
@@ -50,19 +114,34 @@ pub fn process_script_with_actor(
         let command_output = match tokens[0] {
             "setvar" => cmd_setvar(&tokens, stats, cvars),
             "getvar" => cmd_getvar(&tokens, stats, cvars),
+            "help" => cmd_help(&tokens, cvars),
             "listvars" => cmd_listvars(&tokens, stats, cvars),
             "savecvars" => cmd_savecvars(&tokens, stats, cvars),
             "add_gold" => cmd_add_gold(&tokens, stats, cvars),
             "add_stamina" => cmd_add_stamina(&tokens, stats, cvars),
             "quit" => cmd_quit(&tokens, stats, cvars),
+            "echo" => cmd_echo(&tokens),
+            "alias" => cmd_alias(&tokens, aliases),
+            "unalias" => cmd_unalias(&tokens, aliases),
+            "bind" => cmd_bind(&tokens, binds),
+            "unbind" => cmd_unbind(&tokens, binds),
+            "bindlist" => cmd_bindlist(&tokens, binds),
+            // The wall commands spawn and despawn entities, repair needs the
+            // weapon entities, weapon_dump/weapon_reset the weapon
+            // definitions, map the game state and clear the console log,
+            // which scripts don't have access to. The console runs them from
+            // their own systems.
+            name if CONSOLE_COMMAND_NAMES.contains(&name) => {
+                ScriptOutput::error(format!("{} can only be used from the console", tokens[0]))
+            }
             "do_damage" => {
                 if let Some(ref mut actor_ref) = actor {
                     cmd_do_damage(&tokens, actor_ref)
                 } else {
-                    "do_damage can only be used on actors".to_string()
+                    ScriptOutput::error("do_damage can only be used on actors")
                 }
             }
-            _ => format!("Unknown command: {}", tokens.join(" ")),
+            _ => ScriptOutput::error(format!("Unknown command: {}", tokens.join(" "))),
         };
 
         output.push(command_output);
@@ -140,7 +219,179 @@ fn tokenize_command(line: &str) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::tokenize_command;
+    use super::{process_script, tokenize_command};
+    use crate::hud::PlayerStats;
+    use crate::scripting::{AliasRegistry, BindRegistry, CVarRegistry, ScriptOutput, Severity};
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::prelude::*;
+
+    fn make_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<PlayerStats>();
+        world.init_resource::<CVarRegistry>();
+        world.init_resource::<AliasRegistry>();
+        world.init_resource::<BindRegistry>();
+        world
+    }
+
+    fn run_script_output(world: &mut World, script: &'static str) -> Vec<ScriptOutput> {
+        world
+            .run_system_once(
+                move |mut stats: ResMut<PlayerStats>,
+                      mut cvars: ResMut<CVarRegistry>,
+                      mut aliases: ResMut<AliasRegistry>,
+                      mut binds: ResMut<BindRegistry>| {
+                    process_script(script, &mut stats, &mut cvars, &mut aliases, &mut binds)
+                },
+            )
+            .unwrap()
+    }
+
+    fn run_script_in(world: &mut World, script: &'static str) -> Vec<String> {
+        run_script_output(world, script)
+            .into_iter()
+            .map(|line| line.text)
+            .collect()
+    }
+
+    fn run_script(script: &'static str) -> Vec<String> {
+        run_script_in(&mut make_world(), script)
+    }
+
+    #[test]
+    fn test_process_script_echo() {
+        let output = run_script(r#"echo hello   "big world" 42"#);
+        assert_eq!(output, vec!["hello big world 42"]);
+    }
+
+    #[test]
+    fn test_process_script_echo_no_arguments() {
+        let output = run_script("echo");
+        assert_eq!(output, vec![""]);
+    }
+
+    #[test]
+    fn test_process_script_severity() {
+        let mut world = make_world();
+
+        let output = run_script_output(&mut world, "echo fine");
+        assert_eq!(output[0].severity, Severity::Info);
+
+        // Severity comes from the command, not from what the text says
+        let output = run_script_output(&mut world, "echo file not found");
+        assert_eq!(output[0].severity, Severity::Info);
+
+        let output = run_script_output(&mut world, "bogus_command");
+        assert_eq!(output[0].severity, Severity::Error);
+        assert_eq!(output[0].text, "Unknown command: bogus_command");
+
+        let output = run_script_output(&mut world, "getvar no.such.var");
+        assert_eq!(output[0].severity, Severity::Error);
+
+        let output = run_script_output(&mut world, "add_gold");
+        assert_eq!(output[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_process_script_alias_definition() {
+        let mut world = make_world();
+        let output = run_script_in(&mut world, r#"alias greet "echo hello""#);
+        assert_eq!(output, vec![r#"greet = "echo hello""#]);
+        assert_eq!(
+            world.resource::<AliasRegistry>().get("greet"),
+            Some(&"echo hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_process_script_alias_expansion() {
+        let mut world = make_world();
+        run_script_in(&mut world, r#"alias greet "echo hello""#);
+
+        // Extra arguments are appended to the expanded command
+        let output = run_script_in(&mut world, "greet world");
+        assert_eq!(output, vec!["hello world"]);
+
+        // Aliases can refer to other aliases
+        run_script_in(&mut world, "alias greet2 greet");
+        let output = run_script_in(&mut world, "greet2");
+        assert_eq!(output, vec!["hello"]);
+    }
+
+    #[test]
+    fn test_process_script_alias_recursion_is_limited() {
+        let mut world = make_world();
+        run_script_in(&mut world, "alias ping pong");
+        run_script_in(&mut world, "alias pong ping");
+
+        let output = run_script_in(&mut world, "ping");
+        assert_eq!(output.len(), 1);
+        assert!(output[0].starts_with("Alias recursion limit reached"));
+    }
+
+    #[test]
+    fn test_alias_expand() {
+        let mut aliases = AliasRegistry::default();
+        aliases.set("build", "addwall 3").unwrap();
+        aliases.set("b", "build").unwrap();
+        aliases.set("say", r#"echo "hello  world""#).unwrap();
+
+        assert_eq!(aliases.expand("b 4"), Ok("addwall 3 4".to_string()));
+        assert_eq!(aliases.expand("  build  "), Ok("addwall 3".to_string()));
+        assert_eq!(aliases.expand("echo build"), Ok("echo build".to_string()));
+
+        // The rest of the line is passed along as typed, quotes included
+        assert_eq!(
+            aliases.expand(r#"say "to  you""#),
+            Ok(r#"echo "hello  world" "to  you""#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_alias_expand_loop_is_an_error() {
+        let mut aliases = AliasRegistry::default();
+        aliases.set("ping", "pong").unwrap();
+        aliases.set("pong", "ping").unwrap();
+        aliases.set("self", "self").unwrap();
+
+        // Whichever alias the limit stops on, the loop is reported
+        for name in ["ping", "pong", "self"] {
+            let result = aliases.expand(name);
+            assert!(result.unwrap_err().starts_with("Alias recursion limit reached"));
+        }
+    }
+
+    #[test]
+    fn test_process_script_alias_keeps_quoting() {
+        let mut world = make_world();
+        run_script_in(&mut world, "alias say echo");
+
+        let output = run_script_in(&mut world, r#"say "big  world""#);
+        assert_eq!(output, vec!["big  world"]);
+    }
+
+    #[test]
+    fn test_process_script_alias_cannot_shadow_builtin() {
+        let mut world = make_world();
+        let output = run_script_in(&mut world, "alias echo quit");
+        assert_eq!(output, vec!["Cannot alias built-in command: echo"]);
+        assert!(!world.resource::<AliasRegistry>().exists("echo"));
+    }
+
+    #[test]
+    fn test_process_script_unalias() {
+        let mut world = make_world();
+        run_script_in(&mut world, r#"alias greet "echo hello""#);
+
+        let output = run_script_in(&mut world, "unalias greet");
+        assert_eq!(output, vec!["Removed alias: greet"]);
+
+        let output = run_script_in(&mut world, "greet");
+        assert_eq!(output, vec!["Unknown command: greet"]);
+
+        let output = run_script_in(&mut world, "unalias greet");
+        assert_eq!(output, vec!["Alias not found: greet"]);
+    }
 
     #[test]
     fn test_tokenize_simple_command() {
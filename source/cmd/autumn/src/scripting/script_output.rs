@@ -0,0 +1,43 @@
+/// Severity of a line of script or console output, used to color-code the
+/// console log
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Command, // Echo of a command entered in the console
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single line of output produced by running a script command
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptOutput {
+    pub severity: Severity,
+    pub text: String,
+}
+
+impl ScriptOutput {
+    pub fn new(severity: Severity, text: impl Into<String>) -> Self {
+        Self {
+            severity,
+            text: text.into(),
+        }
+    }
+
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(Severity::Info, text)
+    }
+
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, text)
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(Severity::Error, text)
+    }
+}
+
+impl std::fmt::Display for ScriptOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
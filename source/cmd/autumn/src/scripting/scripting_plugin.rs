@@ -1,5 +1,17 @@
-use super::cvars::CVarRegistry;
+use super::aliases::AliasRegistry;
+use super::cvars::{CVarRegistry, CVarValue};
+use super::key_binds::{BindRegistry, DEFAULT_BINDS};
+use crate::camera::{init_camera_shake_cvars, init_dodge_cvars, init_sprint_cvars};
+use crate::combat::{init_combo_cvars, init_visual_feedback_cvars};
+use crate::console::{ConsoleCommand, ConsoleState, init_console_cvars};
+use crate::game_state::GameState;
+use crate::hud::{init_damage_indicator_cvars, init_fps_overlay_cvars, init_stamina_cvars};
+use crate::menu::init_menu_background_cvars;
+use crate::rendering::{init_actor_label_cvars, init_billboard_cvars, init_culling_cvars};
+use crate::weapon::init_durability_cvars;
+use crate::world::DEFAULT_SURFACE_TEXTURE;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 pub struct ScriptingPlugin;
 
@@ -7,26 +19,166 @@ impl Plugin for ScriptingPlugin {
     fn build(&self, app: &mut App) {
         app //
             .init_resource::<CVarRegistry>()
+            .init_resource::<AliasRegistry>()
+            .init_resource::<BindRegistry>()
             .add_systems(
                 PostStartup,
-                (init_camera_cvars, save_cvars_on_startup).chain(),
-            );
+                (
+                    init_camera_cvars,
+                    init_combat_cvars,
+                    init_player_cvars,
+                    init_render_cvars,
+                    init_cheat_cvars,
+                    save_cvars_on_startup,
+                )
+                    .chain(),
+            )
+            .add_systems(PostStartup, init_default_binds)
+            .add_systems(
+                Update,
+                update_key_binds.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, update_resolution_cvar);
     }
 }
 
 /// Initialize camera-related CVars with default values
 fn init_camera_cvars(mut cvars: ResMut<CVarRegistry>) {
-    // Mouse sensitivity (radians per pixel of mouse movement)
-    cvars.init_f32("mouse.sensitivity", 0.0005);
+    let camera_cvars = [
+        (
+            "mouse.sensitivity",
+            CVarValue::F32(0.0005),
+            "Mouse look speed in radians per pixel of mouse movement",
+        ),
+        (
+            "arrow_sensitivity",
+            CVarValue::F32(2.75),
+            "Arrow key turn speed in radians per second",
+        ),
+        (
+            "mouse.smooth",
+            CVarValue::Bool(true),
+            "Smooth out mouse look movement",
+        ),
+        (
+            "mouse.invert_y",
+            CVarValue::Bool(true),
+            "Invert the vertical mouse look axis",
+        ),
+        (
+            "gamepad.sensitivity",
+            CVarValue::F32(2.5),
+            "Right stick turn speed in radians per second at full deflection",
+        ),
+        (
+            "gamepad.deadzone",
+            CVarValue::F32(0.15),
+            "Fraction of stick deflection that is ignored",
+        ),
+    ];
+    for (name, value, help) in camera_cvars {
+        cvars.init_with_help(name, value, help).unwrap();
+    }
+}
+
+/// Initialize combat feedback CVars with default values
+fn init_combat_cvars(mut cvars: ResMut<CVarRegistry>) {
+    // Camera shake presets for combat hits
+    init_camera_shake_cvars(&mut cvars);
+
+    // Blood particle pool size
+    init_visual_feedback_cvars(&mut cvars);
+
+    // Combo window and per-step damage bonus
+    init_combo_cvars(&mut cvars);
+
+    // Weapon wear per successful hit
+    init_durability_cvars(&mut cvars);
+
+    // On-screen arrow toward whoever hit the player
+    init_damage_indicator_cvars(&mut cvars);
+}
+
+/// Initialize player stamina, sprint, dodge and regeneration CVars with default values
+fn init_player_cvars(mut cvars: ResMut<CVarRegistry>) {
+    init_stamina_cvars(&mut cvars);
+    init_sprint_cvars(&mut cvars);
+    init_dodge_cvars(&mut cvars);
+}
+
+/// Initialize cheat CVars. These always start off and are never saved.
+fn init_cheat_cvars(mut cvars: ResMut<CVarRegistry>) {
+    // Walk through walls and out of the map
+    cvars.init_cheat_bool("noclip", false);
+
+    // Ignore all damage from actors
+    cvars.init_cheat_bool("god", false);
+}
+
+/// Initialize rendering CVars with default values
+fn init_render_cvars(mut cvars: ResMut<CVarRegistry>) {
+    // Billboard alpha cutoff (0 switches billboards to alpha blending)
+    init_billboard_cvars(&mut cvars);
 
-    // Arrow key sensitivity (radians per second)
-    cvars.init_f32("arrow_sensitivity", 2.75);
+    // Hide billboards and pause actors far from the camera (0 disables)
+    init_culling_cvars(&mut cvars);
 
-    // Mouse smooth mode
-    cvars.init_bool("mouse.smooth", true);
+    // Name and health labels over actors
+    init_actor_label_cvars(&mut cvars);
 
-    // Mouse invert Y axis
-    cvars.init_bool("mouse.invert_y", true);
+    // Frame rate readout
+    init_fps_overlay_cvars(&mut cvars);
+
+    // Animated main menu background
+    init_menu_background_cvars(&mut cvars);
+
+    // Console scrollback length
+    init_console_cvars(&mut cvars);
+
+    // Map floor and ceiling surfaces
+    cvars.init_bool("r_ceiling", true);
+    cvars
+        .init(
+            "r_floor_texture",
+            CVarValue::String(DEFAULT_SURFACE_TEXTURE.to_string()),
+        )
+        .unwrap();
+    cvars
+        .init(
+            "r_ceiling_texture",
+            CVarValue::String(DEFAULT_SURFACE_TEXTURE.to_string()),
+        )
+        .unwrap();
+
+    // Window size, kept up to date by `update_resolution_cvar`
+    cvars
+        .init_read_only("r_resolution", CVarValue::String(String::new()))
+        .unwrap();
+}
+
+/// Keep the read-only `r_resolution` cvar in step with the window size
+fn update_resolution_cvar(
+    windows: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut cvars: ResMut<CVarRegistry>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let resolution = format!(
+        "{}x{}",
+        window.resolution.physical_width(),
+        window.resolution.physical_height()
+    );
+    if let Err(e) = cvars.set_internal("r_resolution", CVarValue::String(resolution)) {
+        warn!("{}", e);
+    }
+}
+
+/// Bind the keys in `DEFAULT_BINDS`; `bind` can still change them later
+fn init_default_binds(mut binds: ResMut<BindRegistry>) {
+    for (key_name, command) in DEFAULT_BINDS {
+        binds.bind(key_name, command).unwrap();
+    }
 }
 
 fn save_cvars_on_startup(cvars: Res<CVarRegistry>) {
@@ -36,3 +188,24 @@ fn save_cvars_on_startup(cvars: Res<CVarRegistry>) {
         println!("CVars saved to data/cvars.yaml");
     }
 }
+
+/// Send the commands bound to any keys pressed this frame to the console, so
+/// their output lands in the console log like typed commands
+pub(super) fn update_key_binds(
+    input: Res<ButtonInput<KeyCode>>,
+    console_state: Res<ConsoleState>,
+    binds: Res<BindRegistry>,
+    aliases: Res<AliasRegistry>,
+    mut command_writer: MessageWriter<ConsoleCommand>,
+) {
+    // Keys typed into the console should not trigger bindings
+    if console_state.visible {
+        return;
+    }
+
+    for (key_code, bind) in binds.iter() {
+        if input.just_pressed(*key_code) {
+            command_writer.write(ConsoleCommand::expanded(&bind.command, &aliases));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use crate::combat::AttackState;
+use crate::combat::{AttackState, ComboState};
+use super::durability::Durability;
 
 /// Component for weapon sprite attached to camera
 #[derive(Component)]
@@ -15,6 +16,53 @@ pub struct WeaponSprite {
 
     /// Currently equipped weapon type
     pub weapon_type: String,
+
+    /// Chained attack progress
+    pub combo: ComboState,
+
+    /// Remaining durability, or None if the weapon never breaks
+    pub durability: Option<Durability>,
+
+    /// Durability of weapon types that are not equipped, so switching away
+    /// and back doesn't repair them
+    pub stored_durability: std::collections::HashMap<String, Durability>,
+}
+
+impl WeaponSprite {
+    /// A broken weapon is hidden and cannot attack until repaired
+    pub fn is_broken(&self) -> bool {
+        self.durability.is_some_and(|d| d.is_broken())
+    }
+
+    /// Restore the equipped weapon and every stored one to full durability.
+    /// Returns how many breakable weapons were repaired.
+    pub fn repair_all(&mut self) -> usize {
+        let stored = self.stored_durability.values_mut();
+        let mut repaired = 0;
+        for durability in self.durability.iter_mut().chain(stored) {
+            durability.repair();
+            repaired += 1;
+        }
+        repaired
+    }
+
+    /// Switch to another weapon type, cancelling any attack in progress.
+    /// `max_durability` is used the first time a breakable type is equipped.
+    pub fn equip(&mut self, weapon_type: &str, max_durability: Option<f32>) {
+        if let Some(durability) = self.durability.take() {
+            self.stored_durability
+                .insert(self.weapon_type.clone(), durability);
+        }
+        self.durability = self
+            .stored_durability
+            .remove(weapon_type)
+            .or(max_durability.map(Durability::new));
+        self.weapon_type = weapon_type.to_string();
+        self.attack_state = AttackState::Idle;
+        self.charge_progress = 0.0;
+        self.hit_entities.clear();
+        self.combo = ComboState::default();
+    }
 }
 
 impl Default for WeaponSprite {
@@ -24,6 +72,9 @@ impl Default for WeaponSprite {
             charge_progress: 0.0,
             hit_entities: std::collections::HashSet::new(),
             weapon_type: "sword".to_string(), // Default weapon
+            combo: ComboState::default(),
+            durability: None,
+            stored_durability: std::collections::HashMap::new(),
         }
     }
 }
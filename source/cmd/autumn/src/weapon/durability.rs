@@ -0,0 +1,65 @@
+use super::components::WeaponSprite;
+use crate::scripting::{CVarRegistry, ScriptOutput};
+use bevy::prelude::*;
+
+/// Durability lost per successful hit when `weapon.durability_wear` is unset
+pub const DEFAULT_DURABILITY_WEAR: f32 = 1.0;
+
+/// Register durability CVars with their default values
+pub fn init_durability_cvars(cvars: &mut CVarRegistry) {
+    cvars.init_f32("weapon.durability_wear", DEFAULT_DURABILITY_WEAR);
+}
+
+/// Current wear on a breakable weapon
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Durability {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Durability {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Apply the wear from one successful hit.
+    ///
+    /// Returns true only on the hit that breaks the weapon.
+    pub fn wear(&mut self, amount: f32) -> bool {
+        if self.is_broken() {
+            return false;
+        }
+        self.current = (self.current - amount).max(0.0);
+        self.is_broken()
+    }
+
+    pub fn repair(&mut self) {
+        self.current = self.max;
+    }
+}
+
+/// Handle the console-only `repair` command, which restores every breakable
+/// weapon to full durability, including the ones not currently equipped. Returns None for any other command.
+pub fn process_repair_command(
+    script: &str,
+    weapons: &mut Query<&mut WeaponSprite>,
+) -> Option<Vec<ScriptOutput>> {
+    if script.split_whitespace().next() != Some("repair") {
+        return None;
+    }
+
+    let repaired: usize = weapons
+        .iter_mut()
+        .map(|mut weapon| weapon.repair_all())
+        .sum();
+
+    let text = match repaired {
+        0 => "Nothing to repair".to_string(),
+        _ => format!("Repaired {} weapon(s)", repaired),
+    };
+    Some(vec![ScriptOutput::info(text)])
+}
@@ -0,0 +1,97 @@
+use super::components::WeaponSprite;
+use super::durability::Durability;
+
+#[test]
+fn test_new_weapon_is_intact() {
+    let durability = Durability::new(10.0);
+    assert_eq!(durability.current, 10.0);
+    assert!(!durability.is_broken());
+}
+
+#[test]
+fn test_wear_decrements_per_hit() {
+    let mut durability = Durability::new(10.0);
+    assert!(!durability.wear(2.5));
+    assert!(!durability.wear(2.5));
+    assert_eq!(durability.current, 5.0);
+}
+
+#[test]
+fn test_breaks_when_reaching_zero() {
+    let mut durability = Durability::new(3.0);
+    assert!(!durability.wear(1.0));
+    assert!(!durability.wear(1.0));
+    assert!(durability.wear(1.0));
+    assert!(durability.is_broken());
+}
+
+#[test]
+fn test_wear_clamps_at_zero() {
+    let mut durability = Durability::new(1.0);
+    assert!(durability.wear(5.0));
+    assert_eq!(durability.current, 0.0);
+}
+
+#[test]
+fn test_broken_weapon_only_breaks_once() {
+    let mut durability = Durability::new(1.0);
+    assert!(durability.wear(1.0));
+    assert!(!durability.wear(1.0));
+    assert!(durability.is_broken());
+}
+
+#[test]
+fn test_zero_wear_never_breaks() {
+    let mut durability = Durability::new(1.0);
+    for _ in 0..100 {
+        assert!(!durability.wear(0.0));
+    }
+    assert_eq!(durability.current, 1.0);
+}
+
+#[test]
+fn test_repair_restores_max() {
+    let mut durability = Durability::new(4.0);
+    durability.wear(4.0);
+    durability.repair();
+    assert_eq!(durability.current, 4.0);
+    assert!(!durability.is_broken());
+}
+
+#[test]
+fn test_switching_weapons_keeps_durability_per_type() {
+    let mut weapon = WeaponSprite {
+        durability: Some(Durability::new(10.0)),
+        ..Default::default()
+    };
+    weapon.durability.as_mut().unwrap().wear(4.0);
+
+    weapon.equip("axe", Some(20.0));
+    assert_eq!(weapon.weapon_type, "axe");
+    assert_eq!(weapon.durability, Some(Durability::new(20.0)));
+
+    // The sword comes back as worn as it was left
+    weapon.equip("sword", Some(10.0));
+    assert_eq!(weapon.durability.unwrap().current, 6.0);
+
+    // Unbreakable weapons have no durability
+    weapon.equip("spear", None);
+    assert_eq!(weapon.durability, None);
+}
+
+#[test]
+fn test_repair_restores_stored_weapons() {
+    let mut weapon = WeaponSprite {
+        durability: Some(Durability::new(10.0)),
+        ..Default::default()
+    };
+    weapon.durability.as_mut().unwrap().wear(10.0);
+    weapon.equip("axe", Some(20.0));
+
+    assert_eq!(weapon.repair_all(), 2);
+
+    // The sword was repaired while put away
+    weapon.equip("sword", Some(10.0));
+    assert!(!weapon.is_broken());
+    assert_eq!(weapon.durability.unwrap().current, 10.0);
+}
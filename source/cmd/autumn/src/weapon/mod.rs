@@ -1,8 +1,16 @@
 mod components;
+mod durability;
+#[cfg(test)]
+mod durability_test;
 mod easing;
 mod plugin;
 mod spawn;
 mod systems;
+mod targeting;
+#[cfg(test)]
+mod targeting_test;
 
+pub use components::WeaponSprite;
+pub use durability::{init_durability_cvars, process_repair_command};
 pub use plugin::WeaponPlugin;
 pub use spawn::spawn_weapon_sprite;
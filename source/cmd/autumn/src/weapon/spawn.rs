@@ -6,6 +6,7 @@ use crate::game_state::GamePlayEntity;
 use crate::scripting::CVarRegistry;
 use crate::rendering::load_weapon_texture;
 use super::components::WeaponSprite;
+use super::durability::Durability;
 
 /// Spawn weapon sprite as child of camera entity
 pub fn spawn_weapon_sprite(
@@ -66,7 +67,10 @@ pub fn spawn_weapon_sprite(
             Mesh3d(meshes.add(weapon_mesh)),
             MeshMaterial3d(sprite_material),
             Transform::from_translation(rest_pos),
-            WeaponSprite::default(),
+            WeaponSprite {
+                durability: weapon_def.max_durability.map(Durability::new),
+                ..default()
+            },
         ))
         .id();
 
@@ -1,17 +1,20 @@
-use bevy::prelude::*;
+use super::components::WeaponSprite;
+use super::durability::DEFAULT_DURABILITY_WEAR;
+use super::easing::{ease_in_out_cubic, ease_out_quad};
+use super::targeting::select_targets;
+use crate::actor::{Actor, ActorSpatialHash};
 use crate::camera::CameraShake;
 use crate::combat::{
-    AttackState, CombatAudio, CombatInput, StateTransition, WeaponDefinitions,
-    apply_status_effect, spawn_blood_particles, spawn_damage_number,
+    AttackState, BloodParticlePool, CombatAudio, CombatInput, ComboSettings, DamageNumberPool,
+    StateTransition, WeaponDefinitions, apply_status_effect, spawn_blood_particles,
+    spawn_damage_number,
 };
 use crate::console::ConsoleState;
+use crate::hud::Toolbar;
 use crate::item::Item;
 use crate::rendering::Billboard;
-use crate::scripting::CVarRegistry;
-use crate::hud::Toolbar;
-use crate::actor::Actor;
-use super::components::WeaponSprite;
-use super::easing::{ease_in_out_cubic, ease_out_quad};
+use crate::scripting::{CVarRegistry, ScriptOutput};
+use bevy::prelude::*;
 
 /// System to update weapon swing animation and state
 pub fn update_weapon_swing(
@@ -26,36 +29,66 @@ pub fn update_weapon_swing(
     combat_audio: Res<CombatAudio>,
     mut weapon_query: Query<(&mut Transform, &mut WeaponSprite, &mut Visibility)>,
     ui_interaction_query: Query<&Interaction>,
+    gamepads: Query<&Gamepad>,
 ) {
+    let active_weapon = toolbar.active_weapon();
+
     for (mut transform, mut weapon, mut visibility) in weapon_query.iter_mut() {
-        // Only show the weapon sprite when slot 1 is active
-        *visibility = if toolbar.active_slot == 1 {
+        // Swap to the active slot's weapon
+        if let Some(weapon_type) = active_weapon
+            && weapon_type != weapon.weapon_type
+        {
+            let max_durability = weapon_definitions
+                .get_with_cvars(weapon_type, &cvars)
+                .and_then(|def| def.max_durability);
+            weapon.equip(weapon_type, max_durability);
+        }
+
+        // Only show the weapon sprite when the active slot holds a weapon
+        // and it isn't broken
+        let equipped = active_weapon.is_some();
+        let broken = weapon.is_broken();
+        *visibility = if equipped && !broken {
             Visibility::Visible
         } else {
             Visibility::Hidden
         };
 
-        // Get weapon definition with current CVar values
-        let Some(weapon_def) = weapon_definitions.get_with_cvars(&weapon.weapon_type, &cvars)
+        // Get weapon definition with current CVar values, posed for the
+        // current step of the combo
+        let Some(mut weapon_def) = weapon_definitions.get_with_cvars(&weapon.weapon_type, &cvars)
         else {
             continue;
         };
-
-        // Build combat input state
+        weapon_def.apply_combo_keyframes(weapon.combo.depth);
+
+        // Build combat input state (the right trigger attacks on gamepads)
+        let gamepad_attack_pressed = gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::RightTrigger2));
+        let gamepad_attack_held = gamepads
+            .iter()
+            .any(|gamepad| gamepad.pressed(GamepadButton::RightTrigger2));
         let input = CombatInput {
             attack_pressed: (mouse_button.just_pressed(MouseButton::Left)
-                || keyboard.just_pressed(KeyCode::Space))
-                && toolbar.active_slot == 1
+                || keyboard.just_pressed(KeyCode::Space)
+                || gamepad_attack_pressed)
+                && equipped
+                && !broken
                 && !console_state.visible
                 && !ui_interaction_query.iter().any(|i| *i != Interaction::None),
             attack_held: (mouse_button.pressed(MouseButton::Left)
-                || keyboard.pressed(KeyCode::Space))
-                && toolbar.active_slot == 1
+                || keyboard.pressed(KeyCode::Space)
+                || gamepad_attack_held)
+                && equipped
+                && !broken
                 && !console_state.visible,
         };
 
         // Handle charging when idle
         if matches!(weapon.attack_state, AttackState::Idle) {
+            weapon.combo.tick(time.delta_secs());
+
             if input.attack_held {
                 weapon.charge_progress += time.delta_secs();
                 weapon.charge_progress = weapon.charge_progress.min(weapon_def.max_charge_time);
@@ -81,12 +114,17 @@ pub fn update_weapon_swing(
                 // Play swing sound when starting a new attack
                 if matches!(new_state, AttackState::Windup { .. }) {
                     combat_audio.play_swing_sound(&mut commands);
+                    weapon.combo.start_attack();
                 }
 
-                // Clear hit list when returning to idle
+                // Clear hit list when returning to idle and give the player a
+                // moment to chain the next attack
                 if matches!(new_state, AttackState::Idle) {
                     weapon.hit_entities.clear();
                     weapon.charge_progress = 0.0;
+                    weapon
+                        .combo
+                        .open_window(ComboSettings::from_cvars(&cvars).window);
                 }
                 weapon.attack_state = new_state;
             }
@@ -164,23 +202,26 @@ pub fn update_weapon_swing(
 /// System to check weapon collision with actors
 pub fn update_weapon_swing_collision(
     mut commands: Commands,
-    camera_query: Query<(Entity, &Transform), With<Camera3d>>,
+    camera_query: Query<(Entity, &Transform, Option<&CameraShake>), With<Camera3d>>,
     mut actor_query: Query<(Entity, &Transform, &mut Actor), (With<Billboard>, Without<Item>)>,
     mut weapon_query: Query<&mut WeaponSprite>,
     weapon_definitions: Res<WeaponDefinitions>,
     cvars: Res<CVarRegistry>,
     asset_server: Res<AssetServer>,
     combat_audio: Res<CombatAudio>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut blood_particle_pool: ResMut<BloodParticlePool>,
+    mut damage_number_pool: ResMut<DamageNumberPool>,
+    spatial_hash: Res<ActorSpatialHash>,
+    mut console_state: ResMut<ConsoleState>,
 ) {
-    let Ok((camera_entity, camera_transform)) = camera_query.single() else {
+    let Ok((camera_entity, camera_transform, active_shake)) = camera_query.single() else {
         return;
     };
 
     for mut weapon in weapon_query.iter_mut() {
-        // Only check collision when the attack state indicates hit detection is active
-        if !weapon.attack_state.is_hit_active() {
+        // Only check collision when the attack state indicates hit detection
+        // is active, and never for a weapon that broke earlier in the swing
+        if !weapon.attack_state.is_hit_active() || weapon.is_broken() {
             continue;
         }
 
@@ -205,8 +246,12 @@ pub fn update_weapon_swing_collision(
         // Calculate right vector perpendicular to forward (for width check)
         let right_xy = Vec2::new(-forward_xy.y, forward_xy.x);
 
-        // Check all actors (excluding items)
-        for (entity, actor_transform, mut actor) in actor_query.iter_mut() {
+        // Find all actors (excluding items) inside the hitbox, looking only
+        // at those near enough to possibly reach it
+        let reach = Vec2::new(check_distance, check_width).length();
+        let nearby = spatial_hash.nearby(camera_pos.truncate(), reach);
+        let mut candidates = Vec::new();
+        for (entity, actor_transform, actor) in actor_query.iter_many(&nearby) {
             // Skip if already hit during this attack
             if weapon.hit_entities.contains(&entity) {
                 continue;
@@ -245,18 +290,54 @@ pub fn update_weapon_swing_collision(
                 continue;
             }
 
+            candidates.push((entity, forward_distance));
+        }
+
+        // Single-target weapons only hit the nearest actors, counting those
+        // already hit earlier in this swing
+        let remaining =
+            (weapon_def.max_targets.max(1) as usize).saturating_sub(weapon.hit_entities.len());
+        for entity in select_targets(&candidates, remaining) {
+            // The hit that breaks the weapon is its last
+            if weapon.is_broken() {
+                break;
+            }
+            let Ok((_, actor_transform, mut actor)) = actor_query.get_mut(entity) else {
+                continue;
+            };
+            let actor_pos = actor_transform.translation;
+
             // Actor is within hitbox - calculate and apply damage
             weapon.hit_entities.insert(entity);
 
+            // Each successful hit wears down breakable weapons
+            let wear = cvars.get_f32_or("weapon.durability_wear", DEFAULT_DURABILITY_WEAR);
+            let broke = weapon
+                .durability
+                .as_mut()
+                .is_some_and(|durability| durability.wear(wear));
+            if broke {
+                console_state.push_message(ScriptOutput::warning(format!(
+                    "Your {} breaks!",
+                    weapon.weapon_type
+                )));
+            }
+
             // Calculate charge ratio (normalized by weapon's max charge time)
             let charge_ratio = (weapon.charge_progress / weapon_def.max_charge_time).min(1.0);
 
-            // Get target resistance based on damage type
-            let resistance = actor.physical_resistance;
-
-            // Calculate damage
-            let damage_result =
-                crate::combat::calculate_damage(&weapon_def, charge_ratio, actor.armor, resistance);
+            // Calculate damage against the target's resistance to the weapon's
+            // damage type, scaled up by the combo depth
+            let mut damage_result = crate::combat::calculate_damage(
+                &weapon_def,
+                charge_ratio,
+                actor.armor,
+                &actor.resistances,
+            );
+            let combo_multiplier = weapon
+                .combo
+                .damage_multiplier(ComboSettings::from_cvars(&cvars).damage_step);
+            damage_result.amount = (damage_result.amount as f32 * combo_multiplier).round() as i32;
 
             // Apply damage
             actor.health -= damage_result.amount as f32;
@@ -266,21 +347,19 @@ pub fn update_weapon_swing_collision(
 
             // Spawn visual feedback
             // Camera shake
-            if damage_result.critical {
-                commands
-                    .entity(camera_entity)
-                    .insert(CameraShake::critical_shake());
+            let shake = if damage_result.critical {
+                CameraShake::critical_shake(&cvars)
             } else {
-                commands
-                    .entity(camera_entity)
-                    .insert(CameraShake::hit_shake());
-            }
+                CameraShake::hit_shake(&cvars)
+            };
+            commands
+                .entity(camera_entity)
+                .insert(shake.replacing(active_shake));
 
             // Blood particles
             spawn_blood_particles(
                 &mut commands,
-                &mut meshes,
-                &mut materials,
+                &mut blood_particle_pool,
                 actor_pos,
                 if damage_result.critical { 10 } else { 5 },
             );
@@ -289,6 +368,7 @@ pub fn update_weapon_swing_collision(
             spawn_damage_number(
                 &mut commands,
                 &asset_server,
+                &mut damage_number_pool,
                 actor_pos,
                 damage_result.amount,
                 damage_result.critical,
@@ -298,12 +378,7 @@ pub fn update_weapon_swing_collision(
             combat_audio.play_hit_sound(&mut commands, damage_result.critical);
 
             // Apply status effect based on damage type
-            apply_status_effect(
-                &mut commands,
-                entity,
-                damage_result.damage_type,
-                &actor.actor_type,
-            );
+            apply_status_effect(&mut commands, entity, damage_result.damage_type);
 
             // Print hit feedback
             if damage_result.critical {
@@ -0,0 +1,13 @@
+/// Pick which actors in the hitbox an attack hits.
+///
+/// Takes each candidate with its distance along the player's forward
+/// direction and returns up to `max_targets` of them, nearest first.
+pub fn select_targets<T: Copy>(candidates: &[(T, f32)], max_targets: usize) -> Vec<T> {
+    let mut sorted = candidates.to_vec();
+    sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+    sorted
+        .into_iter()
+        .take(max_targets)
+        .map(|(target, _)| target)
+        .collect()
+}
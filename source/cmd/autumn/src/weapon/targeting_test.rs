@@ -0,0 +1,31 @@
+use super::targeting::select_targets;
+
+#[test]
+fn test_single_target_hits_nearest() {
+    let candidates = [("far", 6.0), ("near", 1.5), ("middle", 3.0)];
+    assert_eq!(select_targets(&candidates, 1), vec!["near"]);
+}
+
+#[test]
+fn test_cleave_hits_nearest_in_order() {
+    let candidates = [("far", 6.0), ("near", 1.5), ("middle", 3.0)];
+    assert_eq!(select_targets(&candidates, 2), vec!["near", "middle"]);
+}
+
+#[test]
+fn test_limit_above_candidate_count_hits_all() {
+    let candidates = [(1, 4.0), (2, 2.0)];
+    assert_eq!(select_targets(&candidates, 5), vec![2, 1]);
+}
+
+#[test]
+fn test_zero_limit_hits_nothing() {
+    let candidates = [(1, 4.0), (2, 2.0)];
+    assert!(select_targets(&candidates, 0).is_empty());
+}
+
+#[test]
+fn test_no_candidates() {
+    let candidates: [(u32, f32); 0] = [];
+    assert!(select_targets(&candidates, 3).is_empty());
+}
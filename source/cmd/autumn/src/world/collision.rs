@@ -1,7 +1,8 @@
+use super::map::GRID_SIZE;
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-pub const PLAYER_RADIUS: f32 = 8.0 * 0.2;
+pub const PLAYER_RADIUS: f32 = GRID_SIZE * 0.2;
 
 #[derive(Resource)]
 pub struct CollisionMap {
@@ -38,10 +39,10 @@ impl CollisionMap {
 
         // Calculate grid cell range that the bounding box overlaps
         // Check all cells that any part of the box could touch
-        let min_grid_x = (min_x / 8.0).floor() as i32;
-        let max_grid_x = (max_x / 8.0).floor() as i32;
-        let min_grid_y = (min_y / 8.0).floor() as i32;
-        let max_grid_y = (max_y / 8.0).floor() as i32;
+        let min_grid_x = (min_x / GRID_SIZE).floor() as i32;
+        let max_grid_x = (max_x / GRID_SIZE).floor() as i32;
+        let min_grid_y = (min_y / GRID_SIZE).floor() as i32;
+        let max_grid_y = (max_y / GRID_SIZE).floor() as i32;
 
         // Check if any of the cells the bounding box overlaps is solid
         for grid_y in min_grid_y..=max_grid_y {
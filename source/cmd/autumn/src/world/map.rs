@@ -1,18 +1,116 @@
 use bevy::asset::RenderAssetUsages;
+use bevy::math::Affine2;
 use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::actor::{ActorDefinitions, ActorPosition};
+use crate::actor::{ActorDefinitions, ActorPosition, AnimatedBillboard, BehaviorKind};
 use crate::game_state::GamePlayEntity;
 use crate::item::{Item, ItemDefinitions, ItemPosition};
-use crate::rendering::{Billboard, load_image_texture, load_weapon_texture};
+use crate::rendering::{
+    Billboard, BillboardMode, CullExempt, load_image_texture, load_weapon_texture,
+};
 
 /// Grid size for walls (8×8 grid)
-const GRID_SIZE: f32 = 8.0;
+pub const GRID_SIZE: f32 = 8.0;
+
+/// Map loaded when starting a game, and by `map default`
+pub const DEFAULT_MAP_PATH: &str = "data/map.yaml";
+
+/// Directory the `map` console command loads maps from
+pub const MAPS_DIR: &str = "data/maps";
+
+/// Map file the next game is built from
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct CurrentMap(pub PathBuf);
+
+impl Default for CurrentMap {
+    fn default() -> Self {
+        Self(PathBuf::from(DEFAULT_MAP_PATH))
+    }
+}
+
+/// File for a map name given to the `map` command: `default` for the
+/// built-in map, or a path relative to the maps directory with an optional
+/// `.yaml` extension. Names are limited to letters, digits, `-`, `_` and `.`
+/// in `/`-separated parts, so they can't point outside the maps directory.
+pub fn map_path(name: &str) -> Result<PathBuf, String> {
+    if name == "default" {
+        return Ok(PathBuf::from(DEFAULT_MAP_PATH));
+    }
+
+    let valid_part = |part: &str| {
+        !part.is_empty()
+            && part != "."
+            && part != ".."
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    };
+    if !name.split('/').all(valid_part) {
+        return Err(format!("Invalid map name: {:?}", name));
+    }
+
+    let file = if name.ends_with(".yaml") {
+        name.to_string()
+    } else {
+        format!("{}.yaml", name)
+    };
+    Ok(PathBuf::from(MAPS_DIR).join(file))
+}
+
+/// Height of the ceiling above the floor, matching a full-height wall
+pub const CEILING_HEIGHT: f32 = 16.0;
+
+/// Movement cost of an ordinary open cell
+pub const DEFAULT_MOVE_COST: f32 = 1.0;
+
+/// Built-in terrain tiles: '~' mud is slow going, '=' road is quick. Maps
+/// can add or override tiles with `tile_costs`.
+const DEFAULT_TILE_COSTS: [(char, f32); 2] = [('~', 3.0), ('=', 0.5)];
+
+/// Built-in wall tiles and their heights. Maps can add or override wall
+/// tiles with `wall_heights`. When saving, each wall is written as the first
+/// tile with its height, so 'X' is preferred over '#'.
+const DEFAULT_WALL_HEIGHTS: [(char, f32); 3] = [('X', 16.0), ('#', 16.0), ('x', 8.0)];
+
+/// Tiles with a meaning of their own (doors, open floor and stairs), which a
+/// map can't declare as walls
+const RESERVED_TILES: [char; 4] = ['D', '.', '>', '<'];
+
+/// Characters a saved map can declare as new wall tiles, for walls whose
+/// height has no tile yet (e.g. from `addwall 3 4 12`)
+fn spare_wall_tiles() -> impl Iterator<Item = char> {
+    ('a'..='z').chain('A'..='Z').chain('0'..='9')
+}
+
+/// Wall tiles in saving preference: the map's own tiles, then the built-in
+/// ones it doesn't override
+fn wall_tiles(wall_heights: &BTreeMap<char, f32>) -> Vec<(char, f32)> {
+    wall_heights
+        .iter()
+        .map(|(&tile, &height)| (tile, height))
+        .chain(
+            DEFAULT_WALL_HEIGHTS
+                .into_iter()
+                .filter(|(tile, _)| !wall_heights.contains_key(tile)),
+        )
+        .collect()
+}
+
+/// Tile to save a wall as. Heights without a tile of their own, which only
+/// happens once the spare tiles have run out, get the tile with the closest
+/// height.
+fn wall_tile_for_height(wall_tiles: &[(char, f32)], height: f32) -> char {
+    wall_tiles
+        .iter()
+        .min_by(|a, b| (a.1 - height).abs().total_cmp(&(b.1 - height).abs()))
+        .map(|&(tile, _)| tile)
+        .unwrap_or('X')
+}
 
 /// Wrapper for YAML file format (has "map:" prefix)
 #[derive(Deserialize)]
@@ -27,6 +125,34 @@ pub enum TileType {
     Wall { height: f32 },
 }
 
+/// Stairs tile ('>' up, '<' down) connecting a cell to the same cell on the
+/// floor above or below
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stairs {
+    Up,
+    Down,
+}
+
+/// One floor of a multi-floor map
+#[derive(Debug, Clone, Default)]
+pub struct MapLayer {
+    /// World Z of the floor surface
+    pub base_z: f32,
+
+    /// Collision grid of this floor (8×8 grid aligned)
+    pub collision: HashMap<(i32, i32), TileType>,
+
+    /// Stairs cells on this floor
+    pub stairs: HashMap<(i32, i32), Stairs>,
+
+    /// Movement cost of open cells on this floor that differ from
+    /// `DEFAULT_MOVE_COST`. Pathfinding prefers cheaper cells.
+    pub costs: HashMap<(i32, i32), f32>,
+
+    /// Entity tracking for this floor's walls (8×8 grid aligned)
+    pub walls: HashMap<(i32, i32), Entity>,
+}
+
 /// Unified map resource that tracks walls, items, and actors
 #[derive(Resource)]
 pub struct Map {
@@ -34,11 +160,19 @@ pub struct Map {
     pub width: i32,
     pub height: i32,
 
-    /// Collision grid (8×8 grid aligned)
-    pub collision_grid: HashMap<(i32, i32), TileType>,
+    /// Terrain tile costs declared by the map file, kept for saving
+    pub tile_costs: BTreeMap<char, f32>,
 
-    /// Entity tracking for walls (8×8 grid aligned)
-    pub walls: HashMap<(i32, i32), Entity>,
+    /// Wall tile heights declared by the map file, kept for saving
+    pub wall_heights: BTreeMap<char, f32>,
+
+    /// Every floor of the map, bottom first. Single-floor maps have one.
+    /// Actors, projectiles and walls each know which floor they are on and
+    /// query its collision with the `_on` methods.
+    pub layers: Vec<MapLayer>,
+
+    /// Index into `layers` of the floor the player is on
+    pub active_layer: usize,
 
     /// Entity tracking for items (2×2 grid aligned - intentionally different)
     pub items: HashMap<(i32, i32), Entity>,
@@ -48,6 +182,16 @@ pub struct Map {
 
     /// Entity tracking for actors
     pub actors: HashMap<Entity, ActorPosition>,
+
+    /// Cells the player has seen (8×8 grid aligned). Reset whenever a map is loaded.
+    pub explored: HashSet<(i32, i32)>,
+
+    /// Door cells and the cell each one teleports the player to (8×8 grid aligned)
+    pub doors: HashMap<(i32, i32), (i32, i32)>,
+
+    /// Set when a wall is added or removed, so the floor and ceiling meshes
+    /// get rebuilt to match the open cells
+    pub surfaces_dirty: bool,
 }
 
 impl Map {
@@ -56,23 +200,32 @@ impl Map {
         Self {
             width,
             height,
-            collision_grid: HashMap::new(),
-            walls: HashMap::new(),
+            tile_costs: BTreeMap::new(),
+            wall_heights: BTreeMap::new(),
+            layers: vec![MapLayer::default()],
+            active_layer: 0,
             items: HashMap::new(),
             item_world_positions: Vec::new(),
             actors: HashMap::new(),
+            explored: HashSet::new(),
+            doors: HashMap::new(),
+            surfaces_dirty: false,
         }
     }
 
     /// Load and parse map YAML file (Bevy-independent)
-    pub fn load_map_file(path: &str) -> Result<MapFile, String> {
-        let map_path = PathBuf::from(path);
+    pub fn load_map_file(path: impl AsRef<Path>) -> Result<MapFile, String> {
+        let map_path = path.as_ref();
 
-        let file_contents =
-            fs::read_to_string(&map_path).map_err(|e| format!("Failed to read map file: {}", e))?;
+        let file_contents = fs::read_to_string(map_path)
+            .map_err(|e| format!("Failed to read map file {:?}: {}", map_path, e))?;
 
         let wrapper: MapFileWrapper = serde_yaml::from_str(&file_contents)
             .map_err(|e| format!("Failed to parse map YAML: {}", e))?;
+        wrapper.map.validate_doors()?;
+        wrapper.map.validate_stairs()?;
+        wrapper.map.validate_tile_costs()?;
+        wrapper.map.validate_wall_heights()?;
 
         Ok(wrapper.map)
     }
@@ -87,34 +240,150 @@ impl Map {
         };
 
         let mut map = Self::new(width, height);
+        map.tile_costs = map_file.tile_costs.clone();
+        map.wall_heights = map_file.wall_heights.clone();
+        let tile_costs = map.tile_cost_table();
+        let wall_tiles = wall_tiles(&map.wall_heights);
+
+        // The base grid is the ground floor (layer 0), which starts active
+        map.layers[0] = Self::parse_layer(&map_file.grid, 0.0, &tile_costs, &wall_tiles);
+        for layer in &map_file.layers {
+            map.layers.push(Self::parse_layer(
+                &layer.grid,
+                layer.z,
+                &tile_costs,
+                &wall_tiles,
+            ));
+        }
+
+        for door in &map_file.doors {
+            map.doors
+                .insert((door.x, door.y), (door.target_x, door.target_y));
+        }
+
+        map
+    }
 
-        // Parse grid and populate collision data
-        for (row_idx, row) in map_file.grid.iter().enumerate() {
+    /// Built-in terrain tile costs with the map's own `tile_costs` on top
+    pub fn tile_cost_table(&self) -> BTreeMap<char, f32> {
+        let mut table: BTreeMap<char, f32> = DEFAULT_TILE_COSTS.into_iter().collect();
+        table.extend(&self.tile_costs);
+        table
+    }
+
+    /// Parse one floor's rows of tile characters
+    fn parse_layer(
+        grid: &[String],
+        base_z: f32,
+        tile_costs: &BTreeMap<char, f32>,
+        wall_tiles: &[(char, f32)],
+    ) -> MapLayer {
+        let mut layer = MapLayer {
+            base_z,
+            ..default()
+        };
+
+        for (row_idx, row) in grid.iter().enumerate() {
             for (col_idx, ch) in row.chars().enumerate() {
-                let grid_x = col_idx as i32;
-                let grid_y = row_idx as i32;
+                let cell = (col_idx as i32, row_idx as i32);
+
+                let tile = match wall_tiles.iter().find(|(tile, _)| *tile == ch) {
+                    Some(&(_, height)) => TileType::Wall { height },
+                    None => TileType::Empty,
+                };
+                layer.collision.insert(cell, tile);
+
+                if tile == TileType::Empty
+                    && let Some(&cost) = tile_costs.get(&ch)
+                    && cost != DEFAULT_MOVE_COST
+                {
+                    layer.costs.insert(cell, cost);
+                }
 
                 match ch {
-                    '#' | 'X' => {
-                        map.collision_grid
-                            .insert((grid_x, grid_y), TileType::Wall { height: 16.0 });
-                    }
-                    'x' => {
-                        map.collision_grid
-                            .insert((grid_x, grid_y), TileType::Wall { height: 8.0 });
+                    '>' => {
+                        layer.stairs.insert(cell, Stairs::Up);
                     }
-                    _ => {
-                        map.collision_grid.insert((grid_x, grid_y), TileType::Empty);
+                    '<' => {
+                        layer.stairs.insert(cell, Stairs::Down);
                     }
+                    _ => {}
                 }
             }
         }
 
-        map
+        layer
+    }
+
+    /// Collision grid of a floor
+    pub fn layer_collision(&self, layer: usize) -> Option<&HashMap<(i32, i32), TileType>> {
+        self.layers.get(layer).map(|l| &l.collision)
+    }
+
+    /// Check if a grid position is solid on the given floor
+    pub fn is_solid_on(&self, layer: usize, grid_x: i32, grid_y: i32) -> bool {
+        self.layer_collision(layer).is_some_and(|collision| {
+            matches!(
+                collision.get(&(grid_x, grid_y)),
+                Some(TileType::Wall { .. })
+            )
+        })
+    }
+
+    /// Move the player to another floor. Everything else keeps its own
+    /// layer. Returns false (and changes nothing) if the layer doesn't exist.
+    pub fn set_active_layer(&mut self, layer: usize) -> bool {
+        if layer >= self.layers.len() {
+            return false;
+        }
+        self.active_layer = layer;
+        true
+    }
+
+    /// World Z of a floor's surface, or 0 for a floor that doesn't exist
+    pub fn layer_base_z(&self, layer: usize) -> f32 {
+        self.layers.get(layer).map_or(0.0, |l| l.base_z)
+    }
+
+    /// Cost of moving into a cell of the given floor
+    pub fn move_cost_on(&self, layer: usize, grid_x: i32, grid_y: i32) -> f32 {
+        self.layers
+            .get(layer)
+            .and_then(|l| l.costs.get(&(grid_x, grid_y)))
+            .copied()
+            .unwrap_or(DEFAULT_MOVE_COST)
+    }
+
+    /// Cheapest cell cost on the given floor, for scaling path heuristics
+    pub fn min_move_cost_on(&self, layer: usize) -> f32 {
+        self.layers
+            .get(layer)
+            .into_iter()
+            .flat_map(|l| l.costs.values())
+            .copied()
+            .fold(DEFAULT_MOVE_COST, f32::min)
+    }
+
+    /// Whether every open cell of a floor has the default movement cost
+    pub fn has_uniform_costs_on(&self, layer: usize) -> bool {
+        self.layers.get(layer).is_none_or(|l| l.costs.is_empty())
+    }
+
+    /// Floor the stairs at a cell of the active floor lead to, if any
+    pub fn stairs_destination(&self, grid_x: i32, grid_y: i32) -> Option<usize> {
+        let stairs = self.layers[self.active_layer]
+            .stairs
+            .get(&(grid_x, grid_y))?;
+        let target = match stairs {
+            Stairs::Up => self.active_layer + 1,
+            Stairs::Down => self.active_layer.checked_sub(1)?,
+        };
+        (target < self.layers.len()).then_some(target)
     }
 
     /// Load map from YAML file and spawn all entities
     pub fn load_from_file(
+        path: &Path,
         commands: &mut Commands,
         asset_server: &Res<AssetServer>,
         meshes: &mut Assets<Mesh>,
@@ -122,21 +391,45 @@ impl Map {
         item_defs: &ItemDefinitions,
         actor_defs: &ActorDefinitions,
     ) -> Result<Self, String> {
-        let map_file = Self::load_map_file("data/map.yaml")?;
-        let mut map = Self::from_map_file(&map_file);
-
-        // Spawn wall entities
-        for ((grid_x, grid_y), tile_type) in &map.collision_grid.clone() {
-            if let TileType::Wall { height } = tile_type {
-                map.spawn_wall(
-                    commands,
-                    asset_server,
-                    meshes,
-                    materials,
-                    *grid_x,
-                    *grid_y,
-                    *height,
-                );
+        let map_file = Self::load_map_file(path)?;
+        Ok(Self::spawn_from_map_file(
+            &map_file,
+            commands,
+            asset_server,
+            meshes,
+            materials,
+            item_defs,
+            actor_defs,
+        ))
+    }
+
+    /// Build a map from an already parsed MapFile and spawn all its entities
+    pub fn spawn_from_map_file(
+        map_file: &MapFile,
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        item_defs: &ItemDefinitions,
+        actor_defs: &ActorDefinitions,
+    ) -> Self {
+        let mut map = Self::from_map_file(map_file);
+
+        // Spawn wall entities on every floor, raised to their floor's height
+        for layer in 0..map.layers.len() {
+            for ((grid_x, grid_y), tile_type) in &map.layers[layer].collision.clone() {
+                if let TileType::Wall { height } = tile_type {
+                    map.spawn_wall(
+                        commands,
+                        asset_server,
+                        meshes,
+                        materials,
+                        layer,
+                        *grid_x,
+                        *grid_y,
+                        *height,
+                    );
+                }
             }
         }
 
@@ -162,13 +455,88 @@ impl Map {
                 meshes,
                 materials,
                 actor_defs,
+                actor_pos.layer,
                 actor_pos.x as f32,
                 actor_pos.y as f32,
                 &actor_pos.actor_type,
             );
         }
 
-        Ok(map)
+        map
+    }
+
+    /// All open (non-wall) cells of the given floor, row by row
+    pub fn floor_cells_on(&self, layer: usize) -> Vec<(i32, i32)> {
+        let Some(collision) = self.layer_collision(layer) else {
+            return Vec::new();
+        };
+        let mut cells = Vec::new();
+        for grid_y in 0..self.height {
+            for grid_x in 0..self.width {
+                if matches!(collision.get(&(grid_x, grid_y)), Some(TileType::Empty)) {
+                    cells.push((grid_x, grid_y));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Single mesh with one upward-facing quad per open cell, at z = 0
+    pub fn create_floor_mesh(&self) -> Mesh {
+        Self::create_cell_quads_mesh(&self.floor_cells_on(0), 0.0, true)
+    }
+
+    /// Floor mesh for an upper floor, at that floor's height
+    pub fn create_layer_floor_mesh(&self, layer: usize) -> Mesh {
+        let base_z = self.layers.get(layer).map_or(0.0, |l| l.base_z);
+        Self::create_cell_quads_mesh(&self.floor_cells_on(layer), base_z, true)
+    }
+
+    /// Single mesh with one downward-facing quad per open cell, at the ceiling height
+    pub fn create_ceiling_mesh(&self) -> Mesh {
+        Self::create_cell_quads_mesh(&self.floor_cells_on(0), CEILING_HEIGHT, false)
+    }
+
+    /// Build a mesh with a horizontal quad covering each cell. Every quad maps
+    /// the full texture, so it tiles once per cell.
+    fn create_cell_quads_mesh(cells: &[(i32, i32)], z: f32, facing_up: bool) -> Mesh {
+        let mut positions = Vec::with_capacity(cells.len() * 4);
+        let mut uvs = Vec::with_capacity(cells.len() * 4);
+        let mut indices = Vec::with_capacity(cells.len() * 6);
+
+        for &(grid_x, grid_y) in cells {
+            let x0 = grid_x as f32 * GRID_SIZE;
+            let y0 = grid_y as f32 * GRID_SIZE;
+            let (x1, y1) = (x0 + GRID_SIZE, y0 + GRID_SIZE);
+
+            let base = positions.len() as u32;
+            positions.extend([[x0, y0, z], [x1, y0, z], [x1, y1, z], [x0, y1, z]]);
+            uvs.extend([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+
+            // Counter-clockwise when seen from the side the quad faces
+            if facing_up {
+                indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+            } else {
+                indices.extend([base, base + 2, base + 1, base, base + 3, base + 2]);
+            }
+        }
+
+        let normal = if facing_up {
+            [0.0, 0.0, 1.0]
+        } else {
+            [0.0, 0.0, -1.0]
+        };
+        let vertex_count = positions.len();
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![normal; vertex_count]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_indices(Indices::U32(indices));
+        mesh
     }
 
     /// Create a billboard mesh oriented in the YZ plane (normal along X-axis)
@@ -205,33 +573,65 @@ impl Map {
         billboard_mesh
     }
 
-    /// Spawn a wall at the given grid position (8×8 grid)
+    /// Spawn a wall at the given grid position (8×8 grid) of a floor.
+    /// Does nothing if the floor doesn't exist.
     pub fn spawn_wall(
         &mut self,
         commands: &mut Commands,
         asset_server: &Res<AssetServer>,
         meshes: &mut Assets<Mesh>,
         materials: &mut Assets<StandardMaterial>,
+        layer: usize,
         grid_x: i32,
         grid_y: i32,
         wall_height: f32,
     ) {
+        let Some(map_layer) = self.layers.get_mut(layer) else {
+            return;
+        };
+
         // Register in collision grid
-        self.collision_grid.insert(
+        map_layer.collision.insert(
             (grid_x, grid_y),
             TileType::Wall {
                 height: wall_height,
             },
         );
 
-        // Spawn wall entity
+        let entity = Self::spawn_wall_entity(
+            commands,
+            asset_server,
+            meshes,
+            materials,
+            grid_x,
+            grid_y,
+            map_layer.base_z,
+            wall_height,
+        );
+
+        // Track entity
+        map_layer.walls.insert((grid_x, grid_y), entity);
+        self.surfaces_dirty = true;
+    }
+
+    /// Spawn the mesh for a wall standing on a floor at `base_z`
+    fn spawn_wall_entity(
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        grid_x: i32,
+        grid_y: i32,
+        base_z: f32,
+        wall_height: f32,
+    ) -> Entity {
         // Position at grid corner, then offset by half grid size to center the cuboid in the cell
         let world_x = grid_x as f32 * GRID_SIZE + GRID_SIZE / 2.0;
         let world_y = grid_y as f32 * GRID_SIZE + GRID_SIZE / 2.0;
 
         let texture_handle = load_image_texture(asset_server, "base/textures/stone_2.png");
 
-        let entity = commands
+        commands
             .spawn((
                 GamePlayEntity,
                 Mesh3d(meshes.add(Cuboid::new(GRID_SIZE, GRID_SIZE, wall_height))),
@@ -239,12 +639,9 @@ impl Map {
                     base_color_texture: Some(texture_handle),
                     ..default()
                 })),
-                Transform::from_xyz(world_x, world_y, wall_height / 2.0),
+                Transform::from_xyz(world_x, world_y, base_z + wall_height / 2.0),
             ))
-            .id();
-
-        // Track entity
-        self.walls.insert((grid_x, grid_y), entity);
+            .id()
     }
 
     /// Spawn an item at the given world position (uses 2×2 grid internally for tracking)
@@ -275,6 +672,7 @@ impl Map {
             .spawn((
                 GamePlayEntity,
                 Billboard,
+                BillboardMode::Upright,
                 Item {
                     interaction_radius: 2.0,
                 },
@@ -295,7 +693,7 @@ impl Map {
             .push((world_pos, item_type.to_string()));
     }
 
-    /// Spawn an actor at the given world position
+    /// Spawn an actor at the given world position on a floor
     pub fn spawn_actor(
         &mut self,
         commands: &mut Commands,
@@ -303,6 +701,7 @@ impl Map {
         meshes: &mut Assets<Mesh>,
         materials: &mut Assets<StandardMaterial>,
         actor_defs: &ActorDefinitions,
+        layer: usize,
         world_x: f32,
         world_y: f32,
         actor_type: &str,
@@ -311,44 +710,61 @@ impl Map {
             warn!("Unknown actor type: {}", actor_type);
             return;
         };
+        if layer >= self.layers.len() {
+            warn!(
+                "Actor {} is on floor {}, which doesn't exist",
+                actor_type, layer
+            );
+            return;
+        }
 
-        let world_pos = Vec3::new(world_x, world_y, actor_def.scale);
+        let base_z = self.layer_base_z(layer) + actor_def.scale;
+        let world_pos = Vec3::new(world_x, world_y, base_z);
         let texture_handle = load_weapon_texture(asset_server, &actor_def.sprite);
+        let animation = AnimatedBillboard::from_definition(actor_def);
 
         // Create behavior based on definition
-        let behavior: Option<Box<dyn crate::ai::ActorBehavior>> = match actor_def.behavior.as_str()
-        {
-            "wander" => Some(Box::new(crate::ai::wander_behavior::WanderBehavior::new())),
-            "stand" => Some(Box::new(crate::ai::stand_behavior::StandBehavior)),
-            "aggressive" => Some(Box::new(
-                crate::ai::aggressive_behavior::AggressiveBehavior::new(),
-            )),
-            _ => {
-                warn!(
-                    "Unknown behavior type: {}, defaulting to wander",
-                    actor_def.behavior
-                );
-                Some(Box::new(crate::ai::wander_behavior::WanderBehavior::new()))
+        let kind = actor_def.behavior.parse().unwrap_or_else(|problem| {
+            warn!("{}, defaulting to wander", problem);
+            BehaviorKind::Wander
+        });
+        let behavior: Box<dyn crate::ai::ActorBehavior> = match kind {
+            BehaviorKind::Wander => Box::new(crate::ai::wander_behavior::WanderBehavior::new()),
+            BehaviorKind::Stand => Box::new(crate::ai::stand_behavior::StandBehavior),
+            BehaviorKind::Aggressive => {
+                Box::new(crate::ai::aggressive_behavior::AggressiveBehavior::new())
             }
+            BehaviorKind::Flee => Box::new(crate::ai::flee_behavior::FleeBehavior::new()),
+            BehaviorKind::Flock => Box::new(crate::ai::flock_behavior::FlockBehavior::new()),
+            BehaviorKind::Ranged => Box::new(crate::ai::ranged_behavior::RangedBehavior::new(
+                actor_def.preferred_distance,
+                actor_def.distance_tolerance,
+                actor_def.ranged_cooldown,
+                actor_def.projectile_speed,
+            )),
         };
 
         let entity = commands
             .spawn((
                 GamePlayEntity,
                 Billboard,
+                actor_def.billboard,
                 crate::actor::Actor {
                     actor_type: actor_type.to_string(),
                     health: actor_def.max_health,
                     max_health: actor_def.max_health,
                     scale: actor_def.scale,
                     armor: 0,
-                    physical_resistance: 0.0,
+                    resistances: actor_def.resistances.clone(),
                     actor_radius: 1.2, // 3/4 of player radius (1.6)
                     speed_multiplier: actor_def.speed,
-                    behavior,
+                    behavior: Some(behavior),
                     is_moving: false,
-                    base_z: actor_def.scale,
+                    velocity: Vec2::ZERO,
+                    layer,
+                    base_z,
                     attack_damage: actor_def.attack_damage,
+                    attack_effect: actor_def.attack_effect,
                     attack_range: actor_def.attack_range,
                     attack_cooldown: actor_def.attack_cooldown,
                     attack_timer: 0.0,
@@ -356,16 +772,28 @@ impl Map {
                     attack_state: crate::actor::ActorAttackState::Idle,
                 },
                 Mesh3d(meshes.add(Self::create_billboard_mesh(actor_def.scale))),
-                MeshMaterial3d(materials.add(StandardMaterial {
-                    base_color_texture: Some(texture_handle),
-                    alpha_mode: AlphaMode::Blend,
-                    unlit: false,
-                    ..default()
-                })),
+                MeshMaterial3d(
+                    materials.add(StandardMaterial {
+                        base_color_texture: Some(texture_handle),
+                        alpha_mode: AlphaMode::Blend,
+                        unlit: false,
+                        uv_transform: animation
+                            .as_ref()
+                            .map_or(Affine2::IDENTITY, |a| a.uv_transform()),
+                        ..default()
+                    }),
+                ),
                 Transform::from_translation(world_pos),
             ))
             .id();
 
+        if let Some(animation) = animation {
+            commands.entity(entity).insert(animation);
+        }
+        if actor_def.cull_exempt {
+            commands.entity(entity).insert(CullExempt);
+        }
+
         // Track entity
         self.actors.insert(
             entity,
@@ -373,18 +801,31 @@ impl Map {
                 x: world_x,
                 y: world_y,
                 actor_type: actor_type.to_string(),
+                layer,
             },
         );
     }
 
-    /// Remove a wall at the given grid position
-    pub fn remove_wall(&mut self, commands: &mut Commands, grid_x: i32, grid_y: i32) -> bool {
+    /// Remove a wall at the given grid position of a floor
+    pub fn remove_wall(
+        &mut self,
+        commands: &mut Commands,
+        layer: usize,
+        grid_x: i32,
+        grid_y: i32,
+    ) -> bool {
+        let Some(map_layer) = self.layers.get_mut(layer) else {
+            return false;
+        };
+
         // Update collision grid immediately
-        self.collision_grid
+        map_layer
+            .collision
             .insert((grid_x, grid_y), TileType::Empty);
+        self.surfaces_dirty = true;
 
         // Despawn entity if it exists
-        if let Some(entity) = self.walls.remove(&(grid_x, grid_y)) {
+        if let Some(entity) = map_layer.walls.remove(&(grid_x, grid_y)) {
             commands.entity(entity).despawn();
             true
         } else {
@@ -417,16 +858,20 @@ impl Map {
         self.items.get(&(grid_x, grid_y)).copied()
     }
 
-    /// Check if a grid position is solid (8×8 grid)
+    /// Check if a grid position is solid on the player's floor (8×8 grid)
     pub fn is_solid(&self, grid_x: i32, grid_y: i32) -> bool {
-        matches!(
-            self.collision_grid.get(&(grid_x, grid_y)),
-            Some(TileType::Wall { .. })
-        )
+        self.is_solid_on(self.active_layer, grid_x, grid_y)
     }
 
-    /// Check if player can move to a world position with given bounding box half-size
+    /// Check if the player can move to a world position with given bounding
+    /// box half-size
     pub fn can_move_to(&self, world_x: f32, world_y: f32, half_size: f32) -> bool {
+        self.can_move_to_on(self.active_layer, world_x, world_y, half_size)
+    }
+
+    /// Check if a box with the given half-size fits at a world position on a
+    /// floor
+    pub fn can_move_to_on(&self, layer: usize, world_x: f32, world_y: f32, half_size: f32) -> bool {
         let min_x = world_x - half_size;
         let max_x = world_x + half_size;
         let min_y = world_y - half_size;
@@ -444,7 +889,23 @@ impl Map {
                 if grid_x < 0 || grid_x >= self.width || grid_y < 0 || grid_y >= self.height {
                     return false; // Out of bounds
                 }
-                if self.is_solid(grid_x, grid_y) {
+                if self.is_solid_on(layer, grid_x, grid_y) {
+                    return false;
+                }
+            }
+        }
+
+        // A box whose edge lies exactly on a grid line only counts the cell on
+        // the far side, so it can sit right on the point where two diagonal
+        // walls meet and slip through. Treat touching such a pinch as blocked,
+        // like pathfinding refusing to cut between diagonal walls.
+        let first_corner_x = (min_x / GRID_SIZE).ceil() as i32;
+        let last_corner_x = (max_x / GRID_SIZE).floor() as i32;
+        let first_corner_y = (min_y / GRID_SIZE).ceil() as i32;
+        let last_corner_y = (max_y / GRID_SIZE).floor() as i32;
+        for corner_y in first_corner_y..=last_corner_y {
+            for corner_x in first_corner_x..=last_corner_x {
+                if self.is_diagonal_pinch(layer, corner_x, corner_y) {
                     return false;
                 }
             }
@@ -452,46 +913,288 @@ impl Map {
         true
     }
 
-    /// Convert Map to MapFile for saving
-    pub fn to_map_file(&self) -> MapFile {
-        // Reconstruct grid
+    /// Whether the grid corner at the top-left of a cell is a point where two
+    /// walls touch diagonally, with open cells on the other diagonal
+    fn is_diagonal_pinch(&self, layer: usize, corner_x: i32, corner_y: i32) -> bool {
+        let solid = |dx: i32, dy: i32| self.is_solid_on(layer, corner_x + dx, corner_y + dy);
+        let (top_left, bottom_right) = (solid(-1, -1), solid(0, 0));
+        let (top_right, bottom_left) = (solid(0, -1), solid(-1, 0));
+        (top_left && bottom_right && !top_right && !bottom_left)
+            || (top_right && bottom_left && !top_left && !bottom_right)
+    }
+
+    /// Where the door at the given cell leads, as the world position of the
+    /// center of its target cell. `None` if the cell is not a door.
+    pub fn door_destination(&self, grid_x: i32, grid_y: i32) -> Option<Vec2> {
+        self.doors
+            .get(&(grid_x, grid_y))
+            .map(|&(target_x, target_y)| {
+                Vec2::new(
+                    target_x as f32 * GRID_SIZE + GRID_SIZE / 2.0,
+                    target_y as f32 * GRID_SIZE + GRID_SIZE / 2.0,
+                )
+            })
+    }
+
+    /// Check line of sight between two world positions on the player's floor
+    pub fn has_line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        self.has_line_of_sight_on(self.active_layer, from, to)
+    }
+
+    /// Check if a straight line between two world positions only passes through
+    /// open cells of a floor. Cells outside the map block line of sight. A line
+    /// that passes exactly through a cell corner requires both adjacent cells
+    /// to be open.
+    pub fn has_line_of_sight_on(&self, layer: usize, from: Vec2, to: Vec2) -> bool {
+        let blocks_sight = |grid_x: i32, grid_y: i32| {
+            grid_x < 0
+                || grid_x >= self.width
+                || grid_y < 0
+                || grid_y >= self.height
+                || self.is_solid_on(layer, grid_x, grid_y)
+        };
+
+        let mut grid_x = (from.x / GRID_SIZE).floor() as i32;
+        let mut grid_y = (from.y / GRID_SIZE).floor() as i32;
+        let end_x = (to.x / GRID_SIZE).floor() as i32;
+        let end_y = (to.y / GRID_SIZE).floor() as i32;
+
+        // Grid traversal (Amanatides & Woo): t is the fraction of the segment
+        // travelled when the line crosses the next cell boundary on each axis
+        let dir = to - from;
+        let step_x = dir.x.signum() as i32;
+        let step_y = dir.y.signum() as i32;
+        let t_delta_x = if dir.x != 0.0 {
+            GRID_SIZE / dir.x.abs()
+        } else {
+            f32::INFINITY
+        };
+        let t_delta_y = if dir.y != 0.0 {
+            GRID_SIZE / dir.y.abs()
+        } else {
+            f32::INFINITY
+        };
+        let mut t_max_x = if dir.x > 0.0 {
+            ((grid_x + 1) as f32 * GRID_SIZE - from.x) / dir.x
+        } else if dir.x < 0.0 {
+            (grid_x as f32 * GRID_SIZE - from.x) / dir.x
+        } else {
+            f32::INFINITY
+        };
+        let mut t_max_y = if dir.y > 0.0 {
+            ((grid_y + 1) as f32 * GRID_SIZE - from.y) / dir.y
+        } else if dir.y < 0.0 {
+            (grid_y as f32 * GRID_SIZE - from.y) / dir.y
+        } else {
+            f32::INFINITY
+        };
+
+        // Upper bound on the number of cells visited, in case of rounding issues
+        let max_steps = (end_x - grid_x).abs() + (end_y - grid_y).abs();
+        for _ in 0..=max_steps {
+            if blocks_sight(grid_x, grid_y) {
+                return false;
+            }
+            if grid_x == end_x && grid_y == end_y {
+                return true;
+            }
+
+            if t_max_x < t_max_y {
+                grid_x += step_x;
+                t_max_x += t_delta_x;
+            } else if t_max_y < t_max_x {
+                grid_y += step_y;
+                t_max_y += t_delta_y;
+            } else {
+                if blocks_sight(grid_x + step_x, grid_y) || blocks_sight(grid_x, grid_y + step_y) {
+                    return false;
+                }
+                grid_x += step_x;
+                grid_y += step_y;
+                t_max_x += t_delta_x;
+                t_max_y += t_delta_y;
+            }
+        }
+
+        grid_x == end_x && grid_y == end_y && !blocks_sight(grid_x, grid_y)
+    }
+
+    /// Check if the player has seen a grid cell
+    pub fn is_explored(&self, grid_x: i32, grid_y: i32) -> bool {
+        self.explored.contains(&(grid_x, grid_y))
+    }
+
+    /// Mark all cells within `radius` of a world position that are in line of
+    /// sight as explored. Walls are marked as well as the open cells in front of
+    /// them. Returns the number of newly explored cells.
+    pub fn mark_explored_from(&mut self, position: Vec2, radius: f32) -> usize {
+        let min_grid_x = ((position.x - radius) / GRID_SIZE).floor().max(0.0) as i32;
+        let max_grid_x = ((position.x + radius) / GRID_SIZE).floor() as i32;
+        let min_grid_y = ((position.y - radius) / GRID_SIZE).floor().max(0.0) as i32;
+        let max_grid_y = ((position.y + radius) / GRID_SIZE).floor() as i32;
+
+        let mut newly_explored = 0;
+        for grid_y in min_grid_y..=max_grid_y.min(self.height - 1) {
+            for grid_x in min_grid_x..=max_grid_x.min(self.width - 1) {
+                if self.is_explored(grid_x, grid_y) {
+                    continue;
+                }
+
+                let center = Vec2::new(
+                    grid_x as f32 * GRID_SIZE + GRID_SIZE / 2.0,
+                    grid_y as f32 * GRID_SIZE + GRID_SIZE / 2.0,
+                );
+                if center.distance(position) > radius {
+                    continue;
+                }
+
+                // A wall is visible if the viewer can see its near face, so check
+                // sight to a point just outside the wall's edge facing the viewer
+                let to_viewer = position - center;
+                let edge_distance = to_viewer.x.abs().max(to_viewer.y.abs());
+                let target = if self.is_solid(grid_x, grid_y) && edge_distance > 0.0 {
+                    center + to_viewer * ((GRID_SIZE / 2.0 + 0.01) / edge_distance)
+                } else {
+                    center
+                };
+
+                if self.has_line_of_sight(position, target) {
+                    self.explored.insert((grid_x, grid_y));
+                    newly_explored += 1;
+                }
+            }
+        }
+
+        newly_explored
+    }
+
+    /// Wall tile heights to save: the map's own, plus a new tile for every
+    /// wall height that no tile has, so the saved map loads back the same
+    fn saved_wall_heights(&self) -> BTreeMap<char, f32> {
+        let mut wall_heights = self.wall_heights.clone();
+        let declared = wall_tiles(&wall_heights);
+
+        let mut undeclared: Vec<f32> = (0..self.layers.len())
+            .filter_map(|layer| self.layer_collision(layer))
+            .flat_map(|collision| collision.values())
+            .filter_map(|tile_type| match tile_type {
+                TileType::Wall { height } => Some(*height),
+                TileType::Empty => None,
+            })
+            .filter(|height| {
+                !declared
+                    .iter()
+                    .any(|(_, tile_height)| tile_height == height)
+            })
+            .collect();
+        undeclared.sort_by(f32::total_cmp);
+        undeclared.dedup();
+
+        let tile_costs = self.tile_cost_table();
+        let mut spare = spare_wall_tiles().filter(|tile| {
+            *tile != 'D'
+                && !tile_costs.contains_key(tile)
+                && !declared.iter().any(|(wall, _)| wall == tile)
+        });
+        for height in undeclared {
+            match spare.next() {
+                Some(tile) => {
+                    wall_heights.insert(tile, height);
+                }
+                None => break,
+            }
+        }
+        wall_heights
+    }
+
+    /// Rebuild the rows of tile characters for one floor
+    fn layer_grid_strings(&self, layer: usize, wall_tiles: &[(char, f32)]) -> Vec<String> {
         let mut grid = vec![vec![' '; self.width as usize]; self.height as usize];
+        let collision = self.layer_collision(layer).into_iter().flatten();
+        let stairs = &self.layers[layer].stairs;
+        let costs = &self.layers[layer].costs;
+        let tile_costs = self.tile_cost_table();
 
-        for ((grid_x, grid_y), tile_type) in &self.collision_grid {
+        for ((grid_x, grid_y), tile_type) in collision {
             if *grid_x >= 0 && *grid_x < self.width && *grid_y >= 0 && *grid_y < self.height {
+                let cell = (*grid_x, *grid_y);
                 let ch = match tile_type {
-                    TileType::Wall { height } if *height > 10.0 => 'X',
-                    TileType::Wall { .. } => 'x',
-                    TileType::Empty => '.',
+                    TileType::Wall { height } => wall_tile_for_height(wall_tiles, *height),
+                    TileType::Empty if layer == 0 && self.doors.contains_key(&cell) => 'D',
+                    TileType::Empty => match stairs.get(&cell) {
+                        Some(Stairs::Up) => '>',
+                        Some(Stairs::Down) => '<',
+                        // Terrain is saved as the first tile with a matching cost
+                        None => costs
+                            .get(&cell)
+                            .and_then(|cost| {
+                                tile_costs
+                                    .iter()
+                                    .find(|&(_, tile_cost)| tile_cost == cost)
+                                    .map(|(&tile, _)| tile)
+                            })
+                            .unwrap_or('.'),
+                    },
                 };
                 grid[*grid_y as usize][*grid_x as usize] = ch;
             }
         }
 
-        let grid_strings: Vec<String> = grid.iter().map(|row| row.iter().collect()).collect();
+        grid.iter().map(|row| row.iter().collect()).collect()
+    }
+
+    /// Convert Map to MapFile for saving
+    pub fn to_map_file(&self) -> MapFile {
+        let wall_heights = self.saved_wall_heights();
+        let saved_wall_tiles = wall_tiles(&wall_heights);
+        let grid_strings = self.layer_grid_strings(0, &saved_wall_tiles);
+        let layers = (1..self.layers.len())
+            .map(|layer| LayerDef {
+                z: self.layers[layer].base_z,
+                grid: self.layer_grid_strings(layer, &saved_wall_tiles),
+            })
+            .collect();
+
+        let mut doors: Vec<DoorDef> = self
+            .doors
+            .iter()
+            .map(|(&(x, y), &(target_x, target_y))| DoorDef {
+                x,
+                y,
+                target_x,
+                target_y,
+            })
+            .collect();
+        doors.sort_by_key(|door| (door.y, door.x));
 
         // Items and actors will be filled in by save_to_yaml
         MapFile {
             grid: grid_strings,
             items: Vec::new(),
             actors: Vec::new(),
+            doors,
+            layers,
+            tile_costs: self.tile_costs.clone(),
+            wall_heights,
         }
     }
 
-    /// Save map to YAML file
-    pub fn save_to_yaml(&self) -> Result<(), String> {
-        let mut map_file = self.to_map_file();
-
-        // Build items from world positions with types
-        map_file.items = self
-            .item_world_positions
+    /// Items still lying in the world, in map file form
+    pub fn item_positions(&self) -> Vec<ItemPosition> {
+        self.item_world_positions
             .iter()
             .map(|(pos, item_type)| ItemPosition {
                 x: pos.x,
                 y: pos.y,
                 item_type: item_type.clone(),
             })
-            .collect();
+            .collect()
+    }
+
+    /// Save map to YAML file
+    pub fn save_to_yaml(&self) -> Result<(), String> {
+        let mut map_file = self.to_map_file();
+        map_file.items = self.item_positions();
 
         let yaml_string = serde_yaml::to_string(&map_file)
             .map_err(|e| format!("Failed to serialize map: {}", e))?;
@@ -507,9 +1210,182 @@ impl Map {
 }
 
 /// Map file format for YAML serialization
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct MapFile {
     pub grid: Vec<String>,
     pub items: Vec<ItemPosition>,
     pub actors: Vec<ActorPosition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub doors: Vec<DoorDef>,
+    /// Floors stacked above `grid`, which is always the ground floor
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<LayerDef>,
+    /// Movement costs for extra terrain tile characters, e.g. `',': 2.0`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tile_costs: BTreeMap<char, f32>,
+    /// Heights for extra wall tile characters, e.g. `'w': 12.0`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wall_heights: BTreeMap<char, f32>,
+}
+
+/// An upper floor of the map. Uses the same tile characters as the ground
+/// floor grid, plus '>' and '<' for stairs up and down.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LayerDef {
+    /// World Z of the floor surface
+    pub z: f32,
+    pub grid: Vec<String>,
+}
+
+/// A door tile ('D' in the grid) that teleports the player to another cell.
+/// All coordinates are grid cells.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DoorDef {
+    pub x: i32,
+    pub y: i32,
+    pub target_x: i32,
+    pub target_y: i32,
+}
+
+impl MapFile {
+    fn tile_at(&self, grid_x: i32, grid_y: i32) -> Option<char> {
+        self.layer_tile_at(0, grid_x, grid_y)
+    }
+
+    /// Tile character on a floor, where layer 0 is `grid`
+    fn layer_tile_at(&self, layer: usize, grid_x: i32, grid_y: i32) -> Option<char> {
+        if grid_x < 0 || grid_y < 0 {
+            return None;
+        }
+        let grid = match layer {
+            0 => &self.grid,
+            _ => &self.layers.get(layer - 1)?.grid,
+        };
+        grid.get(grid_y as usize)
+            .and_then(|row| row.chars().nth(grid_x as usize))
+    }
+
+    /// Whether a tile character is a wall, built-in or declared by the map
+    fn is_wall_tile(&self, tile: char) -> bool {
+        wall_tiles(&self.wall_heights)
+            .iter()
+            .any(|&(wall, _)| wall == tile)
+    }
+
+    /// Check that every stairs tile leads to an existing floor, and that the
+    /// same cell on that floor is open.
+    pub fn validate_stairs(&self) -> Result<(), String> {
+        let floors = std::iter::once(&self.grid).chain(self.layers.iter().map(|l| &l.grid));
+        for (layer, grid) in floors.enumerate() {
+            for (row_idx, row) in grid.iter().enumerate() {
+                for (col_idx, ch) in row.chars().enumerate() {
+                    let (grid_x, grid_y) = (col_idx as i32, row_idx as i32);
+                    let target = match ch {
+                        '>' => layer + 1,
+                        '<' if layer > 0 => layer - 1,
+                        '<' => {
+                            return Err(format!(
+                                "Stairs down at ({}, {}) on the ground floor",
+                                grid_x, grid_y
+                            ));
+                        }
+                        _ => continue,
+                    };
+                    match self.layer_tile_at(target, grid_x, grid_y) {
+                        None => {
+                            return Err(format!(
+                                "Stairs at ({}, {}) on floor {} lead outside floor {}",
+                                grid_x, grid_y, layer, target
+                            ));
+                        }
+                        Some(tile) if self.is_wall_tile(tile) => {
+                            return Err(format!(
+                                "Stairs at ({}, {}) on floor {} lead into a wall on floor {}",
+                                grid_x, grid_y, layer, target
+                            ));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every terrain tile cost is a positive number
+    pub fn validate_tile_costs(&self) -> Result<(), String> {
+        for (tile, cost) in &self.tile_costs {
+            if !(cost.is_finite() && *cost > 0.0) {
+                return Err(format!(
+                    "Tile '{}' has cost {}, which is not a positive number",
+                    tile, cost
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every wall tile height is a positive number and that no
+    /// reserved tile is declared as a wall
+    pub fn validate_wall_heights(&self) -> Result<(), String> {
+        for (tile, height) in &self.wall_heights {
+            if RESERVED_TILES.contains(tile) {
+                return Err(format!(
+                    "Tile '{}' is reserved and can't be used as a wall",
+                    tile
+                ));
+            }
+            if !(height.is_finite() && *height > 0.0) {
+                return Err(format!(
+                    "Wall tile '{}' has height {}, which is not a positive number",
+                    tile, height
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every door sits on a 'D' tile and leads to an open cell
+    /// inside the map, and that every 'D' tile has a door definition.
+    pub fn validate_doors(&self) -> Result<(), String> {
+        let mut defined = HashSet::new();
+        for door in &self.doors {
+            if self.tile_at(door.x, door.y) != Some('D') {
+                return Err(format!(
+                    "Door at ({}, {}) is not on a 'D' tile",
+                    door.x, door.y
+                ));
+            }
+            match self.tile_at(door.target_x, door.target_y) {
+                None => {
+                    return Err(format!(
+                        "Door at ({}, {}) targets ({}, {}), which is outside the map",
+                        door.x, door.y, door.target_x, door.target_y
+                    ));
+                }
+                Some(tile) if self.is_wall_tile(tile) => {
+                    return Err(format!(
+                        "Door at ({}, {}) targets ({}, {}), which is a wall",
+                        door.x, door.y, door.target_x, door.target_y
+                    ));
+                }
+                Some(_) => {}
+            }
+            defined.insert((door.x, door.y));
+        }
+
+        for (row_idx, row) in self.grid.iter().enumerate() {
+            for (col_idx, ch) in row.chars().enumerate() {
+                let cell = (col_idx as i32, row_idx as i32);
+                if ch == 'D' && !defined.contains(&cell) {
+                    return Err(format!(
+                        "Door tile at ({}, {}) has no entry in doors",
+                        cell.0, cell.1
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
 use super::*;
+use super::map::{DEFAULT_MOVE_COST, DoorDef, LayerDef};
+use crate::ai::pathfinding::world_to_grid;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
 
 #[test]
 fn test_load_map_file_succeeds() {
@@ -54,12 +58,12 @@ fn test_from_map_file_creates_valid_map() {
     
     // Verify collision grid was populated
     assert!(
-        !map.collision_grid.is_empty(),
+        !map.layers[0].collision.is_empty(),
         "Collision grid should be populated from map data"
     );
     
     // Verify at least some walls exist
-    let wall_count = map.collision_grid.values()
+    let wall_count = map.layers[0].collision.values()
         .filter(|tile| matches!(tile, TileType::Wall { .. }))
         .count();
     
@@ -79,34 +83,38 @@ fn test_wall_height_parsing() {
         ],
         items: vec![],
         actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
     };
     
     let map = Map::from_map_file(&test_map_file);
     
     // Check that 'X' creates tall walls (height 16.0)
     assert_eq!(
-        map.collision_grid.get(&(0, 0)),
+        map.layers[0].collision.get(&(0, 0)),
         Some(&TileType::Wall { height: 16.0 }),
         "Uppercase 'X' should create wall with height 16.0"
     );
     
     // Check that 'x' creates short walls (height 8.0)
     assert_eq!(
-        map.collision_grid.get(&(2, 0)),
+        map.layers[0].collision.get(&(2, 0)),
         Some(&TileType::Wall { height: 8.0 }),
         "Lowercase 'x' should create wall with height 8.0"
     );
     
     // Check that space creates empty tile
     assert_eq!(
-        map.collision_grid.get(&(3, 0)),
+        map.layers[0].collision.get(&(3, 0)),
         Some(&TileType::Empty),
         "Space should create empty tile"
     );
     
     // Check that '.' creates empty tile
     assert_eq!(
-        map.collision_grid.get(&(3, 1)),
+        map.layers[0].collision.get(&(3, 1)),
         Some(&TileType::Empty),
         "Period should create empty tile"
     );
@@ -120,6 +128,10 @@ fn test_is_solid() {
         ],
         items: vec![],
         actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
     };
     
     let map = Map::from_map_file(&test_map_file);
@@ -127,3 +139,559 @@ fn test_is_solid() {
     assert!(map.is_solid(0, 0), "Wall tile should be solid");
     assert!(!map.is_solid(1, 0), "Empty tile should not be solid");
 }
+
+#[test]
+fn test_has_line_of_sight() {
+    let test_map_file = MapFile {
+        grid: vec![
+            ".....".to_string(),
+            "..X..".to_string(),
+            ".....".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    };
+
+    let map = Map::from_map_file(&test_map_file);
+
+    // Horizontal line along an open row
+    assert!(map.has_line_of_sight(Vec2::new(4.0, 4.0), Vec2::new(36.0, 4.0)));
+    // Horizontal line through the wall
+    assert!(!map.has_line_of_sight(Vec2::new(4.0, 12.0), Vec2::new(36.0, 12.0)));
+    // Diagonal line through the wall cell
+    assert!(!map.has_line_of_sight(Vec2::new(4.0, 4.0), Vec2::new(36.0, 20.0)));
+    // Diagonal passing exactly through the wall's corner is blocked
+    assert!(!map.has_line_of_sight(Vec2::new(12.0, 12.0), Vec2::new(20.0, 20.0)));
+    // ...but the same diagonal next to the wall is clear
+    assert!(map.has_line_of_sight(Vec2::new(28.0, 12.0), Vec2::new(36.0, 20.0)));
+    // Same cell is always visible
+    assert!(map.has_line_of_sight(Vec2::new(1.0, 1.0), Vec2::new(7.0, 7.0)));
+    // Lines leaving the map are blocked
+    assert!(!map.has_line_of_sight(Vec2::new(4.0, 4.0), Vec2::new(-4.0, 4.0)));
+}
+
+#[test]
+fn test_mark_explored_from() {
+    let test_map_file = MapFile {
+        grid: vec![
+            "......".to_string(),
+            "..X...".to_string(),
+            "..X...".to_string(),
+            "..X...".to_string(),
+            "......".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    };
+
+    let mut map = Map::from_map_file(&test_map_file);
+    assert!(!map.is_explored(0, 2));
+
+    // Player standing in cell (0, 2), left of the wall column
+    let newly_explored = map.mark_explored_from(Vec2::new(4.0, 20.0), 24.0);
+    assert!(newly_explored > 0);
+
+    // The player's own cell and nearby open cells are explored
+    assert!(map.is_explored(0, 2));
+    assert!(map.is_explored(1, 2));
+    // The near face of the wall is visible
+    assert!(map.is_explored(2, 2));
+    // Cells hidden behind the wall are not
+    assert!(!map.is_explored(3, 2));
+    // Cells beyond the radius are not
+    assert!(!map.is_explored(5, 0));
+
+    // Marking again from the same spot finds nothing new
+    assert_eq!(map.mark_explored_from(Vec2::new(4.0, 20.0), 24.0), 0);
+}
+
+fn door_map_file(doors: Vec<DoorDef>) -> MapFile {
+    MapFile {
+        grid: vec![
+            "XXXXX".to_string(),
+            "XD..X".to_string(),
+            "X..DX".to_string(),
+            "XXXXX".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors,
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    }
+}
+
+fn door(x: i32, y: i32, target_x: i32, target_y: i32) -> DoorDef {
+    DoorDef { x, y, target_x, target_y }
+}
+
+#[test]
+fn test_door_cells_and_destinations() {
+    let map_file = door_map_file(vec![door(1, 1, 2, 2), door(3, 2, 2, 1)]);
+    assert_eq!(map_file.validate_doors(), Ok(()));
+
+    let map = Map::from_map_file(&map_file);
+
+    // Doors are walkable
+    assert!(!map.is_solid(1, 1));
+
+    // Standing anywhere in the door cell finds it
+    assert_eq!(world_to_grid(9.0, 15.5), (1, 1));
+    assert_eq!(map.door_destination(1, 1), Some(Vec2::new(20.0, 20.0)));
+    assert_eq!(map.door_destination(3, 2), Some(Vec2::new(20.0, 12.0)));
+
+    // Other cells are not doors
+    assert_eq!(map.door_destination(2, 1), None);
+}
+
+#[test]
+fn test_door_round_trips_through_map_file() {
+    let map = Map::from_map_file(&door_map_file(vec![door(1, 1, 2, 2), door(3, 2, 2, 1)]));
+    let saved = map.to_map_file();
+
+    assert_eq!(saved.grid[1], "XD..X");
+    assert_eq!(saved.doors, vec![door(1, 1, 2, 2), door(3, 2, 2, 1)]);
+}
+
+#[test]
+fn test_door_validation_errors() {
+    // Target outside the map
+    assert!(door_map_file(vec![door(1, 1, 9, 2), door(3, 2, 2, 1)]).validate_doors().is_err());
+    assert!(door_map_file(vec![door(1, 1, -1, 0), door(3, 2, 2, 1)]).validate_doors().is_err());
+    // Target is a wall
+    assert!(door_map_file(vec![door(1, 1, 0, 0), door(3, 2, 2, 1)]).validate_doors().is_err());
+    // Door definition not on a 'D' tile
+    let misplaced = vec![door(2, 1, 2, 2), door(1, 1, 2, 2), door(3, 2, 2, 1)];
+    assert!(door_map_file(misplaced).validate_doors().is_err());
+    // 'D' tile without a definition
+    assert!(door_map_file(vec![door(1, 1, 2, 2)]).validate_doors().is_err());
+}
+
+#[test]
+fn test_floor_cells() {
+    let test_map_file = MapFile {
+        grid: vec![
+            "X.x".to_string(),
+            " #.".to_string(),
+            "..".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    };
+
+    let map = Map::from_map_file(&test_map_file);
+
+    // Walls of any height are skipped, and cells past a short row are not
+    // part of the map
+    assert_eq!(
+        map.floor_cells_on(0),
+        vec![(1, 0), (0, 1), (2, 1), (0, 2), (1, 2)]
+    );
+}
+
+#[test]
+fn test_floor_mesh_has_a_quad_per_cell() {
+    let test_map_file = MapFile {
+        grid: vec!["X..".to_string(), "...".to_string()],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    };
+
+    let map = Map::from_map_file(&test_map_file);
+    let floor = map.create_floor_mesh();
+    let ceiling = map.create_ceiling_mesh();
+
+    assert_eq!(floor.count_vertices(), 5 * 4);
+    assert_eq!(floor.indices().unwrap().len(), 5 * 6);
+    assert_eq!(ceiling.count_vertices(), 5 * 4);
+}
+
+fn two_floor_map_file() -> MapFile {
+    MapFile {
+        grid: vec![
+            "XXXX".to_string(),
+            "X.>X".to_string(),
+            "XXXX".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![LayerDef {
+            z: 16.0,
+            grid: vec![
+                "XXXX".to_string(),
+                "XX<X".to_string(),
+                "X..X".to_string(),
+            ],
+        }],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn test_two_floor_map_solidity_per_layer() {
+    let map_file = two_floor_map_file();
+    assert_eq!(map_file.validate_stairs(), Ok(()));
+
+    let map = Map::from_map_file(&map_file);
+    assert_eq!(map.layers.len(), 2);
+    assert_eq!(map.layers[1].base_z, 16.0);
+    assert_eq!(map.active_layer, 0);
+
+    // (1, 1) is open downstairs but a wall upstairs, (1, 2) the reverse
+    assert!(!map.is_solid_on(0, 1, 1));
+    assert!(map.is_solid_on(1, 1, 1));
+    assert!(map.is_solid_on(0, 1, 2));
+    assert!(!map.is_solid_on(1, 1, 2));
+
+    // Collision follows the active floor
+    assert!(!map.is_solid(1, 1));
+    assert!(map.is_solid(1, 2));
+
+    // Unknown floors are never solid
+    assert!(!map.is_solid_on(5, 0, 0));
+}
+
+#[test]
+fn test_switching_floors() {
+    let mut map = Map::from_map_file(&two_floor_map_file());
+
+    assert_eq!(map.stairs_destination(2, 1), Some(1));
+    assert_eq!(map.stairs_destination(1, 1), None);
+
+    assert!(map.set_active_layer(1));
+    assert!(map.is_solid(1, 1));
+    assert!(!map.is_solid(1, 2));
+    assert!(!map.is_solid_on(0, 1, 1));
+    assert_eq!(map.stairs_destination(2, 1), Some(0));
+
+    assert!(!map.set_active_layer(2));
+    assert_eq!(map.active_layer, 1);
+
+    assert!(map.set_active_layer(0));
+    assert!(!map.is_solid(1, 1));
+}
+
+#[test]
+fn test_collision_queries_use_the_given_floor() {
+    let map = Map::from_map_file(&two_floor_map_file());
+    assert_eq!(map.active_layer, 0);
+
+    // Something upstairs moves against upstairs walls while the player is
+    // downstairs
+    assert!(map.can_move_to_on(1, 12.0, 20.0, 1.2));
+    assert!(!map.can_move_to(12.0, 20.0, 1.2));
+    assert!(!map.can_move_to_on(1, 12.0, 12.0, 1.2));
+    assert!(map.can_move_to(12.0, 12.0, 1.2));
+
+    assert!(map.has_line_of_sight_on(1, Vec2::new(12.0, 20.0), Vec2::new(20.0, 20.0)));
+    assert!(!map.has_line_of_sight(Vec2::new(12.0, 20.0), Vec2::new(20.0, 20.0)));
+}
+
+#[test]
+fn test_remove_wall_only_touches_its_floor() {
+    use bevy::ecs::world::CommandQueue;
+
+    let mut map = Map::from_map_file(&two_floor_map_file());
+    let world = World::new();
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+    let wall = commands.spawn_empty().id();
+    map.layers[1].walls.insert((1, 1), wall);
+
+    // The ground floor has no wall entity there, so nothing is removed
+    assert!(!map.remove_wall(&mut commands, 0, 1, 1));
+    assert!(map.is_solid_on(1, 1, 1));
+
+    assert!(map.remove_wall(&mut commands, 1, 1, 1));
+    assert!(!map.is_solid_on(1, 1, 1));
+    assert!(map.layers[1].walls.is_empty());
+    assert!(map.is_solid_on(0, 1, 2));
+}
+
+#[test]
+fn test_single_floor_map_has_one_layer() {
+    let map = Map::from_map_file(&door_map_file(vec![]));
+    assert_eq!(map.layers.len(), 1);
+    assert!(map.to_map_file().layers.is_empty());
+}
+
+#[test]
+fn test_layers_round_trip_through_map_file() {
+    let mut map = Map::from_map_file(&two_floor_map_file());
+    // Saving works the same whichever floor is active
+    map.set_active_layer(1);
+    let saved = map.to_map_file();
+
+    assert_eq!(saved.grid, two_floor_map_file().grid);
+    assert_eq!(saved.layers, two_floor_map_file().layers);
+}
+
+#[test]
+fn test_stairs_validation_errors() {
+    // Stairs up from the top floor
+    let mut map_file = two_floor_map_file();
+    map_file.layers[0].grid[2] = "X.>X".to_string();
+    assert!(map_file.validate_stairs().is_err());
+
+    // Stairs down from the ground floor
+    let mut map_file = two_floor_map_file();
+    map_file.grid[1] = "X<>X".to_string();
+    assert!(map_file.validate_stairs().is_err());
+
+    // Stairs up into a wall
+    let mut map_file = two_floor_map_file();
+    map_file.layers[0].grid[1] = "XXXX".to_string();
+    assert!(map_file.validate_stairs().is_err());
+}
+
+fn terrain_map_file() -> MapFile {
+    MapFile {
+        grid: vec![
+            "XXXXX".to_string(),
+            "X~=,X".to_string(),
+            "XXXXX".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::from([(',', 2.0)]),
+        wall_heights: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn test_terrain_tile_costs() {
+    let map = Map::from_map_file(&terrain_map_file());
+
+    assert_eq!(map.move_cost_on(map.active_layer, 1, 1), 3.0);
+    assert_eq!(map.move_cost_on(map.active_layer, 2, 1), 0.5);
+    assert_eq!(map.move_cost_on(map.active_layer, 3, 1), 2.0);
+    assert_eq!(map.min_move_cost_on(map.active_layer), 0.5);
+
+    // Terrain tiles are still open floor
+    assert!(!map.is_solid(1, 1));
+    assert!(!map.is_solid(3, 1));
+
+    // Walls and unlisted tiles cost the default
+    assert_eq!(map.move_cost_on(map.active_layer, 0, 0), DEFAULT_MOVE_COST);
+    assert_eq!(map.layers[0].costs.len(), 3);
+}
+
+#[test]
+fn test_map_file_can_override_built_in_tile_costs() {
+    let mut map_file = terrain_map_file();
+    map_file.tile_costs.insert('~', 5.0);
+
+    let map = Map::from_map_file(&map_file);
+    assert_eq!(map.move_cost_on(map.active_layer, 1, 1), 5.0);
+}
+
+#[test]
+fn test_tile_costs_round_trip_through_map_file() {
+    let map_file = terrain_map_file();
+    let saved = Map::from_map_file(&map_file).to_map_file();
+
+    assert_eq!(saved.grid, map_file.grid);
+    assert_eq!(saved.tile_costs, map_file.tile_costs);
+}
+
+#[test]
+fn test_tile_costs_follow_active_floor() {
+    let mut map_file = two_floor_map_file();
+    map_file.layers[0].grid[2] = "X=.X".to_string();
+
+    let mut map = Map::from_map_file(&map_file);
+    assert_eq!(map.move_cost_on(map.active_layer, 1, 2), DEFAULT_MOVE_COST);
+
+    map.set_active_layer(1);
+    assert_eq!(map.move_cost_on(map.active_layer, 1, 2), 0.5);
+    assert_eq!(map.to_map_file().layers[0].grid[2], "X=.X");
+
+    map.set_active_layer(0);
+    assert!(map.layers[0].costs.is_empty());
+}
+
+#[test]
+fn test_tile_cost_validation() {
+    assert_eq!(terrain_map_file().validate_tile_costs(), Ok(()));
+
+    let mut map_file = terrain_map_file();
+    map_file.tile_costs.insert(',', 0.0);
+    assert!(map_file.validate_tile_costs().is_err());
+
+    map_file.tile_costs.insert(',', f32::NAN);
+    assert!(map_file.validate_tile_costs().is_err());
+}
+
+fn custom_wall_map_file() -> MapFile {
+    let mut map_file = terrain_map_file();
+    map_file.grid[1] = "Xw.xX".to_string();
+    map_file.wall_heights = BTreeMap::from([('w', 12.0)]);
+    map_file
+}
+
+#[test]
+fn test_custom_wall_height_round_trips() {
+    let map_file = custom_wall_map_file();
+    let map = Map::from_map_file(&map_file);
+    assert_eq!(
+        map.layers[0].collision.get(&(1, 1)),
+        Some(&TileType::Wall { height: 12.0 })
+    );
+
+    let yaml = serde_yaml::to_string(&map.to_map_file()).unwrap();
+    let reloaded: MapFile = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(reloaded.grid, map_file.grid);
+    assert_eq!(reloaded.wall_heights, map_file.wall_heights);
+
+    let map = Map::from_map_file(&reloaded);
+    assert_eq!(
+        map.layers[0].collision.get(&(1, 1)),
+        Some(&TileType::Wall { height: 12.0 })
+    );
+    assert_eq!(
+        map.layers[0].collision.get(&(3, 1)),
+        Some(&TileType::Wall { height: 8.0 })
+    );
+}
+
+#[test]
+fn test_wall_without_tile_gets_a_new_tile() {
+    let mut map = Map::from_map_file(&terrain_map_file());
+    map.layers[0].collision
+        .insert((1, 1), TileType::Wall { height: 12.0 });
+    map.layers[0].collision
+        .insert((2, 1), TileType::Wall { height: 13.0 });
+    map.layers[0].collision
+        .insert((3, 1), TileType::Wall { height: 12.0 });
+
+    let map_file = map.to_map_file();
+    assert_eq!(map_file.grid[1], "XabaX");
+    assert_eq!(
+        map_file.wall_heights,
+        BTreeMap::from([('a', 12.0), ('b', 13.0)])
+    );
+}
+
+#[test]
+fn test_undeclared_wall_height_survives_save_and_load() {
+    let mut map = Map::from_map_file(&custom_wall_map_file());
+    // As placed by `addwall 2 1 10`
+    map.layers[0].collision
+        .insert((2, 1), TileType::Wall { height: 10.0 });
+
+    let yaml = serde_yaml::to_string(&map.to_map_file()).unwrap();
+    let reloaded: MapFile = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(reloaded.validate_wall_heights(), Ok(()));
+    let reloaded = Map::from_map_file(&reloaded);
+
+    assert_eq!(
+        reloaded.layers[0].collision.get(&(2, 1)),
+        Some(&TileType::Wall { height: 10.0 })
+    );
+    assert_eq!(reloaded.layers[0].collision, map.layers[0].collision);
+    assert_eq!(reloaded.to_map_file(), map.to_map_file());
+}
+
+#[test]
+fn test_map_can_override_built_in_wall_height() {
+    let mut map_file = custom_wall_map_file();
+    map_file.wall_heights.insert('x', 4.0);
+
+    let map = Map::from_map_file(&map_file);
+    assert_eq!(
+        map.layers[0].collision.get(&(3, 1)),
+        Some(&TileType::Wall { height: 4.0 })
+    );
+    assert_eq!(map.to_map_file().grid, map_file.grid);
+}
+
+#[test]
+fn test_wall_height_validation() {
+    assert_eq!(custom_wall_map_file().validate_wall_heights(), Ok(()));
+
+    let mut map_file = custom_wall_map_file();
+    map_file.wall_heights.insert('w', -1.0);
+    assert!(map_file.validate_wall_heights().is_err());
+
+    for tile in ['D', '.', '>', '<'] {
+        let mut map_file = custom_wall_map_file();
+        map_file.wall_heights.insert(tile, 12.0);
+        let error = map_file.validate_wall_heights().unwrap_err();
+        assert!(error.contains("reserved"), "{}", error);
+    }
+}
+
+#[test]
+fn test_stairs_into_custom_wall_are_rejected() {
+    let mut map_file = two_floor_map_file();
+    map_file.wall_heights.insert('w', 12.0);
+    assert_eq!(map_file.validate_stairs(), Ok(()));
+
+    map_file.layers[0].grid[1] = "wwww".to_string();
+    assert!(map_file.validate_stairs().is_err());
+}
+
+fn pinch_map() -> Map {
+    // Walls at (2, 1) and (1, 2) touch only at the corner point (16, 16)
+    Map::from_map_file(&MapFile {
+        grid: vec![
+            "....".to_string(),
+            "..X.".to_string(),
+            ".X..".to_string(),
+            "....".to_string(),
+        ],
+        items: vec![],
+        actors: vec![],
+        doors: vec![],
+        layers: vec![],
+        tile_costs: BTreeMap::new(),
+        wall_heights: BTreeMap::new(),
+    })
+}
+
+#[test]
+fn test_can_move_to_blocks_diagonal_pinch() {
+    let map = pinch_map();
+    let half_size = 1.6;
+
+    // Box in the open cell (2, 2) with its corner exactly on the pinch point
+    assert!(!map.can_move_to(16.0 + half_size, 16.0 + half_size, half_size));
+    // Same on the other side, in cell (1, 1)
+    assert!(!map.can_move_to(16.0 - half_size, 16.0 - half_size, half_size));
+    // A point-sized mover exactly on the pinch
+    assert!(!map.can_move_to(16.0, 16.0, 0.0));
+}
+
+#[test]
+fn test_can_move_to_allows_positions_near_pinch() {
+    let map = pinch_map();
+    let half_size = 1.6;
+
+    // Just clear of the pinch point
+    assert!(map.can_move_to(16.1 + half_size, 16.1 + half_size, half_size));
+    // Touching a grid corner with no walls around it
+    assert!(map.can_move_to(8.0 + half_size, 8.0 + half_size, half_size));
+    // Touching the corner of a lone wall is fine
+    assert!(map.can_move_to(24.0 + half_size, 8.0 - half_size, half_size));
+}
@@ -5,9 +5,11 @@ pub mod map;
 mod map_test;
 pub mod plugin;
 pub mod systems;
+#[cfg(test)]
+mod systems_test;
 
 pub use collision::{CollisionMap, PLAYER_RADIUS, check_circle_collision};
 pub use editor::MapEditorPlugin;
-pub use map::{Map, MapFile, TileType};
+pub use map::{CurrentMap, GRID_SIZE, Map, MapFile, TileType, map_path};
 pub use plugin::WorldPlugin;
-pub use systems::GroundPlane;
+pub use systems::{DEFAULT_SURFACE_TEXTURE, GroundPlane, setup_world};
@@ -1,11 +1,28 @@
 use bevy::prelude::*;
 use crate::game_state::GameState;
-use super::systems::setup_world;
+use super::map::CurrentMap;
+use super::systems::{
+    setup_world, update_ceiling_visibility, update_door_fade, update_doors, update_explored_cells,
+    update_floor_and_ceiling_meshes, update_stairs,
+};
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Playing), setup_world);
+        app.init_resource::<CurrentMap>()
+            .add_systems(OnEnter(GameState::Playing), setup_world)
+            .add_systems(
+                Update,
+                (
+                    update_explored_cells,
+                    update_doors,
+                    update_stairs,
+                    update_door_fade,
+                    update_ceiling_visibility,
+                    update_floor_and_ceiling_meshes,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
     }
 }
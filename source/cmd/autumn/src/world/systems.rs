@@ -1,80 +1,179 @@
 use super::Map;
-use crate::actor::{ActorDefinitions, ActorDefinitionsFile};
+use super::map::CurrentMap;
+use crate::actor::ActorDefinitions;
+use crate::ai::pathfinding::world_to_grid;
+use crate::camera::Player;
 use crate::camera::{spawn_camera, spawn_player_lights};
 use crate::combat::{CombatAudio, WeaponDefinitions};
-use crate::game_state::GamePlayEntity;
-use crate::item::{ItemDefinitions, ItemDefinitionsFile};
+use crate::game_state::{GamePlayEntity, PendingLoad};
+use crate::item::ItemDefinitions;
 use crate::rendering::load_image_texture;
 use crate::scripting::CVarRegistry;
 use crate::weapon::spawn_weapon_sprite;
 use bevy::prelude::*;
-use std::f32::consts::FRAC_PI_2;
+use fallgray_bevy_ui::EntityCommandsUIExt;
+
+/// How far the player can see when exploring the map, in world units
+const EXPLORE_RADIUS: f32 = 48.0;
+
+/// How long the screen takes to fade back in after going through a door
+const DOOR_FADE_DURATION: f32 = 0.4;
+
+/// Full-screen overlay that fades out after the player uses a door
+#[derive(Component)]
+pub struct DoorFade {
+    remaining: f32,
+}
 
 #[derive(Component)]
 pub struct GroundPlane;
 
-/// System to set up the game world when entering Playing state
-pub fn setup_world(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
-    mut cvars: ResMut<CVarRegistry>,
-) {
-    // Create ground planes
-    let plane_mesh = meshes.add(Plane3d::default().mesh().size(512.0, 512.0));
-    let plane_material = materials.add(StandardMaterial {
-        base_color_texture: Some(load_image_texture(
-            &asset_server,
-            "base/textures/stone_1.png",
-        )),
+/// Texture used for floors and ceilings unless overridden by cvars
+pub const DEFAULT_SURFACE_TEXTURE: &str = "base/textures/stone_1.png";
+
+/// Marker for the ceiling mesh, hidden when `r_ceiling` is off
+#[derive(Component)]
+pub struct Ceiling;
+
+/// Floor mesh of one layer of the map
+#[derive(Component)]
+pub struct FloorSurface {
+    pub layer: usize,
+}
+
+fn surface_texture(cvars: &CVarRegistry, name: &str) -> String {
+    cvars
+        .get(name)
+        .map(|v| v.as_string())
+        .unwrap_or_else(|| DEFAULT_SURFACE_TEXTURE.to_string())
+}
+
+fn cell_surface_material(asset_server: &Res<AssetServer>, texture: &str) -> StandardMaterial {
+    StandardMaterial {
+        base_color_texture: Some(load_image_texture(asset_server, texture)),
         base_color: Color::WHITE,
         perceptual_roughness: 1.0,
         metallic: 0.0,
         reflectance: 0.0,
-        uv_transform: bevy::math::Affine2::from_scale(Vec2::new(64.0, 64.0)),
         ..default()
-    });
+    }
+}
 
+/// Spawn the floor and ceiling meshes covering every open cell of the map
+fn spawn_floor_and_ceiling(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    asset_server: &Res<AssetServer>,
+    map: &Map,
+    cvars: &CVarRegistry,
+) {
+    let floor_texture = surface_texture(cvars, "r_floor_texture");
+    let floor_material = materials.add(cell_surface_material(asset_server, &floor_texture));
     commands.spawn((
         GamePlayEntity,
-        Mesh3d(plane_mesh.clone()),
-        MeshMaterial3d(plane_material.clone()),
-        // Rotate 90 degrees around X to make it XY plane (facing Z)
-        Transform::from_rotation(Quat::from_rotation_x(FRAC_PI_2))
-            .with_translation(Vec3::new(256.0, 256.0, 0.0)),
-        GroundPlane,
+        FloorSurface { layer: 0 },
+        Mesh3d(meshes.add(map.create_floor_mesh())),
+        MeshMaterial3d(floor_material.clone()),
+        Transform::default(),
     ));
 
+    // Upper floors have a floor but no ceiling of their own
+    for layer in 1..map.layers.len() {
+        commands.spawn((
+            GamePlayEntity,
+            FloorSurface { layer },
+            Mesh3d(meshes.add(map.create_layer_floor_mesh(layer))),
+            MeshMaterial3d(floor_material.clone()),
+            Transform::default(),
+        ));
+    }
+
+    let ceiling_texture = surface_texture(cvars, "r_ceiling_texture");
     commands.spawn((
         GamePlayEntity,
-        Mesh3d(plane_mesh.clone()),
-        MeshMaterial3d(plane_material.clone()),
-        Transform::from_rotation(Quat::from_rotation_x(3.0 * FRAC_PI_2))
-            .with_translation(Vec3::new(256.0, 256.0, 16.0)),
+        Ceiling,
+        Mesh3d(meshes.add(map.create_ceiling_mesh())),
+        MeshMaterial3d(materials.add(cell_surface_material(asset_server, &ceiling_texture))),
+        Transform::default(),
+        ceiling_visibility(cvars),
     ));
+}
+
+fn ceiling_visibility(cvars: &CVarRegistry) -> Visibility {
+    let enabled = cvars.get_bool_or("r_ceiling", true);
+    if enabled {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// System to show or hide the ceiling when `r_ceiling` changes
+pub fn update_ceiling_visibility(
+    cvars: Res<CVarRegistry>,
+    mut ceiling_query: Query<&mut Visibility, With<Ceiling>>,
+) {
+    let visibility = ceiling_visibility(&cvars);
+    for mut current in ceiling_query.iter_mut() {
+        current.set_if_neq(visibility);
+    }
+}
+
+/// System to rebuild the floor and ceiling meshes after walls are added or
+/// removed, so a removed wall leaves floor behind and a new wall doesn't
+/// stand on one
+pub fn update_floor_and_ceiling_meshes(
+    mut map: ResMut<Map>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    floors: Query<(&FloorSurface, &Mesh3d)>,
+    ceilings: Query<&Mesh3d, With<Ceiling>>,
+) {
+    if !map.surfaces_dirty {
+        return;
+    }
+    map.surfaces_dirty = false;
+
+    for (floor, handle) in floors.iter() {
+        if let Some(mesh) = meshes.get_mut(&handle.0) {
+            *mesh = map.create_layer_floor_mesh(floor.layer);
+        }
+    }
+    for handle in ceilings.iter() {
+        if let Some(mesh) = meshes.get_mut(&handle.0) {
+            *mesh = map.create_ceiling_mesh();
+        }
+    }
+}
+
+/// System to set up the game world when entering Playing state
+pub fn setup_world(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    mut cvars: ResMut<CVarRegistry>,
+    pending_load: Res<PendingLoad>,
+    current_map: Res<CurrentMap>,
+) {
+    // Clicks in the editor are resolved against the z = 0 plane
+    commands.spawn((GamePlayEntity, GroundPlane, Transform::default()));
 
     // Load item definitions
     let filename = std::env::var("REPO_ROOT")
         .map(|repo_root| format!("{}/source/assets/base/items/items.yaml", repo_root))
-        .unwrap_or_else(|_| "data/item_definitions.yaml".to_string());
+        .unwrap_or_else(|_| "data/items.yaml".to_string());
     let item_defs_yaml = std::fs::read_to_string(&filename)
         .unwrap_or_else(|_| panic!("Failed to read {}", filename));
-    let item_defs_file: ItemDefinitionsFile = serde_yaml::from_str(&item_defs_yaml)
-        .unwrap_or_else(|_| panic!("Failed to parse {}", filename));
-    let item_definitions = ItemDefinitions {
-        items: item_defs_file.items,
-    };
+    let item_definitions = ItemDefinitions::from_yaml(&item_defs_yaml)
+        .unwrap_or_else(|e| panic!("Failed to load {}:\n{}", filename, e));
 
     // Load actor definitions
     let actor_filename = "data/actor_definitions.yaml".to_string();
     let actor_defs_yaml = std::fs::read_to_string(&actor_filename)
         .unwrap_or_else(|_| panic!("Failed to read {}", actor_filename));
-    let actor_defs_file: ActorDefinitionsFile = serde_yaml::from_str(&actor_defs_yaml)
-        .unwrap_or_else(|_| panic!("Failed to parse {}", actor_filename));
-    let actor_definitions = ActorDefinitions {
-        actors: actor_defs_file.actors,
-    };
+    let actor_definitions = ActorDefinitions::from_yaml(&actor_defs_yaml)
+        .unwrap_or_else(|e| panic!("Failed to load {}:\n{}", actor_filename, e));
 
     // Load weapon definitions
     let weapon_filename = std::env::var("REPO_ROOT")
@@ -86,16 +185,39 @@ pub fn setup_world(
     // Register weapon CVars for runtime tuning
     weapon_definitions.register_cvars(&mut cvars);
 
-    // Load map from file and spawn all entities
-    let map = Map::load_from_file(
+    // Load the map from the save being loaded, if any, or else from file,
+    // and spawn all entities
+    let mut map = match &pending_load.0 {
+        Some(save) => Map::spawn_from_map_file(
+            &save.map,
+            &mut commands,
+            &asset_server,
+            &mut meshes,
+            &mut materials,
+            &item_definitions,
+            &actor_definitions,
+        ),
+        None => Map::load_from_file(
+            &current_map.0,
+            &mut commands,
+            &asset_server,
+            &mut meshes,
+            &mut materials,
+            &item_definitions,
+            &actor_definitions,
+        )
+        .expect("Failed to load map"),
+    };
+
+    spawn_floor_and_ceiling(
         &mut commands,
-        &asset_server,
         &mut meshes,
         &mut materials,
-        &item_definitions,
-        &actor_definitions,
-    )
-    .expect("Failed to load map");
+        &asset_server,
+        &map,
+        &cvars,
+    );
+    map.surfaces_dirty = false;
 
     commands.insert_resource(map);
     commands.insert_resource(item_definitions);
@@ -131,3 +253,121 @@ pub fn setup_world(
     let combat_audio = CombatAudio::load_sounds(&asset_server);
     commands.insert_resource(combat_audio);
 }
+
+/// System to mark the cells around the player as explored. The work is only
+/// done when the player moves into a different cell.
+pub fn update_explored_cells(
+    player_query: Query<&Transform, With<Player>>,
+    mut map: ResMut<Map>,
+    mut last_cell: Local<Option<(i32, i32)>>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    let position = player_transform.translation.truncate();
+    let cell = world_to_grid(position.x, position.y);
+
+    // A newly loaded map starts with nothing explored, even if the player
+    // spawns in the same cell as before
+    if *last_cell == Some(cell) && !map.explored.is_empty() {
+        return;
+    }
+    *last_cell = Some(cell);
+
+    map.mark_explored_from(position, EXPLORE_RADIUS);
+}
+
+/// System to teleport the player when they step onto a door cell. Only
+/// entering a door triggers it, so arriving on another door doesn't bounce
+/// the player straight back.
+pub fn update_doors(
+    mut commands: Commands,
+    mut player_query: Query<&mut Transform, With<Player>>,
+    map: Res<Map>,
+    mut last_cell: Local<Option<(i32, i32)>>,
+) {
+    let Ok(mut player_transform) = player_query.single_mut() else {
+        return;
+    };
+
+    let cell = world_to_grid(
+        player_transform.translation.x,
+        player_transform.translation.y,
+    );
+    if *last_cell == Some(cell) {
+        return;
+    }
+    *last_cell = Some(cell);
+
+    // Doors only exist on the ground floor
+    if map.active_layer != 0 {
+        return;
+    }
+    let Some(destination) = map.door_destination(cell.0, cell.1) else {
+        return;
+    };
+
+    player_transform.translation.x = destination.x;
+    player_transform.translation.y = destination.y;
+    *last_cell = Some(world_to_grid(destination.x, destination.y));
+
+    commands
+        .spawn((
+            GamePlayEntity,
+            DoorFade {
+                remaining: DOOR_FADE_DURATION,
+            },
+            GlobalZIndex(100),
+        ))
+        .styles(&vec![
+            "absolute width-100% height-100%",
+            "bg-rgba(0.0,0.0,0.0,1.0)",
+        ]);
+}
+
+/// System to move the player between floors when they step onto stairs.
+/// Like doors, only entering the stairs cell triggers it, so arriving on the
+/// matching stairs of the other floor doesn't send the player straight back.
+pub fn update_stairs(
+    mut player_query: Query<&mut Transform, With<Player>>,
+    mut map: ResMut<Map>,
+    mut last_cell: Local<Option<(i32, i32)>>,
+) {
+    let Ok(mut player_transform) = player_query.single_mut() else {
+        return;
+    };
+
+    let cell = world_to_grid(
+        player_transform.translation.x,
+        player_transform.translation.y,
+    );
+    if *last_cell == Some(cell) {
+        return;
+    }
+    *last_cell = Some(cell);
+
+    let Some(target) = map.stairs_destination(cell.0, cell.1) else {
+        return;
+    };
+
+    let rise = map.layers[target].base_z - map.layers[map.active_layer].base_z;
+    map.set_active_layer(target);
+    player_transform.translation.z += rise;
+}
+
+/// System to fade out and remove door transition overlays
+pub fn update_door_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut fade_query: Query<(Entity, &mut DoorFade, &mut BackgroundColor)>,
+) {
+    for (entity, mut fade, mut background) in fade_query.iter_mut() {
+        fade.remaining -= time.delta_secs();
+        if fade.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        } else {
+            background.0.set_alpha(fade.remaining / DOOR_FADE_DURATION);
+        }
+    }
+}
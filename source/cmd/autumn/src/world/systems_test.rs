@@ -0,0 +1,63 @@
+use super::systems::{Ceiling, FloorSurface, update_floor_and_ceiling_meshes};
+use super::{Map, TileType};
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+
+/// A row of three cells with a wall in the middle
+fn corridor_map() -> Map {
+    let mut map = Map::new(3, 1);
+    let collision = &mut map.layers[0].collision;
+    collision.insert((0, 0), TileType::Empty);
+    collision.insert((1, 0), TileType::Wall { height: 16.0 });
+    collision.insert((2, 0), TileType::Empty);
+    map
+}
+
+fn vertex_count<C: Component>(app: &mut App) -> usize {
+    let world = app.world_mut();
+    let handle = world
+        .query_filtered::<&Mesh3d, With<C>>()
+        .single(world)
+        .unwrap()
+        .0
+        .clone();
+    world
+        .resource::<Assets<Mesh>>()
+        .get(&handle)
+        .unwrap()
+        .count_vertices()
+}
+
+#[test]
+fn test_removing_wall_rebuilds_floor_and_ceiling() {
+    let map = corridor_map();
+    let mut meshes = Assets::<Mesh>::default();
+    let floor = meshes.add(map.create_floor_mesh());
+    let ceiling = meshes.add(map.create_ceiling_mesh());
+
+    let mut app = App::new();
+    app.insert_resource(map)
+        .insert_resource(meshes)
+        .add_systems(Update, update_floor_and_ceiling_meshes);
+    app.world_mut()
+        .spawn((FloorSurface { layer: 0 }, Mesh3d(floor)));
+    app.world_mut().spawn((Ceiling, Mesh3d(ceiling)));
+
+    // Nothing changed yet, so the meshes keep their two open cells
+    app.update();
+    assert_eq!(vertex_count::<FloorSurface>(&mut app), 2 * 4);
+    assert_eq!(vertex_count::<Ceiling>(&mut app), 2 * 4);
+
+    let mut queue = CommandQueue::default();
+    let world = app.world_mut();
+    world.resource_scope(|world, mut map: Mut<Map>| {
+        let mut commands = Commands::new(&mut queue, world);
+        map.remove_wall(&mut commands, 0, 1, 0);
+    });
+    queue.apply(world);
+
+    app.update();
+    assert_eq!(vertex_count::<FloorSurface>(&mut app), 3 * 4);
+    assert_eq!(vertex_count::<Ceiling>(&mut app), 3 * 4);
+    assert!(!app.world().resource::<Map>().surfaces_dirty);
+}
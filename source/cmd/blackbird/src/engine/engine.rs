@@ -1,10 +1,13 @@
 use super::engine_ctx::EngineCtx;
 use super::engine_queue::EngineQueue;
-use super::engine_task::{EngineTask, EngineTaskHandle};
+use super::engine_task::{
+    EngineTask, EngineTaskHandle, TaskPhase, run_tasks_recovering, sort_tasks_by_phase,
+};
 use super::entity_database::EntityDatabase;
 use crate::core;
 use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use wgpu::hal::auxil::db::qualcomm;
 use wgpu::wgc::device::queue;
 use winit::window::Window;
@@ -16,13 +19,59 @@ pub enum EngineError {
 
 pub type EngineWindow = Arc<Window>;
 
+/// Time between frames while the window doesn't have focus. The scene keeps
+/// animating, just slowly enough that an unattended window isn't busy
+/// redrawing.
+pub const UNFOCUSED_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct EngineInternalState {
     current_frame: usize,
+    surface_size: Option<(usize, usize)>,
+    focused: bool,
+    last_frame_at: Option<Instant>,
 }
 
 impl EngineInternalState {
     pub fn new() -> Self {
-        Self { current_frame: 0 }
+        Self {
+            current_frame: 0,
+            surface_size: None,
+            focused: true,
+            last_frame_at: None,
+        }
+    }
+
+    /// Record the window gaining or losing focus
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// When the next frame is due. `None` means frames run back to back,
+    /// which is the case while the window has focus.
+    pub fn next_frame_at(&self) -> Option<Instant> {
+        if self.focused {
+            return None;
+        }
+        self.last_frame_at
+            .map(|last| last + UNFOCUSED_FRAME_INTERVAL)
+    }
+
+    /// Record the new surface size from a window resize. Zero sizes (e.g. a
+    /// minimized window) are ignored so the last usable size is kept.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        if width > 0 && height > 0 {
+            self.surface_size = Some((width, height));
+        }
+    }
+
+    /// Surface size for the next frame. Until the first resize arrives this
+    /// is the window's initial size.
+    pub fn surface_size(&self, window_size: (usize, usize)) -> (usize, usize) {
+        self.surface_size.unwrap_or(window_size)
     }
 }
 
@@ -63,8 +112,33 @@ impl Engine {
         super::prelude::run_event_loop(self.clone());
     }
 
+    /// Called by the window when it is resized.
+    ///
+    /// The size is picked up by the next frame's `EngineCtx`, so every task in
+    /// that frame sees the same dimensions: Update tasks (e.g. setting the
+    /// camera aspect ratio) run first, then the Render task reconfigures the
+    /// surface to match before drawing.
+    pub fn resize(&self, width: usize, height: usize) {
+        let mut state = self.internal_state.lock().unwrap();
+        state.resize(width, height);
+    }
+
+    /// Called by the window when it gains or loses focus. While unfocused,
+    /// frames are throttled to `UNFOCUSED_FRAME_INTERVAL`.
+    pub fn set_focused(&self, focused: bool) {
+        let mut state = self.internal_state.lock().unwrap();
+        state.set_focused(focused);
+    }
+
+    /// When the window should draw its next frame, see
+    /// `EngineInternalState::next_frame_at`
+    pub fn next_frame_at(&self) -> Option<Instant> {
+        let state = self.internal_state.lock().unwrap();
+        state.next_frame_at()
+    }
+
     fn make_context(&self, window: EngineWindow) -> EngineCtx {
-        let (width, height) = {
+        let window_size = {
             let size = window.inner_size();
             (size.width as usize, size.height as usize)
         };
@@ -72,11 +146,14 @@ impl Engine {
         let mut ctx = {
             let mut state = self.internal_state.lock().unwrap();
             state.current_frame += 1;
+            state.last_frame_at = Some(Instant::now());
+            let (width, height) = state.surface_size(window_size);
 
             EngineCtx {
                 frame: state.current_frame,
                 surface_width: width,
                 surface_height: height,
+                focused: state.focused,
                 window: window.clone(),
                 queue: EngineQueue::new(),
                 database: EntityDatabase::new(),
@@ -90,6 +167,7 @@ impl Engine {
 
         let mut tasks = Vec::new();
         self.swap_tasks(&mut tasks);
+        sort_tasks_by_phase(&mut tasks);
 
         // Swap the contents of the engine database and context database
         {
@@ -97,15 +175,13 @@ impl Engine {
             ctx.database.swap_entities(&mut db);
         }
 
-        let mut next_tasks = Vec::with_capacity(tasks.len());
-        for mut task_handle in tasks {
-            if task_handle.run_frame(&mut ctx) {
-                next_tasks.push(task_handle);
-            }
+        let mut next_tasks = run_tasks_recovering(tasks, |task_handle| {
+            let keep = task_handle.run_frame(&mut ctx);
             if ctx.queue.entities.len() > 0 {
                 ctx.database.append(&mut ctx.queue.entities);
             }
-        }
+            keep
+        });
         {
             let mut db = self.database.lock().unwrap();
             ctx.database.swap_entities(&mut db);
@@ -138,9 +214,75 @@ impl Engine {
                 (self.f.borrow_mut())(ctx);
                 false
             }
+            fn phase(&self) -> TaskPhase {
+                TaskPhase::Setup
+            }
         }
         let handle = EngineTaskHandle::new(TaskOnce { f: RefCell::new(f) });
         let mut tasks = self.tasks.lock().unwrap();
         tasks.push(handle);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surface_size_defaults_to_window_size() {
+        let state = EngineInternalState::new();
+        assert_eq!(state.surface_size((800, 600)), (800, 600));
+    }
+
+    #[test]
+    fn test_resize_updates_surface_size() {
+        let mut state = EngineInternalState::new();
+        state.resize(1024, 768);
+        assert_eq!(state.surface_size((800, 600)), (1024, 768));
+
+        state.resize(640, 480);
+        assert_eq!(state.surface_size((800, 600)), (640, 480));
+    }
+
+    #[test]
+    fn test_zero_resize_keeps_last_size() {
+        let mut state = EngineInternalState::new();
+        state.resize(1024, 768);
+        state.resize(0, 0);
+        state.resize(1024, 0);
+        assert_eq!(state.surface_size((800, 600)), (1024, 768));
+    }
+
+    #[test]
+    fn test_focus_toggles() {
+        let mut state = EngineInternalState::new();
+        assert!(state.focused());
+
+        state.set_focused(false);
+        assert!(!state.focused());
+        state.set_focused(true);
+        assert!(state.focused());
+    }
+
+    #[test]
+    fn test_unfocused_frames_are_throttled() {
+        let mut state = EngineInternalState::new();
+        let now = Instant::now();
+        state.last_frame_at = Some(now);
+        assert_eq!(state.next_frame_at(), None);
+
+        state.set_focused(false);
+        assert_eq!(state.next_frame_at(), Some(now + UNFOCUSED_FRAME_INTERVAL));
+
+        // Refocusing goes straight back to running every frame
+        state.set_focused(true);
+        assert_eq!(state.next_frame_at(), None);
+    }
+
+    #[test]
+    fn test_unfocused_before_first_frame_runs_immediately() {
+        let mut state = EngineInternalState::new();
+        state.set_focused(false);
+        assert_eq!(state.next_frame_at(), None);
+    }
+}
@@ -6,6 +6,8 @@ pub struct EngineCtx {
     pub frame: usize,
     pub surface_width: usize,
     pub surface_height: usize,
+    /// Whether the window has focus. Frames are throttled while it doesn't.
+    pub focused: bool,
 
     pub window: EngineWindow,
     pub queue: EngineQueue,
@@ -1,5 +1,5 @@
 use super::engine_ctx::EngineCtx;
-use super::engine_task::EngineTask;
+use super::engine_task::{EngineTask, TaskPhase};
 use std::cell::RefCell;
 
 pub struct EngineQueue {
@@ -19,6 +19,7 @@ impl EngineQueue {
         self.tasks.push(imp.into());
     }
 
+    /// Runs `f` once, in the `Setup` phase of the next frame
     pub fn task_once(&mut self, f: impl FnMut(&mut EngineCtx) + 'static) {
         struct TaskOnce<F: FnMut(&mut EngineCtx)> {
             f: RefCell<F>,
@@ -28,19 +29,34 @@ impl EngineQueue {
                 (self.f.borrow_mut())(_ctx);
                 false
             }
+            fn phase(&self) -> TaskPhase {
+                TaskPhase::Setup
+            }
         }
         self.tasks.push(Box::new(TaskOnce { f: RefCell::new(f) }));
     }
 
-    pub fn task_frame(&mut self, f: impl FnMut(&mut EngineCtx) -> bool + 'static) {
+    /// Runs `f` every frame in the given phase until it returns false
+    pub fn task_frame(
+        &mut self,
+        phase: TaskPhase,
+        f: impl FnMut(&mut EngineCtx) -> bool + 'static,
+    ) {
         struct TaskFrame<F: FnMut(&mut EngineCtx) -> bool> {
             f: RefCell<F>,
+            phase: TaskPhase,
         }
         impl<F: FnMut(&mut EngineCtx) -> bool> EngineTask for TaskFrame<F> {
             fn run_frame(&mut self, ctx: &mut EngineCtx) -> bool {
                 (self.f.borrow_mut())(ctx)
             }
+            fn phase(&self) -> TaskPhase {
+                self.phase
+            }
         }
-        self.tasks.push(Box::new(TaskFrame { f: RefCell::new(f) }));
+        self.tasks.push(Box::new(TaskFrame {
+            f: RefCell::new(f),
+            phase,
+        }));
     }
 }
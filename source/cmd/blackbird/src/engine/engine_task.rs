@@ -1,10 +1,31 @@
 use super::engine_ctx::EngineCtx;
+use super::window::catch_panic;
 use std::cell::RefCell;
 
+/// Phases that tasks run in each frame.
+///
+/// Tasks are ordered by phase before every frame, so a task's position in the
+/// frame doesn't depend on when it was registered. Tasks within the same phase
+/// keep their registration order.
+///
+/// - `Setup`: one-shot work such as building the scene or creating renderers
+/// - `Update`: per-frame mutation of scene state (cameras, animation, ...)
+/// - `Render`: drawing, which always sees the state left by `Update`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPhase {
+    Setup,
+    Update,
+    Render,
+}
+
 pub trait EngineTask {
     fn run_frame(&mut self, _ctx: &mut EngineCtx) -> bool {
         false
     }
+
+    fn phase(&self) -> TaskPhase {
+        TaskPhase::Update
+    }
 }
 
 impl<T: EngineTask + 'static> From<T> for Box<dyn EngineTask> {
@@ -33,4 +54,109 @@ impl EngineTaskHandle {
     pub fn run_frame(&self, ctx: &mut EngineCtx) -> bool {
         self.imp.borrow_mut().run_frame(ctx)
     }
+
+    pub fn phase(&self) -> TaskPhase {
+        self.imp.borrow().phase()
+    }
+}
+
+/// Stable sort of tasks into phase order
+pub fn sort_tasks_by_phase(tasks: &mut [EngineTaskHandle]) {
+    tasks.sort_by_key(|task| task.phase());
+}
+
+/// Run one frame of tasks, returning those that want to keep running.
+///
+/// A task that panics is dropped, and the tasks after it still run, so one
+/// failing task doesn't take down the window.
+pub fn run_tasks_recovering<T>(tasks: Vec<T>, mut run: impl FnMut(&mut T) -> bool) -> Vec<T> {
+    let mut next_tasks = Vec::with_capacity(tasks.len());
+    for mut task in tasks {
+        match catch_panic(|| run(&mut task)) {
+            Some(true) => next_tasks.push(task),
+            Some(false) => {}
+            None => eprintln!("Task panicked and was removed"),
+        }
+    }
+    next_tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    /// Records its id when dropped: dropping a Vec drops its elements front to
+    /// back, which exposes the sorted order without needing an EngineCtx.
+    struct TestTask {
+        id: usize,
+        phase: TaskPhase,
+        log: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl EngineTask for TestTask {
+        fn phase(&self) -> TaskPhase {
+            self.phase
+        }
+    }
+
+    impl Drop for TestTask {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.id);
+        }
+    }
+
+    fn sorted_ids(phases: &[TaskPhase]) -> Vec<usize> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut tasks: Vec<_> = phases
+            .iter()
+            .enumerate()
+            .map(|(id, &phase)| {
+                EngineTaskHandle::new(TestTask {
+                    id,
+                    phase,
+                    log: log.clone(),
+                })
+            })
+            .collect();
+        sort_tasks_by_phase(&mut tasks);
+        drop(tasks);
+        log.take()
+    }
+
+    #[test]
+    fn test_panicking_task_is_removed() {
+        let mut ran = Vec::new();
+        let next = run_tasks_recovering(vec![0, 1, 2, 3], |id| {
+            if *id == 1 {
+                panic!("task {} failed", id);
+            }
+            ran.push(*id);
+            *id != 3
+        });
+        assert_eq!(ran, vec![0, 2, 3]);
+        assert_eq!(next, vec![0, 2]);
+
+        // The surviving tasks keep running on later frames
+        let next = run_tasks_recovering(next, |_| true);
+        assert_eq!(next, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_tasks_sorted_by_phase() {
+        let ids = sorted_ids(&[TaskPhase::Render, TaskPhase::Update, TaskPhase::Setup]);
+        assert_eq!(ids, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_same_phase_keeps_registration_order() {
+        let ids = sorted_ids(&[
+            TaskPhase::Update,
+            TaskPhase::Render,
+            TaskPhase::Update,
+            TaskPhase::Render,
+            TaskPhase::Setup,
+        ]);
+        assert_eq!(ids, vec![4, 0, 2, 1, 3]);
+    }
 }
@@ -1,3 +1,9 @@
+/// Type-keyed store of the engine's entities.
+///
+/// Any number of entities of the same type can be stored. The single-entity
+/// queries (`select`, `select_mut`, `must_select_mut`) return the first one
+/// inserted, which suits types there is only ever one of, such as the scene.
+/// `select_all`, `select_all_mut` and `remove` cover every entity of a type.
 pub struct EntityDatabase {
     entities: Vec<Box<dyn std::any::Any>>,
 }
@@ -9,6 +15,28 @@ impl EntityDatabase {
         }
     }
 
+    //=========================================================================
+    // Mutation
+    //=========================================================================
+
+    pub fn insert<T: 'static>(&mut self, entity: T) {
+        self.entities.push(Box::new(entity));
+    }
+
+    /// Removes every entity of type `T`, returning them in insertion order
+    pub fn remove<T: 'static>(&mut self) -> Vec<T> {
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(self.entities.len());
+        for entity in self.entities.drain(..) {
+            match entity.downcast::<T>() {
+                Ok(e) => removed.push(*e),
+                Err(entity) => kept.push(entity),
+            }
+        }
+        self.entities = kept;
+        removed
+    }
+
     //=========================================================================
     // Queries
     //=========================================================================
@@ -39,6 +67,16 @@ impl EntityDatabase {
         None
     }
 
+    pub fn select_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.entities.iter().filter_map(|e| e.downcast_ref::<T>())
+    }
+
+    pub fn select_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entities
+            .iter_mut()
+            .filter_map(|e| e.downcast_mut::<T>())
+    }
+
     pub fn query<T: 'static>(&self) -> Vec<&T> {
         let mut results = Vec::new();
         for entity in &self.entities {
@@ -61,3 +99,57 @@ impl EntityDatabase {
         std::mem::swap(&mut self.entities, &mut other.entities);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Camera(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Label(&'static str);
+
+    fn sample_database() -> EntityDatabase {
+        let mut db = EntityDatabase::new();
+        db.insert(Camera(1));
+        db.insert(Label("a"));
+        db.insert(Camera(2));
+        db.insert(Label("b"));
+        db
+    }
+
+    #[test]
+    fn test_select_returns_first_inserted() {
+        let mut db = sample_database();
+        assert_eq!(db.select::<Camera>(), Some(&Camera(1)));
+        db.must_select_mut::<Label>().0 = "c";
+        assert_eq!(db.select::<Label>(), Some(&Label("c")));
+        assert_eq!(db.select::<u32>(), None);
+    }
+
+    #[test]
+    fn test_select_all_returns_every_entity_of_type() {
+        let mut db = sample_database();
+        let cameras: Vec<_> = db.select_all::<Camera>().collect();
+        assert_eq!(cameras, vec![&Camera(1), &Camera(2)]);
+
+        for camera in db.select_all_mut::<Camera>() {
+            camera.0 *= 10;
+        }
+        let cameras: Vec<_> = db.select_all::<Camera>().collect();
+        assert_eq!(cameras, vec![&Camera(10), &Camera(20)]);
+        assert_eq!(db.select_all::<u32>().count(), 0);
+    }
+
+    #[test]
+    fn test_remove_takes_only_that_type() {
+        let mut db = sample_database();
+        assert_eq!(db.remove::<Camera>(), vec![Camera(1), Camera(2)]);
+        assert_eq!(db.select::<Camera>(), None);
+
+        let labels: Vec<_> = db.select_all::<Label>().collect();
+        assert_eq!(labels, vec![&Label("a"), &Label("b")]);
+        assert!(db.remove::<Camera>().is_empty());
+    }
+}
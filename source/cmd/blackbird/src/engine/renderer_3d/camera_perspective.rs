@@ -48,6 +48,10 @@ impl CameraPerspective {
         proj * view
     }
 
+    pub fn frustum(&self) -> Frustum {
+        Frustum::from_view_proj(self.view_proj())
+    }
+
     //-----------------------------------------------------------------------//
     // WGPU Bindings
     //-----------------------------------------------------------------------//
@@ -58,8 +58,39 @@ pub fn select_surface_format(surface_caps: &wgpu::SurfaceCapabilities) -> wgpu::
         .unwrap_or(surface_caps.formats[0])
 }
 
+/// Present mode for the vsync setting, out of the modes the surface
+/// supports. With vsync frames wait for the display's refresh (Fifo);
+/// without it they are shown as soon as they're ready (Immediate, or
+/// Mailbox which drops frames instead of tearing). Falls back to Fifo, which
+/// every surface is meant to support, if none of those are available.
+pub fn select_present_mode(vsync: bool, available: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+    let preferred: &[wgpu::PresentMode] = if vsync {
+        &[wgpu::PresentMode::Fifo, wgpu::PresentMode::FifoRelaxed]
+    } else {
+        &[wgpu::PresentMode::Immediate, wgpu::PresentMode::Mailbox]
+    };
+    if let Some(mode) = preferred.iter().find(|mode| available.contains(mode)) {
+        return *mode;
+    }
+
+    let fallback = if available.contains(&wgpu::PresentMode::Fifo) {
+        wgpu::PresentMode::Fifo
+    } else {
+        available
+            .first()
+            .copied()
+            .unwrap_or(wgpu::PresentMode::Fifo)
+    };
+    println!(
+        "No present mode for vsync = {} is available, falling back to {:?}",
+        vsync, fallback
+    );
+    fallback
+}
+
 pub async fn create_device(
     target_window: EngineWindow,
+    vsync: bool,
 ) -> (
     wgpu::Surface<'static>,
     wgpu::SurfaceConfiguration,
@@ -71,7 +102,67 @@ pub async fn create_device(
     let size: winit::dpi::PhysicalSize<u32> = target_window.inner_size();
 
     let (instance, surface) = create_surface(target_window);
+    let (adapter, device, queue) = request_device(&instance, Some(&surface))
+        .await
+        .expect("Could not find an adapter compatible with the surface");
+
+    // --- Surface & render pipeline configuration ---
+    //
+    // Now that the "hardware" is all set up, we need to do further configuration of
+    // the surface we're rendering to and the render pipeline that's sending data
+    // to that surface.
+    //
+    // Loosely, this configuration is more tied to how we want our rendering
+    // engine to work whereas the prior configuration was more tied to what
+    // we needed from the underlying hardware.
+    //
+
+    let surface_caps = surface.get_capabilities(&adapter);
+    let surface_format = select_surface_format(&surface_caps);
+    println!("Using WGPU surface format {:?}", surface_format);
+
+    let surface_config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: surface_format,
+        width: size.width,
+        height: size.height,
+        present_mode: select_present_mode(vsync, &surface_caps.present_modes),
+        alpha_mode: surface_caps.alpha_modes[0],
+        view_formats: vec![],
+        desired_maximum_frame_latency: 2,
+    };
+    println!("Present mode: {:?}", surface_config.present_mode);
+
+    surface.configure(&device, &surface_config);
+
+    (surface, surface_config, device, queue)
+}
 
+/// Create a device that isn't tied to any window, for rendering to offscreen
+/// textures. Returns None if no backend has a usable adapter.
+pub async fn create_headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    println!("Initializing headless WGPU instance...");
+
+    let backend_sets = vec![wgpu::Backends::PRIMARY, wgpu::Backends::SECONDARY];
+    for backends in backend_sets {
+        let desc = wgpu::InstanceDescriptor {
+            backends,
+            ..Default::default()
+        };
+        let instance = wgpu::Instance::new(&desc);
+        if let Some((_adapter, device, queue)) = request_device(&instance, None).await {
+            return Some((device, queue));
+        }
+    }
+    None
+}
+
+/// Pick an adapter, compatible with the given surface if there is one, and
+/// create the device and queue on it
+async fn request_device(
+    instance: &wgpu::Instance,
+    compatible_surface: Option<&wgpu::Surface<'_>>,
+) -> Option<(wgpu::Adapter, wgpu::Device, wgpu::Queue)> {
     //
     // The adapter and device then both act as implementation layers between the
     // backend and the renderable surface. The adapter can allow configuration between,
@@ -82,11 +173,11 @@ pub async fn create_device(
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
             power_preference: wgpu::PowerPreference::default(),
-            compatible_surface: Some(&surface),
+            compatible_surface,
             force_fallback_adapter: false,
         })
         .await
-        .unwrap();
+        .ok()?;
 
     let limits = adapter.limits();
     println!(
@@ -143,36 +234,52 @@ pub async fn create_device(
             experimental_features: wgpu::ExperimentalFeatures::default(),
         })
         .await
-        .unwrap();
+        .ok()?;
 
-    // --- Surface & render pipeline configuration ---
-    //
-    // Now that the "hardware" is all set up, we need to do further configuration of
-    // the surface we're rendering to and the render pipeline that's sending data
-    // to that surface.
-    //
-    // Loosely, this configuration is more tied to how we want our rendering
-    // engine to work whereas the prior configuration was more tied to what
-    // we needed from the underlying hardware.
-    //
+    Some((adapter, device, queue))
+}
 
-    let surface_caps = surface.get_capabilities(&adapter);
-    let surface_format = select_surface_format(&surface_caps);
-    println!("Using WGPU surface format {:?}", surface_format);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::PresentMode;
 
-    let surface_config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        format: surface_format,
-        width: size.width,
-        height: size.height,
-        present_mode: wgpu::PresentMode::AutoNoVsync,
-        alpha_mode: surface_caps.alpha_modes[0],
-        view_formats: vec![],
-        desired_maximum_frame_latency: 2,
-    };
-    println!("Present mode: {:?}", surface_config.present_mode);
+    #[test]
+    fn test_present_mode_preferred() {
+        let all = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+        ];
+        assert_eq!(select_present_mode(true, &all), PresentMode::Fifo);
+        assert_eq!(select_present_mode(false, &all), PresentMode::Immediate);
 
-    surface.configure(&device, &surface_config);
+        let no_immediate = [PresentMode::Fifo, PresentMode::Mailbox];
+        assert_eq!(
+            select_present_mode(false, &no_immediate),
+            PresentMode::Mailbox
+        );
+    }
 
-    (surface, surface_config, device, queue)
+    #[test]
+    fn test_present_mode_fallback() {
+        // Without vsync modes, vsync off still has to present somehow
+        assert_eq!(
+            select_present_mode(false, &[PresentMode::Fifo]),
+            PresentMode::Fifo
+        );
+
+        // Surfaces are meant to support Fifo, but fall back to whatever is
+        // available if one doesn't
+        assert_eq!(
+            select_present_mode(true, &[PresentMode::Mailbox]),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            select_present_mode(false, &[PresentMode::FifoRelaxed]),
+            PresentMode::FifoRelaxed
+        );
+        assert_eq!(select_present_mode(true, &[]), PresentMode::Fifo);
+    }
 }
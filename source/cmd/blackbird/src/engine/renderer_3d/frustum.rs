@@ -0,0 +1,113 @@
+use super::internal::*;
+use crate::geometry::BBox;
+
+/// View frustum as six inward-facing planes, used to skip drawing geometry
+/// that can't be visible.
+///
+/// Planes are stored as `(normal, distance)` in a `Vec4`, so a point `p` is on
+/// the inner side of a plane when `normal.dot(p) + distance >= 0`.
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    //-----------------------------------------------------------------------//
+    // Construction
+    //-----------------------------------------------------------------------//
+
+    /// Extracts the planes from a view-projection matrix (Gribb/Hartmann).
+    /// Assumes the wgpu clip space convention of depth in `0..1`.
+    pub fn from_view_proj(view_proj: Mat4) -> Self {
+        let r0 = view_proj.row(0);
+        let r1 = view_proj.row(1);
+        let r2 = view_proj.row(2);
+        let r3 = view_proj.row(3);
+
+        let planes = [
+            r3 + r0, // left
+            r3 - r0, // right
+            r3 + r1, // bottom
+            r3 - r1, // top
+            r2,      // near
+            r3 - r2, // far
+        ]
+        .map(|plane| plane / plane.truncate().length());
+
+        Self { planes }
+    }
+
+    //-----------------------------------------------------------------------//
+    // Queries
+    //-----------------------------------------------------------------------//
+
+    /// Returns true if any part of the box may be inside the frustum.
+    ///
+    /// This is conservative: boxes near the frustum's corners can be reported
+    /// as intersecting when they are just outside, which only costs a draw.
+    pub fn intersects_bbox(&self, bbox: &BBox) -> bool {
+        if bbox.is_empty() {
+            return false;
+        }
+
+        self.planes.iter().all(|plane| {
+            let normal = plane.truncate();
+            // The corner furthest along the plane normal
+            let corner = Vec3::select(normal.cmpge(Vec3::ZERO), bbox.max, bbox.min);
+            normal.dot(corner) + plane.w >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Camera at +Z looking down at the origin
+    fn test_frustum() -> Frustum {
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(45.0_f32.to_radians(), 1.0, 0.1, 100.0);
+        Frustum::from_view_proj(proj * view)
+    }
+
+    #[test]
+    fn test_box_inside() {
+        let bbox = BBox::from_center_size(Vec3::ZERO, Vec3::ONE);
+        assert!(test_frustum().intersects_bbox(&bbox));
+    }
+
+    #[test]
+    fn test_box_outside() {
+        let frustum = test_frustum();
+
+        // Far off to the side
+        let bbox = BBox::from_center_size(Vec3::new(100.0, 0.0, 0.0), Vec3::ONE);
+        assert!(!frustum.intersects_bbox(&bbox));
+
+        // Behind the camera
+        let bbox = BBox::from_center_size(Vec3::new(0.0, 0.0, 20.0), Vec3::ONE);
+        assert!(!frustum.intersects_bbox(&bbox));
+
+        // Beyond the far plane
+        let bbox = BBox::from_center_size(Vec3::new(0.0, 0.0, -200.0), Vec3::ONE);
+        assert!(!frustum.intersects_bbox(&bbox));
+    }
+
+    #[test]
+    fn test_box_straddling() {
+        let frustum = test_frustum();
+
+        // Crosses the right side plane
+        let bbox = BBox::from_min_max(Vec3::new(0.0, -1.0, -1.0), Vec3::new(100.0, 1.0, 1.0));
+        assert!(frustum.intersects_bbox(&bbox));
+
+        // Contains the camera, so crosses the near plane
+        let bbox = BBox::from_center_size(Vec3::new(0.0, 0.0, 10.0), Vec3::splat(4.0));
+        assert!(frustum.intersects_bbox(&bbox));
+    }
+
+    #[test]
+    fn test_empty_box_is_culled() {
+        assert!(!test_frustum().intersects_bbox(&BBox::empty()));
+    }
+}
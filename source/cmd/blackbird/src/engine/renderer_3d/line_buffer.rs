@@ -43,7 +43,7 @@ impl LineBuffer {
             .as_ref()
             .expect("LineBuffer position array not set");
 
-        BBox::from_array(position_array)
+        BBox::from_points(position_array)
     }
 
     //-----------------------------------------------------------------------//
@@ -1,6 +1,7 @@
 mod camera_perspective;
 mod create_device;
 mod depth_texture;
+mod frustum;
 mod line_buffer;
 mod pipeline_lines;
 mod pipeline_triangles;
@@ -12,6 +13,7 @@ pub mod utils;
 mod vertex;
 
 pub use camera_perspective::CameraPerspective;
+pub use frustum::Frustum;
 pub use line_buffer::LineBuffer;
 pub use renderer_3d::Renderer3D;
 pub use scene_3d::Scene3D;
@@ -7,7 +7,7 @@ pub struct Renderer3D {
     // --- Device ---
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
-    pub surface: wgpu::Surface<'static>,
+    pub surface: Option<wgpu::Surface<'static>>, // None when rendering headless
     pub surface_config: wgpu::SurfaceConfiguration,
     pub depth_texture: DepthTexture,
 
@@ -17,7 +17,9 @@ pub struct Renderer3D {
 }
 
 impl Renderer3D {
-    pub fn new(window: EngineWindow) -> Self {
+    /// Create a renderer drawing to the window. With `vsync` frames are
+    /// capped to the display's refresh rate, see `select_present_mode`.
+    pub fn new(window: EngineWindow, vsync: bool) -> Self {
         // --- ⚠️ WARNING: Poll the future manually... ------------------------
         //
         // It feels a bit risky "hiding" a polling call in here, but it hides
@@ -25,7 +27,7 @@ impl Renderer3D {
         // between winit and wgpu. I'm new enough to Rust to not know how bad of
         // an idea this is!
         //
-        let future = super::create_device::create_device(window);
+        let future = super::create_device::create_device(window, vsync);
         let rt = tokio::runtime::Runtime::new().unwrap();
         let (surface, surface_config, device, queue) = rt.block_on(future);
 
@@ -38,7 +40,7 @@ impl Renderer3D {
         Self {
             device,
             queue,
-            surface,
+            surface: Some(surface),
             surface_config,
             depth_texture,
 
@@ -47,15 +49,178 @@ impl Renderer3D {
         }
     }
 
+    /// Create a renderer with no window surface, which can only draw with
+    /// `render_to_image`. Returns None if there is no usable adapter, e.g.
+    /// on a machine without a GPU or software rasterizer.
+    pub fn new_headless() -> Option<Self> {
+        let future = super::create_device::create_headless_device();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (device, queue) = rt.block_on(future)?;
+
+        // There's no surface to configure, but the config still carries the
+        // color format the pipelines are built for
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            width: 1,
+            height: 1,
+            present_mode: wgpu::PresentMode::AutoNoVsync,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let depth_texture = DepthTexture::create_depth_texture(&device, 1, 1);
+
+        Some(Self {
+            device,
+            queue,
+            surface: None,
+            surface_config,
+            depth_texture,
+
+            pipeline_triangles: None,
+            pipeline_lines: None,
+        })
+    }
+
+    /// Reconfigure the surface and depth texture for a new size, keeping the
+    /// present mode chosen at creation. Does nothing if the size is unchanged
+    /// or zero.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        if width == self.surface_config.width && height == self.surface_config.height {
+            return;
+        }
+
+        self.surface_config.width = width;
+        self.surface_config.height = height;
+        if let Some(surface) = &self.surface {
+            surface.configure(&self.device, &self.surface_config);
+        }
+        self.depth_texture = DepthTexture::create_depth_texture(&self.device, width, height);
+    }
+
     pub fn render_scene(&mut self, scene: &mut Scene3D) {
         let frame = self
             .surface
+            .as_ref()
+            .expect("render_scene needs a window surface, use render_to_image instead")
             .get_current_texture()
             .expect("Failed to acquire next swap chain texture");
         let color_texture_view = frame
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
+        let depth_view = self.depth_texture.view.clone();
+        let encoder = self.encode_scene(scene, &color_texture_view, &depth_view);
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+        frame.present();
+    }
+
+    /// Render the scene to an offscreen texture and read it back. This works
+    /// with or without a window surface and doesn't touch the window's depth
+    /// texture. The camera's aspect ratio is left to the caller.
+    pub fn render_to_image(
+        &mut self,
+        scene: &mut Scene3D,
+        width: u32,
+        height: u32,
+    ) -> image::RgbaImage {
+        let format = self.surface_config.format;
+        let swap_red_blue = match format {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            _ => panic!("render_to_image does not support the {:?} format", format),
+        };
+
+        let width = width.max(1);
+        let height = height.max(1);
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        // Use the same format as the surface so the cached pipelines can
+        // draw into it
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen Color Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let color_texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_texture = DepthTexture::create_depth_texture(&self.device, width, height);
+
+        let mut encoder = self.encode_scene(scene, &color_texture_view, &depth_texture.view);
+
+        // Rows copied into a buffer have to be padded to the copy alignment
+        let row_bytes = width * 4;
+        let padded_row_bytes = row_bytes.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Offscreen Readback Buffer"),
+            size: (padded_row_bytes * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            size,
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| {
+            result.expect("Failed to map the readback buffer");
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("Failed to wait for the offscreen render");
+
+        let mut pixels = Vec::with_capacity((row_bytes * height) as usize);
+        for row in slice.get_mapped_range().chunks(padded_row_bytes as usize) {
+            pixels.extend_from_slice(&row[..row_bytes as usize]);
+        }
+        readback.unmap();
+
+        if swap_red_blue {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        image::RgbaImage::from_raw(width, height, pixels)
+            .expect("Readback size does not match the image size")
+    }
+
+    /// Record the commands to draw the scene into the given color and depth
+    /// targets
+    fn encode_scene(
+        &mut self,
+        scene: &mut Scene3D,
+        color_texture_view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+    ) -> wgpu::CommandEncoder {
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -63,6 +228,7 @@ impl Renderer3D {
             });
 
         scene.camera.update();
+        let frustum = scene.camera.frustum();
 
         for triangle_buffer in &mut scene.triangle_buffers {
             triangle_buffer.prepare(&self.device);
@@ -72,74 +238,71 @@ impl Renderer3D {
             line_buffer.prepare(&self.device);
         }
 
-        run_render_pass(
-            &mut encoder,
-            &color_texture_view,
-            &self.depth_texture,
-            |pass| {
-                if !scene.triangle_buffers.is_empty() {
-                    let pipeline = self.pipeline_triangles.get_or_insert_with(|| {
-                        let start_time = std::time::Instant::now();
-                        let pipeline = PipelineTriangles::new(
-                            &self.device,
-                            &self.surface_config,
-                            self.depth_texture.texture.format(),
-                            &mut scene.camera,
-                        );
-
-                        println!(
-                            "PipelineTriangles created in {} ms",
-                            start_time.elapsed().as_millis()
-                        );
-                        pipeline
-                    });
-                    pass.set_pipeline(&pipeline.pipeline);
-                    pass.set_bind_group(0, &pipeline.bind_group, &[]);
-
-                    scene.camera.activate(&self.device, &self.queue);
-
-                    for triangle_buffer in &scene.triangle_buffers {
-                        triangle_buffer.activate(pass);
+        run_render_pass(&mut encoder, color_texture_view, depth_view, |pass| {
+            if !scene.triangle_buffers.is_empty() {
+                let pipeline = self.pipeline_triangles.get_or_insert_with(|| {
+                    let start_time = std::time::Instant::now();
+                    let pipeline = PipelineTriangles::new(
+                        &self.device,
+                        &self.surface_config,
+                        DepthTexture::FORMAT,
+                        &mut scene.camera,
+                    );
+
+                    println!(
+                        "PipelineTriangles created in {} ms",
+                        start_time.elapsed().as_millis()
+                    );
+                    pipeline
+                });
+                pass.set_pipeline(&pipeline.pipeline);
+                pass.set_bind_group(0, &pipeline.bind_group, &[]);
+
+                scene.camera.activate(&self.device, &self.queue);
+
+                for triangle_buffer in &scene.triangle_buffers {
+                    if !frustum.intersects_bbox(&triangle_buffer.bounding_box()) {
+                        continue;
                     }
+                    triangle_buffer.activate(pass);
                 }
+            }
 
-                if !scene.line_buffers.is_empty() {
-                    let pipeline = self.pipeline_lines.get_or_insert_with(|| {
-                        let start_time = std::time::Instant::now();
-                        let pipeline = PipelineLines::new(
-                            &self.device,
-                            &self.surface_config,
-                            self.depth_texture.texture.format(),
-                            &mut scene.camera,
-                        );
-
-                        println!(
-                            "PipelineLines created in {} ms",
-                            start_time.elapsed().as_millis()
-                        );
-                        pipeline
-                    });
-                    pass.set_pipeline(&pipeline.pipeline);
-                    pass.set_bind_group(0, &pipeline.bind_group, &[]);
-
-                    scene.camera.activate(&self.device, &self.queue);
-
-                    for line_buffer in &scene.line_buffers {
-                        line_buffer.activate(pass);
-                    }
+            if !scene.line_buffers.is_empty() {
+                let pipeline = self.pipeline_lines.get_or_insert_with(|| {
+                    let start_time = std::time::Instant::now();
+                    let pipeline = PipelineLines::new(
+                        &self.device,
+                        &self.surface_config,
+                        DepthTexture::FORMAT,
+                        &mut scene.camera,
+                    );
+
+                    println!(
+                        "PipelineLines created in {} ms",
+                        start_time.elapsed().as_millis()
+                    );
+                    pipeline
+                });
+                pass.set_pipeline(&pipeline.pipeline);
+                pass.set_bind_group(0, &pipeline.bind_group, &[]);
+
+                scene.camera.activate(&self.device, &self.queue);
+
+                for line_buffer in &scene.line_buffers {
+                    line_buffer.activate(pass);
                 }
-            },
-        );
+            }
+        });
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        frame.present();
+        encoder
     }
 }
 
 fn run_render_pass<'a, F>(
     encoder: &'a mut wgpu::CommandEncoder,
     color_texture_view: &'a wgpu::TextureView,
-    depth_texture: &'a DepthTexture,
+    depth_view: &'a wgpu::TextureView,
     f: F,
 ) where
     F: FnOnce(&mut wgpu::RenderPass<'a>),
@@ -161,7 +324,7 @@ fn run_render_pass<'a, F>(
             depth_slice: None,
         })],
         depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-            view: &depth_texture.view,
+            view: depth_view,
             depth_ops: Some(wgpu::Operations {
                 load: wgpu::LoadOp::Clear(1.0),
                 store: wgpu::StoreOp::Store,
@@ -175,3 +338,33 @@ fn run_render_pass<'a, F>(
     let mut pass = encoder.begin_render_pass(&desc);
     f(&mut pass);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::MeshBuilder;
+
+    #[test]
+    fn test_render_cube_to_image() {
+        let Some(mut renderer) = Renderer3D::new_headless() else {
+            eprintln!("Skipping test_render_cube_to_image: no GPU adapter available");
+            return;
+        };
+
+        // Render an empty scene first to find out what the clear color reads
+        // back as in the offscreen format
+        let mut empty = Scene3D::new();
+        let background = renderer.render_to_image(&mut empty, 64, 64);
+
+        let mut scene = Scene3D::new();
+        scene.add(MeshBuilder::make_unit_cube().to_triangle_buffer());
+        // The unit cube spans 0..1, so aim at its center rather than a corner
+        scene.camera.position = Vec3::new(3.5, -3.5, 3.0);
+        scene.camera.look_at = Vec3::splat(0.5);
+
+        let image = renderer.render_to_image(&mut scene, 64, 64);
+        assert_eq!(image.dimensions(), (64, 64));
+        assert_eq!(image.get_pixel(0, 0), background.get_pixel(0, 0));
+        assert_ne!(image.get_pixel(32, 32), background.get_pixel(32, 32));
+    }
+}
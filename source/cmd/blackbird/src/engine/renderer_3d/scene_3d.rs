@@ -1,7 +1,9 @@
 use super::internal::*;
 use super::line_buffer::LineBuffer;
 use super::triangle_buffer::TriangleBuffer;
-use crate::geometry::BBox;
+use crate::geometry::{BBox, LineMesh};
+
+const GRID_COLOR: Vec3 = Vec3::new(0.3, 0.3, 0.35);
 
 pub struct Scene3D {
     pub camera: CameraPerspective,
@@ -19,7 +21,7 @@ impl Scene3D {
     }
 
     pub fn bounding_box(&self) -> BBox {
-        let mut bbox = BBox::new();
+        let mut bbox = BBox::empty();
         for tb in &self.triangle_buffers {
             let b = tb.bounding_box();
             bbox.expand_by_bbox(&b);
@@ -42,4 +44,21 @@ impl Scene3D {
     pub fn add_line_buffer(&mut self, line_buffer: LineBuffer) {
         self.line_buffers.push(line_buffer);
     }
+
+    /// Add a ground grid `size` across with `divisions` cells per side,
+    /// centered under what is in the scene so far
+    pub fn add_grid(&mut self, size: f32, divisions: u32) {
+        let bbox = self.bounding_box();
+        let mut grid = LineMesh::make_grid(size, divisions, GRID_COLOR);
+        if !bbox.is_empty() {
+            let center = bbox.center();
+            grid.translate(center.x, center.y, bbox.min.z);
+        }
+        self.add_line_buffer(grid.to_line_buffer());
+    }
+
+    /// Add red, green and blue lines along the X, Y and Z axes
+    pub fn add_axes(&mut self, length: f32) {
+        self.add_line_buffer(LineMesh::make_axes(length).to_line_buffer());
+    }
 }
@@ -8,6 +8,7 @@ pub struct TriangleBuffer {
     position_array: Option<Vec<Vec3>>,
     color_array: Option<Vec<Vec3>>,
     index_array: Option<Vec<u32>>,
+    bbox: BBox, // Cached for culling, since the positions don't change
 
     vertex_buffer: Option<wgpu::Buffer>,
     index_buffer: Option<wgpu::Buffer>,
@@ -27,6 +28,7 @@ impl TriangleBuffer {
             position_array: Some(position_array.clone()),
             color_array: Some(color_array.clone()),
             index_array: Some(index_array.clone()),
+            bbox: BBox::from_points(position_array),
 
             vertex_buffer: None,
             index_buffer: None,
@@ -38,12 +40,7 @@ impl TriangleBuffer {
     //-----------------------------------------------------------------------//
 
     pub fn bounding_box(&self) -> BBox {
-        let position_array = self
-            .position_array
-            .as_ref()
-            .expect("TriangleBuffer position array not set");
-
-        BBox::from_array(position_array)
+        self.bbox
     }
 
     //-----------------------------------------------------------------------//
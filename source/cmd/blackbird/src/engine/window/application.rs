@@ -20,17 +20,70 @@ impl Application {
             engine,
         }
     }
+
+    /// Save the window's current placement so the next launch restores it
+    fn save_window_state(&self) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        // Minimized windows report meaningless placements, and some
+        // platforms (e.g. Wayland) don't expose the position at all
+        if window.is_minimized() == Some(true) {
+            return;
+        }
+        let Ok(position) = window.outer_position() else {
+            return;
+        };
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        self.engine.local_storage.set(
+            "window_state",
+            &WindowState {
+                position: (position.x, position.y),
+                size: (size.width, size.height),
+            },
+        );
+    }
+}
+
+/// Fit a saved window state to the monitor it was on, or to the primary
+/// monitor if that one is no longer connected
+fn fit_to_monitor(state: WindowState, event_loop: &ActiveEventLoop) -> WindowState {
+    let bounds = |monitor: &MonitorHandle| {
+        let position = monitor.position();
+        let size = monitor.size();
+        ((position.x, position.y), (size.width, size.height))
+    };
+
+    let monitor = event_loop
+        .available_monitors()
+        .find(|monitor| {
+            let (position, size) = bounds(monitor);
+            state.is_on_monitor(position, size)
+        })
+        .or_else(|| event_loop.primary_monitor())
+        .or_else(|| event_loop.available_monitors().next());
+
+    match monitor {
+        Some(monitor) => {
+            let (position, size) = bounds(&monitor);
+            state.clamped_to(position, size)
+        }
+        None => state,
+    }
 }
 
 impl ApplicationHandler for Application {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let window_state = match self.engine.local_storage.get::<WindowState>("window_state") {
-            Some(state) => state,
-            None => WindowState {
-                position: (0, 0),
-                size: (800, 600),
-            },
-        };
+        let window_state = self
+            .engine
+            .local_storage
+            .get::<WindowState>("window_state")
+            .unwrap_or_default();
+        let window_state = fit_to_monitor(window_state, event_loop);
         let position = Position::Physical(PhysicalPosition::new(
             window_state.position.0,
             window_state.position.1,
@@ -53,28 +106,42 @@ impl ApplicationHandler for Application {
         self.window = Some(window.clone());
     }
 
-    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
-        // This is possibly redundant since RedrawRequested also calls
-        // request_redraw.
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         let Some(window) = self.window.as_ref() else {
             return;
         };
-        window.request_redraw();
+
+        // While focused this is possibly redundant since RedrawRequested also
+        // calls request_redraw. Unfocused, it is what paces the throttled
+        // frames: sleep until the next one is due rather than spinning.
+        match self.engine.next_frame_at() {
+            None => {
+                event_loop.set_control_flow(ControlFlow::Poll);
+                window.request_redraw();
+            }
+            Some(at) if at <= Instant::now() => window.request_redraw(),
+            Some(at) => event_loop.set_control_flow(ControlFlow::WaitUntil(at)),
+        }
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         match event {
             WindowEvent::CloseRequested => {
+                self.save_window_state();
                 event_loop.exit();
             }
-            WindowEvent::Moved(position) => {
-                self.engine.local_storage.set(
-                    "window_state",
-                    &WindowState {
-                        position: (position.x, position.y),
-                        size: (800, 600),
-                    },
-                );
+            // Also saved as it changes, since development restarts kill the
+            // process without a close request
+            WindowEvent::Moved(_) => {
+                self.save_window_state();
+            }
+            WindowEvent::Resized(size) => {
+                self.engine
+                    .resize(size.width as usize, size.height as usize);
+                self.save_window_state();
+            }
+            WindowEvent::Focused(focused) => {
+                self.engine.set_focused(focused);
             }
             WindowEvent::RedrawRequested => {
                 let Some(window) = self.window.as_ref() else {
@@ -86,7 +153,9 @@ impl ApplicationHandler for Application {
                     return;
                 }
 
-                window.request_redraw();
+                if self.engine.next_frame_at().is_none() {
+                    window.request_redraw();
+                }
             }
             WindowEvent::KeyboardInput { .. } => {}
             WindowEvent::CursorMoved { .. } => {}
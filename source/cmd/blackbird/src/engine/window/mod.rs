@@ -3,6 +3,8 @@ mod run_event_loop;
 mod soft_panic_hook;
 mod window_state;
 
+pub(crate) use soft_panic_hook::catch_panic;
+
 pub mod prelude {
     pub use super::run_event_loop::init;
     pub use super::run_event_loop::run_event_loop;
@@ -15,9 +17,11 @@ pub mod internal {
     pub use crate::engine::prelude::Engine;
 
     pub use std::sync::Arc;
+    pub use std::time::Instant;
     pub use winit::application::ApplicationHandler;
     pub use winit::dpi::{PhysicalPosition, PhysicalSize, Position};
     pub use winit::event::WindowEvent;
     pub use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+    pub use winit::monitor::MonitorHandle;
     pub use winit::window::{Window, WindowId};
 }
@@ -1,8 +1,29 @@
+use std::cell::Cell;
+use std::panic::AssertUnwindSafe;
+
+thread_local! {
+    /// Set while running code whose panics are caught and recovered from
+    static RECOVERABLE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f`, catching any panic it raises. Returns `None` if it panicked.
+///
+/// The soft panic hook still logs the panic, but leaves the process running
+/// so the caller can carry on without whatever failed.
+pub fn catch_panic<R>(f: impl FnOnce() -> R) -> Option<R> {
+    let outer = RECOVERABLE.replace(true);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    RECOVERABLE.set(outer);
+    result.ok()
+}
+
 /// Custom panic implementation that does not trigger the MacOS native dialog.
 ///
 /// This is implemented so that during development, which uses automatic restarts
 /// of the engine, the native dialog does not steal focus and require a manual click.
 ///
+/// Panics inside `catch_panic` are logged the same way but don't exit.
+///
 pub fn soft_panic_hook(info: &std::panic::PanicHookInfo) {
     println!();
     println!("❌ panic occurred");
@@ -63,5 +84,9 @@ pub fn soft_panic_hook(info: &std::panic::PanicHookInfo) {
     }
     println!();
 
+    if RECOVERABLE.get() {
+        println!("⚠️ recovering from panic");
+        return;
+    }
     std::process::exit(1);
 }
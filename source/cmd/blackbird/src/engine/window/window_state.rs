@@ -1,5 +1,108 @@
-#[derive(serde::Serialize, serde::Deserialize)]
+/// Window placement saved between runs. The position is the outer (frame)
+/// position and the size is the inner (client area) size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct WindowState {
     pub position: (i32, i32),
     pub size: (u32, u32),
 }
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            position: (0, 0),
+            size: (800, 600),
+        }
+    }
+}
+
+impl WindowState {
+    /// Whether the window's top-left corner is on the given monitor
+    pub fn is_on_monitor(&self, monitor_position: (i32, i32), monitor_size: (u32, u32)) -> bool {
+        let (x, y) = self.position;
+        let (left, top) = monitor_position;
+        x >= left
+            && y >= top
+            && (x as i64) < left as i64 + monitor_size.0 as i64
+            && (y as i64) < top as i64 + monitor_size.1 as i64
+    }
+
+    /// Shrink and move the window as needed to fit entirely on the given
+    /// monitor, so a window saved on a larger or since-disconnected display
+    /// doesn't open off-screen
+    pub fn clamped_to(&self, monitor_position: (i32, i32), monitor_size: (u32, u32)) -> Self {
+        let (left, top) = monitor_position;
+        let width = self.size.0.clamp(1, monitor_size.0.max(1));
+        let height = self.size.1.clamp(1, monitor_size.1.max(1));
+        let right = left.saturating_add((monitor_size.0.max(1) - width) as i32);
+        let bottom = top.saturating_add((monitor_size.1.max(1) - height) as i32);
+
+        Self {
+            position: (
+                self.position.0.clamp(left, right),
+                self.position.1.clamp(top, bottom),
+            ),
+            size: (width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR_POSITION: (i32, i32) = (0, 0);
+    const MONITOR_SIZE: (u32, u32) = (1920, 1080);
+
+    #[test]
+    fn test_inside_monitor_unchanged() {
+        let state = WindowState {
+            position: (100, 200),
+            size: (800, 600),
+        };
+        assert_eq!(state.clamped_to(MONITOR_POSITION, MONITOR_SIZE), state);
+    }
+
+    #[test]
+    fn test_offscreen_position_clamped() {
+        let state = WindowState {
+            position: (3000, -500),
+            size: (800, 600),
+        };
+        let clamped = state.clamped_to(MONITOR_POSITION, MONITOR_SIZE);
+        assert_eq!(clamped.position, (1120, 0));
+        assert_eq!(clamped.size, (800, 600));
+
+        let state = WindowState {
+            position: (-2000, 900),
+            size: (800, 600),
+        };
+        let clamped = state.clamped_to(MONITOR_POSITION, MONITOR_SIZE);
+        assert_eq!(clamped.position, (0, 480));
+    }
+
+    #[test]
+    fn test_oversized_window_shrunk() {
+        let state = WindowState {
+            position: (2600, 100),
+            size: (2560, 1440),
+        };
+        let clamped = state.clamped_to((1920, 0), MONITOR_SIZE);
+        assert_eq!(
+            clamped,
+            WindowState {
+                position: (1920, 0),
+                size: (1920, 1080),
+            }
+        );
+    }
+
+    #[test]
+    fn test_is_on_monitor() {
+        let state = WindowState {
+            position: (1919, 0),
+            ..Default::default()
+        };
+        assert!(state.is_on_monitor(MONITOR_POSITION, MONITOR_SIZE));
+        assert!(!state.is_on_monitor((1920, 0), MONITOR_SIZE));
+    }
+}
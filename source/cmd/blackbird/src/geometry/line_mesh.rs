@@ -57,10 +57,60 @@ impl LineMesh {
         LineMesh { vertices, indices }
     }
 
+    /// Square grid in the XY plane, centered on the origin, `size` across with
+    /// `divisions` cells per side
+    pub fn make_grid(size: f32, divisions: u32, color: Vec3) -> LineMesh {
+        let mut mesh = LineMesh {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        };
+        let divisions = divisions.max(1);
+        let half = size * 0.5;
+        for i in 0..=divisions {
+            let t = -half + size * i as f32 / divisions as f32;
+            mesh.add_line(Vec3::new(t, -half, 0.0), Vec3::new(t, half, 0.0), color);
+            mesh.add_line(Vec3::new(-half, t, 0.0), Vec3::new(half, t, 0.0), color);
+        }
+        mesh
+    }
+
+    /// Lines from the origin along +X, +Y and +Z, colored red, green and blue
+    pub fn make_axes(length: f32) -> LineMesh {
+        let mut mesh = LineMesh {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        };
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            mesh.add_line(Vec3::ZERO, axis * length, axis);
+        }
+        mesh
+    }
+
+    //-------------------------------------------------------------------------
+    // Properties
+    //-------------------------------------------------------------------------
+
+    pub fn line_count(&self) -> usize {
+        self.indices.len() / 2
+    }
+
     //-------------------------------------------------------------------------
     // Mutation
     //-------------------------------------------------------------------------
 
+    pub fn add_line(&mut self, a: Vec3, b: Vec3, color: Vec3) {
+        let start = self.vertices.len() as u32;
+        for position in [a, b] {
+            self.vertices.push(MeshVertex {
+                position,
+                color,
+                normal: Vec3::ZERO,
+                uv: None,
+            });
+        }
+        self.indices.extend([start, start + 1]);
+    }
+
     pub fn translate(&mut self, tx: f32, ty: f32, tz: f32) {
         let t = Vec3::new(tx, ty, tz);
         for vertex in self.vertices.iter_mut() {
@@ -124,3 +174,34 @@ impl LineMesh {
         crate::engine::renderer_3d::LineBuffer::new(&position_array, &color_array, &self.indices)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_line_count() {
+        for divisions in [1, 4, 10] {
+            let grid = LineMesh::make_grid(8.0, divisions, Vec3::ONE);
+            assert_eq!(grid.line_count(), 2 * (divisions as usize + 1));
+        }
+    }
+
+    #[test]
+    fn test_grid_is_centered() {
+        let grid = LineMesh::make_grid(8.0, 4, Vec3::ONE);
+        let positions: Vec<Vec3> = grid.vertices.iter().map(|v| v.position).collect();
+        let bbox = BBox::from_points(&positions);
+        assert_eq!(bbox.min, Vec3::new(-4.0, -4.0, 0.0));
+        assert_eq!(bbox.max, Vec3::new(4.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn test_axes_colors() {
+        let axes = LineMesh::make_axes(2.0);
+        assert_eq!(axes.line_count(), 3);
+        assert_eq!(axes.vertices[1].position, Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(axes.vertices[1].color, Vec3::X);
+        assert_eq!(axes.vertices[5].color, Vec3::Z);
+    }
+}
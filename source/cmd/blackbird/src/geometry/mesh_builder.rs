@@ -81,9 +81,290 @@ impl MeshBuilder {
             vertices.push(MeshVertex {
                 position: Vec3::new(pos[0], pos[1], pos[2]),
                 color: Vec3::new(col[0], col[1], col[2]),
+                normal: Vec3::ZERO,
+                uv: None,
             });
         }
 
+        let mut mesh = TriangleMesh::new(vertices);
+        mesh.compute_flat_normals();
+        mesh
+    }
+
+    /// Builds a capped cylinder standing on the XY plane, from z = 0 to
+    /// z = `height`, with `segments` sides around the Z axis.
+    ///
+    /// The mesh has `4 * segments` triangles: `segments` in each cap and two
+    /// per side quad, for `12 * segments` vertices. Caps use flat normals and
+    /// planar UVs; the side uses smooth radial normals and wraps U around the
+    /// circumference.
+    pub fn make_cylinder(radius: f32, height: f32, segments: u32) -> TriangleMesh {
+        let segments = segments.max(3);
+        let mut vertices = Vec::with_capacity(12 * segments as usize);
+
+        for i in 0..segments {
+            let (a0, a1) = segment_angles(i, segments);
+            let (u0, u1) = segment_u(i, segments);
+
+            push_cap_triangle(&mut vertices, radius, 0.0, a1, a0, -Vec3::Z);
+            push_cap_triangle(&mut vertices, radius, height, a0, a1, Vec3::Z);
+
+            let (n0, n1) = (radial(a0), radial(a1));
+            let b0 = side_vertex(radius, 0.0, a0, n0, Vec2::new(u0, 0.0));
+            let b1 = side_vertex(radius, 0.0, a1, n1, Vec2::new(u1, 0.0));
+            let t0 = side_vertex(radius, height, a0, n0, Vec2::new(u0, 1.0));
+            let t1 = side_vertex(radius, height, a1, n1, Vec2::new(u1, 1.0));
+            vertices.extend([b0.clone(), b1, t1.clone(), b0, t1, t0]);
+        }
+
+        TriangleMesh::new(vertices)
+    }
+
+    /// Builds a capped cone with its base on the XY plane and its apex at
+    /// z = `height`, with `segments` sides around the Z axis.
+    ///
+    /// The mesh has `2 * segments` triangles: `segments` in the base cap and
+    /// one per side, for `6 * segments` vertices. The base uses a flat normal
+    /// and planar UVs; the side uses smooth normals tilted by the slope.
+    pub fn make_cone(radius: f32, height: f32, segments: u32) -> TriangleMesh {
+        let segments = segments.max(3);
+        let mut vertices = Vec::with_capacity(6 * segments as usize);
+
+        // Side normals lean up by the slope of the cone
+        let slope_normal = |angle: f32| (radial(angle) * height + Vec3::Z * radius).normalize();
+
+        for i in 0..segments {
+            let (a0, a1) = segment_angles(i, segments);
+            let (u0, u1) = segment_u(i, segments);
+
+            push_cap_triangle(&mut vertices, radius, 0.0, a1, a0, -Vec3::Z);
+
+            // The apex has no single normal; use the one midway across the side
+            let apex_normal = slope_normal((a0 + a1) * 0.5);
+            vertices.extend([
+                side_vertex(radius, 0.0, a0, slope_normal(a0), Vec2::new(u0, 0.0)),
+                side_vertex(radius, 0.0, a1, slope_normal(a1), Vec2::new(u1, 0.0)),
+                side_vertex(
+                    0.0,
+                    height,
+                    0.0,
+                    apex_normal,
+                    Vec2::new((u0 + u1) * 0.5, 1.0),
+                ),
+            ]);
+        }
+
         TriangleMesh::new(vertices)
     }
+
+    /// Builds a torus lying in the XY plane around the Z axis.
+    ///
+    /// The ring is split into `major_segments` slices, each with
+    /// `minor_segments` sides, giving `2 * major * minor` triangles and
+    /// `6 * major * minor` vertices. U runs around the ring and V around the
+    /// tube, both from 0 to 1.
+    pub fn make_torus(
+        major_radius: f32,
+        minor_radius: f32,
+        major_segments: u32,
+        minor_segments: u32,
+    ) -> TriangleMesh {
+        let major_segments = major_segments.max(3);
+        let minor_segments = minor_segments.max(3);
+        let mut vertices = Vec::with_capacity((6 * major_segments * minor_segments) as usize);
+
+        let vertex = |i: u32, j: u32| {
+            // Wrap the angles so the closing seam reuses the exact starting
+            // positions, while UVs run on to 1.0 instead of jumping back to 0
+            let u = (i % major_segments) as f32 / major_segments as f32;
+            let v = (j % minor_segments) as f32 / minor_segments as f32;
+            let (major_angle, minor_angle) = (u * std::f32::consts::TAU, v * std::f32::consts::TAU);
+
+            let ring = radial(major_angle);
+            let normal = ring * minor_angle.cos() + Vec3::Z * minor_angle.sin();
+            MeshVertex {
+                position: ring * major_radius + normal * minor_radius,
+                color: normal_color(normal),
+                normal,
+                uv: Some(Vec2::new(
+                    i as f32 / major_segments as f32,
+                    j as f32 / minor_segments as f32,
+                )),
+            }
+        };
+
+        for i in 0..major_segments {
+            for j in 0..minor_segments {
+                let p00 = vertex(i, j);
+                let p10 = vertex(i + 1, j);
+                let p11 = vertex(i + 1, j + 1);
+                let p01 = vertex(i, j + 1);
+                vertices.extend([p00.clone(), p10, p11.clone(), p00, p11, p01]);
+            }
+        }
+
+        TriangleMesh::new(vertices)
+    }
+}
+
+//=============================================================================
+// Procedural helpers
+//=============================================================================
+
+fn segment_angles(i: u32, segments: u32) -> (f32, f32) {
+    let step = std::f32::consts::TAU / segments as f32;
+    (i as f32 * step, (i + 1) as f32 * step)
+}
+
+fn segment_u(i: u32, segments: u32) -> (f32, f32) {
+    (i as f32 / segments as f32, (i + 1) as f32 / segments as f32)
+}
+
+fn radial(angle: f32) -> Vec3 {
+    Vec3::new(angle.cos(), angle.sin(), 0.0)
+}
+
+/// Colors procedural vertices by their normal so the shape reads without lighting.
+fn normal_color(normal: Vec3) -> Vec3 {
+    normal * 0.5 + Vec3::splat(0.5)
+}
+
+fn side_vertex(radius: f32, z: f32, angle: f32, normal: Vec3, uv: Vec2) -> MeshVertex {
+    MeshVertex {
+        position: radial(angle) * radius + Vec3::Z * z,
+        color: normal_color(normal),
+        normal,
+        uv: Some(uv),
+    }
+}
+
+/// Pushes one wedge of a disc cap at height `z`. The angles are given in
+/// winding order, so swapping them flips which way the cap faces.
+fn push_cap_triangle(
+    vertices: &mut Vec<MeshVertex>,
+    radius: f32,
+    z: f32,
+    a0: f32,
+    a1: f32,
+    normal: Vec3,
+) {
+    let center = Vec3::Z * z;
+    for (position, planar) in [
+        (center, Vec2::ZERO),
+        (center + radial(a0) * radius, radial(a0).truncate()),
+        (center + radial(a1) * radius, radial(a1).truncate()),
+    ] {
+        vertices.push(MeshVertex {
+            position,
+            color: normal_color(normal),
+            normal,
+            uv: Some(planar * 0.5 + Vec2::splat(0.5)),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangles_facing(mesh: &TriangleMesh, normal: Vec3) -> usize {
+        mesh.vertices
+            .chunks_exact(3)
+            .filter(|tri| tri.iter().all(|v| v.normal == normal))
+            .count()
+    }
+
+    /// Every triangle's winding should agree with its vertex normals.
+    fn assert_outward_winding(mesh: &TriangleMesh) {
+        for tri in mesh.vertices.chunks_exact(3) {
+            let face = (tri[1].position - tri[0].position).cross(tri[2].position - tri[0].position);
+            for vertex in tri {
+                assert!(
+                    face.dot(vertex.normal) > 0.0,
+                    "triangle winds against its normals"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cylinder_counts() {
+        let mesh = MeshBuilder::make_cylinder(1.0, 2.0, 16);
+        assert_eq!(mesh.vertices.len(), 12 * 16);
+        assert_eq!(mesh.triangle_count(), 4 * 16);
+        assert_eq!(triangles_facing(&mesh, Vec3::Z), 16);
+        assert_eq!(triangles_facing(&mesh, -Vec3::Z), 16);
+        assert_outward_winding(&mesh);
+    }
+
+    #[test]
+    fn test_cone_counts() {
+        let mesh = MeshBuilder::make_cone(1.0, 2.0, 12);
+        assert_eq!(mesh.vertices.len(), 6 * 12);
+        assert_eq!(mesh.triangle_count(), 2 * 12);
+        assert_eq!(triangles_facing(&mesh, -Vec3::Z), 12);
+        assert_eq!(triangles_facing(&mesh, Vec3::Z), 0);
+        assert_outward_winding(&mesh);
+    }
+
+    #[test]
+    fn test_cylinder_has_uvs() {
+        let mesh = MeshBuilder::make_cylinder(1.0, 1.0, 8);
+        for vertex in &mesh.vertices {
+            let uv = vertex.uv.expect("cylinder vertices carry UVs");
+            assert!((0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y));
+        }
+    }
+
+    #[test]
+    fn test_torus_counts() {
+        let mesh = MeshBuilder::make_torus(2.0, 0.5, 16, 8);
+        assert_eq!(mesh.vertices.len(), 6 * 16 * 8);
+        assert_eq!(mesh.triangle_count(), 2 * 16 * 8);
+        assert_outward_winding(&mesh);
+    }
+
+    #[test]
+    fn test_torus_seam_is_closed() {
+        let mesh = MeshBuilder::make_torus(2.0, 0.5, 16, 8);
+        let first = mesh.vertices.first().unwrap();
+
+        // The last slice ends on the same positions the first one starts from
+        let closing = mesh
+            .vertices
+            .iter()
+            .filter(|v| v.position == first.position)
+            .filter_map(|v| v.uv)
+            .collect::<Vec<_>>();
+        assert!(closing.iter().any(|uv| uv.x == 0.0));
+        assert!(closing.iter().any(|uv| uv.x == 1.0));
+    }
+
+    #[test]
+    fn test_select_torus_outer_ring() {
+        let mut mesh = MeshBuilder::make_torus(2.0, 0.5, 16, 8);
+
+        // Tube sides at 0 and +/-45 degrees sit beyond the threshold; each
+        // grid point is shared by six triangle corners
+        let selection = mesh
+            .vertex_selection()
+            .add(|v| v.position.truncate().length() > 2.25);
+        assert_eq!(selection.count(), 6 * 16 * 3);
+    }
+
+    #[test]
+    fn test_select_cylinder_top() {
+        let mut mesh = MeshBuilder::make_cylinder(1.0, 2.0, 8);
+
+        // Top cap wedges contribute 3 vertices each, side quads 3 more
+        let selection = mesh.vertex_selection().add(|v| v.position.z > 1.0);
+        assert_eq!(selection.count(), 6 * 8);
+
+        // Widening the top ring keeps the bottom untouched
+        selection.scale(Vec3::new(2.0, 2.0, 1.0));
+        let bottom = mesh.vertex_selection().add(|v| v.position.z < 1.0).bbox();
+        let top = mesh.vertex_selection().add(|v| v.position.z > 1.0).bbox();
+        assert!((bottom.size().x - 2.0).abs() < 1e-5);
+        assert!((top.size().x - 4.0).abs() < 1e-5);
+    }
 }
@@ -1,9 +1,33 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
 use super::internal::*;
 
 #[derive(Debug, Clone)]
 pub struct MeshVertex {
     pub position: Vec3,
     pub color: Vec3,
+    pub normal: Vec3,
+    pub uv: Option<Vec2>,
+}
+
+impl MeshVertex {
+    /// Vertex halfway between `self` and `other`, with every attribute averaged.
+    ///
+    /// The result does not depend on argument order, so both triangles sharing
+    /// an edge produce bit-identical midpoints.
+    pub fn midpoint(&self, other: &MeshVertex) -> MeshVertex {
+        MeshVertex {
+            position: (self.position + other.position) * 0.5,
+            color: (self.color + other.color) * 0.5,
+            normal: (self.normal + other.normal).normalize_or_zero(),
+            uv: match (self.uv, other.uv) {
+                (Some(a), Some(b)) => Some((a + b) * 0.5),
+                _ => None,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +43,25 @@ impl TriangleMesh {
     pub fn new(vertices: Vec<MeshVertex>) -> TriangleMesh {
         TriangleMesh { vertices }
     }
+
+    /// Number of triangles in the mesh (every three vertices form one).
+    pub fn triangle_count(&self) -> usize {
+        self.vertices.len() / 3
+    }
+
+    //-------------------------------------------------------------------------
+    // Queries
+    //-------------------------------------------------------------------------
+
+    /// Bounding box of all vertex positions.
+    pub fn bbox(&self) -> BBox {
+        let mut bbox = BBox::empty();
+        for vertex in self.vertices.iter() {
+            bbox.expand_by_point(vertex.position);
+        }
+        bbox
+    }
+
     //-------------------------------------------------------------------------
     // Selection
     //-------------------------------------------------------------------------
@@ -31,6 +74,47 @@ impl TriangleMesh {
     // Mutation
     //-------------------------------------------------------------------------
 
+    /// Replaces every vertex normal with the normal of its triangle.
+    pub fn compute_flat_normals(&mut self) {
+        for tri in self.vertices.chunks_exact_mut(3) {
+            let normal = (tri[1].position - tri[0].position)
+                .cross(tri[2].position - tri[0].position)
+                .normalize_or_zero();
+            for vertex in tri.iter_mut() {
+                vertex.normal = normal;
+            }
+        }
+    }
+
+    /// Splits every triangle into four at its edge midpoints, `iterations` times.
+    ///
+    /// Shape is unchanged: normals and UVs are interpolated, but positions stay
+    /// on the original faces. Push the vertices outward afterwards (e.g. onto a
+    /// sphere) for a rounder result.
+    pub fn subdivide(&mut self, iterations: u32) {
+        for _ in 0..iterations {
+            let mut vertices = Vec::with_capacity(self.vertices.len() * 4);
+            for tri in self.vertices.chunks_exact(3) {
+                let (a, b, c) = (&tri[0], &tri[1], &tri[2]);
+                let ab = a.midpoint(b);
+                let bc = b.midpoint(c);
+                let ca = c.midpoint(a);
+
+                vertices.extend([a.clone(), ab.clone(), ca.clone()]);
+                vertices.extend([ab.clone(), b.clone(), bc.clone()]);
+                vertices.extend([ca.clone(), bc.clone(), c.clone()]);
+                vertices.extend([ab, bc, ca]);
+            }
+            self.vertices = vertices;
+        }
+    }
+
+    /// Appends the triangles of `other`, e.g. to combine primitives into a
+    /// single buffer. The mesh is un-indexed, so no re-basing is needed.
+    pub fn merge(&mut self, other: &TriangleMesh) {
+        self.vertices.extend_from_slice(&other.vertices);
+    }
+
     pub fn translate(&mut self, tx: f32, ty: f32, tz: f32) {
         let t = Vec3::new(tx, ty, tz);
         for vertex in self.vertices.iter_mut() {
@@ -57,4 +141,148 @@ impl TriangleMesh {
 
         crate::engine::renderer_3d::TriangleBuffer::new(&position_array, &color_array, &index_array)
     }
+
+    //-------------------------------------------------------------------------
+    // Export
+    //-------------------------------------------------------------------------
+
+    /// Writes the mesh as a Wavefront OBJ file for inspection in external tools.
+    ///
+    /// Identical positions and normals are merged so shared values appear once.
+    /// Texture coordinates are only written when every vertex has one.
+    pub fn write_obj(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut out = std::io::BufWriter::new(file);
+
+        let mut positions = ObjIndexer::default();
+        let mut normals = ObjIndexer::default();
+        let mut uvs = ObjIndexer::default();
+        let write_uvs = self.vertices.iter().all(|v| v.uv.is_some());
+
+        let mut corners = Vec::with_capacity(self.vertices.len());
+        for vertex in self.vertices.iter() {
+            let p = vertex.position;
+            let (v, is_new) = positions.index(&[p.x, p.y, p.z]);
+            if is_new {
+                writeln!(out, "v {} {} {}", p.x, p.y, p.z)?;
+            }
+
+            let n = vertex.normal;
+            let (vn, is_new) = normals.index(&[n.x, n.y, n.z]);
+            if is_new {
+                writeln!(out, "vn {} {} {}", n.x, n.y, n.z)?;
+            }
+
+            let corner = match vertex.uv.filter(|_| write_uvs) {
+                Some(uv) => {
+                    let (vt, is_new) = uvs.index(&[uv.x, uv.y]);
+                    if is_new {
+                        writeln!(out, "vt {} {}", uv.x, uv.y)?;
+                    }
+                    format!("{v}/{vt}/{vn}")
+                }
+                None => format!("{v}//{vn}"),
+            };
+            corners.push(corner);
+        }
+
+        for tri in corners.chunks_exact(3) {
+            writeln!(out, "f {} {} {}", tri[0], tri[1], tri[2])?;
+        }
+
+        out.flush()
+    }
+}
+
+/// Assigns 1-based OBJ indices to attribute values, merging exact duplicates.
+#[derive(Default)]
+struct ObjIndexer {
+    lookup: HashMap<Vec<u32>, usize>,
+}
+
+impl ObjIndexer {
+    /// Returns the index for `value` and whether it was seen for the first time.
+    fn index(&mut self, value: &[f32]) -> (usize, bool) {
+        // Adding zero folds -0.0 into 0.0 so they share an index
+        let key = value.iter().map(|c| (c + 0.0).to_bits()).collect();
+        let next = self.lookup.len() + 1;
+        let index = *self.lookup.entry(key).or_insert(next);
+        (index, index == next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::MeshBuilder;
+
+    fn position_key(p: Vec3) -> [u32; 3] {
+        [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()]
+    }
+
+    #[test]
+    fn test_subdivide_quadruples_triangles() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        mesh.subdivide(1);
+        assert_eq!(mesh.triangle_count(), 12 * 4);
+
+        mesh.subdivide(2);
+        assert_eq!(mesh.triangle_count(), 12 * 4 * 16);
+    }
+
+    #[test]
+    fn test_subdivide_keeps_mesh_watertight() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        mesh.subdivide(1);
+
+        // 8 corners plus one new vertex on each of the 18 edges (12 + 6 diagonals)
+        let unique: std::collections::HashSet<_> = mesh
+            .vertices
+            .iter()
+            .map(|v| position_key(v.position))
+            .collect();
+        assert_eq!(unique.len(), 8 + 18);
+
+        // A closed surface has every edge shared by exactly two triangles
+        let mut edges: HashMap<([u32; 3], [u32; 3]), usize> = HashMap::new();
+        for tri in mesh.vertices.chunks_exact(3) {
+            for i in 0..3 {
+                let a = position_key(tri[i].position);
+                let b = position_key(tri[(i + 1) % 3].position);
+                *edges.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+        assert!(edges.values().all(|&count| count == 2));
+    }
+
+    #[test]
+    fn test_merge_offset_cubes() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        let mut other = MeshBuilder::make_unit_cube();
+        other.translate(3.0, 0.0, 0.0);
+
+        mesh.merge(&other);
+        assert_eq!(mesh.vertices.len(), 36 * 2);
+        assert_eq!(mesh.triangle_count(), 12 * 2);
+
+        let bbox = mesh.bbox();
+        assert_eq!(bbox.min, Vec3::ZERO);
+        assert_eq!(bbox.max, Vec3::new(4.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_write_obj_unit_cube() {
+        let mesh = MeshBuilder::make_unit_cube();
+        let path = std::env::temp_dir().join(format!("blackbird_cube_{}.obj", std::process::id()));
+
+        mesh.write_obj(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let count = |prefix: &str| text.lines().filter(|l| l.starts_with(prefix)).count();
+        assert_eq!(count("v "), 8);
+        assert_eq!(count("vn "), 6);
+        assert_eq!(count("vt "), 0);
+        assert_eq!(count("f "), 12);
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use super::internal::*;
 use super::triangle_mesh::{MeshVertex, TriangleMesh};
 
@@ -33,11 +35,65 @@ impl<'a> VertexSelection<'a> {
         self
     }
 
+    /// Adds vertices whose color lies within `epsilon` (Euclidean distance in
+    /// RGB) of `target`.
+    pub fn add_by_color(self, target: Vec3, epsilon: f32) -> Self {
+        self.add(move |v| v.color.distance(target) <= epsilon)
+    }
+
     pub fn all(mut self) -> Self {
         self.indices = (0..self.mesh.vertices.len()).collect();
         self
     }
 
+    //=========================================================================
+    // Set Operations
+    //=========================================================================
+    //
+    // A selection holds the mesh mutably, so the other operand is built by a
+    // closure from a fresh selection on the same mesh:
+    //
+    //   mesh.vertex_selection()
+    //       .add(|v| v.position.z > 0.5)
+    //       .intersect(|s| s.add_by_color(white, 1e-4))
+    //
+
+    /// Adds the vertices of the other selection.
+    pub fn union(mut self, other: impl FnOnce(VertexSelection<'_>) -> VertexSelection<'_>) -> Self {
+        let other = self.build_other(other);
+        let selected: HashSet<usize> = self.indices.iter().copied().collect();
+        self.indices
+            .extend(other.into_iter().filter(|i| !selected.contains(i)));
+        self
+    }
+
+    /// Keeps only the vertices that are also in the other selection.
+    pub fn intersect(
+        mut self,
+        other: impl FnOnce(VertexSelection<'_>) -> VertexSelection<'_>,
+    ) -> Self {
+        let other: HashSet<usize> = self.build_other(other).into_iter().collect();
+        self.indices.retain(|i| other.contains(i));
+        self
+    }
+
+    /// Removes the vertices that are in the other selection.
+    pub fn difference(
+        mut self,
+        other: impl FnOnce(VertexSelection<'_>) -> VertexSelection<'_>,
+    ) -> Self {
+        let other: HashSet<usize> = self.build_other(other).into_iter().collect();
+        self.indices.retain(|i| !other.contains(i));
+        self
+    }
+
+    fn build_other(
+        &mut self,
+        build: impl FnOnce(VertexSelection<'_>) -> VertexSelection<'_>,
+    ) -> Vec<usize> {
+        build(VertexSelection::new(self.mesh)).indices
+    }
+
     //=========================================================================
     // Queries
     //=========================================================================
@@ -52,9 +108,15 @@ impl<'a> VertexSelection<'a> {
         self.indices.is_empty()
     }
 
+    /// Returns the indices of the selected vertices, in the order they were
+    /// added.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
     /// Returns the bounding box of the selected vertices.
     pub fn bbox(&self) -> BBox {
-        let mut bbox = BBox::new();
+        let mut bbox = BBox::empty();
         for &idx in &self.indices {
             if let Some(vertex) = self.mesh.vertices.get(idx) {
                 bbox.expand_by_point(vertex.position);
@@ -90,6 +152,8 @@ impl<'a> VertexSelection<'a> {
             if let Some(vertex) = self.mesh.vertices.get_mut(idx) {
                 let offset = vertex.position - center;
                 vertex.position = center + offset * factor;
+                // Normals scale by the inverse so they stay perpendicular to the surface
+                vertex.normal = (vertex.normal / factor).normalize_or_zero();
             }
         }
         self
@@ -100,6 +164,81 @@ impl<'a> VertexSelection<'a> {
         self.scale(Vec3::splat(factor))
     }
 
+    //=========================================================================
+    // Modeling
+    //=========================================================================
+
+    /// Extrudes the selected faces by `distance` along their average normal.
+    ///
+    /// A face is part of the region when all three of its vertices are
+    /// selected. The region is moved out and side walls are built along its
+    /// boundary, so the rest of the mesh stays attached to where it was. The
+    /// selected faces must form a single connected patch; disjoint patches
+    /// would all move along one shared normal.
+    pub fn extrude(self, distance: f32) -> Self {
+        let selected: HashSet<usize> = self.indices.iter().copied().collect();
+        let faces: Vec<usize> = (0..self.mesh.vertices.len() / 3)
+            .filter(|f| (0..3).all(|k| selected.contains(&(f * 3 + k))))
+            .collect();
+        if faces.is_empty() {
+            return self;
+        }
+
+        let position_key = |p: Vec3| [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()];
+        let vertices = &self.mesh.vertices;
+
+        // Average face normal from the winding, which holds even if the
+        // stored vertex normals are missing
+        let mut normal_sum = Vec3::ZERO;
+        for &f in &faces {
+            let [a, b, c] = [0, 1, 2].map(|k| vertices[f * 3 + k].position);
+            normal_sum += (b - a).cross(c - a).normalize_or_zero();
+        }
+        let offset = normal_sum.normalize_or_zero() * distance;
+
+        // Boundary edges are the ones no other face in the region shares.
+        // Interior edges show up once in each direction.
+        let mut edge_counts: HashMap<([u32; 3], [u32; 3]), usize> = HashMap::new();
+        for &f in &faces {
+            for k in 0..3 {
+                let a = position_key(vertices[f * 3 + k].position);
+                let b = position_key(vertices[f * 3 + (k + 1) % 3].position);
+                *edge_counts.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+
+        let mut walls = Vec::new();
+        for &f in &faces {
+            for k in 0..3 {
+                let a = &vertices[f * 3 + k];
+                let b = &vertices[f * 3 + (k + 1) % 3];
+                let (ka, kb) = (position_key(a.position), position_key(b.position));
+                if edge_counts[&(ka.min(kb), ka.max(kb))] != 1 {
+                    continue;
+                }
+
+                // Following the face winding keeps the wall facing outward
+                let wall_normal = (b.position - a.position).cross(offset).normalize_or_zero();
+                let corner = |v: &MeshVertex, lift: Vec3| MeshVertex {
+                    position: v.position + lift,
+                    normal: wall_normal,
+                    ..v.clone()
+                };
+                let (a0, b0) = (corner(a, Vec3::ZERO), corner(b, Vec3::ZERO));
+                let (a1, b1) = (corner(a, offset), corner(b, offset));
+                walls.extend([a0.clone(), b0, b1.clone(), a0, b1, a1]);
+            }
+        }
+
+        for &f in &faces {
+            for k in 0..3 {
+                self.mesh.vertices[f * 3 + k].position += offset;
+            }
+        }
+        self.mesh.vertices.extend(walls);
+        self
+    }
+
     //=========================================================================
     // Attributes
     //=========================================================================
@@ -114,3 +253,174 @@ impl<'a> VertexSelection<'a> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::MeshBuilder;
+
+    /// Unit square in the XY plane facing +Z, split into a 4x4 grid of cells.
+    fn make_subdivided_plane() -> TriangleMesh {
+        let corner = |x: f32, y: f32| MeshVertex {
+            position: Vec3::new(x, y, 0.0),
+            color: Vec3::ONE,
+            normal: Vec3::Z,
+            uv: Some(Vec2::new(x, y)),
+        };
+        let mut mesh = TriangleMesh::new(vec![
+            corner(0.0, 0.0),
+            corner(1.0, 0.0),
+            corner(1.0, 1.0),
+            corner(0.0, 0.0),
+            corner(1.0, 1.0),
+            corner(0.0, 1.0),
+        ]);
+        mesh.subdivide(2);
+        mesh
+    }
+
+    #[test]
+    fn test_extrude_whole_plane() {
+        let mut mesh = make_subdivided_plane();
+        assert_eq!(mesh.triangle_count(), 32);
+
+        let selection = mesh.vertex_selection().all().extrude(2.0);
+        assert_eq!(selection.count(), 32 * 3);
+
+        // 16 perimeter edges each gain a two-triangle wall
+        assert_eq!(mesh.triangle_count(), 32 + 16 * 2);
+        assert_eq!(mesh.vertices.len(), (32 + 16 * 2) * 3);
+
+        let top = mesh.vertices[..32 * 3].iter();
+        assert!(top.clone().all(|v| v.position.z == 2.0));
+    }
+
+    #[test]
+    fn test_extrude_region_builds_outward_walls() {
+        let mut mesh = make_subdivided_plane();
+        mesh.vertex_selection()
+            .add(|v| v.position.x <= 0.5 && v.position.y <= 0.5)
+            .extrude(1.0);
+
+        // A 2x2 block of cells: 8 faces lifted, 8 boundary edges walled
+        let lifted = mesh.vertices[..32 * 3]
+            .chunks_exact(3)
+            .filter(|tri| tri.iter().all(|v| v.position.z == 1.0))
+            .count();
+        assert_eq!(lifted, 8);
+        assert_eq!(mesh.triangle_count(), 32 + 8 * 2);
+
+        // Walls face away from the middle of the block
+        let center = Vec3::new(0.25, 0.25, 0.5);
+        for tri in mesh.vertices[32 * 3..].chunks_exact(3) {
+            let face = (tri[1].position - tri[0].position).cross(tri[2].position - tri[0].position);
+            let mid = (tri[0].position + tri[1].position + tri[2].position) / 3.0;
+            assert!(face.dot(mid - center) > 0.0);
+            assert!(face.normalize().dot(tri[0].normal) > 0.99);
+        }
+    }
+
+    #[test]
+    fn test_add_by_color_selects_painted_subset() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        let white = Vec3::ONE;
+
+        mesh.vertex_selection()
+            .add(|v| v.position.z > 0.5)
+            .set_color(white);
+
+        // Every vertex on the top face, plus the upper corners of the sides
+        let painted = mesh.vertices.iter().filter(|v| v.position.z > 0.5).count();
+        let selection = mesh.vertex_selection().add_by_color(white, 1e-4);
+        assert_eq!(selection.count(), painted);
+
+        // Recolor everything that is currently white
+        selection.set_color(Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(mesh.vertex_selection().add_by_color(white, 1e-4).count(), 0);
+    }
+
+    #[test]
+    fn test_add_by_color_respects_epsilon() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        mesh.vertex_selection().all().set_color(Vec3::splat(0.5));
+
+        let near = Vec3::new(0.5, 0.5, 0.52);
+        assert_eq!(mesh.vertex_selection().add_by_color(near, 0.01).count(), 0);
+        assert_eq!(mesh.vertex_selection().add_by_color(near, 0.05).count(), 36);
+    }
+
+    fn sorted(selection: &VertexSelection) -> Vec<usize> {
+        let mut indices = selection.indices().to_vec();
+        indices.sort();
+        indices
+    }
+
+    fn matching(mesh: &TriangleMesh, predicate: impl Fn(&MeshVertex) -> bool) -> Vec<usize> {
+        (0..mesh.vertices.len())
+            .filter(|&i| predicate(&mesh.vertices[i]))
+            .collect()
+    }
+
+    #[test]
+    fn test_union() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        let expected = matching(&mesh, |v| v.position.z > 0.5 || v.position.x > 0.5);
+
+        let selection = mesh
+            .vertex_selection()
+            .add(|v| v.position.z > 0.5)
+            .union(|s| s.add(|v| v.position.x > 0.5));
+        assert_eq!(sorted(&selection), expected);
+        assert_eq!(selection.count(), expected.len());
+    }
+
+    #[test]
+    fn test_intersect() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        let expected = matching(&mesh, |v| v.position.z > 0.5 && v.position.x > 0.5);
+        assert!(!expected.is_empty());
+
+        let selection = mesh
+            .vertex_selection()
+            .add(|v| v.position.z > 0.5)
+            .intersect(|s| s.add(|v| v.position.x > 0.5));
+        assert_eq!(sorted(&selection), expected);
+    }
+
+    #[test]
+    fn test_difference() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        let expected = matching(&mesh, |v| v.position.z > 0.5 && v.position.x <= 0.5);
+
+        let selection = mesh
+            .vertex_selection()
+            .add(|v| v.position.z > 0.5)
+            .difference(|s| s.add(|v| v.position.x > 0.5));
+        assert_eq!(sorted(&selection), expected);
+
+        // Nothing is left when removing a superset
+        let selection = mesh
+            .vertex_selection()
+            .add(|v| v.position.z > 0.5)
+            .difference(|s| s.all());
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn test_intersect_painted_region() {
+        let mut mesh = MeshBuilder::make_unit_cube();
+        let red = Vec3::new(1.0, 0.0, 0.0);
+        mesh.vertex_selection()
+            .add(|v| v.position.x > 0.5)
+            .set_color(red);
+
+        // Top AND red, then recolor just that part
+        mesh.vertex_selection()
+            .add(|v| v.position.z > 0.5)
+            .intersect(|s| s.add_by_color(red, 1e-4))
+            .set_color(Vec3::ONE);
+        let expected = matching(&mesh, |v| v.position.z > 0.5 && v.position.x > 0.5);
+        let white = mesh.vertex_selection().add_by_color(Vec3::ONE, 1e-4);
+        assert_eq!(sorted(&white), expected);
+    }
+}
@@ -6,7 +6,7 @@ mod engine;
 mod geometry;
 
 use engine::prelude::{
-    CameraPerspective, Engine, EngineCtx, EngineTask, EngineWindow, Renderer3D, Scene3D,
+    CameraPerspective, Engine, EngineCtx, EngineTask, EngineWindow, Renderer3D, Scene3D, TaskPhase,
 };
 use engine::renderer_3d::utils;
 use geometry::{LineMesh, MeshBuilder};
@@ -30,6 +30,10 @@ fn build_scene(ctx: &mut EngineCtx) {
 
     let mut c3 = c1.clone();
     c3.translate(7.0, 7.0, 0.0);
+    c3.vertex_selection()
+        .add(|v| v.position.z > 7.5)
+        .intersect(|s| s.add(|v| v.position.x > 7.5))
+        .set_color(Vec3::new(1.0, 0.5, 0.1));
     scene.add(c3.to_triangle_buffer());
 
     let mut c4 = c1.clone();
@@ -41,24 +45,40 @@ fn build_scene(ctx: &mut EngineCtx) {
     c5.remove_nonorthographic_lines();
     scene.add_line_buffer(c5.to_line_buffer());
 
+    let mut pillar = MeshBuilder::make_cylinder(0.5, 6.0, 24);
+    let mut spire = MeshBuilder::make_cone(0.75, 2.0, 24);
+    spire.translate(0.0, 0.0, 6.0);
+    pillar.merge(&spire);
+    pillar.translate(3.5, 3.5, 0.0);
+    scene.add(pillar.to_triangle_buffer());
+
+    let mut ring = MeshBuilder::make_torus(2.0, 0.4, 32, 12);
+    ring.translate(3.5, 3.5, 3.0);
+    scene.add(ring.to_triangle_buffer());
+
+    scene.add_grid(24.0, 24);
+    scene.add_axes(2.0);
+
     ctx.queue.entities.push(Box::new(scene));
 }
 
 fn setup_renderer(ctx: &mut EngineCtx) {
-    let mut renderer = Renderer3D::new(ctx.window.clone());
+    let mut renderer = Renderer3D::new(ctx.window.clone(), true);
     let closure = move |ctx: &mut engine::prelude::EngineCtx| {
         let scene = ctx
             .database
             .select_mut::<Scene3D>()
             .expect("No Scene3D found in database");
 
+        // Match the surface to the size the Update tasks saw this frame
+        renderer.resize(ctx.surface_width as u32, ctx.surface_height as u32);
         renderer.render_scene(scene);
         true
     };
     ctx.queue.task_once(|ctx| {
         println!("Renderer3D initialized.");
     });
-    ctx.queue.task_frame(closure);
+    ctx.queue.task_frame(TaskPhase::Render, closure);
 }
 
 fn rotate_camera(ctx: &mut EngineCtx) -> bool {
@@ -83,9 +103,11 @@ fn main() {
     let engine = Engine::new("Snowfall (blackbird)".into(), true);
     println!("{}", engine.title);
     engine.init(|mut q| {
-        q.task_once(build_scene);
-        q.task_frame(rotate_camera);
+        // Registration order doesn't matter: phases guarantee the scene is
+        // built before the camera update, which runs before rendering.
+        q.task_frame(TaskPhase::Update, rotate_camera);
         q.task_once(setup_renderer);
+        q.task_once(build_scene);
     });
     engine.run();
 }
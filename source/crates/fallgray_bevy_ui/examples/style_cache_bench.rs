@@ -0,0 +1,41 @@
+//! Rough comparison of applying a style string with and without the parse cache.
+//!
+//! Run with `cargo run --release --example style_cache_bench`.
+
+use bevy::prelude::*;
+use fallgray_bevy_ui::{EntityCommandsUIExt, clear_style_cache};
+use std::time::{Duration, Instant};
+
+const ITERATIONS: usize = 10_000;
+const STYLE: &str = "absolute top-8 left-8 width-320 height-240 flex-col-center gap8 \
+                     p12 mt4 bg-rgba(0.1,0.1,0.12,0.9) outline-width-2 \
+                     outline-rgb(0.4,0.4,0.5) fg-white font-size-16 z10";
+
+fn run(clear_cache: bool) -> Duration {
+    let mut world = World::new();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        if clear_cache {
+            clear_style_cache();
+        }
+        world.commands().spawn_empty().style(STYLE);
+        world.flush();
+    }
+    start.elapsed()
+}
+
+fn main() {
+    // Warm up the compiled regex patterns so they aren't counted in either run
+    run(true);
+
+    let uncached = run(true);
+    let cached = run(false);
+
+    println!("{} styled entities", ITERATIONS);
+    println!("  uncached: {:?}", uncached);
+    println!("  cached:   {:?}", cached);
+    println!(
+        "  speedup:  {:.1}x",
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
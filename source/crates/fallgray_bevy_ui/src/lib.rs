@@ -1,11 +1,39 @@
+use bevy::ecs::system::IntoSystem;
+use bevy::picking::events::{Pointer, Press};
+use bevy::picking::pointer::PointerButton;
 use bevy::{log, prelude::*};
 use regex::Regex;
-use std::sync::LazyLock;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
 
 pub trait EntityCommandsUIExt {
     fn style(self, style: &str) -> Self;
     fn styles(self, styles: &Vec<&str>) -> Self;
     fn text(self, content: &str) -> Self;
+    /// Runs `system` whenever the node is pressed with the primary mouse
+    /// button. An [`Interaction`] component is inserted so the node also
+    /// reports hover / pressed state like any other button.
+    ///
+    /// The system is registered as a one-shot system owned by the entity: it
+    /// is unregistered when the entity is despawned, so it can't be shared
+    /// between entities or outlive the node. Any state it needs must be
+    /// captured by value (`move` closures) or read from resources/queries.
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use fallgray_bevy_ui::EntityCommandsUIExt;
+    ///
+    /// fn spawn_menu(mut commands: Commands) {
+    ///     commands
+    ///         .spawn_empty()
+    ///         .style("p8 bg-rgb(0.2,0.2,0.25) fg-white")
+    ///         .text("Play")
+    ///         .on_click(|mut exit: MessageWriter<AppExit>| {
+    ///             exit.write(AppExit::Success);
+    ///         });
+    /// }
+    /// ```
+    fn on_click<M>(self, system: impl IntoSystem<(), (), M> + Send + 'static) -> Self;
 }
 
 impl<'a> EntityCommandsUIExt for EntityCommands<'a> {
@@ -21,16 +49,78 @@ impl<'a> EntityCommandsUIExt for EntityCommands<'a> {
         self.insert(Text::new(content));
         self
     }
+    fn on_click<M>(mut self, system: impl IntoSystem<(), (), M> + Send + 'static) -> Self {
+        let system_id = self.commands().register_system(system);
+        self.insert(Interaction::default());
+        self.observe(move |press: On<Pointer<Press>>, mut commands: Commands| {
+            if press.button == PointerButton::Primary {
+                commands.run_system(system_id);
+            }
+        });
+        self.observe(move |_: On<Despawn>, mut commands: Commands| {
+            commands.unregister_system(system_id);
+        });
+        self
+    }
 }
 
-#[derive(Default)]
+/// Named fonts that styles can reference with `font-<name>` (e.g. `font-mono`).
+///
+/// Fonts are looked up when the style is applied, so register them before
+/// spawning the UI that uses them. Unknown names fall back to the default font
+/// with a warning.
+#[derive(Resource, Default)]
+pub struct FontRegistry {
+    fonts: HashMap<String, Handle<Font>>,
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: &str, font: Handle<Font>) {
+        self.fonts.insert(name.to_string(), font);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Handle<Font>> {
+        self.fonts.get(name)
+    }
+}
+
+#[derive(Default, Clone)]
 struct StyledBundle {
     node: Node,
     z_index: Option<ZIndex>,
     background_color: Option<BackgroundColor>,
     text_font: Option<TextFont>,
     text_color: Option<TextColor>,
+    text_layout: Option<TextLayout>,
+    font_name: Option<String>,
     outline: Option<Outline>,
+    /// Alpha applied to the background color once all tokens are parsed, so
+    /// `opacity-*` works regardless of where it appears relative to `bg-*`
+    opacity: Option<f32>,
+}
+
+/// Narrowest and widest aspect ratios accepted by the `aspect-*` style
+const MIN_ASPECT_RATIO: f32 = 0.01;
+const MAX_ASPECT_RATIO: f32 = 100.0;
+
+/// Clamps a style value into `min..=max`, warning when it had to be adjusted
+fn clamp_style_value(name: &str, value: f32, min: f32, max: f32) -> f32 {
+    let clamped = value.clamp(min, max);
+    if clamped != value {
+        log::warn!(
+            "{} value {} out of range {}..={}, clamping to {}",
+            name,
+            value,
+            min,
+            max,
+            clamped
+        );
+    }
+    clamped
 }
 
 enum StyleHandler {
@@ -60,24 +150,96 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
                 b.node.position_type = PositionType::Relative;
             }),
         ),
+        (
+            r"top-(\d+)%",
+            I32(|b, v| {
+                b.node.top = Val::Percent(v as f32);
+            }),
+        ),
+        (
+            r"top-(\d+)vw",
+            I32(|b, v| {
+                b.node.top = Val::Vw(v as f32);
+            }),
+        ),
+        (
+            r"top-(\d+)vh",
+            I32(|b, v| {
+                b.node.top = Val::Vh(v as f32);
+            }),
+        ),
         (
             r"top-(\d+)",
             I32(|b, v| {
                 b.node.top = Val::Px(v as f32);
             }),
         ),
+        (
+            r"left-(\d+)%",
+            I32(|b, v| {
+                b.node.left = Val::Percent(v as f32);
+            }),
+        ),
+        (
+            r"left-(\d+)vw",
+            I32(|b, v| {
+                b.node.left = Val::Vw(v as f32);
+            }),
+        ),
+        (
+            r"left-(\d+)vh",
+            I32(|b, v| {
+                b.node.left = Val::Vh(v as f32);
+            }),
+        ),
         (
             r"left-(\d+)",
             I32(|b, v| {
                 b.node.left = Val::Px(v as f32);
             }),
         ),
+        (
+            r"bottom-(\d+)%",
+            I32(|b, v| {
+                b.node.bottom = Val::Percent(v as f32);
+            }),
+        ),
+        (
+            r"bottom-(\d+)vw",
+            I32(|b, v| {
+                b.node.bottom = Val::Vw(v as f32);
+            }),
+        ),
+        (
+            r"bottom-(\d+)vh",
+            I32(|b, v| {
+                b.node.bottom = Val::Vh(v as f32);
+            }),
+        ),
         (
             r"bottom-(\d+)",
             I32(|b, v| {
                 b.node.bottom = Val::Px(v as f32);
             }),
         ),
+        (
+            r"right-(\d+)%",
+            I32(|b, v| {
+                b.node.right = Val::Percent(v as f32);
+            }),
+        ),
+        (
+            r"right-(\d+)vw",
+            I32(|b, v| {
+                b.node.right = Val::Vw(v as f32);
+            }),
+        ),
+        (
+            r"right-(\d+)vh",
+            I32(|b, v| {
+                b.node.right = Val::Vh(v as f32);
+            }),
+        ),
         (
             r"right-(\d+)",
             I32(|b, v| {
@@ -109,7 +271,16 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
             }),
         ),
         (
-            r"z(\d+)",
+            // Fixed width / height ratio, e.g. `aspect-1` for square slots
+            r"aspect-([\d.]+)",
+            F32(|b, v| {
+                let ratio = clamp_style_value("aspect", v, MIN_ASPECT_RATIO, MAX_ASPECT_RATIO);
+                b.node.aspect_ratio = Some(ratio);
+            }),
+        ),
+        (
+            // Negative values (`z-5`) sit behind siblings with the default z
+            r"z(-?\d+)",
             I32(|b, v| {
                 b.z_index = Some(ZIndex(v));
             }),
@@ -235,6 +406,37 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
             I32(|b, v| b.node.margin = UiRect::all(Val::Px(v as f32))),
         ),
         //
+        // Negative margins (e.g. `-mt-8`) for overlapping elements
+        //
+        (
+            r"-mt-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::top(Val::Px(-(v as f32)))),
+        ),
+        (
+            r"-mb-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::bottom(Val::Px(-(v as f32)))),
+        ),
+        (
+            r"-ml-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::left(Val::Px(-(v as f32)))),
+        ),
+        (
+            r"-mr-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::right(Val::Px(-(v as f32)))),
+        ),
+        (
+            r"-mx-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::horizontal(Val::Px(-(v as f32)))),
+        ),
+        (
+            r"-my-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::vertical(Val::Px(-(v as f32)))),
+        ),
+        (
+            r"-m-(\d+)",
+            I32(|b, v| b.node.margin = UiRect::all(Val::Px(-(v as f32)))),
+        ),
+        //
         // Padding
         //
         (
@@ -282,6 +484,13 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
                 bundle.background_color = Some(BackgroundColor(color));
             }),
         ),
+        (
+            // Sets the alpha of the background color (0 = transparent, 1 = opaque)
+            r"opacity-([\d.]+)",
+            F32(|b, v| {
+                b.opacity = Some(clamp_style_value("opacity", v, 0.0, 1.0));
+            }),
+        ),
         (
             r"fg-white",
             Void(|b| {
@@ -311,6 +520,12 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
                 b.outline.get_or_insert_with(Outline::default).width = Val::Px(v);
             }),
         ),
+        (
+            r"outline-offset-([\d\.]+)",
+            F32(|b, v| {
+                b.outline.get_or_insert_with(Outline::default).offset = Val::Px(v);
+            }),
+        ),
         (
             r"outline-rgb\(([\d\.]+),([\d\.]+),([\d\.]+)\)",
             F32F32F32(|bundle, r, g, b| {
@@ -334,6 +549,27 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
                 b.text_font.get_or_insert_with(TextFont::default).font_size = v;
             }),
         ),
+        (
+            r"font-([a-z0-9_]+)",
+            Str(|b, v| {
+                b.font_name = Some(v.to_string());
+            }),
+        ),
+        (
+            r"text-(left|center|right)",
+            Str(|b, v| {
+                let justify = match v {
+                    "left" => Justify::Left,
+                    "center" => Justify::Center,
+                    "right" => Justify::Right,
+                    _ => {
+                        log::warn!("Invalid text alignment: {}", v);
+                        Justify::Left
+                    }
+                };
+                b.text_layout = Some(TextLayout::new_with_justify(justify));
+            }),
+        ),
     ];
 
     let mut compiled = Vec::new();
@@ -348,8 +584,47 @@ static COMPILED_PATTERNS: LazyLock<Vec<(Regex, StyleHandler)>> = LazyLock::new(|
     compiled
 });
 
+/// Parsed bundles keyed by the exact style string, so UIs that apply the same
+/// style to many entities only pay for the regex matching once. Warnings for
+/// unknown or invalid tokens are therefore only logged the first time a given
+/// string is parsed.
+static STYLE_CACHE: LazyLock<Mutex<HashMap<String, StyledBundle>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Drops all cached style parses (e.g. after registering new fonts or when
+/// measuring uncached parsing)
+pub fn clear_style_cache() {
+    STYLE_CACHE.lock().unwrap().clear();
+}
+
+/// Number of distinct style strings currently cached
+pub fn style_cache_len() -> usize {
+    STYLE_CACHE.lock().unwrap().len()
+}
+
+/// Returns the parsed bundle for a style string, parsing and caching it on
+/// first use
+fn cached_style(sl: &str) -> StyledBundle {
+    if let Some(bundle) = STYLE_CACHE.lock().unwrap().get(sl) {
+        return bundle.clone();
+    }
+
+    // Parse without holding the lock; a concurrent parse of the same string
+    // just produces an identical entry.
+    let bundle = parse_style(sl);
+    STYLE_CACHE
+        .lock()
+        .unwrap()
+        .insert(sl.to_string(), bundle.clone());
+    bundle
+}
+
 /// Uses a tailwind-like shorthand to allow for more concise UI definitions
 fn node_style(commands: &mut EntityCommands, sl: &str) {
+    insert_styled_bundle(commands, cached_style(sl));
+}
+
+fn parse_style(sl: &str) -> StyledBundle {
     let mut bundle = StyledBundle::default();
 
     let tokens: Vec<&str> = sl.split_whitespace().collect();
@@ -458,10 +733,20 @@ fn node_style(commands: &mut EntityCommands, sl: &str) {
         }
     }
 
+    bundle
+}
+
+fn insert_styled_bundle(commands: &mut EntityCommands, mut bundle: StyledBundle) {
     commands.insert(bundle.node);
     if let Some(z_index) = bundle.z_index {
         commands.insert(z_index);
     }
+    if let Some(opacity) = bundle.opacity {
+        match bundle.background_color.as_mut() {
+            Some(background_color) => background_color.0.set_alpha(opacity),
+            None => log::warn!("opacity-{} has no effect without a bg color", opacity),
+        }
+    }
     if let Some(background_color) = bundle.background_color {
         commands.insert(background_color);
     }
@@ -471,7 +756,197 @@ fn node_style(commands: &mut EntityCommands, sl: &str) {
     if let Some(text_color) = bundle.text_color {
         commands.insert(text_color);
     }
+    if let Some(text_layout) = bundle.text_layout {
+        commands.insert(text_layout);
+    }
+    if let Some(font_name) = bundle.font_name {
+        // The registry lives in the world, so resolve the font once the
+        // command is applied rather than while parsing the style.
+        commands.queue(move |mut entity: EntityWorldMut| {
+            let font = entity
+                .world()
+                .get_resource::<FontRegistry>()
+                .and_then(|registry| registry.get(&font_name))
+                .cloned();
+            let Some(font) = font else {
+                log::warn!("Unknown font '{}', using the default font", font_name);
+                return;
+            };
+            match entity.get_mut::<TextFont>() {
+                Some(mut text_font) => text_font.font = font,
+                None => {
+                    entity.insert(TextFont::from(font));
+                }
+            }
+        });
+    }
     if let Some(outline) = bundle.outline {
         commands.insert(outline);
     }
 }
+
+//=============================================================================
+// Tests
+//=============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled_text_layout(style: &str) -> Option<TextLayout> {
+        let mut world = World::new();
+        let entity = world.commands().spawn_empty().style(style).id();
+        world.flush();
+        world.get::<TextLayout>(entity).copied()
+    }
+
+    #[test]
+    fn test_text_alignment_tokens() {
+        let cases = [
+            ("text-left", Justify::Left),
+            ("text-center", Justify::Center),
+            ("text-right", Justify::Right),
+        ];
+        for (style, expected) in cases {
+            let layout = styled_text_layout(style).expect("TextLayout should be inserted");
+            assert_eq!(layout.justify, expected, "style: {}", style);
+        }
+    }
+
+    fn styled_margin(style: &str) -> UiRect {
+        let mut world = World::new();
+        let entity = world.commands().spawn_empty().style(style).id();
+        world.flush();
+        world.get::<Node>(entity).unwrap().margin
+    }
+
+    #[test]
+    fn test_negative_margin_single_side() {
+        let margin = styled_margin("-mt-8");
+        assert_eq!(margin.top, Val::Px(-8.0));
+        assert_eq!(margin.bottom, Val::ZERO);
+
+        assert_eq!(styled_margin("-mb-3").bottom, Val::Px(-3.0));
+        assert_eq!(styled_margin("-ml-5").left, Val::Px(-5.0));
+        assert_eq!(styled_margin("-mr-7").right, Val::Px(-7.0));
+    }
+
+    #[test]
+    fn test_negative_margin_axes() {
+        let margin = styled_margin("-mx-4");
+        assert_eq!(margin.left, Val::Px(-4.0));
+        assert_eq!(margin.right, Val::Px(-4.0));
+        assert_eq!(margin.top, Val::ZERO);
+
+        let margin = styled_margin("-my-2");
+        assert_eq!(margin.top, Val::Px(-2.0));
+        assert_eq!(margin.bottom, Val::Px(-2.0));
+        assert_eq!(margin.left, Val::ZERO);
+
+        assert_eq!(styled_margin("-m-6"), UiRect::all(Val::Px(-6.0)));
+    }
+
+    #[test]
+    fn test_positive_margin_unchanged() {
+        assert_eq!(styled_margin("mt-8").top, Val::Px(8.0));
+        assert_eq!(styled_margin("m-2"), UiRect::all(Val::Px(2.0)));
+    }
+
+    fn styled_node(style: &str) -> Node {
+        let mut world = World::new();
+        let entity = world.commands().spawn_empty().style(style).id();
+        world.flush();
+        world.get::<Node>(entity).unwrap().clone()
+    }
+
+    #[test]
+    fn test_position_pixels() {
+        let node = styled_node("top-4 left-8 bottom-12 right-16");
+        assert_eq!(node.top, Val::Px(4.0));
+        assert_eq!(node.left, Val::Px(8.0));
+        assert_eq!(node.bottom, Val::Px(12.0));
+        assert_eq!(node.right, Val::Px(16.0));
+    }
+
+    #[test]
+    fn test_position_percent() {
+        let node = styled_node("top-10% left-20% bottom-30% right-40%");
+        assert_eq!(node.top, Val::Percent(10.0));
+        assert_eq!(node.left, Val::Percent(20.0));
+        assert_eq!(node.bottom, Val::Percent(30.0));
+        assert_eq!(node.right, Val::Percent(40.0));
+    }
+
+    #[test]
+    fn test_position_viewport_units() {
+        let node = styled_node("top-5vh left-15vw bottom-25vh right-35vw");
+        assert_eq!(node.top, Val::Vh(5.0));
+        assert_eq!(node.left, Val::Vw(15.0));
+        assert_eq!(node.bottom, Val::Vh(25.0));
+        assert_eq!(node.right, Val::Vw(35.0));
+
+        let node = styled_node("top-50vw left-50vh");
+        assert_eq!(node.top, Val::Vw(50.0));
+        assert_eq!(node.left, Val::Vh(50.0));
+    }
+
+    fn styled_z_index(style: &str) -> Option<ZIndex> {
+        let mut world = World::new();
+        let entity = world.commands().spawn_empty().style(style).id();
+        world.flush();
+        world.get::<ZIndex>(entity).copied()
+    }
+
+    #[test]
+    fn test_z_index_tokens() {
+        assert_eq!(styled_z_index("z1000"), Some(ZIndex(1000)));
+        assert_eq!(styled_z_index("z7"), Some(ZIndex(7)));
+        assert_eq!(styled_z_index("z-5"), Some(ZIndex(-5)));
+        assert_eq!(styled_z_index("z-120"), Some(ZIndex(-120)));
+    }
+
+    #[test]
+    fn test_new_style_string_populates_cache() {
+        // Unique string so parallel tests sharing the cache don't interfere
+        let style = "flex-col p7 -mt-13 text-right";
+        assert!(!STYLE_CACHE.lock().unwrap().contains_key(style));
+
+        let mut world = World::new();
+        let first = world.commands().spawn_empty().style(style).id();
+        world.flush();
+        assert!(STYLE_CACHE.lock().unwrap().contains_key(style));
+
+        // The cached parse must produce the same components as the fresh one
+        let second = world.commands().spawn_empty().style(style).id();
+        world.flush();
+        for entity in [first, second] {
+            let node = world.get::<Node>(entity).unwrap();
+            assert_eq!(node.flex_direction, FlexDirection::Column);
+            assert_eq!(node.padding, UiRect::all(Val::Px(7.0)));
+            assert_eq!(node.margin.top, Val::Px(-13.0));
+            let layout = world.get::<TextLayout>(entity).unwrap();
+            assert_eq!(layout.justify, Justify::Right);
+        }
+    }
+
+    #[test]
+    fn test_outline_properties_compose() {
+        let mut world = World::new();
+        let entity = world
+            .commands()
+            .spawn_empty()
+            .style("outline-width-2 outline-rgb(1.0,0.5,0.0) outline-offset-3")
+            .id();
+        world.flush();
+
+        let outline = world.get::<Outline>(entity).unwrap();
+        assert_eq!(outline.width, Val::Px(2.0));
+        assert_eq!(outline.offset, Val::Px(3.0));
+        assert_eq!(outline.color, Color::srgb(1.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_no_text_layout_without_alignment_token() {
+        assert!(styled_text_layout("flex-row p4").is_none());
+    }
+}
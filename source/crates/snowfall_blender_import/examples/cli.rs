@@ -21,6 +21,16 @@ fn main() -> Result<()> {
     println!("Endianness: {:?}", blend_file.endianness);
     println!("Meshes Found: {}", blend_file.scene.meshes.len());
 
+    let stats = blend_file.stats();
+    println!("\n=== Scene Statistics ===");
+    println!("Meshes: {}", stats.mesh_count);
+    println!("Vertices: {}", stats.total_vertices);
+    println!("Triangles: {}", stats.total_triangles);
+    println!("Instances: {}", stats.instance_count);
+    println!("Groups: {}", stats.group_count);
+    println!("Materials: {}", stats.material_count);
+    println!("Linked Libraries: {}", stats.linked_library_count);
+
     println!("\n=== Meshes ===");
     for (mesh_id, mesh) in &blend_file.scene.meshes {
         println!(
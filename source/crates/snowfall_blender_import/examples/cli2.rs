@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
 use blend::{Blend, Instance};
 use glam::Vec3;
-use snowfall_blender_import::{BBox, MGroup, MInstance, MLink, MMesh, MNode, MTransform};
+use snowfall_blender_import::{
+    BBox, BlendLibrary, MGroup, MInstance, MLink, MMesh, MNode, MTransform, resolve_links,
+};
 use std::collections::HashMap;
 use std::env;
 use std::io::Cursor;
@@ -21,13 +23,6 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-pub struct BlendLibrary {
-    pub name: String,
-    pub filepath: String,
-    pub assets: HashMap<String, MNode>,
-    pub meshes: HashMap<String, MMesh>,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PointerSize {
     Bits32,
@@ -100,6 +95,14 @@ fn cmd_summary(filename: &str) -> Result<()> {
 
     print_summary(&file);
 
+    let resolved = resolve_links(&mut file.root, &file.libraries);
+    println!("\n=== Resolved Links ===");
+    print_hierarchy(&file.root.children, 1);
+    println!("Merged meshes: {}", resolved.meshes.len());
+    for link in &resolved.unresolved {
+        println!("Unresolved link: \"{}\" ({})", link.id, link.library);
+    }
+
     Ok(())
 }
 
@@ -224,6 +227,7 @@ fn extract_transform(instance: &Instance) -> MTransform {
         translation,
         rotation,
         scale,
+        matrix: None,
     }
 }
 
@@ -398,6 +402,6 @@ fn extract_mesh_data_v4(instance: &Instance) -> Result<MMesh> {
         }
     }
 
-    mesh.bbox = BBox::from_positions(&mesh.positions);
+    mesh.bbox = BBox::from_points(&mesh.positions);
     Ok(mesh)
 }
@@ -0,0 +1,343 @@
+use blend::Instance;
+
+use crate::{TransformKey, TransformParts, compose_transform, list_items};
+
+/// Object transform properties that can be animated, indexing into
+/// `TransformParts`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransformProperty {
+    Location = 0,
+    Rotation = 1,
+    Scale = 2,
+}
+
+impl TransformProperty {
+    fn from_rna_path(path: &str) -> Option<Self> {
+        match path {
+            "location" => Some(TransformProperty::Location),
+            "rotation_euler" => Some(TransformProperty::Rotation),
+            "scale" => Some(TransformProperty::Scale),
+            _ => None,
+        }
+    }
+}
+
+/// One animated component of an object's transform, e.g. location X
+#[derive(Debug, Clone)]
+struct TransformCurve {
+    property: TransformProperty,
+    axis: usize,
+    /// (frame, value) pairs sorted by frame
+    keys: Vec<(f32, f32)>,
+}
+
+/// Keyframes of the action assigned to an object, or None if it isn't
+/// animated. `base` holds the object's static transform, which fills in the
+/// components that have no curve.
+///
+/// The saved world matrix is ignored here: every key is rebuilt from
+/// loc/rot/scale, so it is local to the object's parent and has no `matrix`.
+/// Skew from parenting or constraints is lost while the animation plays.
+///
+/// Only the F-Curves on `location`, `rotation_euler` and `scale` are read.
+/// Keys are always interpolated linearly: Bezier handles, constant and easing
+/// interpolation are ignored, as are curve modifiers, drivers, NLA strips,
+/// and quaternion or axis-angle rotations.
+pub(crate) fn extract_animation(
+    object: &Instance,
+    base: &TransformParts,
+) -> Option<Vec<TransformKey>> {
+    if !object.is_valid("adt") {
+        return None;
+    }
+    let anim_data = object.get("adt");
+    if !anim_data.is_valid("action") {
+        return None;
+    }
+
+    let curves: Vec<TransformCurve> = list_items(&anim_data.get("action"), "curves")
+        .iter()
+        .filter_map(extract_curve)
+        .collect();
+    let keys = sample_curves(base, &curves);
+    (!keys.is_empty()).then_some(keys)
+}
+
+fn extract_curve(fcurve: &Instance) -> Option<TransformCurve> {
+    if !fcurve.is_valid("rna_path") || !fcurve.is_valid("bezt") {
+        return None;
+    }
+    let path: Vec<u8> = fcurve
+        .get_u8_vec("rna_path")
+        .into_iter()
+        .take_while(|&c| c != 0)
+        .collect();
+    let property = TransformProperty::from_rna_path(std::str::from_utf8(&path).ok()?)?;
+    let axis = fcurve.get_i32("array_index");
+    if !(0..3).contains(&axis) {
+        return None;
+    }
+
+    // Each BezTriple's `vec` holds the left handle, the key and the right
+    // handle as (frame, value, 0) triples
+    let count = fcurve.get_i32("totvert").max(0) as usize;
+    let mut keys: Vec<(f32, f32)> = fcurve
+        .get_iter("bezt")
+        .take(count)
+        .filter_map(|bezt| {
+            let vec = bezt.get_f32_vec("vec");
+            (vec.len() >= 6).then(|| (vec[3], vec[4]))
+        })
+        .collect();
+    keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    Some(TransformCurve {
+        property,
+        axis: axis as usize,
+        keys,
+    })
+}
+
+/// One key for every frame any curve has a key on
+fn sample_curves(base: &TransformParts, curves: &[TransformCurve]) -> Vec<TransformKey> {
+    let mut frames: Vec<f32> = curves
+        .iter()
+        .flat_map(|curve| curve.keys.iter().map(|&(frame, _)| frame))
+        .collect();
+    frames.sort_by(f32::total_cmp);
+    frames.dedup();
+
+    frames
+        .into_iter()
+        .map(|frame| {
+            let mut parts = *base;
+            for curve in curves {
+                if let Some(value) = evaluate_curve(&curve.keys, frame) {
+                    parts[curve.property as usize].0[curve.axis] = value;
+                }
+            }
+            TransformKey {
+                frame,
+                transform: compose_transform(parts[0], parts[1], parts[2]),
+            }
+        })
+        .collect()
+}
+
+/// Value of a curve at a frame, interpolating linearly between keys and
+/// holding the first and last values outside them
+fn evaluate_curve(keys: &[(f32, f32)], frame: f32) -> Option<f32> {
+    let &(first_frame, first_value) = keys.first()?;
+    if frame <= first_frame {
+        return Some(first_value);
+    }
+    for pair in keys.windows(2) {
+        let ((start, from), (end, to)) = (pair[0], pair[1]);
+        if frame <= end {
+            let t = if end > start {
+                (frame - start) / (end - start)
+            } else {
+                1.0
+            };
+            return Some(from + (to - from) * t);
+        }
+    }
+    keys.last().map(|&(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blend_writer::BlendWriter;
+    use blend::Blend;
+    use glam::Vec3;
+    use std::io::Cursor;
+
+    fn static_parts(location: Vec3) -> TransformParts {
+        [
+            (location, Vec3::ZERO),
+            (Vec3::ZERO, Vec3::ZERO),
+            (Vec3::ONE, Vec3::ONE),
+        ]
+    }
+
+    #[test]
+    fn test_two_keyframe_translation() {
+        let curves = [TransformCurve {
+            property: TransformProperty::Location,
+            axis: 0,
+            keys: vec![(1.0, 0.0), (25.0, 4.0)],
+        }];
+        let keys = sample_curves(&static_parts(Vec3::new(0.0, 2.0, 3.0)), &curves);
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].frame, 1.0);
+        assert_eq!(keys[0].transform.translation, Vec3::new(0.0, 2.0, 3.0));
+        assert_eq!(keys[1].frame, 25.0);
+        assert_eq!(keys[1].transform.translation, Vec3::new(4.0, 2.0, 3.0));
+        assert_eq!(keys[1].transform.scale, Vec3::ONE);
+
+        let halfway = TransformKey::sample(&keys, 13.0).unwrap();
+        assert_eq!(halfway.translation, Vec3::new(2.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_curves_with_different_frames() {
+        // A key on one curve samples the others in between their keys
+        let curves = [
+            TransformCurve {
+                property: TransformProperty::Location,
+                axis: 2,
+                keys: vec![(0.0, 0.0), (10.0, 10.0)],
+            },
+            TransformCurve {
+                property: TransformProperty::Scale,
+                axis: 1,
+                keys: vec![(5.0, 2.0)],
+            },
+        ];
+        let keys = sample_curves(&static_parts(Vec3::ZERO), &curves);
+
+        let frames: Vec<f32> = keys.iter().map(|key| key.frame).collect();
+        assert_eq!(frames, vec![0.0, 5.0, 10.0]);
+        assert_eq!(keys[1].transform.translation.z, 5.0);
+        assert_eq!(keys[0].transform.scale, Vec3::new(1.0, 2.0, 1.0));
+    }
+
+    #[test]
+    fn test_evaluate_curve_holds_ends() {
+        let keys = [(10.0, 1.0), (20.0, 3.0)];
+        assert_eq!(evaluate_curve(&keys, 0.0), Some(1.0));
+        assert_eq!(evaluate_curve(&keys, 15.0), Some(2.0));
+        assert_eq!(evaluate_curve(&keys, 30.0), Some(3.0));
+        assert_eq!(evaluate_curve(&[], 0.0), None);
+    }
+
+    #[test]
+    fn test_rna_paths() {
+        assert_eq!(
+            TransformProperty::from_rna_path("rotation_euler"),
+            Some(TransformProperty::Rotation)
+        );
+        // Quaternion rotation isn't supported
+        assert_eq!(
+            TransformProperty::from_rna_path("rotation_quaternion"),
+            None
+        );
+    }
+
+    /// An object "OBMover" whose action has two F-Curves: `location[0]` keyed
+    /// from 0 at frame 1 to 4 at frame 25, and a `rotation_quaternion` curve
+    /// the importer skips
+    fn animated_object_blend() -> Vec<u8> {
+        let mut writer = BlendWriter::new(&[
+            ("ListBase", 16, &[("void", "*first"), ("void", "*last")]),
+            ("ID", 64, &[("char", "name[64]")]),
+            ("Object", 72, &[("ID", "id"), ("AnimData", "*adt")]),
+            ("AnimData", 8, &[("bAction", "*action")]),
+            ("bAction", 80, &[("ID", "id"), ("ListBase", "curves")]),
+            (
+                "FCurve",
+                40,
+                &[
+                    ("FCurve", "*next"),
+                    ("FCurve", "*prev"),
+                    ("BezTriple", "*bezt"),
+                    ("char", "*rna_path"),
+                    ("int", "array_index"),
+                    ("int", "totvert"),
+                ],
+            ),
+            ("BezTriple", 36, &[("float", "vec[3][3]")]),
+        ]);
+        let object_dna = writer.struct_index("Object");
+        let anim_data_dna = writer.struct_index("AnimData");
+        let action_dna = writer.struct_index("bAction");
+        let fcurve_dna = writer.struct_index("FCurve");
+        let bezt_dna = writer.struct_index("BezTriple");
+
+        let with_name = |name: &str, len: usize| {
+            let mut data = vec![0; len];
+            data[..name.len()].copy_from_slice(name.as_bytes());
+            data
+        };
+        let pointers = |data: &mut Vec<u8>, at: usize, values: &[u64]| {
+            for (i, value) in values.iter().enumerate() {
+                data[at + i * 8..at + i * 8 + 8].copy_from_slice(&value.to_le_bytes());
+            }
+        };
+        let fcurve = |next: u64, prev: u64, bezt: u64, rna_path: u64, totvert: i32| {
+            let mut data = vec![0; 40];
+            pointers(&mut data, 0, &[next, prev, bezt, rna_path]);
+            data[36..40].copy_from_slice(&totvert.to_le_bytes());
+            data
+        };
+        // Left handle, key and right handle, each (frame, value, 0)
+        let bezt = |frame: f32, value: f32| -> Vec<u8> {
+            [frame - 1.0, frame, frame + 1.0]
+                .into_iter()
+                .flat_map(|frame| [frame, value, 0.0])
+                .flat_map(f32::to_le_bytes)
+                .collect()
+        };
+
+        let mut object = with_name("OBMover", 72);
+        pointers(&mut object, 64, &[0x2000]);
+        writer.block(b"OB\0\0", 0x1000, object_dna, &object);
+        writer.block(b"DATA", 0x2000, anim_data_dna, &0x3000u64.to_le_bytes());
+        let mut action = with_name("ACMove", 80);
+        pointers(&mut action, 64, &[0x4000, 0x5000]);
+        writer.block(b"AC\0\0", 0x3000, action_dna, &action);
+
+        writer.block(
+            b"DATA",
+            0x4000,
+            fcurve_dna,
+            &fcurve(0x5000, 0, 0x6000, 0x7000, 2),
+        );
+        writer.array_block(
+            b"DATA",
+            0x6000,
+            bezt_dna,
+            2,
+            &[bezt(25.0, 4.0), bezt(1.0, 0.0)].concat(),
+        );
+        writer.block(b"DATA", 0x7000, 0, b"location\0");
+
+        writer.block(
+            b"DATA",
+            0x5000,
+            fcurve_dna,
+            &fcurve(0, 0x4000, 0x8000, 0x9000, 1),
+        );
+        writer.block(b"DATA", 0x8000, bezt_dna, &bezt(1.0, 1.0));
+        writer.block(b"DATA", 0x9000, 0, b"rotation_quaternion\0");
+        writer.finish()
+    }
+
+    #[test]
+    fn test_animated_object_fixture() {
+        let blend = Blend::new(Cursor::new(animated_object_blend())).unwrap();
+        let object = blend.instances_with_code(*b"OB").next().unwrap();
+
+        // Both curves are listed, but only the location one is read, with
+        // its keys put in frame order
+        let action = object.get("adt").get("action");
+        let curves: Vec<_> = list_items(&action, "curves")
+            .iter()
+            .map(extract_curve)
+            .collect();
+        assert_eq!(curves.len(), 2);
+        let location = curves[0].as_ref().unwrap();
+        assert_eq!(location.property, TransformProperty::Location);
+        assert_eq!(location.axis, 0);
+        assert_eq!(location.keys, vec![(1.0, 0.0), (25.0, 4.0)]);
+        assert!(curves[1].is_none());
+
+        let keys = extract_animation(&object, &static_parts(Vec3::new(0.0, 2.0, 3.0))).unwrap();
+        let frames: Vec<f32> = keys.iter().map(|key| key.frame).collect();
+        assert_eq!(frames, vec![1.0, 25.0]);
+        assert_eq!(keys[0].transform.translation, Vec3::new(0.0, 2.0, 3.0));
+        assert_eq!(keys[1].transform.translation, Vec3::new(4.0, 2.0, 3.0));
+    }
+}
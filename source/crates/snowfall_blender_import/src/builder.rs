@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use crate::{MGroup, MInstance, MMaterial, MMaterialID, MMesh, MMeshID, MNode, MScene, MTransform};
+
+/// Builds an `MScene` in code, for tests and procedural content
+///
+/// Instances and groups added directly to the builder go in the scene's root
+/// group. Nodes the shorthand methods don't cover, such as links or
+/// instances with a material, can be added with `node`.
+#[derive(Debug, Clone, Default)]
+pub struct SceneBuilder {
+    meshes: HashMap<MMeshID, MMesh>,
+    materials: HashMap<MMaterialID, MMaterial>,
+    root: GroupBuilder,
+}
+
+impl SceneBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_mesh(mut self, id: impl Into<MMeshID>, mesh: MMesh) -> Self {
+        self.meshes.insert(id.into(), mesh);
+        self
+    }
+
+    pub fn add_material(mut self, id: impl Into<MMaterialID>, material: MMaterial) -> Self {
+        self.materials.insert(id.into(), material);
+        self
+    }
+
+    /// Add an instance of a mesh to the root of the scene
+    pub fn instance(
+        mut self,
+        geometry_id: impl Into<MMeshID>,
+        transform: impl Into<Option<MTransform>>,
+    ) -> Self {
+        self.root = self.root.instance(geometry_id, transform);
+        self
+    }
+
+    /// Add a named group to the root of the scene, filled in by `build`
+    pub fn group(
+        mut self,
+        name: impl Into<String>,
+        build: impl FnOnce(GroupBuilder) -> GroupBuilder,
+    ) -> Self {
+        self.root = self.root.group(name, build);
+        self
+    }
+
+    /// Add any node to the root of the scene, e.g. a link or an instance
+    /// with a material
+    pub fn node(mut self, node: MNode) -> Self {
+        self.root = self.root.node(node);
+        self
+    }
+
+    pub fn build(self) -> MScene {
+        MScene {
+            meshes: self.meshes,
+            materials: self.materials,
+            animations: HashMap::new(),
+            root: self.root.build(None),
+        }
+    }
+}
+
+/// Builds the children of a group for `SceneBuilder::group`
+#[derive(Debug, Clone, Default)]
+pub struct GroupBuilder {
+    children: Vec<MNode>,
+    transform: Option<MTransform>,
+}
+
+impl GroupBuilder {
+    /// Set the transform of the group itself
+    pub fn transform(mut self, transform: MTransform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    pub fn instance(
+        self,
+        geometry_id: impl Into<MMeshID>,
+        transform: impl Into<Option<MTransform>>,
+    ) -> Self {
+        self.node(MNode::MInstance(MInstance {
+            name: None,
+            geometry_id: geometry_id.into(),
+            material_id: None,
+            transform: transform.into(),
+        }))
+    }
+
+    pub fn group(
+        self,
+        name: impl Into<String>,
+        build: impl FnOnce(GroupBuilder) -> GroupBuilder,
+    ) -> Self {
+        let group = build(GroupBuilder::default()).build(Some(name.into()));
+        self.node(MNode::MGroup(group))
+    }
+
+    pub fn node(mut self, node: MNode) -> Self {
+        self.children.push(node);
+        self
+    }
+
+    fn build(self, name: Option<String>) -> MGroup {
+        MGroup {
+            name,
+            children: self.children,
+            transform: self.transform,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    fn instance(mesh: &str, transform: Option<MTransform>) -> MNode {
+        MNode::MInstance(MInstance {
+            name: None,
+            geometry_id: mesh.to_string(),
+            material_id: None,
+            transform,
+        })
+    }
+
+    #[test]
+    fn test_builder_matches_hand_built_scene() {
+        let cube = MMesh::new("Cube".to_string());
+        let raised = MTransform {
+            translation: Vec3::new(0.0, 0.0, 2.0),
+            ..MTransform::IDENTITY
+        };
+
+        let built = SceneBuilder::new()
+            .add_mesh("Cube", cube.clone())
+            .add_material("Stone", MMaterial::default())
+            .instance("Cube", None)
+            .group("Props", |group| {
+                group
+                    .transform(raised)
+                    .instance("Cube", raised)
+                    .group("Empty", |group| group)
+            })
+            .build();
+
+        let by_hand = MScene {
+            meshes: HashMap::from([("Cube".to_string(), cube)]),
+            materials: HashMap::from([("Stone".to_string(), MMaterial::default())]),
+            animations: HashMap::new(),
+            root: MGroup {
+                name: None,
+                children: vec![
+                    instance("Cube", None),
+                    MNode::MGroup(MGroup {
+                        name: Some("Props".to_string()),
+                        children: vec![
+                            instance("Cube", Some(raised)),
+                            MNode::MGroup(MGroup {
+                                name: Some("Empty".to_string()),
+                                children: Vec::new(),
+                                transform: None,
+                            }),
+                        ],
+                        transform: Some(raised),
+                    }),
+                ],
+                transform: None,
+            },
+        };
+
+        assert_eq!(built, by_hand);
+    }
+
+    #[test]
+    fn test_node_adds_any_node() {
+        let with_material = MNode::MInstance(MInstance {
+            name: Some("Wall".to_string()),
+            geometry_id: "Cube".to_string(),
+            material_id: Some("Stone".to_string()),
+            transform: None,
+        });
+        let scene = SceneBuilder::new().node(with_material.clone()).build();
+        assert_eq!(scene.root.children, vec![with_material]);
+    }
+}
@@ -1,14 +1,22 @@
 use anyhow::{Context, Result};
 use blend::{Blend, Instance};
-use glam::Vec3;
-use std::collections::HashMap;
+use glam::{EulerRot, Mat4, Quat, Vec3};
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
+use std::num::NonZeroU64;
 use std::path::Path;
 
-mod bbox;
-pub use bbox::BBox;
+pub use snowfall_geometry::BBox;
+mod animation;
+mod builder;
+pub use builder::{GroupBuilder, SceneBuilder};
+mod link;
+pub use link::{BlendLibrary, ResolvedLinks, resolve_links};
 mod mesh;
 pub use mesh::*;
+#[cfg(test)]
+#[path = "../tests/common/blend_writer.rs"]
+mod blend_writer;
 // Blender object type constants
 const OBJ_TYPE_EMPTY: i32 = 0;
 const OBJ_TYPE_MESH: i32 = 1;
@@ -21,6 +29,11 @@ pub enum PointerSize {
 }
 
 /// Byte order in the blend file
+///
+/// Files written on big-endian hosts load the same way as little-endian ones:
+/// the `blend` crate reads the header, block headers, DNA and every field
+/// value using the byte order recorded in the file header, and mesh data is
+/// only ever read through its typed accessors, never as raw bytes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Endianness {
     Little,
@@ -35,11 +48,71 @@ struct CollectionData {
     collection_children: Vec<String>,
 }
 
+/// Unique ids for the ID blocks of one type, i.e. meshes or collections.
+///
+/// Cleaned names can collide, e.g. a local `Cube` mesh and a `Cube` linked
+/// from a library are both stored as `MECube`. Rather than one overwriting
+/// the other, the later block is renamed with a Blender-style numeric suffix
+/// (`Cube.001`, `Cube.002`, ...) and a warning is printed. Ids are keyed by
+/// the block's address in the file, so the objects and collections that
+/// point at a renamed block pick up its new id.
+#[derive(Debug, Default)]
+struct UniqueIds {
+    by_address: HashMap<NonZeroU64, String>,
+    taken: HashSet<String>,
+}
+
+impl UniqueIds {
+    /// Give the block at `address` an id, `name` unless that's already taken.
+    /// A block registered before keeps the id it was given then.
+    fn register(&mut self, address: Option<NonZeroU64>, name: String) -> String {
+        if let Some(id) = address.and_then(|address| self.by_address.get(&address)) {
+            return id.clone();
+        }
+
+        let id = if self.taken.contains(&name) {
+            let unique_id = (1..)
+                .map(|n| format!("{}.{:03}", name, n))
+                .find(|id| !self.taken.contains(id))
+                .unwrap();
+            eprintln!(
+                "Warning: Duplicate name '{}', importing as '{}'",
+                name, unique_id
+            );
+            unique_id
+        } else {
+            name
+        };
+
+        self.taken.insert(id.clone());
+        if let Some(address) = address {
+            self.by_address.insert(address, id.clone());
+        }
+        id
+    }
+
+    /// Id of the block at `address`, or `name` if it was never registered
+    fn id_of(&self, address: Option<NonZeroU64>, name: String) -> String {
+        address
+            .and_then(|address| self.by_address.get(&address))
+            .cloned()
+            .unwrap_or(name)
+    }
+}
+
+/// Address of a block in the file, which pointers to it refer to
+fn block_address(instance: &Instance) -> Option<NonZeroU64> {
+    instance.data.memory_address()
+}
+
 #[derive(Debug, Clone)]
 struct InstanceData {
+    /// Object name, also the key of the object's `MScene::animations` entry
+    name: String,
     mesh_ref: Option<String>,
     collection_ref: Option<String>,
     collection_library_path: Option<String>,
+    material_id: Option<MMaterialID>,
     transform: MTransform,
 }
 
@@ -56,6 +129,22 @@ pub struct BlendFile {
     pub scene: MScene,
 }
 
+/// Summary of what was imported from a Blender file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SceneStats {
+    pub mesh_count: usize,
+    /// Vertices across all meshes, counting each mesh once however often it
+    /// is instanced
+    pub total_vertices: usize,
+    pub total_triangles: usize,
+    /// Mesh instances anywhere in the scene graph
+    pub instance_count: usize,
+    /// Groups in the scene graph, not counting the root
+    pub group_count: usize,
+    pub material_count: usize,
+    pub linked_library_count: usize,
+}
+
 impl BlendFile {
     /// Get the Blender version as a string (e.g., "4.0.5")
     pub fn version_string(&self) -> String {
@@ -64,12 +153,102 @@ impl BlendFile {
             self.version[0] as char, self.version[1] as char, self.version[2] as char
         )
     }
+
+    /// Count what was loaded, walking the whole scene graph
+    pub fn stats(&self) -> SceneStats {
+        let mut stats = SceneStats {
+            mesh_count: self.scene.meshes.len(),
+            total_vertices: self.scene.meshes.values().map(MMesh::vertex_count).sum(),
+            total_triangles: self.scene.meshes.values().map(MMesh::triangle_count).sum(),
+            material_count: self.scene.materials.len(),
+            linked_library_count: self.linked_libraries.len(),
+            ..Default::default()
+        };
+        count_nodes(&self.scene.root.children, &mut stats);
+        stats
+    }
 }
 
-/// Load mesh data from a .blend file
+fn count_nodes(nodes: &[MNode], stats: &mut SceneStats) {
+    for node in nodes {
+        match node {
+            MNode::MInstance(_) => stats.instance_count += 1,
+            MNode::MGroup(group) => {
+                stats.group_count += 1;
+                count_nodes(&group.children, stats);
+            }
+            MNode::MLink(_) => {}
+        }
+    }
+}
+
+/// Pointer size and byte order of the host that wrote the file
+fn file_format(header: &blend::parsers::blend::Header) -> (PointerSize, Endianness) {
+    let pointer_size = match header.pointer_size {
+        blend::parsers::PointerSize::Bits32 => PointerSize::Bits32,
+        blend::parsers::PointerSize::Bits64 => PointerSize::Bits64,
+    };
+
+    let endianness = match header.endianness {
+        blend::parsers::Endianness::Little => Endianness::Little,
+        blend::parsers::Endianness::Big => Endianness::Big,
+    };
+
+    (pointer_size, endianness)
+}
+
+/// Processing applied to meshes after they are loaded
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Compute area-weighted vertex normals (see `MMesh::compute_normals`).
+    /// `None`, the default, only does this for meshes that were imported
+    /// without normals; `Some(true)` also replaces extracted normals and
+    /// `Some(false)` leaves every mesh as it was loaded.
+    pub recompute_normals: Option<bool>,
+    /// Extract object transform keyframes into `MScene::animations`. Off by
+    /// default; see `TransformKey` for how they are interpolated.
+    pub import_animation: bool,
+    /// Run `MMesh::fix_winding` on every mesh. This happens before any
+    /// normals are recomputed, so triangles are checked against the normals
+    /// stored in the file; meshes imported without normals are skipped. Off
+    /// by default since Blender's own winding and normals already agree.
+    pub fix_winding: bool,
+}
+
+/// Load mesh data from a .blend file with the default `ImportOptions`.
+///
+/// Note the defaults are not a plain read: meshes stored without normals get
+/// computed ones, which splits vertices along hard edges, so such a mesh can
+/// come back with more vertices than the file has. Set
+/// `ImportOptions::recompute_normals` to `Some(false)` to keep the file's
+/// geometry as it is.
 pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<BlendFile> {
-    let path = path.as_ref();
+    load_from_file_with_options(path, &ImportOptions::default())
+}
+
+/// Load mesh data from a .blend file
+pub fn load_from_file_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &ImportOptions,
+) -> Result<BlendFile> {
+    let mut blend_file = load_blend_file(path.as_ref(), options.import_animation)?;
+    apply_import_options(&mut blend_file.scene, options);
+    Ok(blend_file)
+}
+
+fn apply_import_options(scene: &mut MScene, options: &ImportOptions) {
+    for mesh in scene.meshes.values_mut() {
+        if options.fix_winding {
+            mesh.fix_winding();
+        }
+        let has_normals = !mesh.normals.is_empty() && mesh.normals.len() == mesh.positions.len();
+        if options.recompute_normals.unwrap_or(!has_normals) {
+            mesh.compute_normals(NORMAL_SMOOTHING_ANGLE);
+        }
+    }
+}
 
+fn load_blend_file(path: &Path, import_animation: bool) -> Result<BlendFile> {
     // First, scan for linked library files
     let data =
         std::fs::read(path).with_context(|| format!("Failed to read file: {}", path.display()))?;
@@ -125,7 +304,13 @@ pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<BlendFile> {
     let linked_library_paths: Vec<String> =
         linked_scenes.iter().map(|(path, _)| path.clone()).collect();
 
-    load_from_memory_with_linked_scenes(&data, None, &linked_scenes, linked_library_paths)
+    load_from_memory_with_linked_scenes(
+        &data,
+        None,
+        &linked_scenes,
+        linked_library_paths,
+        import_animation,
+    )
 }
 
 fn load_linked_scene<P: AsRef<Path>>(path: P, _lib_path: &str) -> Result<MScene> {
@@ -139,40 +324,33 @@ fn load_linked_scene<P: AsRef<Path>>(path: P, _lib_path: &str) -> Result<MScene>
     let mut scene = MScene {
         meshes: HashMap::new(),
         materials: HashMap::new(),
+        animations: HashMap::new(),
         root: MGroup {
             name: None,
             children: Vec::new(),
             transform: None,
         },
     };
+    let mut mesh_ids = UniqueIds::default();
     for instance in blend_file.instances_with_code(*b"ME") {
-        let (mesh_id, mesh) = extract_mesh_data(&instance, None)?;
+        let (mesh_id, mesh) = extract_mesh_data(&instance, None, &mut mesh_ids)?;
         scene.meshes.insert(mesh_id, mesh);
     }
-
-    // Extract collections from linked file
-    let mut collections = Vec::new();
-    let mut collection_names = std::collections::HashSet::new();
-
-    let mut add_collection = |coll: CollectionData| {
-        if collection_names.insert(coll.name.clone()) {
-            collections.push(coll);
-        }
-    };
-
-    // Extract CO blocks
-    for instance in blend_file.instances_with_code(*b"CO") {
-        let collection = extract_collection_data(&instance)
-            .with_context(|| "Failed to extract collection from linked file")?;
-        add_collection(collection);
+    for instance in blend_file.instances_with_code(*b"MA") {
+        scene.materials.insert(
+            clean_blender_id(&instance, "MA"),
+            extract_material(&instance),
+        );
     }
 
-    // Extract GR blocks
-    for instance in blend_file.instances_with_code(*b"GR") {
-        let collection = extract_group_data(&instance)
-            .with_context(|| "Failed to extract group from linked file")?;
-        add_collection(collection);
-    }
+    // Extract collections from linked file: CO blocks, then GR blocks
+    let collection_blocks = unique_blocks(
+        blend_file
+            .instances_with_code(*b"CO")
+            .chain(blend_file.instances_with_code(*b"GR")),
+    );
+    let collections = extract_collections(&collection_blocks, &mesh_ids)
+        .with_context(|| "Failed to extract collection from linked file")?;
 
     // Build collection map
     let mut collection_map: HashMap<String, CollectionData> = HashMap::new();
@@ -196,6 +374,7 @@ fn load_from_memory_with_linked_scenes(
     mesh_id_prefix: Option<&str>,
     linked_scenes: &[(String, MScene)],
     linked_libraries: Vec<String>,
+    import_animation: bool,
 ) -> Result<BlendFile> {
     let blend_file = Blend::new(Cursor::new(data))
         .map_err(|e| anyhow::anyhow!("Failed to parse .blend file: {:?}", e))?;
@@ -212,79 +391,76 @@ fn load_from_memory_with_linked_scenes(
         ));
     }
 
-    let pointer_size = match header.pointer_size {
-        blend::parsers::PointerSize::Bits32 => PointerSize::Bits32,
-        blend::parsers::PointerSize::Bits64 => PointerSize::Bits64,
-    };
-
-    let endianness = match header.endianness {
-        blend::parsers::Endianness::Little => Endianness::Little,
-        blend::parsers::Endianness::Big => Endianness::Big,
-    };
+    let (pointer_size, endianness) = file_format(header);
 
     // Initialize MScene
     let mut scene = MScene {
         meshes: HashMap::new(),
         materials: HashMap::new(),
+        animations: HashMap::new(),
         root: MGroup {
             name: None,
             children: Vec::new(),
             transform: None,
         },
     };
+    let mut mesh_ids = UniqueIds::default();
     for instance in blend_file.instances_with_code(*b"ME") {
-        let (mesh_id, mesh) = extract_mesh_data(&instance, mesh_id_prefix)?;
+        let (mesh_id, mesh) = extract_mesh_data(&instance, mesh_id_prefix, &mut mesh_ids)?;
         scene.meshes.insert(mesh_id, mesh);
     }
-
-    // Extract instances
-    let mut instances = Vec::new();
-    for instance in blend_file.instances_with_code(*b"OB") {
-        if let Some(instance_data) = extract_instance_data(&instance)? {
-            instances.push(instance_data);
-        }
-    }
-    println!("Total instances: {}", instances.len());
-
-    // Extract collections from main file
-    let mut collections = Vec::new();
-    let mut collection_names = std::collections::HashSet::new();
-
-    // Helper to add collection without duplicates
-    let mut add_collection = |coll: CollectionData| {
-        if collection_names.insert(coll.name.clone()) {
-            collections.push(coll);
-        }
-    };
-
-    // Extract ALL CO blocks from main file
-    for instance in blend_file.instances_with_code(*b"CO") {
-        let collection =
-            extract_collection_data(&instance).with_context(|| "Failed to extract collection")?;
-        add_collection(collection);
+    for instance in blend_file.instances_with_code(*b"MA") {
+        let material_id = clean_blender_id(&instance, "MA");
+        scene.materials.insert(
+            prefixed_id(mesh_id_prefix, &material_id),
+            extract_material(&instance),
+        );
     }
 
-    // Extract from Scene's master_collection hierarchy (but skip the master collection itself)
-    let mut scene_collections = Vec::new();
+    // Extract collections from main file: all CO blocks, those in the
+    // scene's collection hierarchy (but not the master collection itself),
+    // then GR (Group) blocks
+    let mut hierarchy = Vec::new();
     for scene_instance in blend_file.instances_with_code(*b"SC") {
         if scene_instance.is_valid("master_collection") {
             let master_coll = scene_instance.get("master_collection");
-            extract_collections_from_hierarchy(&master_coll, &mut scene_collections)?;
+            collect_collection_hierarchy(&master_coll, &mut hierarchy);
         }
     }
-    for coll in scene_collections {
-        // Skip "Scene Collection" which is the master collection container
-        if coll.name != "Scene Collection" {
-            add_collection(coll);
+    let collection_blocks = unique_blocks(
+        blend_file
+            .instances_with_code(*b"CO")
+            .chain(
+                hierarchy
+                    .into_iter()
+                    .filter(|coll| collection_block_name(coll) != "Scene Collection"),
+            )
+            .chain(blend_file.instances_with_code(*b"GR")),
+    );
+    let collection_ids = register_collections(&collection_blocks);
+
+    // Extract instances
+    let mut instances = Vec::new();
+    for instance in blend_file.instances_with_code(*b"OB") {
+        if let Some(instance_data) = extract_instance_data(&instance, &mesh_ids, &collection_ids)? {
+            instances.push(instance_data);
+        }
+        if import_animation
+            && let Some(keys) =
+                animation::extract_animation(&instance, &extract_transform_parts(&instance))
+        {
+            scene
+                .animations
+                .insert(clean_blender_id(&instance, "OB"), keys);
         }
     }
+    println!("Total instances: {}", instances.len());
 
-    // Extract GR (Group) blocks
-    for instance in blend_file.instances_with_code(*b"GR") {
-        let collection =
-            extract_group_data(&instance).with_context(|| "Failed to extract group")?;
-        add_collection(collection);
-    }
+    let collections = collection_blocks
+        .iter()
+        .map(|block| extract_collection_data(block, &mesh_ids, &collection_ids))
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| "Failed to extract collection")?;
 
     for collection_data in &collections {
         println!(
@@ -327,8 +503,9 @@ fn load_from_memory_with_linked_scenes(
 fn extract_mesh_data(
     instance: &Instance,
     mesh_id_prefix: Option<&str>,
+    mesh_ids: &mut UniqueIds,
 ) -> Result<(MMeshID, MMesh)> {
-    let clean_name = clean_blender_id(instance, "ME");
+    let clean_name = mesh_ids.register(block_address(instance), clean_blender_id(instance, "ME"));
 
     let mesh_id = if let Some(prefix) = mesh_id_prefix {
         format!("{}{}", prefix, clean_name)
@@ -383,34 +560,121 @@ fn extract_mesh_data_v4(instance: &Instance, mut mesh: MMesh) -> Result<MMesh> {
     }
 
     let corners_per_poly = if totpoly > 0 { totloop / totpoly } else { 0 };
+    mesh.indices = triangulate_polygons(&mesh.positions, &corner_verts, totpoly, corners_per_poly);
+    if totpoly > 0 && mesh.indices.is_empty() {
+        eprintln!("Warning: Mesh '{}' has no valid triangles", mesh.id);
+    }
+
+    mesh.bbox = BBox::from_points(&mesh.positions);
+    Ok(mesh)
+}
+
+/// Twice the area below which a triangle is treated as degenerate
+const MIN_TRIANGLE_AREA: f32 = 1e-10;
+
+/// Fan-triangulate polygons of `corners_per_poly` corners each.
+///
+/// Triangles with (near) zero area, from coincident or collinear corners, are
+/// skipped: they have no meaningful normal and only add noise for the
+/// renderer. So are triangles referencing positions that don't exist.
+///
+/// Triangles keep Blender's counter-clockwise winding. Instances with a
+/// mirroring (negative) scale flip that in world space, see
+/// `MTransform::mirrors_winding`.
+fn triangulate_polygons(
+    positions: &[Vec3],
+    corner_verts: &[u32],
+    totpoly: usize,
+    corners_per_poly: usize,
+) -> Vec<u32> {
+    let mut indices = Vec::new();
+    if corners_per_poly < 3 {
+        return indices;
+    }
 
     for poly_idx in 0..totpoly {
         let start = poly_idx * corners_per_poly;
         let end = start + corners_per_poly;
+        if end > corner_verts.len() {
+            break;
+        }
 
-        if end <= corner_verts.len() && corners_per_poly >= 3 {
-            for i in 1..(corners_per_poly - 1) {
-                mesh.indices.push(corner_verts[start]);
-                mesh.indices.push(corner_verts[start + i]);
-                mesh.indices.push(corner_verts[start + i + 1]);
+        for i in 1..(corners_per_poly - 1) {
+            let triangle = [
+                corner_verts[start],
+                corner_verts[start + i],
+                corner_verts[start + i + 1],
+            ];
+            let [Some(a), Some(b), Some(c)] = triangle.map(|i| positions.get(i as usize).copied())
+            else {
+                continue;
+            };
+            if (b - a).cross(c - a).length() <= MIN_TRIANGLE_AREA {
+                continue;
             }
+            indices.extend_from_slice(&triangle);
         }
     }
+    indices
+}
 
-    mesh.bbox = BBox::from_positions(&mesh.positions);
-    Ok(mesh)
+/// Blocks in order, without any reached a second time
+fn unique_blocks<'a>(blocks: impl Iterator<Item = Instance<'a>>) -> Vec<Instance<'a>> {
+    let mut seen = HashSet::new();
+    blocks
+        .filter(|block| block_address(block).is_none_or(|address| seen.insert(address)))
+        .collect()
+}
+
+/// Cleaned name of a collection, or of a legacy group
+fn collection_block_name(instance: &Instance) -> String {
+    if !instance.is_valid("id") {
+        return "Unknown".to_string();
+    }
+    let name = instance.get("id").get_string("name");
+    if name.starts_with("GR") {
+        strip_blender_prefix(&name, "GR")
+    } else {
+        strip_blender_prefix(&name, "CO")
+    }
 }
 
-fn extract_collection_data(instance: &Instance) -> Result<CollectionData> {
-    let name = clean_blender_id(instance, "CO");
-    let mesh_children = extract_mesh_children(instance);
+/// Give each collection block a unique id, then extract them all. The ids
+/// are registered first so references to collections that come later in
+/// `blocks` resolve too.
+fn extract_collections(blocks: &[Instance], mesh_ids: &UniqueIds) -> Result<Vec<CollectionData>> {
+    let collection_ids = register_collections(blocks);
+    blocks
+        .iter()
+        .map(|block| extract_collection_data(block, mesh_ids, &collection_ids))
+        .collect()
+}
+
+fn register_collections(blocks: &[Instance]) -> UniqueIds {
+    let mut collection_ids = UniqueIds::default();
+    for block in blocks {
+        collection_ids.register(block_address(block), collection_block_name(block));
+    }
+    collection_ids
+}
+
+fn extract_collection_data(
+    instance: &Instance,
+    mesh_ids: &UniqueIds,
+    collection_ids: &UniqueIds,
+) -> Result<CollectionData> {
+    let name = collection_ids.id_of(block_address(instance), collection_block_name(instance));
+    let mesh_children = extract_mesh_children(instance, mesh_ids);
 
     let mut collection_children = Vec::new();
     if instance.is_valid("children") {
         for child_coll_instance in instance.get_iter("children") {
             if child_coll_instance.is_valid("collection") {
                 let child_coll = child_coll_instance.get("collection");
-                let child_name = clean_blender_id(&child_coll, "CO");
+                let child_name = collection_ids.id_of(
+                    block_address(&child_coll),
+                    collection_block_name(&child_coll),
+                );
                 collection_children.push(child_name);
             }
         }
@@ -423,23 +687,25 @@ fn extract_collection_data(instance: &Instance) -> Result<CollectionData> {
     })
 }
 
-fn extract_group_data(instance: &Instance) -> Result<CollectionData> {
-    let name = if instance.is_valid("id") {
-        clean_blender_id(instance, "GR")
-    } else {
-        "Unknown".to_string()
-    };
-
-    let mesh_children = extract_mesh_children(instance);
-
-    Ok(CollectionData {
-        name,
-        mesh_children,
-        collection_children: Vec::new(),
-    })
+/// Id of a collection an object instances. Linked collections keep their
+/// name, which is looked up in the library's own scene.
+fn instanced_collection_id(
+    collection: &Instance,
+    name: String,
+    lib_path: &Option<String>,
+    collection_ids: &UniqueIds,
+) -> String {
+    match lib_path {
+        Some(_) => name,
+        None => collection_ids.id_of(block_address(collection), name),
+    }
 }
 
-fn extract_instance_data(instance: &Instance) -> Result<Option<InstanceData>> {
+fn extract_instance_data(
+    instance: &Instance,
+    mesh_ids: &UniqueIds,
+    collection_ids: &UniqueIds,
+) -> Result<Option<InstanceData>> {
     if !instance.is_valid("type") {
         return Ok(None);
     }
@@ -448,8 +714,11 @@ fn extract_instance_data(instance: &Instance) -> Result<Option<InstanceData>> {
 
     let (mesh_ref, collection_ref, collection_library_path) = match obj_type {
         OBJ_TYPE_MESH if instance.is_valid("data") => {
-            let mesh_name =
-                strip_blender_prefix(&instance.get("data").get("id").get_string("name"), "ME");
+            let mesh = instance.get("data");
+            let mesh_name = mesh_ids.id_of(
+                block_address(&mesh),
+                strip_blender_prefix(&mesh.get("id").get_string("name"), "ME"),
+            );
             println!("Instance: mesh type, mesh_name={}", mesh_name);
             (Some(mesh_name), None, None)
         }
@@ -458,8 +727,13 @@ fn extract_instance_data(instance: &Instance) -> Result<Option<InstanceData>> {
             if instance.is_valid("instance_collection") {
                 let coll = instance.get("instance_collection");
                 println!("{:?}", coll);
-                let collection_name = clean_blender_id(&coll, "CO");
                 let lib_path = extract_library_path_from_id(&coll);
+                let collection_name = instanced_collection_id(
+                    &coll,
+                    clean_blender_id(&coll, "CO"),
+                    &lib_path,
+                    collection_ids,
+                );
                 println!(
                     "Instance: instance_collection, coll_name={}, lib_path={:?}",
                     collection_name, lib_path
@@ -468,9 +742,14 @@ fn extract_instance_data(instance: &Instance) -> Result<Option<InstanceData>> {
             } else if instance.is_valid("dup_group") {
                 let dup = instance.get("dup_group");
                 if dup.is_valid("name") {
-                    let collection_name = strip_blender_prefix(&dup.get_string("name"), "GR");
-                    println!("Collection name: {}", collection_name);
                     let lib_path = extract_library_path(&dup);
+                    let collection_name = instanced_collection_id(
+                        &dup,
+                        strip_blender_prefix(&dup.get_string("name"), "GR"),
+                        &lib_path,
+                        collection_ids,
+                    );
+                    println!("Collection name: {}", collection_name);
                     println!(
                         "Instance: dup_group, coll_name={}, lib_path={:?}",
                         collection_name, lib_path
@@ -491,16 +770,157 @@ fn extract_instance_data(instance: &Instance) -> Result<Option<InstanceData>> {
         }
     };
 
+    let material_id = extract_material_id(instance);
     let transform = extract_transform(instance);
 
     Ok(Some(InstanceData {
+        name: clean_blender_id(instance, "OB"),
         mesh_ref,
         collection_ref,
         collection_library_path,
+        material_id,
         transform,
     }))
 }
 
+/// Material for an object's instance
+///
+/// Each of Blender's material slots is linked either to the mesh data, and so
+/// shared by every object using the mesh, or to the object itself (the slot's
+/// `matbits` entry is set). An object-linked material takes precedence over
+/// the mesh's material, which is how one mesh gets instanced with different
+/// materials. Only the first slot is used since an instance has a single
+/// material.
+fn extract_material_id(object: &Instance) -> Option<MMaterialID> {
+    let object_linked = object.is_valid("matbits")
+        && object
+            .get_u8_vec("matbits")
+            .first()
+            .is_some_and(|&bit| bit != 0);
+    let object_material = first_material_name(object);
+    let mesh_material = if object.is_valid("data") {
+        first_material_name(&object.get("data"))
+    } else {
+        None
+    };
+    resolve_material_id(object_linked, object_material, mesh_material)
+}
+
+/// Name of the material in the first slot of an object or mesh, if any
+fn first_material_name(instance: &Instance) -> Option<String> {
+    if !instance.is_valid("mat") {
+        return None;
+    }
+    instance
+        .get_iter("mat")
+        .next()
+        .map(|material| clean_blender_id(&material, "MA"))
+}
+
+/// Object-linked material if there is one, otherwise the mesh's material
+fn resolve_material_id(
+    object_linked: bool,
+    object_material: Option<MMaterialID>,
+    mesh_material: Option<MMaterialID>,
+) -> Option<MMaterialID> {
+    match object_material {
+        Some(material) if object_linked => Some(material),
+        _ => mesh_material,
+    }
+}
+
+fn extract_material(material: &Instance) -> MMaterial {
+    MMaterial {
+        base_color_image: base_color_image(material).and_then(|image| extract_image(&image)),
+    }
+}
+
+/// Image of the texture node linked to the material's Base Color input, or
+/// failing that of the first image texture node in its node tree
+fn base_color_image<'a>(material: &Instance<'a>) -> Option<Instance<'a>> {
+    if !material.is_valid("nodetree") {
+        return None;
+    }
+    let tree = material.get("nodetree");
+
+    let image_nodes: Vec<Instance> = list_items(&tree, "nodes")
+        .into_iter()
+        .filter(|node| node.get_string("idname") == "ShaderNodeTexImage" && node.is_valid("id"))
+        .collect();
+    let linked_node = list_items(&tree, "links").into_iter().find_map(|link| {
+        let linked = link.is_valid("fromnode")
+            && link.is_valid("tosock")
+            && link.get("tosock").get_string("name") == "Base Color";
+        linked.then(|| link.get("fromnode").get_string("name"))
+    });
+
+    let node = image_nodes
+        .iter()
+        .find(|node| Some(node.get_string("name")) == linked_node)
+        .or(image_nodes.first())?;
+    Some(node.get("id"))
+}
+
+fn extract_image(image: &Instance) -> Option<ImageData> {
+    let path = if image.is_valid("filepath") {
+        image.get_string("filepath")
+    } else if image.is_valid("name") {
+        image.get_string("name")
+    } else {
+        String::new()
+    };
+    image_data(packed_image_bytes(image), path)
+}
+
+/// Contents of a packed image. Images keep a list of packed files (one per
+/// view or UDIM tile); only the first is used.
+fn packed_image_bytes(image: &Instance) -> Option<Vec<u8>> {
+    let packed_file = list_items(image, "packedfiles")
+        .into_iter()
+        .find(|entry| entry.is_valid("packedfile"))
+        .map(|entry| entry.get("packedfile"))
+        .or_else(|| {
+            // Files from before the list existed have a single packed file
+            image
+                .is_valid("packedfile")
+                .then(|| image.get("packedfile"))
+        })?;
+    if !packed_file.is_valid("data") {
+        return None;
+    }
+
+    // The data block can be padded past the file's own size
+    let mut bytes = packed_file.get_u8_vec("data");
+    bytes.truncate(packed_file.get_i32("size").max(0) as usize);
+    Some(bytes)
+}
+
+/// Packed bytes take precedence over the path, which Blender keeps around
+/// for unpacking. Generated images have neither.
+fn image_data(packed: Option<Vec<u8>>, path: String) -> Option<ImageData> {
+    match packed {
+        Some(bytes) if !bytes.is_empty() => Some(ImageData::Packed(bytes)),
+        _ if !path.is_empty() => Some(ImageData::External(path)),
+        _ => None,
+    }
+}
+
+/// Items of a ListBase field. Unlike `Instance::get_iter`, an empty list
+/// gives no items rather than a panic.
+fn list_items<'a>(instance: &Instance<'a>, name: &str) -> Vec<Instance<'a>> {
+    if !instance.is_valid(name) || !instance.get(name).is_valid("first") {
+        return Vec::new();
+    }
+    instance.get_iter(name).collect()
+}
+
+fn prefixed_id(prefix: Option<&str>, name: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{}{}", prefix, name),
+        None => name.to_string(),
+    }
+}
+
 /// Build the scene graph from collections and instances
 fn build_scene_graph(
     scene: &mut MScene,
@@ -534,10 +954,15 @@ fn build_scene_graph(
                     mesh_name.clone()
                 };
 
+                let material_id = instance_data
+                    .material_id
+                    .as_deref()
+                    .map(|material| prefixed_id(mesh_id_prefix, material));
+
                 scene.root.children.push(MNode::MInstance(MInstance {
-                    name: None,
+                    name: Some(instance_data.name.clone()),
                     geometry_id: mesh_id,
-                    material_id: None,
+                    material_id,
                     transform: Some(instance_data.transform),
                 }));
             }
@@ -617,7 +1042,7 @@ fn build_scene_graph(
 }
 
 /// Recursively collect mesh IDs from nodes and merge them from source if not already present
-fn merge_meshes_from_nodes(
+pub(crate) fn merge_meshes_from_nodes(
     nodes: &[MNode],
     source_meshes: &HashMap<MMeshID, MMesh>,
     target_meshes: &mut HashMap<MMeshID, MMesh>,
@@ -734,8 +1159,8 @@ fn extract_library_path_from_id(instance: &Instance) -> Option<String> {
     }
 }
 
-/// Extract mesh name from an object instance
-fn extract_mesh_from_object(object: &Instance) -> Option<String> {
+/// Extract mesh id from an object instance
+fn extract_mesh_from_object(object: &Instance, mesh_ids: &UniqueIds) -> Option<String> {
     if !object.is_valid("type") {
         return None;
     }
@@ -747,11 +1172,14 @@ fn extract_mesh_from_object(object: &Instance) -> Option<String> {
 
     let mesh_data = object.get("data");
     let mesh_name = mesh_data.get("id").get_string("name");
-    Some(strip_blender_prefix(&mesh_name, "ME"))
+    Some(mesh_ids.id_of(
+        block_address(&mesh_data),
+        strip_blender_prefix(&mesh_name, "ME"),
+    ))
 }
 
 /// Iterator over mesh children in a collection's gobject list
-fn extract_mesh_children(instance: &Instance) -> Vec<String> {
+fn extract_mesh_children(instance: &Instance, mesh_ids: &UniqueIds) -> Vec<String> {
     let mut mesh_children = Vec::new();
 
     if !instance.is_valid("gobject") {
@@ -761,7 +1189,7 @@ fn extract_mesh_children(instance: &Instance) -> Vec<String> {
     let mut current = instance.get("gobject");
     loop {
         if current.is_valid("ob") {
-            if let Some(mesh_name) = extract_mesh_from_object(&current.get("ob")) {
+            if let Some(mesh_name) = extract_mesh_from_object(&current.get("ob"), mesh_ids) {
                 mesh_children.push(mesh_name);
             }
         }
@@ -775,17 +1203,92 @@ fn extract_mesh_children(instance: &Instance) -> Vec<String> {
     mesh_children
 }
 
+/// Location, rotation and scale of an object, each as its regular value and
+/// the delta Blender applies on top of it
+type TransformParts = [(Vec3, Vec3); 3];
+
 /// Extract transform data from an instance
+///
+/// The world matrix saved with the object is used as is when there is one,
+/// since it keeps the skew from parenting and constraints that loc/rot/scale
+/// can't express. Otherwise the transform is rebuilt from loc/rot/scale and
+/// the delta transforms (`dloc`, `drot`, `dscale`) Blender applies on top.
 fn extract_transform(instance: &Instance) -> MTransform {
-    let translation = extract_vec3(instance, "loc", Vec3::ZERO);
-    let rotation = extract_vec3(instance, "rot", Vec3::ZERO);
-    let scale =
-        extract_vec3(instance, "scale", Vec3::ONE).max(extract_vec3(instance, "size", Vec3::ONE));
+    if let Some(matrix) = extract_world_matrix(instance) {
+        return MTransform::from_matrix(matrix);
+    }
+    let [location, rotation, scale] = extract_transform_parts(instance);
+    compose_transform(location, rotation, scale)
+}
+
+/// The object's world matrix, named `object_to_world` or, in older files,
+/// `obmat`. Recent Blender versions keep it in runtime data that isn't saved,
+/// so it is often missing.
+fn extract_world_matrix(instance: &Instance) -> Option<Mat4> {
+    ["object_to_world", "obmat"]
+        .into_iter()
+        .filter(|field| instance.is_valid(field))
+        .find_map(|field| matrix_from_values(&instance.get_f32_vec(field)))
+}
+
+/// Blender's `float[4][4]` matrices are arrays of columns, the same layout as
+/// `Mat4`. A singular matrix, such as the all zero one of an object that was
+/// never evaluated, is treated as missing.
+fn matrix_from_values(values: &[f32]) -> Option<Mat4> {
+    let values: &[f32; 16] = values.get(..16)?.try_into().ok()?;
+    let matrix = Mat4::from_cols_array(values);
+    (matrix.is_finite() && matrix.determinant() != 0.0).then_some(matrix)
+}
+
+/// Scales keep their sign: a mirrored object has a negative component. Older
+/// files name the fields `size` and `dsize`.
+fn extract_transform_parts(instance: &Instance) -> TransformParts {
+    let scale = if instance.is_valid("scale") {
+        extract_vec3(instance, "scale", Vec3::ONE)
+    } else {
+        extract_vec3(instance, "size", Vec3::ONE)
+    };
+    let delta_scale = if instance.is_valid("dscale") {
+        extract_vec3(instance, "dscale", Vec3::ONE)
+    } else {
+        extract_vec3(instance, "dsize", Vec3::ONE)
+    };
+
+    [
+        (
+            extract_vec3(instance, "loc", Vec3::ZERO),
+            extract_vec3(instance, "dloc", Vec3::ZERO),
+        ),
+        (
+            extract_vec3(instance, "rot", Vec3::ZERO),
+            extract_vec3(instance, "drot", Vec3::ZERO),
+        ),
+        (scale, delta_scale),
+    ]
+}
+
+/// Combine regular and delta transforms the way Blender does: locations add,
+/// scales multiply, and the delta rotation is applied after the regular one.
+/// Rotations are XYZ Euler angles in radians.
+fn compose_transform(
+    (loc, dloc): (Vec3, Vec3),
+    (rot, drot): (Vec3, Vec3),
+    (scale, dscale): (Vec3, Vec3),
+) -> MTransform {
+    let rotation = if drot == Vec3::ZERO {
+        rot
+    } else {
+        // Blender's XYZ order rotates around X first, i.e. Rz * Ry * Rx
+        let euler = |v: Vec3| Quat::from_euler(EulerRot::ZYX, v.z, v.y, v.x);
+        let (z, y, x) = (euler(drot) * euler(rot)).to_euler(EulerRot::ZYX);
+        Vec3::new(x, y, z)
+    };
 
     MTransform {
-        translation,
+        translation: loc + dloc,
         rotation,
-        scale,
+        scale: scale * dscale,
+        matrix: None,
     }
 }
 
@@ -804,37 +1307,640 @@ fn extract_vec3(instance: &Instance, field: &str, default: Vec3) -> Vec3 {
 }
 
 /// Recursively extract collections from a Scene's master_collection hierarchy
-fn extract_collections_from_hierarchy(
-    collection_instance: &Instance,
-    collections: &mut Vec<CollectionData>,
-) -> Result<()> {
-    // Extract the collection itself
-    let collection_data = if collection_instance.is_valid("id") {
-        let id_name = collection_instance.get("id").get_string("name");
-        // Determine if it's a GR or CO block by the prefix
-        if id_name.starts_with("GR") {
-            extract_group_data(collection_instance)?
-        } else if id_name.starts_with("CO") {
-            extract_collection_data(collection_instance)?
-        } else {
-            // Fallback: treat as collection
-            extract_collection_data(collection_instance)?
-        }
-    } else {
-        return Ok(());
-    };
-
-    collections.push(collection_data);
+/// A collection and, recursively, all of its child collections
+fn collect_collection_hierarchy<'a>(
+    collection: &Instance<'a>,
+    collections: &mut Vec<Instance<'a>>,
+) {
+    if !collection.is_valid("id") {
+        return;
+    }
+    collections.push(collection.clone());
 
-    // Recursively process children
-    if collection_instance.is_valid("children") {
-        for child in collection_instance.get_iter("children") {
+    if collection.is_valid("children") {
+        for child in collection.get_iter("children") {
             if child.is_valid("collection") {
-                let child_coll = child.get("collection");
-                extract_collections_from_hierarchy(&child_coll, collections)?;
+                collect_collection_hierarchy(&child.get("collection"), collections);
             }
         }
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blend_writer::BlendWriter;
+
+    fn test_mesh(name: &str, vertex_count: usize) -> MMesh {
+        let mut mesh = MMesh::new(name.to_string());
+        mesh.positions = vec![Vec3::ZERO; vertex_count];
+        mesh
+    }
+
+    fn address(value: u64) -> Option<NonZeroU64> {
+        NonZeroU64::new(value)
+    }
+
+    #[test]
+    fn test_duplicate_names_are_disambiguated() {
+        // "MECube" and a library mesh both clean to "Cube"
+        let mut ids = UniqueIds::default();
+        assert_eq!(ids.register(address(0x10), "Cube".to_string()), "Cube");
+        assert_eq!(ids.register(address(0x20), "Cube".to_string()), "Cube.001");
+        assert_eq!(ids.register(address(0x30), "Cube".to_string()), "Cube.002");
+
+        // Whatever points at a block finds the id it was given
+        assert_eq!(ids.id_of(address(0x10), "Cube".to_string()), "Cube");
+        assert_eq!(ids.id_of(address(0x20), "Cube".to_string()), "Cube.001");
+        assert_eq!(ids.id_of(address(0x30), "Cube".to_string()), "Cube.002");
+    }
+
+    #[test]
+    fn test_unique_names_are_unchanged() {
+        let mut ids = UniqueIds::default();
+        ids.register(address(0x10), "Cube".to_string());
+        assert_eq!(ids.register(address(0x20), "Sphere".to_string()), "Sphere");
+
+        // The same block reached again, e.g. a collection found both as a
+        // CO block and in the scene hierarchy, keeps its id
+        assert_eq!(ids.register(address(0x10), "Cube".to_string()), "Cube");
+        assert_eq!(ids.id_of(address(0x40), "Cone".to_string()), "Cone");
+    }
+
+    #[test]
+    fn test_duplicate_collection_names_are_both_instanced() {
+        let mut ids = UniqueIds::default();
+        let collections = [(0x10, "Rock"), (0x20, "Pebble")]
+            .map(|(block, mesh)| CollectionData {
+                name: ids.register(address(block), "Rocks".to_string()),
+                mesh_children: vec![mesh.to_string()],
+                collection_children: Vec::new(),
+            })
+            .to_vec();
+        let instances = [0x10, 0x20]
+            .map(|block| InstanceData {
+                name: "Rocks".to_string(),
+                mesh_ref: None,
+                collection_ref: Some(ids.id_of(address(block), "Rocks".to_string())),
+                collection_library_path: None,
+                material_id: None,
+                transform: MTransform {
+                    translation: Vec3::ZERO,
+                    rotation: Vec3::ZERO,
+                    scale: Vec3::ONE,
+                    matrix: None,
+                },
+            })
+            .to_vec();
+
+        let mut scene = MScene {
+            meshes: HashMap::new(),
+            materials: HashMap::new(),
+            animations: HashMap::new(),
+            root: MGroup {
+                name: None,
+                children: Vec::new(),
+                transform: None,
+            },
+        };
+        build_scene_graph(&mut scene, collections, instances, None, &[]).unwrap();
+
+        let instanced: Vec<_> = scene
+            .root
+            .children
+            .iter()
+            .map(|node| match node {
+                MNode::MGroup(group) => match &group.children[..] {
+                    [MNode::MInstance(instance)] => instance.geometry_id.clone(),
+                    _ => panic!("expected one mesh instance"),
+                },
+                _ => panic!("expected a collection instance"),
+            })
+            .collect();
+        assert_eq!(instanced, vec!["Rock", "Pebble"]);
+    }
+
+    #[test]
+    fn test_collapsed_quad_emits_no_triangles() {
+        // A unit quad squashed to zero height: its top corners land on the
+        // bottom ones, so both fan triangles are flat
+        let positions = vec![Vec3::ZERO, Vec3::X, Vec3::X, Vec3::ZERO];
+        let indices = triangulate_polygons(&positions, &[0, 1, 2, 3], 1, 4);
+        assert!(indices.is_empty());
+    }
+
+    #[test]
+    fn test_triangulation_skips_only_degenerate_triangles() {
+        // A quad, then a quad collapsed onto a line
+        let positions = vec![
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::Y,
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        ];
+        let corner_verts = [0, 1, 2, 3, 0, 1, 4, 5];
+        let indices = triangulate_polygons(&positions, &corner_verts, 2, 4);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+            assert!((b - a).cross(c - a).length() > MIN_TRIANGLE_AREA);
+        }
+    }
+
+    #[test]
+    fn test_triangulation_skips_missing_positions() {
+        let positions = vec![Vec3::ZERO, Vec3::X, Vec3::Y];
+        let indices = triangulate_polygons(&positions, &[0, 1, 2, 0, 1, 7], 2, 3);
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_big_endian_header_is_recognized() {
+        // "V" marks a big-endian host, "-" a 64-bit one
+        let (_, header) = blend::parsers::blend::header(b"BLENDER-V405").unwrap();
+        assert_eq!(header.version, *b"405");
+        assert_eq!(file_format(&header), (PointerSize::Bits64, Endianness::Big));
+
+        let (_, header) = blend::parsers::blend::header(b"BLENDER_v405").unwrap();
+        assert_eq!(
+            file_format(&header),
+            (PointerSize::Bits32, Endianness::Little)
+        );
+    }
+
+    fn mesh_instance(mesh: &str, material: Option<&str>) -> InstanceData {
+        InstanceData {
+            name: mesh.to_string(),
+            mesh_ref: Some(mesh.to_string()),
+            collection_ref: None,
+            collection_library_path: None,
+            material_id: material.map(str::to_string),
+            transform: MTransform {
+                translation: Vec3::ZERO,
+                rotation: Vec3::ZERO,
+                scale: Vec3::ONE,
+                matrix: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_object_material_overrides_mesh_material() {
+        let mesh = Some("Stone".to_string());
+        let object = Some("Moss".to_string());
+        assert_eq!(
+            resolve_material_id(true, object.clone(), mesh.clone()),
+            object
+        );
+        // Slot linked to the mesh data: the object's entry is ignored
+        assert_eq!(resolve_material_id(false, object, mesh.clone()), mesh);
+        // Object-linked slot left empty
+        assert_eq!(resolve_material_id(true, None, mesh.clone()), mesh);
+    }
+
+    #[test]
+    fn test_same_mesh_instanced_with_different_materials() {
+        let mut scene = MScene {
+            meshes: HashMap::new(),
+            materials: HashMap::new(),
+            animations: HashMap::new(),
+            root: MGroup {
+                name: None,
+                children: Vec::new(),
+                transform: None,
+            },
+        };
+        let instances = vec![
+            mesh_instance("Rock", Some("Stone")),
+            mesh_instance("Rock", Some("Moss")),
+        ];
+        build_scene_graph(&mut scene, Vec::new(), instances, Some("lib/"), &[]).unwrap();
+
+        let materials: Vec<_> = scene
+            .root
+            .children
+            .iter()
+            .map(|node| match node {
+                MNode::MInstance(instance) => {
+                    assert_eq!(instance.geometry_id, "lib/Rock");
+                    instance.material_id.clone()
+                }
+                _ => panic!("expected a mesh instance"),
+            })
+            .collect();
+        assert_eq!(
+            materials,
+            vec![Some("lib/Stone".to_string()), Some("lib/Moss".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_animated_object_node_found_by_animation_key() {
+        let mut scene = MScene {
+            meshes: HashMap::new(),
+            materials: HashMap::new(),
+            animations: HashMap::new(),
+            root: MGroup {
+                name: None,
+                children: Vec::new(),
+                transform: None,
+            },
+        };
+        let mut spinner = mesh_instance("Rock", None);
+        spinner.name = "Spinner".to_string();
+        let instances = vec![mesh_instance("Rock", None), spinner];
+        build_scene_graph(&mut scene, Vec::new(), instances, None, &[]).unwrap();
+        scene.animations.insert("Spinner".to_string(), Vec::new());
+
+        for key in scene.animations.keys() {
+            let animated: Vec<_> = scene
+                .root
+                .children
+                .iter()
+                .filter(|node| {
+                    matches!(node, MNode::MInstance(instance) if instance.name.as_ref() == Some(key))
+                })
+                .collect();
+            assert_eq!(animated.len(), 1, "no single node for animation '{}'", key);
+        }
+    }
+
+    #[test]
+    fn test_scene_stats() {
+        let instance = |mesh: &str| {
+            MNode::MInstance(MInstance {
+                name: None,
+                geometry_id: mesh.to_string(),
+                material_id: None,
+                transform: None,
+            })
+        };
+
+        let mut cube = test_mesh("Cube", 8);
+        cube.indices = vec![0; 36];
+        let mut quad = test_mesh("Quad", 4);
+        quad.indices = vec![0, 1, 2, 0, 2, 3];
+
+        let blend_file = BlendFile {
+            version: *b"405",
+            pointer_size: PointerSize::Bits64,
+            endianness: Endianness::Little,
+            linked_libraries: vec!["//props.blend".to_string()],
+            collections: Vec::new(),
+            scene: MScene {
+                meshes: HashMap::from([("Cube".to_string(), cube), ("Quad".to_string(), quad)]),
+                materials: HashMap::from([("Stone".to_string(), MMaterial::default())]),
+                animations: HashMap::new(),
+                root: MGroup {
+                    name: None,
+                    children: vec![
+                        instance("Cube"),
+                        MNode::MGroup(MGroup {
+                            name: Some("Props".to_string()),
+                            children: vec![
+                                instance("Cube"),
+                                instance("Quad"),
+                                MNode::MGroup(MGroup {
+                                    name: Some("Empty".to_string()),
+                                    children: Vec::new(),
+                                    transform: None,
+                                }),
+                            ],
+                            transform: None,
+                        }),
+                    ],
+                    transform: None,
+                },
+            },
+        };
+
+        assert_eq!(
+            blend_file.stats(),
+            SceneStats {
+                mesh_count: 2,
+                total_vertices: 12,
+                total_triangles: 14,
+                instance_count: 3,
+                group_count: 2,
+                material_count: 1,
+                linked_library_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_import_options_recompute_normals() {
+        // A unit cube with shared corners and no normals
+        let mut cube = test_mesh("Cube", 0);
+        cube.positions = (0..8)
+            .map(|i| Vec3::new((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32))
+            .collect();
+        cube.indices = vec![
+            0, 2, 1, 1, 2, 3, // -Z
+            4, 5, 6, 5, 7, 6, // +Z
+            0, 1, 4, 1, 5, 4, // -Y
+            2, 6, 3, 3, 6, 7, // +Y
+            0, 4, 2, 2, 4, 6, // -X
+            1, 3, 5, 3, 7, 5, // +X
+        ];
+        let mut smooth = cube.clone();
+        smooth.id = "Smooth".to_string();
+        smooth.normals = vec![Vec3::X; 8];
+
+        let mut scene = MScene {
+            meshes: HashMap::from([("Cube".to_string(), cube), ("Smooth".to_string(), smooth)]),
+            materials: HashMap::new(),
+            animations: HashMap::new(),
+            root: MGroup {
+                name: None,
+                children: Vec::new(),
+                transform: None,
+            },
+        };
+        let normals = |scene: &MScene, id: &str| scene.meshes[id].normals.clone();
+
+        // By default only meshes without normals get them (the split itself
+        // is covered by test_compute_normals_splits_cube_faces)
+        apply_import_options(&mut scene, &ImportOptions::default());
+        assert_eq!(normals(&scene, "Cube").len(), 24);
+        assert_eq!(scene.meshes["Cube"].vertex_count(), 24);
+        assert_eq!(normals(&scene, "Smooth"), vec![Vec3::X; 8]);
+
+        let options = ImportOptions {
+            recompute_normals: Some(false),
+            ..Default::default()
+        };
+        scene.meshes.get_mut("Cube").unwrap().normals.clear();
+        apply_import_options(&mut scene, &options);
+        assert!(normals(&scene, "Cube").is_empty());
+
+        let options = ImportOptions {
+            recompute_normals: Some(true),
+            ..Default::default()
+        };
+        apply_import_options(&mut scene, &options);
+        assert_eq!(normals(&scene, "Cube").len(), 24);
+        assert_eq!(normals(&scene, "Smooth"), normals(&scene, "Cube"));
+    }
+
+    #[test]
+    fn test_mirrored_object_keeps_negative_scale() {
+        let no_delta = |scale: Vec3| {
+            compose_transform(
+                (Vec3::ZERO, Vec3::ZERO),
+                (Vec3::ZERO, Vec3::ZERO),
+                (scale, Vec3::ONE),
+            )
+        };
+
+        let mirrored = no_delta(Vec3::new(-1.0, 1.0, 1.0));
+        assert_eq!(mirrored.scale, Vec3::new(-1.0, 1.0, 1.0));
+        assert!(mirrored.mirrors_winding());
+
+        // Mirroring twice is a rotation, which keeps the winding
+        let flipped = no_delta(Vec3::new(-2.0, -1.0, 1.0));
+        assert_eq!(flipped.scale, Vec3::new(-2.0, -1.0, 1.0));
+        assert!(!flipped.mirrors_winding());
+        assert!(!no_delta(Vec3::ONE).mirrors_winding());
+    }
+
+    #[test]
+    fn test_world_matrix_imports_exactly() {
+        // A sheared matrix, which no translation, rotation and scale can hold
+        let matrix = Mat4::from_cols_array(&[
+            2.0, 0.0, 0.0, 0.0, //
+            0.5, 1.0, 0.0, 0.0, //
+            0.0, 0.25, -1.0, 0.0, //
+            3.0, -4.0, 5.0, 1.0,
+        ]);
+        let transform = MTransform::from_matrix(
+            matrix_from_values(&matrix.to_cols_array()).expect("matrix should be read"),
+        );
+        assert!(transform.to_mat4().abs_diff_eq(matrix, 1e-6));
+        assert_eq!(transform.translation, Vec3::new(3.0, -4.0, 5.0));
+        assert!(transform.mirrors_winding());
+    }
+
+    #[test]
+    fn test_world_matrix_decomposes_like_loc_rot_scale() {
+        let parts = [
+            (Vec3::new(1.0, 2.0, 3.0), Vec3::ZERO),
+            (Vec3::new(0.3, -0.2, 1.1), Vec3::ZERO),
+            (Vec3::new(2.0, 1.0, 0.5), Vec3::ONE),
+        ];
+        let composed = compose_transform(parts[0], parts[1], parts[2]);
+        let transform = MTransform::from_matrix(composed.to_mat4());
+
+        assert!(
+            transform
+                .translation
+                .abs_diff_eq(composed.translation, 1e-5)
+        );
+        assert!(transform.rotation.abs_diff_eq(composed.rotation, 1e-5));
+        assert!(transform.scale.abs_diff_eq(composed.scale, 1e-5));
+    }
+
+    #[test]
+    fn test_missing_world_matrix_falls_back() {
+        assert_eq!(matrix_from_values(&[1.0; 9]), None);
+        assert_eq!(matrix_from_values(&[0.0; 16]), None);
+        assert!(matrix_from_values(&Mat4::IDENTITY.to_cols_array()).is_some());
+    }
+
+    #[test]
+    fn test_world_matrix_read_from_object_block() {
+        let mut writer = BlendWriter::new(&[
+            ("ID", 64, &[("char", "name[64]")]),
+            (
+                "Object",
+                140,
+                &[("ID", "id"), ("float", "loc[3]"), ("float", "obmat[4][4]")],
+            ),
+        ]);
+        let object_dna = writer.struct_index("Object");
+
+        // The saved matrix wins over a loc that disagrees with it
+        let matrix = Mat4::from_scale_rotation_translation(
+            Vec3::new(2.0, 3.0, 4.0),
+            Quat::IDENTITY,
+            Vec3::new(5.0, -6.0, 7.0),
+        );
+        let mut object = vec![0; 140];
+        object[..7].copy_from_slice(b"OBMoved");
+        object[64..76].copy_from_slice(&[1.0f32; 3].map(f32::to_le_bytes).concat());
+        let obmat: Vec<u8> = matrix
+            .to_cols_array()
+            .into_iter()
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        object[76..140].copy_from_slice(&obmat);
+        writer.block(b"OB\0\0", 0x1000, object_dna, &object);
+
+        let blend = Blend::new(Cursor::new(writer.finish())).unwrap();
+        let instance = blend.instances_with_code(*b"OB").next().unwrap();
+        let transform = extract_transform(&instance);
+
+        assert_eq!(transform.matrix, Some(matrix));
+        assert_eq!(transform, MTransform::from_matrix(matrix));
+        assert_eq!(transform.translation, Vec3::new(5.0, -6.0, 7.0));
+        assert!(transform.scale.abs_diff_eq(Vec3::new(2.0, 3.0, 4.0), 1e-6));
+    }
+
+    #[test]
+    fn test_delta_transforms_apply_on_top() {
+        let transform = compose_transform(
+            (Vec3::new(1.0, 2.0, 3.0), Vec3::new(0.5, 0.0, -1.0)),
+            (Vec3::new(0.0, 0.0, 0.25), Vec3::new(0.0, 0.0, 0.5)),
+            (Vec3::new(-1.0, 2.0, 1.0), Vec3::new(2.0, 0.5, 3.0)),
+        );
+        assert_eq!(transform.translation, Vec3::new(1.5, 2.0, 2.0));
+        assert_eq!(transform.scale, Vec3::new(-2.0, 1.0, 3.0));
+        // Rotations around the same axis add up
+        assert!(transform.rotation.distance(Vec3::new(0.0, 0.0, 0.75)) < 1e-5);
+
+        // Without a delta rotation the Euler angles are passed through as is
+        let transform = compose_transform(
+            (Vec3::ZERO, Vec3::ZERO),
+            (Vec3::new(3.0, -2.0, 1.0), Vec3::ZERO),
+            (Vec3::ONE, Vec3::ONE),
+        );
+        assert_eq!(transform.rotation, Vec3::new(3.0, -2.0, 1.0));
+    }
+
+    #[test]
+    fn test_delta_rotation_is_applied_after_rotation() {
+        // 90 degrees around X, then 90 around Z (as a delta): X-axis
+        // rotations move +Y to +Z and the delta Z turn leaves +Z alone
+        let rot = Vec3::new(std::f32::consts::FRAC_PI_2, 0.0, 0.0);
+        let drot = Vec3::new(0.0, 0.0, std::f32::consts::FRAC_PI_2);
+        let transform = compose_transform(
+            (Vec3::ZERO, Vec3::ZERO),
+            (rot, drot),
+            (Vec3::ONE, Vec3::ONE),
+        );
+        let r = transform.rotation;
+        let matrix = Quat::from_euler(EulerRot::ZYX, r.z, r.y, r.x);
+        assert!((matrix * Vec3::Y).distance(Vec3::Z) < 1e-5);
+        assert!((matrix * Vec3::X).distance(Vec3::Y) < 1e-5);
+    }
+
+    #[test]
+    fn test_packed_image_yields_bytes() {
+        let png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        let image = image_data(Some(png.clone()), "//textures/wood.png".to_string()).unwrap();
+        assert_eq!(image, ImageData::Packed(png.clone()));
+        assert_eq!(image.bytes(), Some(png.as_slice()));
+        assert_eq!(image.path(), None);
+    }
+
+    #[test]
+    fn test_external_image_yields_path() {
+        let image = image_data(None, "//textures/wood.png".to_string()).unwrap();
+        assert_eq!(image.path(), Some("//textures/wood.png"));
+        assert_eq!(image.bytes(), None);
+
+        // An empty packed file is no better than none
+        let image = image_data(Some(Vec::new()), "//textures/wood.png".to_string()).unwrap();
+        assert_eq!(
+            image,
+            ImageData::External("//textures/wood.png".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generated_image_has_no_data() {
+        assert_eq!(image_data(None, String::new()), None);
+    }
+
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    /// Two packed images: "IMBrick" keeps its file in the `packedfiles` list,
+    /// "IMOld" in the single `packedfile` pointer older files use. Both point
+    /// at the same packed file, whose data block is padded past its size.
+    fn packed_texture_blend() -> Vec<u8> {
+        let mut writer = BlendWriter::new(&[
+            ("ListBase", 16, &[("void", "*first"), ("void", "*last")]),
+            ("ID", 64, &[("char", "name[64]")]),
+            (
+                "Image",
+                1112,
+                &[
+                    ("ID", "id"),
+                    ("char", "filepath[1024]"),
+                    ("ListBase", "packedfiles"),
+                    ("PackedFile", "*packedfile"),
+                ],
+            ),
+            (
+                "ImagePackedFile",
+                1056,
+                &[
+                    ("ImagePackedFile", "*next"),
+                    ("ImagePackedFile", "*prev"),
+                    ("PackedFile", "*packedfile"),
+                    ("int", "view"),
+                    ("int", "tile_number"),
+                    ("char", "filepath[1024]"),
+                ],
+            ),
+            (
+                "PackedFile",
+                16,
+                &[("int", "size"), ("int", "seek"), ("void", "*data")],
+            ),
+        ]);
+        let image_dna = writer.struct_index("Image");
+        let image_packed_file_dna = writer.struct_index("ImagePackedFile");
+        let packed_file_dna = writer.struct_index("PackedFile");
+
+        let image = |name: &str, packed_files: u64, packed_file: u64| {
+            let mut data = vec![0; 1112];
+            data[..name.len()].copy_from_slice(name.as_bytes());
+            let filepath = b"//textures/brick.png";
+            data[64..64 + filepath.len()].copy_from_slice(filepath);
+            data[1088..1096].copy_from_slice(&packed_files.to_le_bytes());
+            data[1096..1104].copy_from_slice(&packed_files.to_le_bytes());
+            data[1104..1112].copy_from_slice(&packed_file.to_le_bytes());
+            data
+        };
+
+        writer.block(b"IM\0\0", 0x1000, image_dna, &image("IMBrick", 0x2000, 0));
+        let mut entry = vec![0; 1056];
+        entry[16..24].copy_from_slice(&0x3000u64.to_le_bytes());
+        writer.block(b"DATA", 0x2000, image_packed_file_dna, &entry);
+        let mut packed_file = (PNG_SIGNATURE.len() as i32).to_le_bytes().to_vec();
+        packed_file.extend(0i32.to_le_bytes());
+        packed_file.extend(0x4000u64.to_le_bytes());
+        writer.block(b"DATA", 0x3000, packed_file_dna, &packed_file);
+        writer.block(
+            b"DATA",
+            0x4000,
+            0,
+            &[PNG_SIGNATURE.as_slice(), &[0; 8]].concat(),
+        );
+        writer.block(b"IM\0\0", 0x5000, image_dna, &image("IMOld", 0, 0x3000));
+        writer.finish()
+    }
+
+    #[test]
+    fn test_packed_texture_fixture_yields_image_bytes() {
+        let blend = Blend::new(Cursor::new(packed_texture_blend())).unwrap();
+        let images: Vec<_> = blend
+            .instances_with_code(*b"IM")
+            .map(|image| (image.get("id").get_string("name"), extract_image(&image)))
+            .collect();
+
+        // The data block's padding is cut off at the packed file's size
+        let packed = Some(ImageData::Packed(PNG_SIGNATURE.to_vec()));
+        assert_eq!(
+            images,
+            vec![
+                ("IMBrick".to_string(), packed.clone()),
+                ("IMOld".to_string(), packed),
+            ]
+        );
+    }
 }
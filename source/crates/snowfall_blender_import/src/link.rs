@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{MGroup, MLink, MMesh, MMeshID, MNode, merge_meshes_from_nodes};
+
+/// Guards against libraries whose assets (indirectly) link back to themselves
+const MAX_LINK_DEPTH: usize = 8;
+
+/// An external .blend file whose collections can be referenced by `MLink` nodes
+pub struct BlendLibrary {
+    /// File name of the library (e.g. "props.blend")
+    pub name: String,
+    pub filepath: String,
+    /// Linkable assets keyed by their Blender ID name (e.g. "GRCrate")
+    pub assets: HashMap<String, MNode>,
+    pub meshes: HashMap<String, MMesh>,
+}
+
+impl BlendLibrary {
+    /// Whether a link's library path refers to this library. Links store the
+    /// path as written in the blend file (often Blender-relative, "//props.blend"),
+    /// so fall back to comparing file names.
+    fn matches(&self, library_path: &str) -> bool {
+        if self.filepath == library_path {
+            return true;
+        }
+        let file_name = Path::new(library_path.trim_start_matches("//"))
+            .file_name()
+            .and_then(|name| name.to_str());
+        file_name == Some(self.name.as_str())
+    }
+}
+
+/// Result of flattening the links in a scene graph
+#[derive(Debug, Default)]
+pub struct ResolvedLinks {
+    /// Meshes referenced by the linked assets, copied out of their libraries
+    pub meshes: HashMap<MMeshID, MMesh>,
+    /// Links that could not be resolved and were left in place
+    pub unresolved: Vec<MLink>,
+}
+
+/// Replace every `MLink` under `root` with a copy of the linked asset's node
+/// subtree.
+///
+/// Each resolved link becomes a group carrying the link's transform, with the
+/// asset as its only child, so the asset's own transforms compose with the
+/// placement of the link. Meshes used by the linked assets are collected into
+/// the returned `ResolvedLinks::meshes`; links whose library or asset can't be
+/// found are left untouched and reported in `ResolvedLinks::unresolved`.
+pub fn resolve_links(root: &mut MGroup, libraries: &[BlendLibrary]) -> ResolvedLinks {
+    let mut resolved = ResolvedLinks::default();
+    resolve_links_recursive(&mut root.children, libraries, &mut resolved, 0);
+    resolved
+}
+
+fn resolve_links_recursive(
+    nodes: &mut [MNode],
+    libraries: &[BlendLibrary],
+    resolved: &mut ResolvedLinks,
+    depth: usize,
+) {
+    for node in nodes.iter_mut() {
+        match node {
+            MNode::MInstance(_) => {}
+            MNode::MGroup(group) => {
+                resolve_links_recursive(&mut group.children, libraries, resolved, depth);
+            }
+            MNode::MLink(link) => {
+                if depth >= MAX_LINK_DEPTH {
+                    resolved.unresolved.push(link.clone());
+                    continue;
+                }
+
+                let found = libraries
+                    .iter()
+                    .filter(|library| library.matches(&link.library))
+                    .find_map(|library| library.assets.get(&link.id).map(|asset| (library, asset)));
+                let Some((library, asset)) = found else {
+                    resolved.unresolved.push(link.clone());
+                    continue;
+                };
+
+                let mut children = vec![asset.clone()];
+                merge_meshes_from_nodes(&children, &library.meshes, &mut resolved.meshes);
+                // Assets may themselves contain links to other libraries
+                resolve_links_recursive(&mut children, libraries, resolved, depth + 1);
+
+                *node = MNode::MGroup(MGroup {
+                    name: Some(link.id.clone()),
+                    children,
+                    transform: link.transform,
+                });
+            }
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
 use crate::BBox;
-use glam::{Vec2, Vec3};
+use glam::{EulerRot, Mat4, Quat, Vec2, Vec3};
 
 pub type MMeshID = String;
 pub type MMaterialID = String;
 
-#[derive(Debug, Clone)]
+/// Faces meeting at less than this angle share smoothed normals in
+/// `MMesh::compute_normals`, matching Blender's default auto smooth angle
+pub const NORMAL_SMOOTHING_ANGLE: f32 = 30.0 * std::f32::consts::PI / 180.0;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct MMesh {
     pub id: MMeshID,
     pub positions: Vec<Vec3>,
@@ -37,26 +41,359 @@ impl MMesh {
     pub fn triangle_count(&self) -> usize {
         self.indices.len() / 3
     }
+
+    /// Replace the normals with area-weighted vertex normals.
+    ///
+    /// Around each vertex, faces whose normals are within `smoothing_angle`
+    /// of each other are smoothed together; where faces meet at a sharper
+    /// angle the vertex is split so each side keeps its own normal. A cube
+    /// comes out with three vertices per corner, one for each face. Uvs are
+    /// carried over to split vertices and unused vertices are dropped.
+    pub fn compute_normals(&mut self, smoothing_angle: f32) {
+        let cos_limit = smoothing_angle.cos();
+        let has_uvs = self.uvs.len() == self.positions.len();
+
+        // Unnormalized, so larger faces count for more
+        let face_normals: Vec<Vec3> = self
+            .indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]]
+                    .map(|index| self.positions[index as usize]);
+                (b - a).cross(c - a)
+            })
+            .collect();
+
+        // Triangle corners around each vertex
+        let mut vertex_corners: Vec<Vec<usize>> = vec![Vec::new(); self.positions.len()];
+        for (corner, &index) in self.indices[..face_normals.len() * 3].iter().enumerate() {
+            vertex_corners[index as usize].push(corner);
+        }
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = vec![0; face_normals.len() * 3];
+        for (vertex, corners) in vertex_corners.iter().enumerate() {
+            // Faces around the vertex in smoothing groups of (direction of
+            // the first face, summed normal, corners)
+            let mut groups: Vec<(Vec3, Vec3, Vec<usize>)> = Vec::new();
+            for &corner in corners {
+                let face_normal = face_normals[corner / 3];
+                let direction = face_normal.normalize_or_zero();
+                match groups
+                    .iter_mut()
+                    .find(|(first, _, _)| first.dot(direction) >= cos_limit)
+                {
+                    Some((_, sum, group_corners)) => {
+                        *sum += face_normal;
+                        group_corners.push(corner);
+                    }
+                    None => groups.push((direction, face_normal, vec![corner])),
+                }
+            }
+
+            for (_, sum, group_corners) in groups {
+                let new_index = positions.len() as u32;
+                positions.push(self.positions[vertex]);
+                normals.push(sum.normalize_or_zero());
+                if has_uvs {
+                    uvs.push(self.uvs[vertex]);
+                }
+                for corner in group_corners {
+                    indices[corner] = new_index;
+                }
+            }
+        }
+
+        self.positions = positions;
+        self.normals = normals;
+        self.uvs = uvs;
+        self.indices = indices;
+    }
+
+    /// Flip triangles whose winding disagrees with their vertex normals and
+    /// return how many were flipped.
+    ///
+    /// A triangle is flipped when its geometric normal, from the
+    /// counter-clockwise winding, points away from the sum of its vertex
+    /// normals. This repairs meshes whose positions went through a
+    /// handedness-inverting change, such as swapping two axes to convert
+    /// Z-up to Y-up, as long as the normals were converted the same way.
+    /// Meshes without a normal per vertex are left alone.
+    pub fn fix_winding(&mut self) -> usize {
+        if self.normals.len() != self.positions.len() {
+            return 0;
+        }
+
+        let mut flipped = 0;
+        for triangle in self.indices.chunks_exact_mut(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| index as usize);
+            if [a, b, c].iter().any(|&index| index >= self.positions.len()) {
+                continue;
+            }
+            let face = (self.positions[b] - self.positions[a])
+                .cross(self.positions[c] - self.positions[a]);
+            let vertex_normal = self.normals[a] + self.normals[b] + self.normals[c];
+            if face.dot(vertex_normal) < 0.0 {
+                triangle.swap(1, 2);
+                flipped += 1;
+            }
+        }
+        flipped
+    }
+
+    /// Expand the mesh so every triangle corner has its own vertex.
+    ///
+    /// Positions, normals and uvs are duplicated per corner, so the result has
+    /// `indices.len()` vertices and sequential indices (`0, 1, 2, ...`). This
+    /// is what flat shading or per-face attributes need. Normals and uvs are
+    /// only carried over when they have one entry per vertex.
+    pub fn to_non_indexed(&self) -> MMesh {
+        let has_normals = self.normals.len() == self.positions.len();
+        let has_uvs = self.uvs.len() == self.positions.len();
+
+        let mut mesh = MMesh::new(self.id.clone());
+        for &index in &self.indices {
+            let index = index as usize;
+            mesh.positions.push(self.positions[index]);
+            if has_normals {
+                mesh.normals.push(self.normals[index]);
+            }
+            if has_uvs {
+                mesh.uvs.push(self.uvs[index]);
+            }
+        }
+        mesh.indices = (0..self.indices.len() as u32).collect();
+        mesh.bbox = self.bbox;
+        mesh
+    }
+
+    /// Re-index the mesh by welding vertices that are within `epsilon` of each
+    /// other in position, normal and uv.
+    ///
+    /// Vertices whose normals or uvs differ (e.g. across hard edges) stay
+    /// separate, so welding a mesh from `to_non_indexed` gives back the
+    /// original sharing. Unused vertices are dropped.
+    pub fn to_indexed(&mut self, epsilon: f32) {
+        let has_normals = self.normals.len() == self.positions.len();
+        let has_uvs = self.uvs.len() == self.positions.len();
+        let epsilon = epsilon.max(f32::EPSILON);
+
+        // Bucket welded vertices on a grid with cells the size of epsilon, so
+        // only the 27 neighboring cells need to be searched for a match.
+        let cell_of = |p: Vec3| {
+            let c = (p / epsilon).floor();
+            (c.x as i64, c.y as i64, c.z as i64)
+        };
+        let mut grid: HashMap<(i64, i64, i64), Vec<u32>> = HashMap::new();
+
+        let mut positions: Vec<Vec3> = Vec::new();
+        let mut normals: Vec<Vec3> = Vec::new();
+        let mut uvs: Vec<Vec2> = Vec::new();
+        let mut remap: HashMap<u32, u32> = HashMap::new();
+
+        let mut indices = Vec::with_capacity(self.indices.len());
+        for &old_index in &self.indices {
+            if let Some(&new_index) = remap.get(&old_index) {
+                indices.push(new_index);
+                continue;
+            }
+
+            let i = old_index as usize;
+            let position = self.positions[i];
+            let cell = cell_of(position);
+
+            let mut found = None;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let key = (cell.0 + dx, cell.1 + dy, cell.2 + dz);
+                        let Some(candidates) = grid.get(&key) else {
+                            continue;
+                        };
+                        for &candidate in candidates {
+                            let c = candidate as usize;
+                            let matches = positions[c].distance(position) <= epsilon
+                                && (!has_normals
+                                    || normals[c].distance(self.normals[i]) <= epsilon)
+                                && (!has_uvs || uvs[c].distance(self.uvs[i]) <= epsilon);
+                            if matches {
+                                found = Some(candidate);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let new_index = found.unwrap_or_else(|| {
+                let new_index = positions.len() as u32;
+                positions.push(position);
+                if has_normals {
+                    normals.push(self.normals[i]);
+                }
+                if has_uvs {
+                    uvs.push(self.uvs[i]);
+                }
+                grid.entry(cell).or_default().push(new_index);
+                new_index
+            });
+            remap.insert(old_index, new_index);
+            indices.push(new_index);
+        }
+
+        self.positions = positions;
+        self.normals = normals;
+        self.uvs = uvs;
+        self.indices = indices;
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MTransform {
     pub translation: Vec3,
+    /// XYZ Euler angles in radians
     pub rotation: Vec3,
+    /// Negative components mirror the object
     pub scale: Vec3,
+    /// The object's world matrix as stored in the file, if it has one. It can
+    /// hold skew the fields above can't, which then only hold its closest
+    /// decomposition.
+    pub matrix: Option<Mat4>,
+}
+
+impl MTransform {
+    /// Leaves an object where it is
+    pub const IDENTITY: MTransform = MTransform {
+        translation: Vec3::ZERO,
+        rotation: Vec3::ZERO,
+        scale: Vec3::ONE,
+        matrix: None,
+    };
+
+    /// Transform placing an object exactly at `matrix`, with translation,
+    /// rotation and scale decomposed from it
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+        let (z, y, x) = rotation.to_euler(EulerRot::ZYX);
+        MTransform {
+            translation,
+            rotation: Vec3::new(x, y, z),
+            scale,
+            matrix: Some(matrix),
+        }
+    }
+
+    /// Object-to-world matrix: the one from the file when there is one,
+    /// otherwise composed from translation, rotation and scale
+    pub fn to_mat4(&self) -> Mat4 {
+        self.matrix.unwrap_or_else(|| {
+            // Blender's XYZ order rotates around X first, i.e. Rz * Ry * Rx
+            let rotation = Quat::from_euler(
+                EulerRot::ZYX,
+                self.rotation.z,
+                self.rotation.y,
+                self.rotation.x,
+            );
+            Mat4::from_scale_rotation_translation(self.scale, rotation, self.translation)
+        })
+    }
+
+    /// Whether the transform mirrors the geometry, which happens when an odd
+    /// number of scale components are negative.
+    ///
+    /// Triangles are emitted counter-clockwise in mesh space. A mirroring
+    /// transform turns them clockwise in world space, so renderers must flip
+    /// the winding (or the cull face) for such instances or they render
+    /// inside out.
+    pub fn mirrors_winding(&self) -> bool {
+        self.scale.x * self.scale.y * self.scale.z < 0.0
+    }
+}
+
+/// An object's transform at one frame of its animation.
+///
+/// Keys are composed from the object's loc/rot/scale, so they are relative
+/// to its parent and carry no `matrix`. They replace the object's static
+/// transform while the animation plays rather than compose with it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformKey {
+    pub frame: f32,
+    pub transform: MTransform,
 }
 
-#[derive(Debug, Clone)]
+impl TransformKey {
+    /// Transform at `frame`, interpolating linearly between the keys around
+    /// it and holding the first and last keys outside them. Keys must be
+    /// sorted by frame, as they are on import.
+    pub fn sample(keys: &[TransformKey], frame: f32) -> Option<MTransform> {
+        let first = keys.first()?;
+        if frame <= first.frame {
+            return Some(first.transform);
+        }
+        for pair in keys.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if frame <= to.frame {
+                let t = (frame - from.frame) / (to.frame - from.frame);
+                let (a, b) = (&from.transform, &to.transform);
+                return Some(MTransform {
+                    translation: a.translation.lerp(b.translation, t),
+                    rotation: a.rotation.lerp(b.rotation, t),
+                    scale: a.scale.lerp(b.scale, t),
+                    matrix: None,
+                });
+            }
+        }
+        keys.last().map(|key| key.transform)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum MNode {
     MInstance(MInstance),
     MGroup(MGroup),
     MLink(MLink),
 }
 
-#[derive(Debug, Clone)]
-pub struct MMaterial {}
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MMaterial {
+    /// Texture feeding the material's base color, if it has one
+    pub base_color_image: Option<ImageData>,
+}
 
-#[derive(Debug, Clone)]
+/// Where to find the contents of an image used by a material
+///
+/// Blender can pack images into the .blend itself. Packed images carry the
+/// file's bytes exactly as they were packed, so they are still encoded (PNG,
+/// JPEG, ...) and need decoding by the consumer. Images that aren't packed
+/// only have a path, which is relative to the .blend file when it starts with
+/// `//`, and may not exist on the machine doing the import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageData {
+    Packed(Vec<u8>),
+    External(String),
+}
+
+impl ImageData {
+    /// Encoded bytes of a packed image
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            ImageData::Packed(bytes) => Some(bytes),
+            ImageData::External(_) => None,
+        }
+    }
+
+    /// Path of an image stored outside the .blend
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            ImageData::Packed(_) => None,
+            ImageData::External(path) => Some(path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct MInstance {
     pub name: Option<String>,
     pub geometry_id: MMeshID,
@@ -64,64 +401,52 @@ pub struct MInstance {
     pub transform: Option<MTransform>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MLink {
     pub id: String,
     pub library: String,
     pub transform: Option<MTransform>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MGroup {
     pub name: Option<String>,
     pub children: Vec<MNode>,
     pub transform: Option<MTransform>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MScene {
     pub meshes: HashMap<MMeshID, MMesh>,
     pub materials: HashMap<MMaterialID, MMaterial>,
+    /// Transform keyframes keyed by object name, only filled in when
+    /// `ImportOptions::import_animation` is set
+    pub animations: HashMap<String, Vec<TransformKey>>,
     pub root: MGroup,
 }
 
 impl MScene {
     /// Compute the world-space bounding box of the entire scene
     pub fn scene_bounds(&self) -> BBox {
-        let identity = MTransform {
-            translation: Vec3::ZERO,
-            rotation: Vec3::ZERO,
-            scale: Vec3::ONE,
-        };
-        self.compute_bounds_recursive(&self.root.children, &identity)
+        self.compute_bounds_recursive(&self.root.children, Mat4::IDENTITY)
     }
 
-    fn compute_bounds_recursive(&self, nodes: &[MNode], parent_transform: &MTransform) -> BBox {
+    fn compute_bounds_recursive(&self, nodes: &[MNode], parent_matrix: Mat4) -> BBox {
         let mut bounds = BBox::empty();
 
         for node in nodes {
             match node {
                 MNode::MInstance(instance) => {
                     if let Some(mesh) = self.meshes.get(&instance.geometry_id) {
-                        let transform = if let Some(t) = &instance.transform {
-                            combine_transforms(parent_transform, t)
-                        } else {
-                            *parent_transform
-                        };
-
-                        let transformed_bbox = transform_bbox(&mesh.bbox, &transform);
-                        bounds = bounds.merge(&transformed_bbox);
+                        let matrix = child_matrix(parent_matrix, &instance.transform);
+                        let transformed_bbox = transform_bbox(&mesh.bbox, &matrix);
+                        bounds = bounds.union(&transformed_bbox);
                     }
                 }
                 MNode::MGroup(group) => {
-                    let transform = if let Some(t) = &group.transform {
-                        combine_transforms(parent_transform, t)
-                    } else {
-                        *parent_transform
-                    };
-
-                    let child_bounds = self.compute_bounds_recursive(&group.children, &transform);
-                    bounds = bounds.merge(&child_bounds);
+                    let matrix = child_matrix(parent_matrix, &group.transform);
+                    let child_bounds = self.compute_bounds_recursive(&group.children, matrix);
+                    bounds = bounds.union(&child_bounds);
                 }
                 MNode::MLink(_link) => {
                     // TODO
@@ -133,15 +458,15 @@ impl MScene {
     }
 }
 
-fn combine_transforms(parent: &MTransform, child: &MTransform) -> MTransform {
-    MTransform {
-        translation: parent.translation + child.translation * parent.scale,
-        rotation: parent.rotation + child.rotation,
-        scale: parent.scale * child.scale,
+/// Object-to-world matrix of a node placed under `parent_matrix`
+fn child_matrix(parent_matrix: Mat4, transform: &Option<MTransform>) -> Mat4 {
+    match transform {
+        Some(transform) => parent_matrix * transform.to_mat4(),
+        None => parent_matrix,
     }
 }
 
-fn transform_bbox(bbox: &BBox, transform: &MTransform) -> BBox {
+fn transform_bbox(bbox: &BBox, matrix: &Mat4) -> BBox {
     if bbox.is_empty() {
         return *bbox;
     }
@@ -159,9 +484,9 @@ fn transform_bbox(bbox: &BBox, transform: &MTransform) -> BBox {
 
     let mut result = BBox::empty();
     for corner in &corners {
-        let transformed = transform.translation + (*corner * transform.scale);
+        let transformed = matrix.transform_point3(*corner);
         if result.is_empty() {
-            result = BBox::new(transformed, transformed);
+            result = BBox::from_min_max(transformed, transformed);
         } else {
             result.min = result.min.min(transformed);
             result.max = result.max.max(transformed);
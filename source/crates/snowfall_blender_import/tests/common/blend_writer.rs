@@ -0,0 +1,136 @@
+//! Writes minimal .blend files for tests.
+//!
+//! The .blend fixtures in this repo are git-lfs pointers that are not always
+//! checked out, so tests that need a particular block layout build the file
+//! here instead. Files are 64-bit little-endian, and the DNA only needs to
+//! declare the structs and fields the code under test reads.
+
+/// A DNA struct: its name, size in bytes and (type, name) fields
+pub type DnaStruct = (&'static str, u16, &'static [(&'static str, &'static str)]);
+
+const PRIMITIVES: [(&str, u16); 13] = [
+    ("char", 1),
+    ("uchar", 1),
+    ("short", 2),
+    ("ushort", 2),
+    ("int", 4),
+    ("long", 4),
+    ("ulong", 4),
+    ("float", 4),
+    ("double", 8),
+    ("int64_t", 8),
+    ("uint64_t", 8),
+    ("void", 0),
+    ("int8_t", 1),
+];
+
+/// Kept clear of the addresses tests pick for their own blocks
+const DNA_ADDRESS: u64 = 0xffff_0000;
+
+pub struct BlendWriter {
+    structs: Vec<DnaStruct>,
+    blocks: Vec<u8>,
+}
+
+impl BlendWriter {
+    pub fn new(structs: &[DnaStruct]) -> Self {
+        Self {
+            structs: structs.to_vec(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// The SDNA index to give blocks holding the named struct
+    pub fn struct_index(&self, name: &str) -> u32 {
+        self.structs
+            .iter()
+            .position(|(n, _, _)| *n == name)
+            .unwrap_or_else(|| panic!("{name} is not in the DNA")) as u32
+    }
+
+    /// Append a file block holding a single struct at `address`
+    pub fn block(&mut self, code: &[u8; 4], address: u64, dna_index: u32, data: &[u8]) {
+        self.array_block(code, address, dna_index, 1, data);
+    }
+
+    /// Append a file block holding `count` structs back to back at `address`
+    pub fn array_block(
+        &mut self,
+        code: &[u8; 4],
+        address: u64,
+        dna_index: u32,
+        count: u32,
+        data: &[u8],
+    ) {
+        self.blocks.extend(code);
+        self.blocks.extend((data.len() as u32).to_le_bytes());
+        self.blocks.extend(address.to_le_bytes());
+        self.blocks.extend(dna_index.to_le_bytes());
+        self.blocks.extend(count.to_le_bytes());
+        self.blocks.extend(data);
+    }
+
+    /// The complete file: header, blocks, DNA and end marker
+    pub fn finish(mut self) -> Vec<u8> {
+        let dna = self.dna();
+        self.block(b"DNA1", DNA_ADDRESS, 0, &dna);
+        let mut blend = b"BLENDER-v405".to_vec();
+        blend.extend(self.blocks);
+        blend.extend(b"ENDB");
+        blend
+    }
+
+    fn dna(&self) -> Vec<u8> {
+        let types: Vec<(&str, u16)> = PRIMITIVES
+            .into_iter()
+            .chain(self.structs.iter().map(|(name, len, _)| (*name, *len)))
+            .collect();
+        let type_index = |name: &str| {
+            types
+                .iter()
+                .position(|(n, _)| *n == name)
+                .unwrap_or_else(|| panic!("{name} is not a DNA type")) as u16
+        };
+        let mut names: Vec<&str> = Vec::new();
+        for (_, _, fields) in &self.structs {
+            for (_, name) in fields.iter() {
+                if !names.contains(name) {
+                    names.push(name);
+                }
+            }
+        }
+
+        let pad = |bytes: &mut Vec<u8>| bytes.resize(bytes.len().next_multiple_of(4), 0);
+        let mut dna = b"SDNANAME".to_vec();
+        dna.extend((names.len() as u32).to_le_bytes());
+        for name in &names {
+            dna.extend(name.as_bytes());
+            dna.push(0);
+        }
+        pad(&mut dna);
+        dna.extend(b"TYPE");
+        dna.extend((types.len() as u32).to_le_bytes());
+        for (name, _) in &types {
+            dna.extend(name.as_bytes());
+            dna.push(0);
+        }
+        pad(&mut dna);
+        dna.extend(b"TLEN");
+        for (_, len) in &types {
+            dna.extend(len.to_le_bytes());
+        }
+        pad(&mut dna);
+        dna.extend(b"STRC");
+        dna.extend((self.structs.len() as u32).to_le_bytes());
+        for (name, _, fields) in &self.structs {
+            dna.extend(type_index(name).to_le_bytes());
+            dna.extend((fields.len() as u16).to_le_bytes());
+            for (field_type, field_name) in fields.iter() {
+                dna.extend(type_index(field_type).to_le_bytes());
+                let name_index = names.iter().position(|n| n == field_name).unwrap();
+                dna.extend((name_index as u16).to_le_bytes());
+            }
+        }
+        dna
+    }
+}
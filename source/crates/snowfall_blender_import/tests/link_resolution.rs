@@ -0,0 +1,99 @@
+use glam::Vec3;
+use snowfall_blender_import::{
+    BBox, BlendLibrary, MGroup, MInstance, MLink, MMesh, MNode, MTransform, resolve_links,
+};
+use std::collections::HashMap;
+
+fn translation(x: f32, y: f32, z: f32) -> MTransform {
+    MTransform {
+        translation: Vec3::new(x, y, z),
+        rotation: Vec3::ZERO,
+        scale: Vec3::ONE,
+        matrix: None,
+    }
+}
+
+fn crate_library() -> BlendLibrary {
+    let mut mesh = MMesh::new("MECrate".to_string());
+    mesh.positions = vec![Vec3::ZERO, Vec3::X, Vec3::Y];
+    mesh.indices = vec![0, 1, 2];
+    mesh.bbox = BBox::from_points(&mesh.positions);
+
+    let asset = MNode::MInstance(MInstance {
+        name: None,
+        geometry_id: "MECrate".to_string(),
+        material_id: None,
+        transform: Some(translation(0.0, 0.0, 1.0)),
+    });
+
+    BlendLibrary {
+        name: "props.blend".to_string(),
+        filepath: "/assets/props.blend".to_string(),
+        assets: HashMap::from([("GRCrate".to_string(), asset)]),
+        meshes: HashMap::from([("MECrate".to_string(), mesh)]),
+    }
+}
+
+fn linked_empty(id: &str, library: &str) -> MNode {
+    MNode::MLink(MLink {
+        id: id.to_string(),
+        library: library.to_string(),
+        transform: Some(translation(4.0, 5.0, 0.0)),
+    })
+}
+
+#[test]
+fn test_resolve_linked_empty() {
+    let mut root = MGroup {
+        name: None,
+        children: vec![linked_empty("GRCrate", "//props.blend")],
+        transform: None,
+    };
+
+    let resolved = resolve_links(&mut root, &[crate_library()]);
+    assert!(resolved.unresolved.is_empty());
+
+    // The link is replaced by a group placed at the link's transform that
+    // contains the asset with its own transform intact
+    let MNode::MGroup(group) = &root.children[0] else {
+        panic!("Link was not replaced by a group");
+    };
+    assert_eq!(group.name.as_deref(), Some("GRCrate"));
+    assert_eq!(
+        group.transform.unwrap().translation,
+        Vec3::new(4.0, 5.0, 0.0)
+    );
+    let MNode::MInstance(instance) = &group.children[0] else {
+        panic!("Linked asset should be a mesh instance");
+    };
+    assert_eq!(instance.geometry_id, "MECrate");
+    assert_eq!(
+        instance.transform.unwrap().translation,
+        Vec3::new(0.0, 0.0, 1.0)
+    );
+
+    // The referenced mesh is copied out of the library
+    let mesh = resolved.meshes.get("MECrate").expect("Mesh not merged");
+    assert_eq!(mesh.triangle_count(), 1);
+}
+
+#[test]
+fn test_unresolved_links_are_reported() {
+    let mut root = MGroup {
+        name: None,
+        children: vec![
+            linked_empty("GRBarrel", "//props.blend"),
+            linked_empty("GRCrate", "//missing.blend"),
+        ],
+        transform: None,
+    };
+
+    let resolved = resolve_links(&mut root, &[crate_library()]);
+    assert_eq!(resolved.unresolved.len(), 2);
+    assert!(resolved.meshes.is_empty());
+    assert!(
+        root.children
+            .iter()
+            .all(|node| matches!(node, MNode::MLink(_)))
+    );
+}
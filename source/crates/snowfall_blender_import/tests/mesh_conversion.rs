@@ -0,0 +1,227 @@
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use snowfall_blender_import::{BBox, MMesh, MTransform, NORMAL_SMOOTHING_ANGLE, SceneBuilder};
+
+/// Unit cube with 8 shared corners and 12 triangles
+fn unit_cube() -> MMesh {
+    let mut mesh = MMesh::new("Cube".to_string());
+    for i in 0..8 {
+        mesh.positions.push(Vec3::new(
+            (i & 1) as f32,
+            ((i >> 1) & 1) as f32,
+            ((i >> 2) & 1) as f32,
+        ));
+    }
+    mesh.indices = vec![
+        0, 2, 1, 1, 2, 3, // -Z
+        4, 5, 6, 5, 7, 6, // +Z
+        0, 1, 4, 1, 5, 4, // -Y
+        2, 6, 3, 3, 6, 7, // +Y
+        0, 4, 2, 2, 4, 6, // -X
+        1, 3, 5, 3, 7, 5, // +X
+    ];
+    mesh.bbox = BBox::from_points(&mesh.positions);
+    mesh
+}
+
+#[test]
+fn test_to_non_indexed_expands_corners() {
+    let cube = unit_cube();
+    let flat = cube.to_non_indexed();
+
+    assert_eq!(flat.vertex_count(), cube.indices.len());
+    assert_eq!(flat.triangle_count(), cube.triangle_count());
+    assert_eq!(flat.indices, (0..36).collect::<Vec<u32>>());
+
+    // Each expanded corner matches the corner it came from
+    for (corner, &index) in cube.indices.iter().enumerate() {
+        assert_eq!(flat.positions[corner], cube.positions[index as usize]);
+    }
+}
+
+#[test]
+fn test_to_indexed_round_trip() {
+    let cube = unit_cube();
+    let mut welded = cube.to_non_indexed();
+    welded.to_indexed(1e-5);
+
+    assert_eq!(welded.vertex_count(), 8);
+    assert_eq!(welded.triangle_count(), 12);
+    for triangle in 0..welded.triangle_count() {
+        for corner in 0..3 {
+            let i = triangle * 3 + corner;
+            assert_eq!(
+                welded.positions[welded.indices[i] as usize],
+                cube.positions[cube.indices[i] as usize]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_to_indexed_welds_within_epsilon() {
+    let mut mesh = MMesh::new("Tri".to_string());
+    mesh.positions = vec![
+        Vec3::ZERO,
+        Vec3::X,
+        Vec3::Y,
+        Vec3::new(0.0005, 0.0, 0.0), // Nearly the same as the first vertex
+        Vec3::X,
+        Vec3::new(0.0, 0.0, 1.0),
+    ];
+    mesh.indices = (0..6).collect();
+
+    let mut loose = mesh.clone();
+    loose.to_indexed(0.001);
+    assert_eq!(loose.vertex_count(), 4);
+    assert_eq!(loose.indices, vec![0, 1, 2, 0, 1, 3]);
+
+    let mut tight = mesh.clone();
+    tight.to_indexed(0.0001);
+    assert_eq!(tight.vertex_count(), 5);
+}
+
+#[test]
+fn test_to_indexed_keeps_vertices_with_different_uvs() {
+    let mut mesh = MMesh::new("Seam".to_string());
+    mesh.positions = vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::ZERO, Vec3::Y, Vec3::Z];
+    mesh.uvs = vec![
+        Vec2::ZERO,
+        Vec2::X,
+        Vec2::Y,
+        Vec2::ONE, // Same position as vertex 0 but on the other side of a seam
+        Vec2::Y,
+        Vec2::ZERO,
+    ];
+    mesh.indices = (0..6).collect();
+    mesh.to_indexed(1e-5);
+
+    assert_eq!(mesh.vertex_count(), 5);
+    assert_eq!(mesh.uvs.len(), 5);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 3, 2, 4]);
+}
+
+#[test]
+fn test_compute_normals_splits_cube_faces() {
+    let mut cube = unit_cube();
+    cube.compute_normals(NORMAL_SMOOTHING_ANGLE);
+
+    // Every corner is split into one vertex per face
+    assert_eq!(cube.vertex_count(), 24);
+    assert_eq!(cube.triangle_count(), 12);
+    assert_eq!(cube.normals.len(), 24);
+
+    // Each triangle's corners carry that face's outward normal
+    let center = Vec3::splat(0.5);
+    for triangle in cube.indices.chunks_exact(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| i as usize);
+        let normal = cube.normals[a];
+        assert_eq!(cube.normals[b], normal);
+        assert_eq!(cube.normals[c], normal);
+        assert_eq!(normal.abs().max_element(), 1.0);
+        assert!(normal.dot(cube.positions[a] - center) > 0.0);
+    }
+
+    let mut distinct: Vec<[i32; 3]> = cube
+        .normals
+        .iter()
+        .map(|n| n.to_array().map(|v| v.round() as i32))
+        .collect();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), 6);
+}
+
+#[test]
+fn test_compute_normals_smooths_shallow_angles() {
+    // Two triangles folded by 10 degrees share smoothed normals
+    let fold = 10.0_f32.to_radians();
+    let mut mesh = MMesh::new("Fold".to_string());
+    mesh.positions = vec![
+        Vec3::ZERO,
+        Vec3::X,
+        Vec3::Y,
+        Vec3::new(-fold.cos(), 0.0, fold.sin()),
+    ];
+    mesh.indices = vec![0, 1, 2, 0, 2, 3];
+    mesh.compute_normals(NORMAL_SMOOTHING_ANGLE);
+
+    assert_eq!(mesh.vertex_count(), 4);
+    let shared = mesh.normals[0];
+    assert!(shared.dot(Vec3::Z) > fold.cos());
+    assert!(mesh.normals[1].dot(shared) < 1.0);
+}
+
+#[test]
+fn test_fix_winding_after_axis_swap() {
+    let mut cube = unit_cube();
+    cube.compute_normals(NORMAL_SMOOTHING_ANGLE);
+    assert_eq!(cube.fix_winding(), 0);
+
+    // Converting Z-up to Y-up by swapping Y and Z mirrors the mesh, which
+    // turns every triangle inside out relative to its normals
+    let swap = |v: Vec3| Vec3::new(v.x, v.z, v.y);
+    cube.positions = cube.positions.iter().copied().map(swap).collect();
+    cube.normals = cube.normals.iter().copied().map(swap).collect();
+
+    assert_eq!(cube.fix_winding(), 12);
+    let center = Vec3::splat(0.5);
+    for triangle in cube.indices.chunks_exact(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| cube.positions[i as usize]);
+        let face = (b - a).cross(c - a);
+        assert!(
+            face.dot((a + b + c) / 3.0 - center) > 0.0,
+            "face points inward"
+        );
+        assert!(face.dot(cube.normals[triangle[0] as usize]) > 0.0);
+    }
+}
+
+#[test]
+fn test_fix_winding_needs_normals() {
+    let mut cube = unit_cube();
+    cube.indices.swap(1, 2);
+    assert_eq!(cube.fix_winding(), 0);
+    assert_eq!(&cube.indices[..3], &[0, 1, 2]);
+}
+
+#[test]
+fn test_scene_bounds_use_sheared_world_matrix() {
+    // x' = x + y, which translation, rotation and scale can't express
+    let shear = Mat4::from_cols(
+        Vec4::new(1.0, 0.0, 0.0, 0.0),
+        Vec4::new(1.0, 1.0, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 1.0, 0.0),
+        Vec4::new(0.0, 0.0, 3.0, 1.0),
+    );
+    let scene = SceneBuilder::new()
+        .add_mesh("Cube", unit_cube())
+        .instance("Cube", MTransform::from_matrix(shear))
+        .build();
+
+    let bounds = scene.scene_bounds();
+    assert!(bounds.min.abs_diff_eq(Vec3::new(0.0, 0.0, 3.0), 1e-5));
+    assert!(bounds.max.abs_diff_eq(Vec3::new(2.0, 1.0, 4.0), 1e-5));
+}
+
+#[test]
+fn test_scene_bounds_apply_group_rotation() {
+    let turned = MTransform {
+        rotation: Vec3::new(0.0, 0.0, std::f32::consts::FRAC_PI_2),
+        ..MTransform::IDENTITY
+    };
+    let moved = MTransform {
+        translation: Vec3::new(2.0, 0.0, 0.0),
+        ..MTransform::IDENTITY
+    };
+    let scene = SceneBuilder::new()
+        .add_mesh("Cube", unit_cube())
+        .group("Turned", |group| {
+            group.transform(turned).instance("Cube", moved)
+        })
+        .build();
+
+    // The child's offset along X is turned to point along Y
+    let bounds = scene.scene_bounds();
+    assert!(bounds.min.abs_diff_eq(Vec3::new(-1.0, 2.0, 0.0), 1e-5));
+    assert!(bounds.max.abs_diff_eq(Vec3::new(0.0, 3.0, 1.0), 1e-5));
+}
@@ -3,6 +3,8 @@ use glam::Vec3;
 /// An axis-aligned bounding box.
 ///
 /// Initialized to invalid infinity values (min > max) to represent an empty box.
+/// Empty boxes need no special casing when growing: expanding or taking the
+/// union with an empty box leaves the other operand unchanged.
 /// Designed with THREE.Box3 from three.js in mind.
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -17,7 +19,7 @@ impl BBox {
     //=========================================================================
 
     /// Creates a new BBox with invalid infinity values (empty box).
-    pub fn new() -> Self {
+    pub fn empty() -> Self {
         Self {
             min: Vec3::splat(f32::INFINITY),
             max: Vec3::splat(f32::NEG_INFINITY),
@@ -38,24 +40,13 @@ impl BBox {
         }
     }
 
-    /// Creates a BBox that contains all given points.
+    /// Creates a BBox that contains all given points. Empty if there are none.
     pub fn from_points(points: &[Vec3]) -> Self {
-        let mut bbox = Self::new();
+        let mut bbox = Self::empty();
         bbox.expand_by_points(points);
         bbox
     }
 
-    /// Creates a BBox from an array of positions by computing min/max.
-    pub fn from_array(position_array: &[Vec3]) -> Self {
-        let mut min = Vec3::splat(f32::INFINITY);
-        let mut max = Vec3::splat(f32::NEG_INFINITY);
-        for pos in position_array.iter() {
-            min = min.min(*pos);
-            max = max.max(*pos);
-        }
-        Self::from_min_max(min, max)
-    }
-
     //=========================================================================
     // Properties
     //=========================================================================
@@ -89,6 +80,21 @@ impl BBox {
         }
     }
 
+    /// Returns the radius of the sphere through the box's corners.
+    pub fn sphere_radius(&self) -> f32 {
+        self.size().length() * 0.5
+    }
+
+    /// Returns the total area of the box's six faces.
+    pub fn surface_area(&self) -> f32 {
+        if self.is_empty() {
+            0.0
+        } else {
+            let size = self.size();
+            2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+        }
+    }
+
     //=========================================================================
     // Mutation
     //=========================================================================
@@ -198,7 +204,16 @@ impl BBox {
     pub fn union(&self, other: &BBox) -> BBox {
         BBox {
             min: self.min.min(other.min),
-            max: self.max.min(other.max),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Returns the box interpolated between this one (t = 0) and another
+    /// (t = 1), corner by corner. Both boxes should be non-empty.
+    pub fn lerp(&self, other: &BBox, t: f32) -> BBox {
+        BBox {
+            min: self.min.lerp(other.min, t),
+            max: self.max.lerp(other.max, t),
         }
     }
 
@@ -213,7 +228,7 @@ impl BBox {
 
 impl Default for BBox {
     fn default() -> Self {
-        Self::new()
+        Self::empty()
     }
 }
 
@@ -222,14 +237,17 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_new_bbox_is_empty() {
-        let bbox = BBox::new();
+    fn test_empty_bbox() {
+        let bbox = BBox::empty();
         assert!(bbox.is_empty());
+        assert_eq!(bbox.size(), Vec3::ZERO);
+        assert_eq!(BBox::default(), bbox);
+        assert_eq!(BBox::from_points(&[]), bbox);
     }
 
     #[test]
     fn test_expand_by_point() {
-        let mut bbox = BBox::new();
+        let mut bbox = BBox::empty();
         bbox.expand_by_point(Vec3::new(1.0, 2.0, 3.0));
         bbox.expand_by_point(Vec3::new(-1.0, -2.0, -3.0));
 
@@ -268,7 +286,62 @@ mod tests {
         let bbox = BBox::from_min_max(Vec3::ZERO, Vec3::new(2.0, 3.0, 4.0));
         assert_eq!(bbox.volume(), 24.0);
 
-        let empty_bbox = BBox::new();
+        let empty_bbox = BBox::empty();
         assert_eq!(empty_bbox.volume(), 0.0);
     }
+
+    #[test]
+    fn test_surface_area() {
+        let unit = BBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        assert_eq!(unit.surface_area(), 6.0);
+
+        let bbox = BBox::from_min_max(Vec3::ZERO, Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(bbox.surface_area(), 52.0);
+
+        assert_eq!(BBox::empty().surface_area(), 0.0);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = BBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let b = BBox::from_min_max(Vec3::new(2.0, 4.0, 6.0), Vec3::new(4.0, 6.0, 8.0));
+
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+        let mid = a.lerp(&b, 0.5);
+        assert_eq!(mid.min, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(mid.max, Vec3::new(2.5, 3.5, 4.5));
+    }
+
+    #[test]
+    fn test_from_points() {
+        let bbox = BBox::from_points(&[
+            Vec3::new(1.0, -2.0, 0.5),
+            Vec3::new(-1.0, 3.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+        ]);
+        assert_eq!(bbox.min, Vec3::new(-1.0, -2.0, 0.0));
+        assert_eq!(bbox.max, Vec3::new(1.0, 3.0, 2.0));
+    }
+
+    #[test]
+    fn test_union() {
+        let a = BBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let b = BBox::from_min_max(Vec3::new(2.0, -1.0, 0.5), Vec3::new(3.0, 0.5, 4.0));
+        let union = a.union(&b);
+        assert_eq!(union.min, Vec3::new(0.0, -1.0, 0.0));
+        assert_eq!(union.max, Vec3::new(3.0, 1.0, 4.0));
+        assert!(union.contains_bbox(&a) && union.contains_bbox(&b));
+
+        assert_eq!(a.union(&BBox::empty()), a);
+        assert_eq!(BBox::empty().union(&a), a);
+        assert!(BBox::empty().union(&BBox::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_sphere_radius() {
+        let bbox = BBox::from_center_size(Vec3::ZERO, Vec3::new(2.0, 4.0, 4.0));
+        assert_eq!(bbox.sphere_radius(), 3.0);
+        assert_eq!(BBox::empty().sphere_radius(), 0.0);
+    }
 }
@@ -0,0 +1,3 @@
+mod bbox;
+
+pub use bbox::BBox;